@@ -3,7 +3,7 @@ use syn::parse::{Error, Result};
 use syn::spanned::Spanned;
 use syn::{Attribute, Ident, Lit, LitStr, Meta, NestedMeta, Path};
 
-use crate::structures::{Checks, Colour, HelpBehaviour, OnlyIn, Permissions};
+use crate::structures::{Checks, Colour, HelpBehaviour, HelpOrder, OnlyIn, Permissions};
 use crate::util::LitExt;
 
 use std::fmt::{self, Write};
@@ -279,6 +279,16 @@ impl AttributeOption for HelpBehaviour {
     }
 }
 
+impl AttributeOption for HelpOrder {
+    fn parse(values: Values) -> Result<Self> {
+        let span = values.span;
+        let value = String::parse(values)?;
+
+        HelpOrder::from_str(&value)
+            .ok_or_else(|| Error::new(span, format_args!("invalid help order: \"{}\"", value)))
+    }
+}
+
 impl AttributeOption for Checks {
     #[inline]
     fn parse(values: Values) -> Result<Self> {
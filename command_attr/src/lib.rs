@@ -78,6 +78,7 @@ macro_rules! match_options {
 ///
 /// - `#[example(ex)]`/`#[example = ex]`
 /// Example of the command's usage.
+/// May be given multiple times to provide several examples.
 ///
 /// - `#[min_args(min)]`, `#[max_args(max)]`, `#[num_args(min_and_max)]`
 /// The minimum and/or maximum amount of arguments that the command should/can receive.
@@ -112,10 +113,19 @@ macro_rules! match_options {
 /// - `#[owner_privilege]`/`#[owner_privilege]`
 /// Whether this command has a privilege for owners (i.e certain options are ignored for them).
 ///
+/// - `#[nsfw_only]`/`#[nsfw_only(bool)]`
+/// Whether this command can only be used in NSFW channels.
+///
 /// - `#[sub_commands(commands)]`
 /// A list of command names, separated by a comma, stating the subcommands of this command.
 /// These are executed in the form: `this-command sub-command`
 ///
+/// # Asynchronous commands
+/// The applied function may be an `async fn`, allowing futures to be awaited
+/// within the command. It is driven to completion by
+/// `serenity::framework::standard::block_on` on the thread the command was
+/// dispatched to, which stays occupied while the command awaits.
+///
 /// # Notes
 /// The name of the command is parsed from the applied function,
 /// or may be specified inside the `#[command]` attribute, a lá `#[command("foobar")]`.
@@ -165,7 +175,7 @@ pub fn command(attr: TokenStream, input: TokenStream) -> TokenStream {
                 options.usage = Some(propagate_err!(attributes::parse(values)));
             }
             "example" => {
-                options.example = Some(propagate_err!(attributes::parse(values)));
+                options.examples.push(propagate_err!(attributes::parse(values)));
             }
             _ => {
                 match_options!(name, values, options, span => [
@@ -181,6 +191,7 @@ pub fn command(attr: TokenStream, input: TokenStream) -> TokenStream {
                     only_in;
                     owners_only;
                     owner_privilege;
+                    nsfw_only;
                     sub_commands
                 ]);
             }
@@ -194,7 +205,7 @@ pub fn command(attr: TokenStream, input: TokenStream) -> TokenStream {
         description,
         delimiters,
         usage,
-        example,
+        examples,
         min_args,
         max_args,
         allowed_roles,
@@ -203,13 +214,13 @@ pub fn command(attr: TokenStream, input: TokenStream) -> TokenStream {
         only_in,
         owners_only,
         owner_privilege,
+        nsfw_only,
         sub_commands,
     } = options;
 
     let description = AsOption(description);
     let usage = AsOption(usage);
     let bucket = AsOption(bucket);
-    let example = AsOption(example);
     let min_args = AsOption(min_args);
     let max_args = AsOption(max_args);
 
@@ -249,7 +260,7 @@ pub fn command(attr: TokenStream, input: TokenStream) -> TokenStream {
             desc: #description,
             delimiters: &[#(#delimiters),*],
             usage: #usage,
-            example: #example,
+            examples: &[#(#examples),*],
             min_args: #min_args,
             max_args: #max_args,
             allowed_roles: &[#(#allowed_roles),*],
@@ -258,6 +269,7 @@ pub fn command(attr: TokenStream, input: TokenStream) -> TokenStream {
             only_in: #only_in,
             owners_only: #owners_only,
             owner_privilege: #owner_privilege,
+            nsfw_only: #nsfw_only,
             sub_commands: &[#(&#sub_commands),*],
         };
 
@@ -345,17 +357,23 @@ pub fn command(attr: TokenStream, input: TokenStream) -> TokenStream {
 /// - `#[lacking_role(s)]`/`#[lacking_role = s]`
 /// If a user lacks required roles, this will treat how commands will be displayed.
 ///
-/// Accepts `strike` (strikethroughs), `hide` (will not be listed) or `nothing` (leave be).
+/// Accepts `strike` (strikethroughs), `lock` (marked with a lock), `hide` (will not be listed) or `nothing` (leave be).
 ///
 /// - `#[lacking_ownership(s)]`/`#[lacking_ownership = s]`
 /// If a user lacks ownership, this will treat how these commands will be displayed.
 ///
-/// Accepts `strike` (strikethroughs), `hide` (will not be listed) or `nothing` (leave be).
+/// Accepts `strike` (strikethroughs), `lock` (marked with a lock), `hide` (will not be listed) or `nothing` (leave be).
 ///
 /// - `#[lacking_permissions(s)]`/`#[lacking_role = s]`
 /// If a user lacks permissions, this will treat how commands will be displayed.
 ///
-/// Accepts `strike` (strikethroughs), `hide` (will not be listed) or `nothing` (leave be).
+/// Accepts `strike` (strikethroughs), `lock` (marked with a lock), `hide` (will not be listed) or `nothing` (leave be).
+///
+/// - `#[lacking_conditions(s)]`/`#[lacking_conditions = s]`
+/// If a user fails a command's checks, this will treat how commands will be displayed.
+/// Only checks with `check_in_help` set are evaluated.
+///
+/// Accepts `strike` (strikethroughs), `lock` (marked with a lock), `hide` (will not be listed) or `nothing` (leave be).
 ///
 /// - `#[embed_error_colour(n)]`
 /// Colour that the help-embed will use upon an error.
@@ -375,6 +393,11 @@ pub fn command(attr: TokenStream, input: TokenStream) -> TokenStream {
 /// would be level three.
 /// - `#[indention_prefix = s]`
 ///
+/// - `#[group_order(s)]`/`#[group_order = s]`
+/// In which order groups and their sub-groups are listed.
+///
+/// Accepts `declaration` (the order they were declared in) or `alphabetical` (by their help name).
+///
 /// [`command`]: fn.command.html
 #[proc_macro_attribute]
 pub fn help(attr: TokenStream, input: TokenStream) -> TokenStream {
@@ -425,13 +448,15 @@ pub fn help(attr: TokenStream, input: TokenStream) -> TokenStream {
             lacking_role;
             lacking_permissions;
             lacking_ownership;
+            lacking_conditions;
             wrong_channel;
             embed_error_colour;
             embed_success_colour;
             strikethrough_commands_tip_in_dm;
             strikethrough_commands_tip_in_guild;
             max_levenshtein_distance;
-            indention_prefix
+            indention_prefix;
+            group_order
         ]);
     }
 
@@ -462,6 +487,17 @@ pub fn help(attr: TokenStream, input: TokenStream) -> TokenStream {
             }
         }
 
+        if options.lacking_conditions == HelpBehaviour::Strike {
+            is_any_option_strike = true;
+
+            if concat_with_comma {
+                strike_text.push_str(", fail a check");
+            } else {
+                strike_text.push_str(" fail a check");
+                concat_with_comma = true;
+            }
+        }
+
         if options.wrong_channel == HelpBehaviour::Strike {
             is_any_option_strike = true;
 
@@ -512,15 +548,21 @@ pub fn help(attr: TokenStream, input: TokenStream) -> TokenStream {
         lacking_role,
         lacking_permissions,
         lacking_ownership,
+        lacking_conditions,
         wrong_channel,
         embed_error_colour,
         embed_success_colour,
         max_levenshtein_distance,
         indention_prefix,
+        group_order,
     } = options;
 
-    let strikethrough_commands_tip_in_dm = AsOption(strikethrough_commands_tip_in_dm);
-    let strikethrough_commands_tip_in_guild = AsOption(strikethrough_commands_tip_in_guild);
+    let cow_path = quote!(std::borrow::Cow);
+
+    let strikethrough_commands_tip_in_dm =
+        AsOption(strikethrough_commands_tip_in_dm.map(|s| quote!(#cow_path::Borrowed(#s))));
+    let strikethrough_commands_tip_in_guild =
+        AsOption(strikethrough_commands_tip_in_guild.map(|s| quote!(#cow_path::Borrowed(#s))));
 
     let Colour(embed_error_colour) = embed_error_colour;
     let Colour(embed_success_colour) = embed_success_colour;
@@ -550,32 +592,34 @@ pub fn help(attr: TokenStream, input: TokenStream) -> TokenStream {
         #(#cfgs)*
         pub static #options: #options_path = #options_path {
             names: &[#(#names),*],
-            suggestion_text: #suggestion_text,
-            no_help_available_text: #no_help_available_text,
-            usage_label: #usage_label,
-            usage_sample_label: #usage_sample_label,
-            ungrouped_label: #ungrouped_label,
-            grouped_label: #grouped_label,
-            aliases_label: #aliases_label,
-            description_label: #description_label,
-            guild_only_text: #guild_only_text,
-            checks_label: #checks_label,
-            dm_only_text: #dm_only_text,
-            dm_and_guild_text: #dm_and_guild_text,
-            available_text: #available_text,
-            command_not_found_text: #command_not_found_text,
-            individual_command_tip: #individual_command_tip,
-            group_prefix: #group_prefix,
+            suggestion_text: #cow_path::Borrowed(#suggestion_text),
+            no_help_available_text: #cow_path::Borrowed(#no_help_available_text),
+            usage_label: #cow_path::Borrowed(#usage_label),
+            usage_sample_label: #cow_path::Borrowed(#usage_sample_label),
+            ungrouped_label: #cow_path::Borrowed(#ungrouped_label),
+            grouped_label: #cow_path::Borrowed(#grouped_label),
+            aliases_label: #cow_path::Borrowed(#aliases_label),
+            description_label: #cow_path::Borrowed(#description_label),
+            guild_only_text: #cow_path::Borrowed(#guild_only_text),
+            checks_label: #cow_path::Borrowed(#checks_label),
+            dm_only_text: #cow_path::Borrowed(#dm_only_text),
+            dm_and_guild_text: #cow_path::Borrowed(#dm_and_guild_text),
+            available_text: #cow_path::Borrowed(#available_text),
+            command_not_found_text: #cow_path::Borrowed(#command_not_found_text),
+            individual_command_tip: #cow_path::Borrowed(#individual_command_tip),
+            group_prefix: #cow_path::Borrowed(#group_prefix),
             strikethrough_commands_tip_in_dm: #strikethrough_commands_tip_in_dm,
             strikethrough_commands_tip_in_guild: #strikethrough_commands_tip_in_guild,
             lacking_role: #lacking_role,
             lacking_permissions: #lacking_permissions,
             lacking_ownership: #lacking_ownership,
+            lacking_conditions: #lacking_conditions,
             wrong_channel: #wrong_channel,
             embed_error_colour: #colour_path(#embed_error_colour),
             embed_success_colour: #colour_path(#embed_success_colour),
             max_levenshtein_distance: #max_levenshtein_distance,
-            indention_prefix: #indention_prefix,
+            indention_prefix: #cow_path::Borrowed(#indention_prefix),
+            group_order: #group_order,
         };
 
         #(#cfgs2)*
@@ -629,7 +673,8 @@ pub fn help(attr: TokenStream, input: TokenStream) -> TokenStream {
 /// group!({
 ///     name: "baz",
 ///     options: {
-///         // All sub-groups must own at least one prefix.
+///         // Sub-groups without a prefix have their commands invoked
+///         // through the prefix of their parent group.
 ///         prefix: "baz",
 ///     },
 ///     commands: [answer_to_life],
@@ -670,11 +715,16 @@ pub fn help(attr: TokenStream, input: TokenStream) -> TokenStream {
 ///
 /// - `checks`: Array\<Ident\>\
 /// A set of preconditions that must be met before a group command's execution.
+/// They also apply to the commands of sub-groups, after the checks of this group.
 /// Refer to [`command`]'s `checks` documentation.
 ///
 /// - `required_permissions`: Array\<Ident\>\
 /// A set of permissions needed by the user before a group command's execution.
 ///
+/// - `bucket`: String\
+/// The bucket of the group's commands that do not specify their own bucket,
+/// including the commands of sub-groups.
+///
 /// - `default_command`: Ident\
 /// Command to be executed if none of the group's prefixes are given.
 /// Identifier must refer to a `#[command]`'d function.
@@ -95,6 +95,7 @@ fn parse_argument(arg: FnArg) -> Result<Argument> {
 #[derive(Debug)]
 pub struct CommandFun {
     pub _pub: Option<Pub>,
+    pub asyncness: Option<Token![async]>,
     pub cfgs: Vec<Attribute>,
     pub docs: Vec<Attribute>,
     pub attributes: Vec<Attribute>,
@@ -122,6 +123,8 @@ impl Parse for CommandFun {
             None
         };
 
+        let asyncness = input.parse::<Option<Token![async]>>()?;
+
         input.parse::<Token![fn]>()?;
         let name = input.parse()?;
 
@@ -148,6 +151,7 @@ impl Parse for CommandFun {
 
         Ok(CommandFun {
             _pub,
+            asyncness,
             cfgs,
             docs,
             attributes,
@@ -163,6 +167,7 @@ impl ToTokens for CommandFun {
     fn to_tokens(&self, stream: &mut TokenStream2) {
         let CommandFun {
             _pub,
+            asyncness,
             cfgs,
             docs,
             attributes: _,
@@ -172,6 +177,37 @@ impl ToTokens for CommandFun {
             body,
         } = self;
 
+        // An asynchronous function is wrapped by a synchronous one of the same
+        // signature, which drives it to completion.
+        if asyncness.is_some() {
+            let names = args
+                .iter()
+                .enumerate()
+                .map(|(i, arg)| {
+                    if arg.name == "_" {
+                        Ident::new(&format!("__arg{}", i), arg.name.span())
+                    } else {
+                        arg.name.clone()
+                    }
+                })
+                .collect::<Vec<_>>();
+            let kinds = args.iter().map(|arg| &arg.kind);
+
+            stream.extend(quote! {
+                #(#cfgs)*
+                #(#docs)*
+                #_pub fn #name (#(#names: #kinds),*) -> #ret {
+                    async fn __async_fn(#(#args),*) -> #ret {
+                        #(#body)*
+                    }
+
+                    serenity::framework::standard::block_on(__async_fn(#(#names),*))
+                }
+            });
+
+            return;
+        }
+
         stream.extend(quote! {
             #(#cfgs)*
             #(#docs)*
@@ -283,7 +319,7 @@ pub struct Options {
     pub description: Option<String>,
     pub delimiters: Vec<String>,
     pub usage: Option<String>,
-    pub example: Option<String>,
+    pub examples: Vec<String>,
     pub min_args: Option<u16>,
     pub max_args: Option<u16>,
     pub allowed_roles: Vec<String>,
@@ -292,6 +328,7 @@ pub struct Options {
     pub only_in: OnlyIn,
     pub owners_only: bool,
     pub owner_privilege: bool,
+    pub nsfw_only: bool,
     pub sub_commands: Vec<Ident>,
 }
 
@@ -309,6 +346,7 @@ impl Options {
 
 #[derive(PartialEq, Debug)]
 pub enum HelpBehaviour {
+    Lock,
     Strike,
     Hide,
     Nothing,
@@ -317,6 +355,7 @@ pub enum HelpBehaviour {
 impl HelpBehaviour {
     pub fn from_str(s: &str) -> Option<Self> {
         Some(match s.to_lowercase().as_str() {
+            "lock" => HelpBehaviour::Lock,
             "strike" => HelpBehaviour::Strike,
             "hide" => HelpBehaviour::Hide,
             "nothing" => HelpBehaviour::Nothing,
@@ -329,6 +368,7 @@ impl ToTokens for HelpBehaviour {
     fn to_tokens(&self, stream: &mut TokenStream2) {
         let help_behaviour_path = quote!(serenity::framework::standard::HelpBehaviour);
         match self {
+            HelpBehaviour::Lock => stream.extend(quote!(#help_behaviour_path::Lock)),
             HelpBehaviour::Strike => stream.extend(quote!(#help_behaviour_path::Strike)),
             HelpBehaviour::Hide => stream.extend(quote!(#help_behaviour_path::Hide)),
             HelpBehaviour::Nothing => stream.extend(quote!(#help_behaviour_path::Nothing)),
@@ -336,6 +376,32 @@ impl ToTokens for HelpBehaviour {
     }
 }
 
+#[derive(PartialEq, Debug)]
+pub enum HelpOrder {
+    Declaration,
+    Alphabetical,
+}
+
+impl HelpOrder {
+    pub fn from_str(s: &str) -> Option<Self> {
+        Some(match s.to_lowercase().as_str() {
+            "declaration" => HelpOrder::Declaration,
+            "alphabetical" => HelpOrder::Alphabetical,
+            _ => return None,
+        })
+    }
+}
+
+impl ToTokens for HelpOrder {
+    fn to_tokens(&self, stream: &mut TokenStream2) {
+        let help_order_path = quote!(serenity::framework::standard::HelpOrder);
+        match self {
+            HelpOrder::Declaration => stream.extend(quote!(#help_order_path::Declaration)),
+            HelpOrder::Alphabetical => stream.extend(quote!(#help_order_path::Alphabetical)),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub struct HelpOptions {
     pub suggestion_text: String,
@@ -359,11 +425,13 @@ pub struct HelpOptions {
     pub lacking_role: HelpBehaviour,
     pub lacking_permissions: HelpBehaviour,
     pub lacking_ownership: HelpBehaviour,
+    pub lacking_conditions: HelpBehaviour,
     pub wrong_channel: HelpBehaviour,
     pub embed_error_colour: Colour,
     pub embed_success_colour: Colour,
     pub max_levenshtein_distance: usize,
     pub indention_prefix: String,
+    pub group_order: HelpOrder,
 }
 
 impl Default for HelpOptions {
@@ -392,11 +460,13 @@ impl Default for HelpOptions {
             lacking_role: HelpBehaviour::Strike,
             lacking_permissions: HelpBehaviour::Strike,
             lacking_ownership: HelpBehaviour::Hide,
+            lacking_conditions: HelpBehaviour::Strike,
             wrong_channel: HelpBehaviour::Strike,
             embed_error_colour: Colour::from_str("DARK_RED").unwrap(),
             embed_success_colour: Colour::from_str("ROSEWATER").unwrap(),
             max_levenshtein_distance: 0,
             indention_prefix: "-".to_string(),
+            group_order: HelpOrder::Declaration,
         }
     }
 }
@@ -411,6 +481,7 @@ pub struct GroupOptions {
     pub allowed_roles: Vec<String>,
     pub required_permissions: Permissions,
     pub checks: Checks,
+    pub bucket: Option<String>,
     pub default_command: Option<Ident>,
     pub description: Option<String>,
     pub inherit: Option<IdentAccess>,
@@ -530,6 +601,9 @@ impl Parse for GroupOptions {
                         options.description = Some(s);
                     }
                 }
+                ("bucket", Expr::Lit(s)) => {
+                    options.bucket = Some(s.to_str());
+                }
                 ("inherit", Expr::Access(access)) => {
                     options.inherit = Some(access);
                 }
@@ -557,11 +631,13 @@ impl ToTokens for GroupOptions {
             only_in,
             description,
             checks,
+            bucket,
             default_command,
             inherit,
         } = self;
 
         let description = AsOption(description.clone());
+        let bucket = AsOption(bucket.clone());
         let mut dc = quote! { None };
 
         if let Some(cmd) = default_command {
@@ -646,6 +722,12 @@ impl ToTokens for GroupOptions {
                 quote!()
             };
 
+            let bucket = if bucket.0.is_some() {
+                quote! { bucket: #bucket, }
+            } else {
+                quote!()
+            };
+
             let default_command = if default_command.is_some() {
                 quote! { default_command: #dc, }
             } else {
@@ -663,6 +745,7 @@ impl ToTokens for GroupOptions {
                     #only_in
                     #description
                     #checks
+                    #bucket
                     #default_command
                     ..#inherit
                 }
@@ -679,6 +762,7 @@ impl ToTokens for GroupOptions {
                     only_in: #only_in,
                     description: #description,
                     checks: #checks,
+                    bucket: #bucket,
                     default_command: #dc,
                 }
             });
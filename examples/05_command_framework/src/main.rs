@@ -20,7 +20,7 @@ use serenity::{
     utils::{content_safe, ContentSafeOptions},
 };
 
-// This imports `typemap`'s `Key` as `TypeMapKey`.
+// This imports `TypeMap` and `TypeMapKey`.
 use serenity::prelude::*;
 
 // A container type is created for inserting into the Client's `data`, which
@@ -153,7 +153,11 @@ fn main() {
     let (owners, bot_id) = match client.cache_and_http.http.get_current_application_info() {
         Ok(info) => {
             let mut owners = HashSet::new();
-            owners.insert(info.owner.id);
+            if let Some(team) = info.team {
+                owners.extend(team.members.iter().map(|member| member.user.id));
+            } else {
+                owners.insert(info.owner.id);
+            }
 
             (owners, info.id)
         },
@@ -208,7 +212,7 @@ fn main() {
             // the command's name does not exist in the counter, add a default
             // value of 0.
             let mut data = ctx.data.write();
-            let counter = data.get_mut::<CommandCounter>().expect("Expected CommandCounter in ShareMap.");
+            let counter = data.get_mut::<CommandCounter>().expect("Expected CommandCounter in TypeMap.");
             let entry = counter.entry(command_name.to_string()).or_insert(0);
             *entry += 1;
 
@@ -216,16 +220,20 @@ fn main() {
         })
         // Similar to `before`, except will be called directly _after_
         // command execution.
-        .after(|_, _, command_name, error| {
+        .after(|_, _, command_name, elapsed, error| {
             match error {
-                Ok(()) => println!("Processed command '{}'", command_name),
+                Ok(()) => println!("Processed command '{}' in {:?}", command_name, elapsed),
                 Err(why) => println!("Command '{}' returned error {:?}", command_name, why),
             }
         })
         // Set a function that's called whenever an attempted command-call's
         // command could not be found.
-        .unrecognised_command(|_, _, unknown_command_name| {
+        .unrecognised_command(|_, _, unknown_command_name, suggestions| {
             println!("Could not find command named '{}'", unknown_command_name);
+
+            if !suggestions.is_empty() {
+                println!("Similar commands: {}", suggestions.join(", "));
+            }
         })
         // Set a function that's called whenever a message is not a command.
         .normal_message(|_, message| {
@@ -235,8 +243,14 @@ fn main() {
         // reason or another. For example, when a user has exceeded a rate-limit or a command
         // can only be performed by the bot owner.
         .on_dispatch_error(|ctx, msg, error| {
-            if let DispatchError::Ratelimited(seconds) = error {
-                let _ = msg.channel_id.say(&ctx.http, &format!("Try this again in {} seconds.", seconds));
+            match error {
+                DispatchError::Ratelimited(seconds) => {
+                    let _ = msg.channel_id.say(&ctx.http, &format!("Try this again in {} seconds.", seconds));
+                },
+                DispatchError::LackingPermissions(missing) => {
+                    let _ = msg.channel_id.say(&ctx.http, &format!("You are missing these permissions: {:?}", missing));
+                },
+                _ => {},
             }
         })
         .help(&MY_HELP)
@@ -267,7 +281,7 @@ fn commands(ctx: &mut Context, msg: &Message) -> CommandResult {
     let mut contents = "Commands used:\n".to_string();
 
     let data = ctx.data.read();
-    let counter = data.get::<CommandCounter>().expect("Expected CommandCounter in ShareMap.");
+    let counter = data.get::<CommandCounter>().expect("Expected CommandCounter in TypeMap.");
 
     for (k, v) in counter {
         let _ = write!(contents, "- {name}: {amount}\n", name=k, amount=v);
@@ -514,7 +528,7 @@ fn am_i_admin(ctx: &mut Context, msg: &Message) -> CommandResult {
 #[command]
 fn slow_mode(ctx: &mut Context, msg: &Message, mut args: Args) -> CommandResult {
     let say_content = if let Ok(slow_mode_rate_seconds) = args.single::<u64>() {
-        if let Err(why) = msg.channel_id.edit(&ctx.http, |c| c.slow_mode_rate(slow_mode_rate_seconds)) {
+        if let Err(why) = msg.channel_id.set_slowmode(&ctx.http, slow_mode_rate_seconds) {
             println!("Error setting channel's slow mode rate: {:?}", why);
 
             format!("Failed to set slow mode to `{}` seconds.", slow_mode_rate_seconds)
@@ -522,7 +536,7 @@ fn slow_mode(ctx: &mut Context, msg: &Message, mut args: Args) -> CommandResult
             format!("Successfully set slow mode rate to `{}` seconds.", slow_mode_rate_seconds)
         }
     } else if let Some(Channel::Guild(channel)) = msg.channel_id.to_channel_cached(&ctx.cache) {
-        format!("Current slow mode rate is `{}` seconds.", channel.read().slow_mode_rate.unwrap_or(0))
+        format!("Current slow mode rate is `{}` seconds.", channel.read().rate_limit_per_user.unwrap_or(0))
     } else {
         "Failed to find channel in cache.".to_string()
     };
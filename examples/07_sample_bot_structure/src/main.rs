@@ -79,7 +79,11 @@ fn main() {
     let owners = match client.cache_and_http.http.get_current_application_info() {
         Ok(info) => {
             let mut set = HashSet::new();
-            set.insert(info.owner.id);
+            if let Some(team) = info.team {
+                set.extend(team.members.iter().map(|member| member.user.id));
+            } else {
+                set.insert(info.owner.id);
+            }
 
             set
         },
@@ -17,7 +17,7 @@ use serenity::{
             macros::{command, group},
         },
     },
-    model::{channel::Message, gateway::Ready, id::ChannelId, misc::Mentionable},
+    model::{channel::Message, gateway::Ready, id::ChannelId, misc::Mentionable, voice::SpeakingState},
     prelude::*,
     voice::AudioReceiver,
     Result as SerenityResult,
@@ -48,7 +48,7 @@ impl Receiver {
 }
 
 impl AudioReceiver for Receiver {
-    fn speaking_update(&mut self, _ssrc: u32, _user_id: u64, _speaking: bool) {
+    fn speaking_update(&mut self, _ssrc: u32, _user_id: u64, _speaking: SpeakingState) {
         // You can implement logic here so that you can differentiate users'
         // SSRCs and map the SSRC to the User ID and maintain a state in
         // `Receiver`. Using this map, you can map the `ssrc` in `voice_packet`
@@ -135,7 +135,7 @@ fn join(ctx: &mut Context, msg: &Message, mut args: Args) -> CommandResult {
         },
     };
 
-    let manager_lock = ctx.data.read().get::<VoiceManager>().cloned().expect("Expected VoiceManager in ShareMap.");
+    let manager_lock = ctx.data.read().get::<VoiceManager>().cloned().expect("Expected VoiceManager in TypeMap.");
     let mut manager = manager_lock.lock();
 
     if let Some(handler) = manager.join(guild_id, connect_to) {
@@ -159,7 +159,7 @@ fn leave(ctx: &mut Context, msg: &Message) -> CommandResult {
         },
     };
 
-    let manager_lock = ctx.data.read().get::<VoiceManager>().cloned().expect("Expected VoiceManager in ShareMap.");
+    let manager_lock = ctx.data.read().get::<VoiceManager>().cloned().expect("Expected VoiceManager in TypeMap.");
     let mut manager = manager_lock.lock();
     let has_handler = manager.get(guild_id).is_some();
 
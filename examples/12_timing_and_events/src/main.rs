@@ -159,7 +159,7 @@ fn main() {
                 let _ = msg.channel_id.say(&ctx.http, &format!("Try this again in {} seconds.", seconds));
             }
         })
-        .after(|_ctx, _msg, cmd_name, error| {
+        .after(|_ctx, _msg, cmd_name, _elapsed, error| {
 
         if let Err(why) = error {
             println!("Error in {}: {:?}", cmd_name, why);
@@ -1,7 +1,9 @@
 use crate::internal::prelude::*;
 use crate::model::prelude::*;
+use crate::model::ModelError;
 
 use serde_json::{json, Value};
+use super::validation::check_channel;
 
 use std::collections::HashMap;
 
@@ -18,7 +20,7 @@ pub struct CreateChannel(pub HashMap<&'static str, Value>);
 impl CreateChannel {
     /// Specify how to call this new channel.
     ///
-    /// **Note**: Must be between 2 and 100 characters long.
+    /// **Note**: Must be between 1 and 100 characters long.
     pub fn name<D: ToString>(&mut self, name: D) -> &mut Self {
         self.0.insert("name", Value::String(name.to_string()));
 
@@ -26,7 +28,7 @@ impl CreateChannel {
     }
     /// Specify what type the channel is, whether it's a text, voice, category or news channel.
     pub fn kind(&mut self, kind: ChannelType) -> &mut Self {
-        self.0.insert("type", Value::Number(Number::from(kind as u8)));
+        self.0.insert("type", Value::Number(Number::from(kind.num())));
 
         self
     }
@@ -40,7 +42,7 @@ impl CreateChannel {
 
     /// Set an interesting topic.
     ///
-    /// **Note**: Must be between 0 and 1000 characters long.
+    /// **Note**: Must be between 0 and 1024 characters long.
     pub fn topic<D: ToString>(&mut self, topic: D) -> &mut Self {
         self.0.insert("topic", Value::String(topic.to_string()));
 
@@ -62,6 +64,8 @@ impl CreateChannel {
     }
 
     /// [Voice-only] Set how many users may occupy this voice channel.
+    ///
+    /// **Note**: Must be between 0 and 99, where 0 means no limit.
     pub fn user_limit(&mut self, limit: u32) -> &mut Self {
         self.0.insert("user_limit", Value::Number(Number::from(limit)));
 
@@ -74,12 +78,19 @@ impl CreateChannel {
     /// from this restriction.
     ///
     /// **Note**: Must be between 0 and 21600 seconds (360 minutes or 6 hours).
-    pub fn rate_limit(&mut self, limit: u64) -> &mut Self {
-        self.0.insert("rate_limit_per_user", Value::Number(Number::from(limit)));
+    pub fn rate_limit_per_user(&mut self, seconds: u64) -> &mut Self {
+        self.0.insert("rate_limit_per_user", Value::Number(Number::from(seconds)));
 
         self
     }
 
+    /// How many seconds must a user wait before sending another message.
+    #[deprecated(since="0.7.3", note="Please use `rate_limit_per_user` instead.")]
+    #[inline]
+    pub fn rate_limit(&mut self, limit: u64) -> &mut Self {
+        self.rate_limit_per_user(limit)
+    }
+
     /// Specify where the channel should be located.
     pub fn position(&mut self, pos: u32) -> &mut Self {
         self.0.insert("position", Value::Number(Number::from(pos)));
@@ -122,6 +133,61 @@ impl CreateChannel {
 
         self
     }
+
+    /// Checks that the channel is within Discord's limits, and that no field
+    /// is set which its kind of channel does not support.
+    ///
+    /// This is done automatically by [`GuildId::create_channel`], but may be
+    /// used to validate a channel ahead of time.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ModelError::NameTooShort`] if the name is missing or empty,
+    /// a [`ModelError::NameTooLong`] or [`ModelError::TopicTooLong`] if the
+    /// name or topic is over its limit, or a [`ModelError::UserLimitAmount`]
+    /// or [`ModelError::RateLimitAmount`] if the user limit or slow mode rate
+    /// is over its limit.
+    ///
+    /// Returns a [`ModelError::InvalidChannelType`] if the kind is not that of
+    /// a guild channel, or does not support a field which is set, such as a
+    /// bitrate for a text channel or a parent for a category.
+    ///
+    /// [`GuildId::create_channel`]: ../model/id/struct.GuildId.html#method.create_channel
+    /// [`ModelError::InvalidChannelType`]: ../model/error/enum.Error.html#variant.InvalidChannelType
+    /// [`ModelError::NameTooLong`]: ../model/error/enum.Error.html#variant.NameTooLong
+    /// [`ModelError::NameTooShort`]: ../model/error/enum.Error.html#variant.NameTooShort
+    /// [`ModelError::RateLimitAmount`]: ../model/error/enum.Error.html#variant.RateLimitAmount
+    /// [`ModelError::TopicTooLong`]: ../model/error/enum.Error.html#variant.TopicTooLong
+    /// [`ModelError::UserLimitAmount`]: ../model/error/enum.Error.html#variant.UserLimitAmount
+    pub fn validate(&self) -> Result<()> {
+        if !self.0.contains_key("name") {
+            return Err(Error::Model(ModelError::NameTooShort));
+        }
+
+        check_channel(&self.0)?;
+
+        let kind = self.0.get("type").and_then(Value::as_u64).unwrap_or(0);
+        let is = |other: ChannelType| kind == other.num();
+        let has = |key: &str| self.0.contains_key(key);
+
+        let unsupported = if is(ChannelType::Private) || is(ChannelType::Group) {
+            true
+        } else if is(ChannelType::Category) {
+            ["parent_id", "topic", "bitrate", "user_limit", "rate_limit_per_user"].iter().any(|key| has(*key))
+        } else if is(ChannelType::Voice) {
+            has("topic")
+        } else if is(ChannelType::Stage) {
+            false
+        } else {
+            has("bitrate") || has("user_limit")
+        };
+
+        if unsupported {
+            return Err(Error::Model(ModelError::InvalidChannelType));
+        }
+
+        Ok(())
+    }
 }
 
 impl Default for CreateChannel {
@@ -143,3 +209,62 @@ impl Default for CreateChannel {
         builder
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{model::{channel::ChannelType, ModelError}, Error};
+    use super::CreateChannel;
+
+    #[test]
+    fn test_validate() {
+        let mut builder = CreateChannel::default();
+
+        match builder.validate() {
+            Err(Error::Model(ModelError::NameTooShort)) => {},
+            other => panic!("expected a missing name, got {:?}", other),
+        }
+
+        builder.name("general").topic("a".repeat(1024)).rate_limit_per_user(21600);
+        assert!(builder.validate().is_ok());
+
+        builder.name("a".repeat(102));
+
+        match builder.validate() {
+            Err(Error::Model(ModelError::NameTooLong(2))) => {},
+            other => panic!("expected the name to be 2 over, got {:?}", other),
+        }
+
+        builder.name("general").topic("a".repeat(1025));
+
+        match builder.validate() {
+            Err(Error::Model(ModelError::TopicTooLong(1))) => {},
+            other => panic!("expected the topic to be 1 over, got {:?}", other),
+        }
+
+        builder.topic("").bitrate(64000);
+
+        match builder.validate() {
+            Err(Error::Model(ModelError::InvalidChannelType)) => {},
+            other => panic!("expected a bitrate on a text channel to fail, got {:?}", other),
+        }
+
+        let mut builder = CreateChannel::default();
+        builder.name("voice").kind(ChannelType::Voice).bitrate(64000).user_limit(99);
+        assert!(builder.validate().is_ok());
+
+        builder.user_limit(100);
+
+        match builder.validate() {
+            Err(Error::Model(ModelError::UserLimitAmount(100))) => {},
+            other => panic!("expected the user limit to be invalid, got {:?}", other),
+        }
+
+        let mut builder = CreateChannel::default();
+        builder.name("category").kind(ChannelType::Category).category(1);
+
+        match builder.validate() {
+            Err(Error::Model(ModelError::InvalidChannelType)) => {},
+            other => panic!("expected a nested category to fail, got {:?}", other),
+        }
+    }
+}
@@ -15,8 +15,9 @@
 //! [`ExecuteWebhook::embeds`]: struct.ExecuteWebhook.html#method.embeds
 //! [here]: https://discordapp.com/developers/docs/resources/channel#embed-object
 
+use crate::constants;
 use crate::internal::prelude::*;
-use crate::model::channel::Embed;
+use crate::model::{channel::Embed, ModelError};
 use crate::utils;
 
 use chrono::{DateTime, TimeZone};
@@ -36,8 +37,13 @@ use crate::utils::Colour;
 /// Refer to the documentation for [`ChannelId::send_message`] for a very in-depth
 /// example on how to use this.
 ///
+/// An existing embed - such as one of a received [`Message`] - can be turned
+/// into a builder via `CreateEmbed::from`, to change some of its values before
+/// sending it again.
+///
 /// [`ChannelId::send_message`]: ../model/id/struct.ChannelId.html#method.send_message
 /// [`Embed`]: ../model/channel/struct.Embed.html
+/// [`Message`]: ../model/channel/struct.Message.html
 /// [`ExecuteWebhook::embeds`]: struct.ExecuteWebhook.html#method.embeds
 #[derive(Clone, Debug)]
 pub struct CreateEmbed(pub HashMap<&'static str, Value>);
@@ -343,6 +349,115 @@ impl CreateEmbed {
 
         self
     }
+
+    /// Checks that the embed is within Discord's limits for the length of its
+    /// title, description, fields, footer text and author name, the number of
+    /// fields, and the combined length of all of these.
+    ///
+    /// This is done automatically when sending a message through
+    /// [`ChannelId::send_message`], but may be used to validate an embed
+    /// ahead of time.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ModelError::EmbedTooLarge`] if a length limit is exceeded,
+    /// or a [`ModelError::TooManyEmbedFields`] if there are over 25 fields,
+    /// containing the amount over that limit.
+    ///
+    /// [`ChannelId::send_message`]: ../model/id/struct.ChannelId.html#method.send_message
+    /// [`ModelError::EmbedTooLarge`]: ../model/error/enum.Error.html#variant.EmbedTooLarge
+    /// [`ModelError::TooManyEmbedFields`]: ../model/error/enum.Error.html#variant.TooManyEmbedFields
+    pub fn check_length(&self) -> Result<()> {
+        check_embed_length(&utils::hashmap_to_json_map(self.0.clone()))
+    }
+}
+
+/// Checks a serialized embed against Discord's embed limits.
+pub(crate) fn check_embed_length(embed: &JsonMap) -> Result<()> {
+    fn check(value: Option<&Value>, limit: u16, total: &mut usize) -> Result<()> {
+        if let Some(&Value::String(ref value)) = value {
+            let count = value.chars().count();
+            *total += count;
+
+            if count > limit as usize {
+                let overflow = (count - limit as usize) as u64;
+
+                return Err(Error::Model(ModelError::EmbedTooLarge(overflow)));
+            }
+        }
+
+        Ok(())
+    }
+
+    let mut total: usize = 0;
+
+    if let Some(&Value::Object(ref author)) = embed.get("author") {
+        check(author.get("name"), constants::EMBED_AUTHOR_NAME_LIMIT, &mut total)?;
+    }
+
+    check(embed.get("description"), constants::EMBED_DESCRIPTION_LIMIT, &mut total)?;
+
+    if let Some(&Value::Array(ref fields)) = embed.get("fields") {
+        if fields.len() > constants::EMBED_FIELD_COUNT_LIMIT as usize {
+            let overflow = (fields.len() - constants::EMBED_FIELD_COUNT_LIMIT as usize) as u64;
+
+            return Err(Error::Model(ModelError::TooManyEmbedFields(overflow)));
+        }
+
+        for field_as_value in fields {
+            if let Value::Object(ref field) = *field_as_value {
+                check(field.get("name"), constants::EMBED_FIELD_NAME_LIMIT, &mut total)?;
+                check(field.get("value"), constants::EMBED_FIELD_VALUE_LIMIT, &mut total)?;
+            }
+        }
+    }
+
+    if let Some(&Value::Object(ref footer)) = embed.get("footer") {
+        check(footer.get("text"), constants::EMBED_FOOTER_TEXT_LIMIT, &mut total)?;
+    }
+
+    check(embed.get("title"), constants::EMBED_TITLE_LIMIT, &mut total)?;
+
+    if total <= constants::EMBED_MAX_LENGTH as usize {
+        Ok(())
+    } else {
+        let overflow = total as u64 - u64::from(constants::EMBED_MAX_LENGTH);
+
+        Err(Error::Model(ModelError::EmbedTooLarge(overflow)))
+    }
+}
+
+/// Checks the serialized embeds of a message against Discord's limit on the
+/// number of embeds, and each embed against the embed limits.
+pub(crate) fn check_embeds_length(map: &JsonMap) -> Result<()> {
+    if let Some(&Value::Array(ref embeds)) = map.get("embeds") {
+        if embeds.len() > constants::EMBED_COUNT_LIMIT as usize {
+            let overflow = (embeds.len() - constants::EMBED_COUNT_LIMIT as usize) as u64;
+
+            return Err(Error::Model(ModelError::TooManyEmbeds(overflow)));
+        }
+
+        for embed in embeds {
+            if let Value::Object(ref embed) = *embed {
+                check_embed_length(embed)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Appends embeds to the `embeds` array of a message builder's map, creating
+/// the array if it is not yet set.
+pub(crate) fn push_embeds<It>(map: &mut HashMap<&'static str, Value>, embeds: It)
+    where It: IntoIterator<Item=CreateEmbed> {
+    let values = embeds.into_iter()
+        .map(|embed| Value::Object(utils::hashmap_to_json_map(embed.0)));
+
+    match map.entry("embeds").or_insert_with(|| Value::Array(Vec::new())) {
+        Value::Array(ref mut array) => array.extend(values),
+        other => *other = Value::Array(values.collect()),
+    }
 }
 
 impl Default for CreateEmbed {
@@ -423,6 +538,16 @@ impl From<Embed> for CreateEmbed {
     }
 }
 
+impl<'a> From<&'a Embed> for CreateEmbed {
+    /// Converts the fields of an embed into the values for a new embed builder,
+    /// leaving the embed intact.
+    ///
+    /// Some values - such as Proxy URLs - are not preserved.
+    fn from(embed: &'a Embed) -> Self {
+        CreateEmbed::from(embed.clone())
+    }
+}
+
 /// A builder to create a fake [`Embed`] object's author, for use with the
 /// [`CreateEmbed::author`] method.
 ///
@@ -510,10 +635,11 @@ impl<'a, Tz: TimeZone> From<&'a DateTime<Tz>> for Timestamp
 
 #[cfg(test)]
 mod test {
-    use crate::{model::channel::{Embed, EmbedField, EmbedFooter, EmbedImage, EmbedVideo},
-        utils::{self, Colour}};
+    use crate::{model::{channel::{Embed, EmbedAuthor, EmbedField, EmbedFooter, EmbedImage, EmbedThumbnail, EmbedVideo}, ModelError},
+        utils::{self, Colour}, Error};
     use serde_json::{json, Value};
-    use super::CreateEmbed;
+    use std::collections::HashMap;
+    use super::{check_embeds_length, push_embeds, CreateEmbed};
 
     #[test]
     fn test_from_embed() {
@@ -526,27 +652,23 @@ mod test {
                     inline: false,
                     name: "a".to_string(),
                     value: "b".to_string(),
-                    _nonexhaustive: (),
                 },
                 EmbedField {
                     inline: true,
                     name: "c".to_string(),
                     value: "z".to_string(),
-                    _nonexhaustive: (),
                 },
             ],
             footer: Some(EmbedFooter {
                 icon_url: Some("https://i.imgur.com/XfWpfCV.gif".to_string()),
                 proxy_icon_url: None,
                 text: "This is a hakase footer".to_string(),
-                _nonexhaustive: (),
             }),
             image: Some(EmbedImage {
                 height: 213,
                 proxy_url: "a".to_string(),
                 url: "https://i.imgur.com/XfWpfCV.gif".to_string(),
                 width: 224,
-                _nonexhaustive: (),
             }),
             kind: "rich".to_string(),
             provider: None,
@@ -558,9 +680,7 @@ mod test {
                 height: 213,
                 url: "https://i.imgur.com/XfWpfCV.mp4".to_string(),
                 width: 224,
-                _nonexhaustive: (),
             }),
-            _nonexhaustive: (),
         };
 
         let mut builder = CreateEmbed::from(embed);
@@ -601,4 +721,121 @@ mod test {
 
         assert_eq!(built, obj);
     }
+
+    #[test]
+    fn test_from_embed_ref() {
+        let embed = Embed {
+            author: Some(EmbedAuthor {
+                icon_url: Some("https://i.imgur.com/XfWpfCV.gif".to_string()),
+                name: "hakase".to_string(),
+                proxy_icon_url: Some("a".to_string()),
+                url: None,
+            }),
+            colour: Colour::new(0xFF0011),
+            description: None,
+            fields: vec![],
+            footer: None,
+            image: None,
+            kind: "rich".to_string(),
+            provider: None,
+            thumbnail: Some(EmbedThumbnail {
+                height: 213,
+                proxy_url: "a".to_string(),
+                url: "https://i.imgur.com/XfWpfCV.gif".to_string(),
+                width: 224,
+            }),
+            timestamp: Some("2019-09-01T00:00:00+00:00".to_string()),
+            title: None,
+            url: None,
+            video: None,
+        };
+
+        let mut builder = CreateEmbed::from(&embed);
+        builder.title("hakase");
+
+        let built = Value::Object(utils::hashmap_to_json_map(builder.0));
+
+        let obj = json!({
+            "color": 0xFF0011,
+            "title": "hakase",
+            "type": "rich",
+            "author": {
+                "icon_url": "https://i.imgur.com/XfWpfCV.gif",
+                "name": "hakase",
+            },
+            "thumbnail": {
+                "url": "https://i.imgur.com/XfWpfCV.gif",
+            },
+            "timestamp": "2019-09-01T00:00:00+00:00",
+        });
+
+        assert_eq!(built, obj);
+        assert_eq!(embed.title, None);
+    }
+
+    #[test]
+    fn test_check_length() {
+        let mut builder = CreateEmbed::default();
+        builder.title("a".repeat(256));
+        builder.description("b".repeat(4096));
+
+        assert!(builder.check_length().is_ok());
+
+        builder.title("a".repeat(260));
+
+        match builder.check_length() {
+            Err(Error::Model(ModelError::EmbedTooLarge(4))) => {},
+            other => panic!("expected the title to be 4 over, got {:?}", other),
+        }
+
+        let mut builder = CreateEmbed::default();
+        builder.fields((0..26).map(|i| (i, i, false)));
+
+        match builder.check_length() {
+            Err(Error::Model(ModelError::TooManyEmbedFields(1))) => {},
+            other => panic!("expected 1 field over, got {:?}", other),
+        }
+
+        let mut builder = CreateEmbed::default();
+        builder.fields((0..6).map(|_| ("a", "b".repeat(1000), false)));
+
+        match builder.check_length() {
+            Err(Error::Model(ModelError::EmbedTooLarge(6))) => {},
+            other => panic!("expected 6 over the total, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_check_embeds_length() {
+        let embed = |title: &str| {
+            let mut builder = CreateEmbed::default();
+            builder.title(title);
+
+            builder
+        };
+
+        let mut map = HashMap::new();
+        push_embeds(&mut map, (0..9).map(|i| embed(&i.to_string())));
+        push_embeds(&mut map, vec![embed("9")]);
+
+        let json = utils::hashmap_to_json_map(map.clone());
+        assert_eq!(json["embeds"].as_array().map(Vec::len), Some(10));
+        assert_eq!(json["embeds"][9]["title"], json!("9"));
+        assert!(check_embeds_length(&json).is_ok());
+
+        push_embeds(&mut map, vec![embed("10"), embed("11")]);
+
+        match check_embeds_length(&utils::hashmap_to_json_map(map)) {
+            Err(Error::Model(ModelError::TooManyEmbeds(2))) => {},
+            other => panic!("expected 2 embeds over, got {:?}", other),
+        }
+
+        let mut map = HashMap::new();
+        push_embeds(&mut map, vec![embed("a"), embed(&"b".repeat(257))]);
+
+        match check_embeds_length(&utils::hashmap_to_json_map(map)) {
+            Err(Error::Model(ModelError::EmbedTooLarge(1))) => {},
+            other => panic!("expected the second title to be 1 over, got {:?}", other),
+        }
+    }
 }
@@ -1,7 +1,8 @@
+use crate::constants;
 use crate::internal::prelude::*;
 use crate::http::AttachmentType;
-use crate::model::channel::ReactionType;
-use super::CreateEmbed;
+use crate::model::{channel::ReactionType, ModelError};
+use super::{check_embeds_length, push_embeds, CreateEmbed};
 use crate::utils;
 
 use std::collections::HashMap;
@@ -11,7 +12,7 @@ use std::collections::HashMap;
 ///
 /// There are two situations where different field requirements are present:
 ///
-/// 1. When sending an [`embed`], no other field is required;
+/// 1. When sending one or more [`embed`]s, no other field is required;
 /// 2. Otherwise, [`content`] is the only required field that is required to be
 /// set.
 ///
@@ -70,17 +71,47 @@ impl<'a> CreateMessage<'a> {
     }
 
     /// Set an embed for the message.
+    ///
+    /// This replaces any embeds added previously. To send multiple embeds,
+    /// use [`add_embed`] instead.
+    ///
+    /// [`add_embed`]: #method.add_embed
     pub fn embed<F>(&mut self, f: F) -> &mut Self
+    where F: FnOnce(&mut CreateEmbed) -> &mut CreateEmbed {
+        self.0.remove("embeds");
+        self.add_embed(f)
+    }
+
+    /// Appends an embed to the message.
+    ///
+    /// **Note**: A message may have at most 10 embeds.
+    pub fn add_embed<F>(&mut self, f: F) -> &mut Self
     where F: FnOnce(&mut CreateEmbed) -> &mut CreateEmbed {
         let mut embed = CreateEmbed::default();
         f(&mut embed);
-        let map = utils::hashmap_to_json_map(embed.0);
-        let embed = Value::Object(map);
 
-        self.0.insert("embed", embed);
+        self.add_embeds(vec![embed])
+    }
+
+    /// Appends a list of embeds to the message.
+    ///
+    /// **Note**: A message may have at most 10 embeds.
+    pub fn add_embeds(&mut self, embeds: Vec<CreateEmbed>) -> &mut Self {
+        push_embeds(&mut self.0, embeds);
         self
     }
 
+    /// Sets a list of embeds to include in the message.
+    ///
+    /// Calling this multiple times will overwrite the embed list.
+    /// To append embeds, call `add_embed` or `add_embeds` instead.
+    ///
+    /// **Note**: A message may have at most 10 embeds.
+    pub fn set_embeds(&mut self, embeds: Vec<CreateEmbed>) -> &mut Self {
+        self.0.remove("embeds");
+        self.add_embeds(embeds)
+    }
+
     /// Set whether the message is text-to-speech.
     ///
     /// Think carefully before setting this to `true`.
@@ -114,6 +145,40 @@ impl<'a> CreateMessage<'a> {
         self
     }
 
+    /// Checks that the content and embeds of the message are within Discord's
+    /// limits.
+    ///
+    /// This is done automatically by [`ChannelId::send_message`], but may be
+    /// used to validate a message ahead of time.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ModelError::MessageTooLong`] if the content is over 2000
+    /// unicode code points, a [`ModelError::TooManyEmbeds`] if there are more
+    /// than 10 embeds, or a [`ModelError::EmbedTooLarge`] or
+    /// [`ModelError::TooManyEmbedFields`] if an embed is over one of the limits
+    /// checked by [`CreateEmbed::check_length`].
+    ///
+    /// [`ChannelId::send_message`]: ../model/id/struct.ChannelId.html#method.send_message
+    /// [`CreateEmbed::check_length`]: struct.CreateEmbed.html#method.check_length
+    /// [`ModelError::EmbedTooLarge`]: ../model/error/enum.Error.html#variant.EmbedTooLarge
+    /// [`ModelError::MessageTooLong`]: ../model/error/enum.Error.html#variant.MessageTooLong
+    /// [`ModelError::TooManyEmbedFields`]: ../model/error/enum.Error.html#variant.TooManyEmbedFields
+    /// [`ModelError::TooManyEmbeds`]: ../model/error/enum.Error.html#variant.TooManyEmbeds
+    pub fn check_length(&self) -> Result<()> {
+        if let Some(&Value::String(ref content)) = self.0.get("content") {
+            let count = content.chars().count();
+
+            if count > constants::MESSAGE_CODE_LIMIT as usize {
+                let overflow = (count - constants::MESSAGE_CODE_LIMIT as usize) as u64;
+
+                return Err(Error::Model(ModelError::MessageTooLong(overflow)));
+            }
+        }
+
+        check_embeds_length(&utils::hashmap_to_json_map(self.0.clone()))
+    }
+
     /// Sets a list of files to include in the message.
     ///
     /// Calling this multiple times will overwrite the file list.
@@ -1,5 +1,8 @@
 use crate::internal::prelude::*;
+use crate::model::channel::{PermissionOverwrite, PermissionOverwriteType};
 use crate::model::id::ChannelId;
+use serde_json::json;
+use super::validation::check_channel;
 use std::collections::HashMap;
 
 /// A builder to edit a [`GuildChannel`] for use via [`GuildChannel::edit`]
@@ -35,7 +38,7 @@ impl EditChannel {
 
     /// The name of the channel.
     ///
-    /// Must be between 2 and 100 characters long.
+    /// Must be between 1 and 100 characters long.
     pub fn name<S: ToString>(&mut self, name: S) -> &mut Self {
         self.0.insert("name", Value::String(name.to_string()));
         self
@@ -99,13 +102,82 @@ impl EditChannel {
         });
     }
 
+    /// A set of overwrites defining what a user or a user carrying a certain
+    /// role can and cannot do, replacing all of the channel's current
+    /// overwrites.
+    ///
+    /// # Example
+    ///
+    /// Copying the permissions of an existing channel:
+    ///
+    /// ```rust,ignore
+    /// // Assuming two channels have already been bound.
+    /// channel.edit(&context, |c| c.permissions(other.permission_overwrites.clone()));
+    /// ```
+    pub fn permissions<I>(&mut self, perms: I) -> &mut Self
+        where I: IntoIterator<Item=PermissionOverwrite>
+    {
+        let overwrites = perms.into_iter().map(|perm| {
+            let (id, kind) = match perm.kind {
+                PermissionOverwriteType::Member(id) => (id.0, "member"),
+                PermissionOverwriteType::Role(id) => (id.0, "role"),
+                PermissionOverwriteType::__Nonexhaustive => unreachable!(),
+            };
+
+            json!({
+                "allow": perm.allow.bits(),
+                "deny": perm.deny.bits(),
+                "id": id,
+                "type": kind,
+            })
+        }).collect();
+
+        self.0.insert("permission_overwrites", Value::Array(overwrites));
+
+        self
+    }
+
     /// The seconds a user has to wait before sending another message.
     ///
-    /// **Info**: Only values from 0 to 120 are valid.
+    /// Bots, or users with the `MANAGE_MESSAGES` and/or `MANAGE_CHANNEL`
+    /// permissions are exempt from this restriction.
+    ///
+    /// **Note**: Must be between 0 and 21600 seconds (360 minutes or 6 hours).
     #[inline]
-    pub fn slow_mode_rate(&mut self, seconds: u64) -> &mut Self {
+    pub fn rate_limit_per_user(&mut self, seconds: u64) -> &mut Self {
         self.0.insert("rate_limit_per_user", Value::Number(Number::from(seconds)));
 
         self
     }
+
+    /// The seconds a user has to wait before sending another message.
+    #[deprecated(since="0.7.3", note="Please use `rate_limit_per_user` instead.")]
+    #[inline]
+    pub fn slow_mode_rate(&mut self, seconds: u64) -> &mut Self {
+        self.rate_limit_per_user(seconds)
+    }
+
+    /// Checks that the edited fields are within Discord's limits.
+    ///
+    /// This is done automatically by [`GuildChannel::edit`], but may be used
+    /// to validate an edit ahead of time.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ModelError::NameTooShort`] if the name is empty, a
+    /// [`ModelError::NameTooLong`] or [`ModelError::TopicTooLong`] if the
+    /// name or topic is over its limit, or a [`ModelError::UserLimitAmount`]
+    /// or [`ModelError::RateLimitAmount`] if the user limit or slow mode rate
+    /// is over its limit.
+    ///
+    /// [`GuildChannel::edit`]: ../model/channel/struct.GuildChannel.html#method.edit
+    /// [`ModelError::NameTooLong`]: ../model/error/enum.Error.html#variant.NameTooLong
+    /// [`ModelError::NameTooShort`]: ../model/error/enum.Error.html#variant.NameTooShort
+    /// [`ModelError::RateLimitAmount`]: ../model/error/enum.Error.html#variant.RateLimitAmount
+    /// [`ModelError::TopicTooLong`]: ../model/error/enum.Error.html#variant.TopicTooLong
+    /// [`ModelError::UserLimitAmount`]: ../model/error/enum.Error.html#variant.UserLimitAmount
+    #[inline]
+    pub fn validate(&self) -> Result<()> {
+        check_channel(&self.0)
+    }
 }
@@ -1,6 +1,8 @@
 use crate::internal::prelude::*;
-use super::CreateEmbed;
-use crate::utils;
+use crate::http::AttachmentType;
+use crate::model::{channel::MessageFlags, id::AttachmentId};
+use super::{push_embeds, CreateEmbed};
+use serde_json::json;
 
 use std::collections::HashMap;
 
@@ -30,11 +32,17 @@ use std::collections::HashMap;
 /// # fn main() {}
 /// ```
 ///
+/// **Note**: Changing the attachments of a message, via [`attachment`] and
+/// similar, or [`add_file`], is not part of the documented version 6 of the
+/// API used by the library, and may be ignored by Discord.
+///
 /// [`Message`]: ../model/channel/struct.Message.html
+/// [`add_file`]: #method.add_file
+/// [`attachment`]: #method.attachment
 #[derive(Clone, Debug, Default)]
-pub struct EditMessage(pub HashMap<&'static str, Value>);
+pub struct EditMessage<'a>(pub HashMap<&'static str, Value>, pub Vec<AttachmentType<'a>>);
 
-impl EditMessage {
+impl<'a> EditMessage<'a> {
     /// Set the content of the message.
     ///
     /// **Note**: Message contents must be under 2000 unicode code points.
@@ -45,14 +53,164 @@ impl EditMessage {
     }
 
     /// Set an embed for the message.
+    ///
+    /// This replaces any embeds added previously. To set multiple embeds,
+    /// use [`add_embed`] instead.
+    ///
+    /// [`add_embed`]: #method.add_embed
     pub fn embed<F>(&mut self, f: F) -> &mut Self
     where F: FnOnce(&mut CreateEmbed) -> &mut CreateEmbed {
-        let mut create_embed = CreateEmbed::default();
-        f(&mut create_embed);
-        let map = utils::hashmap_to_json_map(create_embed.0);
-        let embed = Value::Object(map);
+        self.0.remove("embeds");
+        self.add_embed(f)
+    }
+
+    /// Appends an embed to the message.
+    ///
+    /// **Note**: A message may have at most 10 embeds.
+    pub fn add_embed<F>(&mut self, f: F) -> &mut Self
+    where F: FnOnce(&mut CreateEmbed) -> &mut CreateEmbed {
+        let mut embed = CreateEmbed::default();
+        f(&mut embed);
+
+        self.add_embeds(vec![embed])
+    }
+
+    /// Appends a list of embeds to the message.
+    ///
+    /// **Note**: A message may have at most 10 embeds.
+    pub fn add_embeds(&mut self, embeds: Vec<CreateEmbed>) -> &mut Self {
+        push_embeds(&mut self.0, embeds);
+        self
+    }
+
+    /// Sets the list of embeds of the message, replacing its current embeds.
+    ///
+    /// Passing an empty list removes all embeds from the message.
+    ///
+    /// **Note**: A message may have at most 10 embeds.
+    pub fn set_embeds(&mut self, embeds: Vec<CreateEmbed>) -> &mut Self {
+        self.0.insert("embeds", Value::Array(Vec::new()));
+        self.add_embeds(embeds)
+    }
+
+    /// Keeps an existing attachment of the message.
+    ///
+    /// Once any attachment is kept, or [`remove_all_attachments`] is called,
+    /// every existing attachment which is not kept is removed. If neither is
+    /// done, all existing attachments are left untouched.
+    ///
+    /// **Note**: [`Message::edit`] keeps all current attachments of the
+    /// message by default, such that only [`remove_attachment`] is needed to
+    /// remove one. The attachments to keep are only sent if they were changed.
+    ///
+    /// [`Message::edit`]: ../model/channel/struct.Message.html#method.edit
+    /// [`remove_all_attachments`]: #method.remove_all_attachments
+    /// [`remove_attachment`]: #method.remove_attachment
+    pub fn attachment<A: Into<AttachmentId>>(&mut self, attachment: A) -> &mut Self {
+        self._attachment(attachment.into());
+        self
+    }
+
+    fn _attachment(&mut self, attachment: AttachmentId) {
+        let value = json!({ "id": attachment.to_string() });
+
+        match self.0.entry("attachments").or_insert_with(|| Value::Array(Vec::new())) {
+            Value::Array(ref mut attachments) => {
+                if !attachments.contains(&value) {
+                    attachments.push(value);
+                }
+            },
+            other => *other = Value::Array(vec![value]),
+        }
+    }
+
+    /// Removes an attachment kept via [`attachment`].
+    ///
+    /// [`attachment`]: #method.attachment
+    pub fn remove_attachment<A: Into<AttachmentId>>(&mut self, attachment: A) -> &mut Self {
+        let id = Value::String(attachment.into().to_string());
+
+        if let Some(&mut Value::Array(ref mut attachments)) = self.0.get_mut("attachments") {
+            attachments.retain(|a| a.get("id") != Some(&id));
+        }
+
+        self
+    }
+
+    /// Removes all existing attachments of the message.
+    ///
+    /// Files added via [`add_file`] are still uploaded.
+    ///
+    /// [`add_file`]: #method.add_file
+    pub fn remove_all_attachments(&mut self) -> &mut Self {
+        self.0.insert("attachments", Value::Array(Vec::new()));
+        self
+    }
 
-        self.0.insert("embed", embed);
+    /// Appends a new file to upload to the message.
+    pub fn add_file<T: Into<AttachmentType<'a>>>(&mut self, file: T) -> &mut Self {
+        self.1.push(file.into());
+        self
+    }
+
+    /// Appends a list of new files to upload to the message.
+    pub fn add_files<T: Into<AttachmentType<'a>>, It: IntoIterator<Item=T>>(&mut self, files: It) -> &mut Self {
+        self.1.extend(files.into_iter().map(|f| f.into()));
+        self
+    }
+
+    /// Set the flags of the message.
+    ///
+    /// **Note**: Only [`SUPPRESS_EMBEDS`] can currently be changed by
+    /// editing a message, any other flags are ignored by Discord.
+    ///
+    /// [`SUPPRESS_EMBEDS`]: ../model/channel/struct.MessageFlags.html#associatedconstant.SUPPRESS_EMBEDS
+    #[inline]
+    pub fn flags(&mut self, flags: MessageFlags) -> &mut Self {
+        self.0.insert("flags", Value::Number(Number::from(flags.bits())));
         self
     }
+
+    /// Suppress or unsuppress the embeds of the message.
+    ///
+    /// This is a shorthand for setting the [`SUPPRESS_EMBEDS`] flag via
+    /// [`flags`].
+    ///
+    /// **Note**: Suppressing the embeds of another user's message requires
+    /// the [Manage Messages] permission.
+    ///
+    /// [`SUPPRESS_EMBEDS`]: ../model/channel/struct.MessageFlags.html#associatedconstant.SUPPRESS_EMBEDS
+    /// [`flags`]: #method.flags
+    /// [Manage Messages]: ../model/permissions/struct.Permissions.html#associatedconstant.MANAGE_MESSAGES
+    #[inline]
+    pub fn suppress_embeds(&mut self, suppress: bool) -> &mut Self {
+        let flags = if suppress {
+            MessageFlags::SUPPRESS_EMBEDS
+        } else {
+            MessageFlags::empty()
+        };
+
+        self.flags(flags)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+    use super::EditMessage;
+
+    #[test]
+    fn test_attachments() {
+        let mut builder = EditMessage::default();
+        assert!(builder.0.get("attachments").is_none());
+
+        builder.attachment(1).attachment(2).attachment(1).remove_attachment(2);
+        assert_eq!(builder.0["attachments"], json!([{ "id": "1" }]));
+
+        builder.remove_all_attachments();
+        assert_eq!(builder.0["attachments"], json!([]));
+
+        builder.add_file("./file.txt").add_files(vec!["./a.png", "./b.png"]);
+        assert_eq!(builder.1.len(), 3);
+    }
 }
@@ -1,9 +1,11 @@
+use crate::constants;
 use crate::internal::prelude::*;
 use crate::model::{
     guild::Role,
     Permissions
 };
 use std::collections::HashMap;
+use super::validation::check_name;
 
 /// A builder to create or edit a [`Role`] for use via a number of model methods.
 ///
@@ -93,6 +95,8 @@ impl EditRole {
     }
 
     /// The name of the role to set.
+    ///
+    /// **Note**: Must be between 1 and 100 characters long.
     pub fn name<S: ToString>(&mut self, name: S) -> &mut Self {
         self.0.insert("name", Value::String(name.to_string()));
         self
@@ -113,4 +117,21 @@ impl EditRole {
         self.0.insert("position", Value::Number(Number::from(position)));
         self
     }
+
+    /// Checks that the role is within Discord's limits.
+    ///
+    /// This is done automatically when creating or editing a role, but may be
+    /// used to validate a role ahead of time.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ModelError::NameTooShort`] if the name is empty, or a
+    /// [`ModelError::NameTooLong`] if it is over 100 unicode code points.
+    ///
+    /// [`ModelError::NameTooLong`]: ../model/error/enum.Error.html#variant.NameTooLong
+    /// [`ModelError::NameTooShort`]: ../model/error/enum.Error.html#variant.NameTooShort
+    #[inline]
+    pub fn validate(&self) -> Result<()> {
+        check_name(&self.0, constants::ROLE_NAME_LIMIT)
+    }
 }
@@ -1,5 +1,6 @@
 use serde_json::Value;
 use std::collections::HashMap;
+use super::{push_embeds, CreateEmbed};
 
 /// A builder to create the inner content of a [`Webhook`]'s execution.
 ///
@@ -124,6 +125,38 @@ impl ExecuteWebhook {
         self
     }
 
+    /// Appends an embed to the message.
+    ///
+    /// **Note**: A message may have at most 10 embeds.
+    pub fn add_embed<F>(&mut self, f: F) -> &mut Self
+    where F: FnOnce(&mut CreateEmbed) -> &mut CreateEmbed {
+        let mut embed = CreateEmbed::default();
+        f(&mut embed);
+
+        self.add_embeds(vec![embed])
+    }
+
+    /// Appends a list of embeds to the message.
+    ///
+    /// **Note**: A message may have at most 10 embeds.
+    pub fn add_embeds(&mut self, embeds: Vec<CreateEmbed>) -> &mut Self {
+        push_embeds(&mut self.0, embeds);
+        self
+    }
+
+    /// Sets a list of embeds to include in the message.
+    ///
+    /// Unlike [`embeds`], this takes embed builders rather than fake embeds.
+    /// Calling this multiple times will overwrite the embed list.
+    ///
+    /// **Note**: A message may have at most 10 embeds.
+    ///
+    /// [`embeds`]: #method.embeds
+    pub fn set_embeds(&mut self, embeds: Vec<CreateEmbed>) -> &mut Self {
+        self.0.insert("embeds", Value::Array(Vec::new()));
+        self.add_embeds(embeds)
+    }
+
     /// Whether the message is a text-to-speech message.
     ///
     /// # Examples
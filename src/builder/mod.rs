@@ -17,6 +17,7 @@ mod edit_profile;
 mod edit_role;
 mod execute_webhook;
 mod get_messages;
+mod validation;
 
 pub use self::{
     create_embed::{CreateEmbed, CreateEmbedAuthor, CreateEmbedFooter},
@@ -32,3 +33,5 @@ pub use self::{
     execute_webhook::ExecuteWebhook,
     get_messages::GetMessages
 };
+
+pub(crate) use self::create_embed::{check_embeds_length, push_embeds};
@@ -0,0 +1,61 @@
+//! Checks of builder fields against Discord's limits, shared between
+//! builders.
+
+use crate::constants;
+use crate::internal::prelude::*;
+use crate::model::ModelError;
+
+use std::collections::HashMap;
+
+/// Returns by how many unicode code points the string is over the limit, if
+/// it is.
+fn overflow(value: &str, limit: u16) -> Option<u64> {
+    let count = value.chars().count();
+
+    if count > limit as usize {
+        Some((count - limit as usize) as u64)
+    } else {
+        None
+    }
+}
+
+/// Checks that the `name` of a builder, if set, is not empty and within the
+/// given limit.
+pub(crate) fn check_name(map: &HashMap<&'static str, Value>, limit: u16) -> Result<()> {
+    if let Some(&Value::String(ref name)) = map.get("name") {
+        if name.trim().is_empty() {
+            return Err(Error::Model(ModelError::NameTooShort));
+        }
+
+        if let Some(overflow) = overflow(name, limit) {
+            return Err(Error::Model(ModelError::NameTooLong(overflow)));
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks the fields shared by the channel builders against Discord's limits.
+pub(crate) fn check_channel(map: &HashMap<&'static str, Value>) -> Result<()> {
+    check_name(map, constants::CHANNEL_NAME_LIMIT)?;
+
+    if let Some(&Value::String(ref topic)) = map.get("topic") {
+        if let Some(overflow) = overflow(topic, constants::CHANNEL_TOPIC_LIMIT) {
+            return Err(Error::Model(ModelError::TopicTooLong(overflow)));
+        }
+    }
+
+    if let Some(limit) = map.get("user_limit").and_then(Value::as_u64) {
+        if limit > u64::from(constants::CHANNEL_USER_LIMIT) {
+            return Err(Error::Model(ModelError::UserLimitAmount(limit)));
+        }
+    }
+
+    if let Some(rate) = map.get("rate_limit_per_user").and_then(Value::as_u64) {
+        if rate > u64::from(constants::CHANNEL_RATE_LIMIT) {
+            return Err(Error::Model(ModelError::RateLimitAmount(rate)));
+        }
+    }
+
+    Ok(())
+}
@@ -830,6 +830,53 @@ impl Cache {
             },
         }
     }
+
+    /// Updates a guild's member with the partial member data sent along with
+    /// messages and reactions, inserting the member if it was not yet
+    /// cached and its user is known.
+    pub(crate) fn update_partial_member(
+        &mut self,
+        guild_id: GuildId,
+        user_id: UserId,
+        partial: &PartialMember,
+    ) {
+        if let Some(ref user) = partial.user {
+            self.update_user_entry(user);
+        }
+
+        let guild = match self.guilds.get(&guild_id) {
+            Some(guild) => guild,
+            None => return,
+        };
+        let mut guild = guild.write();
+
+        if let Some(member) = guild.members.get_mut(&user_id) {
+            member.deaf = partial.deaf;
+            member.mute = partial.mute;
+            member.nick.clone_from(&partial.nick);
+            member.premium_since = partial.premium_since;
+            member.roles.clone_from(&partial.roles);
+
+            if partial.joined_at.is_some() {
+                member.joined_at = partial.joined_at;
+            }
+
+            return;
+        }
+
+        if let Some(user) = self.users.get(&user_id) {
+            guild.members.insert(user_id, Member {
+                deaf: partial.deaf,
+                guild_id,
+                joined_at: partial.joined_at,
+                mute: partial.mute,
+                nick: partial.nick.clone(),
+                premium_since: partial.premium_since,
+                roles: partial.roles.clone(),
+                user: Arc::clone(user),
+            });
+        }
+    }
 }
 
 impl Default for Cache {
@@ -890,7 +937,6 @@ mod test {
                     bot: false,
                     discriminator: 1,
                     name: "user 1".to_owned(),
-                    _nonexhaustive: (),
                 },
                 channel_id: ChannelId(2),
                 guild_id: Some(GuildId(1)),
@@ -912,10 +958,10 @@ mod test {
                 activity: None,
                 application: None,
                 message_reference: None,
+                referenced_message: None,
                 flags: None,
-                _nonexhaustive: (),
+                sticker_items: vec![],
             },
-            _nonexhaustive: (),
         };
         // Check that the channel cache doesn't exist.
         assert!(!cache.messages.contains_key(&event.message.channel_id));
@@ -957,15 +1003,13 @@ mod test {
             topic: None,
             user_limit: None,
             nsfw: false,
-            slow_mode_rate: Some(0),
-            _nonexhaustive: (),
+            rate_limit_per_user: Some(0),
         };
 
         // Add a channel delete event to the cache, the cached messages for that
         // channel should now be gone.
         let mut delete = ChannelDeleteEvent {
             channel: Channel::Guild(Arc::new(RwLock::new(guild_channel.clone()))),
-            _nonexhaustive: (),
         };
         assert!(cache.update(&mut delete).is_none());
         assert!(!cache.messages.contains_key(&delete.channel.id()));
@@ -998,6 +1042,7 @@ mod test {
                     region: String::new(),
                     roles: HashMap::new(),
                     splash: None,
+                    discovery_splash: None,
                     system_channel_id: None,
                     verification_level: VerificationLevel::Low,
                     voice_states: HashMap::new(),
@@ -1008,9 +1053,14 @@ mod test {
                     banner: None,
                     vanity_url_code: Some("bruhmoment".to_string()),
                     preferred_locale: "en-US".to_string(),
-                    _nonexhaustive: (),
+                    stage_instances: vec![],
+                    max_members: None,
+                    max_presences: None,
+                    max_video_channel_users: None,
+                    approximate_member_count: None,
+                    approximate_presence_count: None,
+                    welcome_screen: None,
                 },
-                _nonexhaustive: (),
             }
         };
         assert!(cache.update(&mut guild_create).is_none());
@@ -1033,15 +1083,20 @@ mod test {
                 region: String::new(),
                 roles: HashMap::new(),
                 splash: None,
+                discovery_splash: None,
                 verification_level: VerificationLevel::Low,
                 description: None,
                 premium_tier: Tier2,
                 premium_subscription_count: 12,
                 banner: None,
                 vanity_url_code: Some("bruhmoment".to_string()),
-                _nonexhaustive: (),
+                max_members: None,
+                max_presences: None,
+                max_video_channel_users: None,
+                approximate_member_count: None,
+                approximate_presence_count: None,
+                welcome_screen: None,
             },
-            _nonexhaustive: (),
         };
 
         // The guild existed in the cache, so the cache's guild is returned by the
@@ -57,7 +57,7 @@ mod shard_queuer;
 mod shard_runner;
 mod shard_runner_message;
 
-pub use self::shard_manager::{ShardManager, ShardManagerOptions};
+pub use self::shard_manager::{ShardManager, ShardManagerOptions, ShutdownHandle};
 pub use self::shard_manager_monitor::ShardManagerMonitor;
 pub use self::shard_messenger::ShardMessenger;
 pub use self::shard_queuer::ShardQueuer;
@@ -74,6 +74,7 @@ use std::{
     time::Duration as StdDuration
 };
 use crate::gateway::{ConnectionStage, InterMessage};
+use crate::model::gateway::BotGateway;
 
 /// A message either for a [`ShardManager`] or a [`ShardRunner`].
 ///
@@ -113,6 +114,13 @@ pub enum ShardManagerMessage {
     ///
     /// [`ShardManagerMonitor`]: struct.ShardManagerMonitor.html
     Shutdown(ShardId),
+    /// Indicator that a shard should be shut down without bringing it back up,
+    /// closing its session with a code that leaves the session resumable.
+    ShutdownResumable(ShardId),
+    /// Indicator that all shards should be restarted with the given total
+    /// number of shards, as Discord recommends a different number of shards
+    /// than are in use.
+    Reshard(u64),
     /// Indicator that a [`ShardManagerMonitor`] should fully shutdown all shards
     /// and end its monitoring process for the [`ShardManager`].
     ///
@@ -141,6 +149,15 @@ pub enum ShardQueuerMessage {
     /// Message to start a shard, where the 0-index element is the ID of the
     /// Shard to start and the 1-index element is the total shards in use.
     Start(ShardId, ShardId),
+    /// Message for the shard queuer to adapt the starts of shards to the
+    /// session start limit, and to restart all shards with the number of
+    /// shards recommended by Discord if that changes while shards are
+    /// disconnected for a long time.
+    ///
+    /// This is sent when [autosharding].
+    ///
+    /// [autosharding]: ../../struct.Client.html#method.start_autosharded
+    Autoshard(BotGateway),
     /// Message to shutdown the shard queuer.
     Shutdown,
 }
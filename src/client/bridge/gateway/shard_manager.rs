@@ -1,5 +1,7 @@
 use crate::gateway::InterMessage;
 use crate::internal::prelude::*;
+use crate::model::prelude::*;
+use crate::utils;
 use crate::CacheAndHttp;
 use parking_lot::Mutex;
 use parking_lot::RwLock;
@@ -10,7 +12,7 @@ use std::{
         Arc,
     },
     thread,
-    time::Duration
+    time::{Duration, Instant},
 };
 use super::super::super::{EventHandler, RawEventHandler};
 use super::{
@@ -18,12 +20,13 @@ use super::{
     ShardId,
     ShardManagerMessage,
     ShardManagerMonitor,
+    ShardMessenger,
     ShardQueuer,
     ShardQueuerMessage,
     ShardRunnerInfo,
 };
 use threadpool::ThreadPool;
-use typemap::ShareMap;
+use crate::client::{ErrorHook, EventQueue, TypeMap};
 use log::{info, warn};
 
 #[cfg(feature = "framework")]
@@ -31,6 +34,12 @@ use crate::framework::Framework;
 #[cfg(feature = "voice")]
 use crate::client::bridge::voice::ClientVoiceManager;
 
+/// How often [`ShardManager::shutdown_all_gracefully`] checks whether running
+/// handlers have finished.
+///
+/// [`ShardManager::shutdown_all_gracefully`]: struct.ShardManager.html#method.shutdown_all_gracefully
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
 /// A manager for handling the status of shards by starting them, restarting
 /// them, and stopping them when required.
 ///
@@ -58,12 +67,10 @@ use crate::client::bridge::voice::ClientVoiceManager;
 /// use parking_lot::{Mutex, RwLock};
 /// use serenity::client::bridge::gateway::{ShardManager, ShardManagerOptions};
 /// use serenity::client::{EventHandler, RawEventHandler};
-/// // Of note, this imports `typemap`'s `ShareMap` type.
+/// // Of note, this imports the `TypeMap` type.
 /// use serenity::prelude::*;
 /// use serenity::http::Http;
 /// use serenity::CacheAndHttp;
-/// // Of note, this imports `typemap`'s `ShareMap` type.
-/// use serenity::prelude::*;
 /// use std::sync::Arc;
 /// use std::env;
 /// use threadpool::ThreadPool;
@@ -76,7 +83,7 @@ use crate::client::bridge::voice::ClientVoiceManager;
 /// # let cache_and_http = Arc::new(CacheAndHttp::default());
 /// # let http = &cache_and_http.http;
 /// let gateway_url = Arc::new(Mutex::new(http.get_gateway()?.url));
-/// let data = Arc::new(RwLock::new(ShareMap::custom()));
+/// let data = Arc::new(RwLock::new(TypeMap::new()));
 /// let event_handler = Arc::new(Handler);
 /// let framework = Arc::new(Mutex::new(None));
 /// let threadpool = ThreadPool::with_name("my threadpool".to_owned(), 5);
@@ -129,13 +136,39 @@ pub struct ShardManager {
     shard_total: u64,
     shard_queuer: Sender<ShardQueuerMessage>,
     shard_shutdown: Receiver<ShardId>,
+    threadpool: ThreadPool,
 }
 
 impl ShardManager {
     /// Creates a new shard manager, returning both the manager and a monitor
     /// for usage in a separate thread.
+    ///
+    /// Event handlers are run without the limits of an [`EventQueue`], and
+    /// errors are not reported to any hook. Use [`new_with_hooks`] to provide
+    /// them.
+    ///
+    /// [`EventQueue`]: ../../struct.EventQueue.html
+    /// [`new_with_hooks`]: #method.new_with_hooks
+    #[inline]
     pub fn new<H, RH>(
         opt: ShardManagerOptions<'_, H, RH>,
+    ) -> (Arc<Mutex<Self>>, ShardManagerMonitor)
+        where H: EventHandler + Send + Sync + 'static,
+              RH: RawEventHandler + Send + Sync + 'static {
+        Self::new_with_hooks(opt, &Arc::new(EventQueue::default()), &ErrorHook::new())
+    }
+
+    /// Creates a new shard manager like [`new`], running event handlers
+    /// through the given [`EventQueue`] and reporting errors to the given
+    /// [`ErrorHook`].
+    ///
+    /// [`ErrorHook`]: ../../struct.ErrorHook.html
+    /// [`EventQueue`]: ../../struct.EventQueue.html
+    /// [`new`]: #method.new
+    pub fn new_with_hooks<H, RH>(
+        opt: ShardManagerOptions<'_, H, RH>,
+        event_queue: &Arc<EventQueue>,
+        error_hook: &ErrorHook,
     ) -> (Arc<Mutex<Self>>, ShardManagerMonitor)
         where H: EventHandler + Send + Sync + 'static,
               RH: RawEventHandler + Send + Sync + 'static {
@@ -155,11 +188,15 @@ impl ShardManager {
             queue: VecDeque::new(),
             runners: Arc::clone(&runners),
             rx: shard_queue_rx,
-            threadpool: opt.threadpool,
+            threadpool: opt.threadpool.clone(),
+            event_queue: Arc::clone(event_queue),
+            error_hook: error_hook.clone(),
             #[cfg(feature = "voice")]
             voice_manager: Arc::clone(opt.voice_manager),
             ws_url: Arc::clone(opt.ws_url),
             cache_and_http: Arc::clone(&opt.cache_and_http),
+            autoshard: None,
+            failing_since: None,
         };
 
         thread::spawn(move || {
@@ -175,6 +212,7 @@ impl ShardManager {
             shard_total: opt.shard_total,
             shard_shutdown: shutdown_recv,
             runners,
+            threadpool: opt.threadpool,
         }));
 
         (Arc::clone(&manager), ShardManagerMonitor {
@@ -227,6 +265,45 @@ impl ShardManager {
         self.shard_total = total;
     }
 
+    /// Restarts all shards with a new total number of shards, making the
+    /// manager responsible for all of them.
+    ///
+    /// Unlike [`set_shards`], this only restarts the shard runners, keeping
+    /// the [`ShardQueuer`] and the [`ShardManagerMonitor`] running.
+    ///
+    /// [`ShardManagerMonitor`]: struct.ShardManagerMonitor.html
+    /// [`ShardQueuer`]: struct.ShardQueuer.html
+    /// [`set_shards`]: #method.set_shards
+    pub fn reshard(&mut self, total: u64) {
+        info!("Resharding to {} shards", total);
+
+        for shard_id in self.shards_instantiated() {
+            self.shutdown(shard_id);
+        }
+
+        self.shard_index = 0;
+        self.shard_init = total;
+        self.shard_total = total;
+
+        if let Err(why) = self.initialize() {
+            warn!("Failed to reshard to {} shards: {:?}", total, why);
+        }
+    }
+
+    /// Makes the [`ShardQueuer`] adapt the starts of shards to the session
+    /// start limit, and restart all shards with the number of shards
+    /// recommended by Discord if that changes while shards fail to connect
+    /// for a long time.
+    ///
+    /// This is used by [`Client::start_autosharded`], and should only be used
+    /// when the manager is responsible for all shards.
+    ///
+    /// [`Client::start_autosharded`]: ../../struct.Client.html#method.start_autosharded
+    /// [`ShardQueuer`]: struct.ShardQueuer.html
+    pub fn set_autosharding(&mut self, info: BotGateway) {
+        let _ = self.shard_queuer.send(ShardQueuerMessage::Autoshard(info));
+    }
+
     /// Restarts a shard runner.
     ///
     /// This sends a shutdown signal to a shard's associated [`ShardRunner`],
@@ -289,10 +366,21 @@ impl ShardManager {
     /// know it should shut down. This _should never happen_. It may already be
     /// stopped.
     pub fn shutdown(&mut self, shard_id: ShardId) -> bool {
+        self.shutdown_with(shard_id, ShardManagerMessage::Shutdown(shard_id))
+    }
+
+    /// Attempts to shut down the shard runner by Id, like [`shutdown`], but
+    /// closes the gateway session in a way that Discord keeps it resumable.
+    ///
+    /// [`shutdown`]: #method.shutdown
+    pub fn shutdown_resumable(&mut self, shard_id: ShardId) -> bool {
+        self.shutdown_with(shard_id, ShardManagerMessage::ShutdownResumable(shard_id))
+    }
+
+    fn shutdown_with(&mut self, shard_id: ShardId, shutdown: ShardManagerMessage) -> bool {
         info!("Shutting down shard {}", shard_id);
 
         if let Some(runner) = self.runners.lock().get(&shard_id) {
-            let shutdown = ShardManagerMessage::Shutdown(shard_id);
             let client_msg = ShardClientMessage::Manager(shutdown);
             let msg = InterMessage::Client(Box::new(client_msg));
 
@@ -350,6 +438,183 @@ impl ShardManager {
         let _ = self.monitor_tx.send(ShardManagerMessage::ShutdownInitiated);
     }
 
+    /// Shuts down all shards like [`shutdown_all`], but without losing the
+    /// work of event handlers and commands that are still running.
+    ///
+    /// First, every shard's gateway session is closed with a resumable close
+    /// code, so no new events are dispatched. Then this waits up to `timeout`
+    /// for the handlers already running on the threadpool to finish. Only
+    /// then is the [`Client`] told to stop, returning from its `start`
+    /// method.
+    ///
+    /// The manager is only locked while the shards are told to close, and not
+    /// while waiting, so that running handlers may still use it.
+    ///
+    /// Returns whether all running handlers finished before the timeout.
+    ///
+    /// **Note**: When called from within an event handler or command, that
+    /// handler is itself waited for, so the full timeout passes. Call this
+    /// from a separate thread in that case.
+    ///
+    /// # Examples
+    ///
+    /// Shut down gracefully on a "quit" command, giving other commands up to
+    /// 30 seconds to finish:
+    ///
+    /// ```rust,ignore
+    /// use std::{thread, time::Duration};
+    ///
+    /// let handle = client.shutdown_handle();
+    ///
+    /// thread::spawn(move || {
+    ///     if !handle.shutdown(Duration::from_secs(30)) {
+    ///         println!("Some commands did not finish in time");
+    ///     }
+    /// });
+    /// ```
+    ///
+    /// See [`ShutdownHandle`] for a cloneable way to call this from anywhere.
+    ///
+    /// [`Client`]: ../../struct.Client.html
+    /// [`ShutdownHandle`]: struct.ShutdownHandle.html
+    /// [`shutdown_all`]: #method.shutdown_all
+    pub fn shutdown_all_gracefully(manager: &Arc<Mutex<Self>>, timeout: Duration) -> bool {
+        let (threadpool, monitor_tx) = {
+            let mut manager = manager.lock();
+            let keys = manager.runners.lock().keys().cloned().collect::<Vec<_>>();
+
+            info!("Gracefully shutting down all shards");
+
+            for shard_id in keys {
+                manager.shutdown_resumable(shard_id);
+            }
+
+            let _ = manager.shard_queuer.send(ShardQueuerMessage::Shutdown);
+
+            (manager.threadpool.clone(), manager.monitor_tx.clone())
+        };
+
+        // `ThreadPool::join` has no timeout, so the pool is polled until it
+        // is idle instead.
+        let deadline = Instant::now() + timeout;
+
+        let drained = loop {
+            if threadpool.active_count() == 0 && threadpool.queued_count() == 0 {
+                break true;
+            }
+
+            let now = Instant::now();
+
+            if now >= deadline {
+                break false;
+            }
+
+            thread::sleep(DRAIN_POLL_INTERVAL.min(deadline - now));
+        };
+
+        if !drained {
+            warn!("Timed out waiting for event handlers to finish");
+        }
+
+        let _ = monitor_tx.send(ShardManagerMessage::ShutdownInitiated);
+
+        drained
+    }
+
+    /// Retrieves a [`ShardMessenger`] for communicating with the shard, if it
+    /// is running.
+    ///
+    /// [`ShardMessenger`]: struct.ShardMessenger.html
+    pub fn messenger(&self, shard_id: ShardId) -> Option<ShardMessenger> {
+        self.runners
+            .lock()
+            .get(&shard_id)
+            .map(|runner| ShardMessenger::new(runner.runner_tx.clone()))
+    }
+
+    /// Calls `f` with a [`ShardMessenger`] for each running shard.
+    ///
+    /// # Examples
+    ///
+    /// Setting the status of all shards from outside of any [`Context`], such
+    /// as a thread rotating the status periodically:
+    ///
+    /// ```rust,ignore
+    /// use serenity::model::user::OnlineStatus;
+    ///
+    /// client.shard_manager.lock().broadcast(|shard| {
+    ///     shard.set_status(OnlineStatus::Idle);
+    /// });
+    /// ```
+    ///
+    /// [`Context`]: ../../struct.Context.html
+    /// [`ShardMessenger`]: struct.ShardMessenger.html
+    pub fn broadcast<F>(&self, mut f: F) where F: FnMut(&ShardMessenger) {
+        for runner in self.runners.lock().values() {
+            f(&ShardMessenger::new(runner.runner_tx.clone()));
+        }
+    }
+
+    /// Sets the current activity on all running shards.
+    ///
+    /// Refer to [`ShardMessenger::set_activity`] for more information.
+    ///
+    /// [`ShardMessenger::set_activity`]: struct.ShardMessenger.html#method.set_activity
+    pub fn set_activity(&self, activity: Option<Activity>) {
+        self.broadcast(|shard| shard.set_activity(activity.clone()));
+    }
+
+    /// Sets the full presence on all running shards.
+    ///
+    /// Refer to [`ShardMessenger::set_presence`] for more information.
+    ///
+    /// [`ShardMessenger::set_presence`]: struct.ShardMessenger.html#method.set_presence
+    pub fn set_presence<I>(&self, activities: I, status: OnlineStatus)
+        where I: IntoIterator<Item = Activity> {
+        let activities = activities.into_iter().collect::<Vec<_>>();
+
+        self.broadcast(|shard| shard.set_presence(activities.clone(), status));
+    }
+
+    /// Sets the online status on all running shards.
+    ///
+    /// Refer to [`ShardMessenger::set_status`] for more information.
+    ///
+    /// [`ShardMessenger::set_status`]: struct.ShardMessenger.html#method.set_status
+    pub fn set_status(&self, status: OnlineStatus) {
+        self.broadcast(|shard| shard.set_status(status));
+    }
+
+    /// Requests that the guild's members be chunked, through the shard
+    /// responsible for the guild.
+    ///
+    /// Returns whether that shard is managed by this manager and running.
+    ///
+    /// Refer to [`ShardMessenger::chunk_guilds`] for more information.
+    ///
+    /// [`ShardMessenger::chunk_guilds`]: struct.ShardMessenger.html#method.chunk_guilds
+    pub fn chunk_guild(
+        &self,
+        guild_id: GuildId,
+        limit: Option<u16>,
+        query: Option<String>,
+    ) -> bool {
+        if self.shard_total == 0 {
+            return false;
+        }
+
+        let shard_id = ShardId(utils::shard_id(guild_id.0, self.shard_total));
+
+        match self.messenger(shard_id) {
+            Some(shard) => {
+                shard.chunk_guilds(vec![guild_id], limit, query);
+
+                true
+            },
+            None => false,
+        }
+    }
+
     fn boot(&mut self, shard_info: [ShardId; 2]) {
         info!("Telling shard queuer to start shard {}", shard_info[0]);
 
@@ -375,8 +640,41 @@ impl Drop for ShardManager {
     }
 }
 
+/// A cloneable handle to gracefully shut down a [`ShardManager`], and with it
+/// the [`Client`] running it, from anywhere, such as another thread or a
+/// command.
+///
+/// Retrieved from a running client with [`Client::shutdown_handle`].
+///
+/// [`Client`]: ../../struct.Client.html
+/// [`Client::shutdown_handle`]: ../../struct.Client.html#method.shutdown_handle
+/// [`ShardManager`]: struct.ShardManager.html
+#[derive(Clone, Debug)]
+pub struct ShutdownHandle(Arc<Mutex<ShardManager>>);
+
+impl ShutdownHandle {
+    /// Creates a handle to the given manager.
+    pub fn new(manager: &Arc<Mutex<ShardManager>>) -> Self {
+        ShutdownHandle(Arc::clone(manager))
+    }
+
+    /// Shuts down all shards gracefully, waiting up to `timeout` for running
+    /// event handlers and commands to finish.
+    ///
+    /// Returns whether they all finished before the timeout.
+    ///
+    /// Refer to [`ShardManager::shutdown_all_gracefully`] for more
+    /// information.
+    ///
+    /// [`ShardManager::shutdown_all_gracefully`]: struct.ShardManager.html#method.shutdown_all_gracefully
+    #[inline]
+    pub fn shutdown(&self, timeout: Duration) -> bool {
+        ShardManager::shutdown_all_gracefully(&self.0, timeout)
+    }
+}
+
 pub struct ShardManagerOptions<'a, H: EventHandler + Send + Sync + 'static, RH: RawEventHandler + Send + Sync + 'static> {
-    pub data: &'a Arc<RwLock<ShareMap>>,
+    pub data: &'a Arc<RwLock<TypeMap>>,
     pub event_handler: &'a Option<Arc<H>>,
     pub raw_event_handler: &'a Option<Arc<RH>>,
     #[cfg(feature = "framework")]
@@ -390,3 +688,65 @@ pub struct ShardManagerOptions<'a, H: EventHandler + Send + Sync + 'static, RH:
     pub ws_url: &'a Arc<Mutex<String>>,
     pub cache_and_http: &'a Arc<CacheAndHttp>,
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::gateway::ConnectionStage;
+
+    #[test]
+    fn test_reshard_keeps_queuer_and_monitor() {
+        let (monitor_tx, monitor_rx) = channel();
+        let (queuer_tx, queuer_rx) = channel();
+        let (shutdown_tx, shutdown_rx) = channel();
+        let (runner_tx, runner_rx) = channel();
+
+        let mut runners = HashMap::new();
+        runners.insert(ShardId(0), ShardRunnerInfo {
+            latency: None,
+            runner_tx,
+            stage: ConnectionStage::Connected,
+        });
+
+        let manager = Arc::new(Mutex::new(ShardManager {
+            monitor_tx: monitor_tx.clone(),
+            runners: Arc::new(Mutex::new(runners)),
+            shard_index: 0,
+            shard_init: 1,
+            shard_total: 1,
+            shard_queuer: queuer_tx,
+            shard_shutdown: shutdown_rx,
+            threadpool: ThreadPool::new(1),
+        }));
+
+        let mut monitor = ShardManagerMonitor {
+            manager: Arc::clone(&manager),
+            rx: monitor_rx,
+            shutdown: shutdown_tx,
+        };
+
+        // The runner acknowledges its shutdown right away.
+        monitor.shutdown.send(ShardId(0)).unwrap();
+
+        monitor_tx.send(ShardManagerMessage::Reshard(2)).unwrap();
+        monitor_tx.send(ShardManagerMessage::ShutdownInitiated).unwrap();
+        monitor.run();
+
+        match runner_rx.try_recv() {
+            Ok(InterMessage::Client(msg)) => match *msg {
+                ShardClientMessage::Manager(ShardManagerMessage::Shutdown(ShardId(0))) => {},
+                other => panic!("unexpected runner message: {:?}", other),
+            },
+            other => panic!("unexpected runner message: {:?}", other),
+        }
+
+        let started = queuer_rx.try_iter().map(|msg| match msg {
+            ShardQueuerMessage::Start(id, total) => (id.0, total.0),
+            other => panic!("unexpected queuer message: {:?}", other),
+        }).collect::<Vec<_>>();
+
+        assert_eq!(started, vec![(0, 2), (1, 2)]);
+        assert!(monitor.rx.try_recv().is_err());
+        assert!(manager.lock().runners.lock().is_empty());
+    }
+}
@@ -63,6 +63,12 @@ impl ShardManagerMonitor {
                 ShardManagerMessage::Shutdown(shard_id) => {
                     self.manager.lock().shutdown(shard_id);
                 },
+                ShardManagerMessage::ShutdownResumable(shard_id) => {
+                    self.manager.lock().shutdown_resumable(shard_id);
+                },
+                ShardManagerMessage::Reshard(total) => {
+                    self.manager.lock().reshard(total);
+                },
                 ShardManagerMessage::ShutdownAll => {
                     self.manager.lock().shutdown_all();
 
@@ -4,6 +4,9 @@ use super::{ShardClientMessage, ShardRunnerMessage};
 use std::sync::mpsc::{SendError, Sender};
 use tungstenite::Message;
 
+#[cfg(feature = "collector")]
+use crate::collector::{MessageFilter, ReactionFilter};
+
 /// A lightweight wrapper around an mpsc sender.
 ///
 /// This is used to cleanly communicate with a shard's respective
@@ -150,11 +153,35 @@ impl ShardMessenger {
         let _ = self.send(ShardRunnerMessage::SetActivity(activity));
     }
 
+    /// Sets the user's current activities, replacing any previous ones.
+    ///
+    /// # Examples
+    ///
+    /// Setting the current activities to playing `"Heroes of the Storm"` and
+    /// listening to `"Rust"`:
+    ///
+    /// ```rust,ignore
+    /// use serenity::model::gateway::Activity;
+    ///
+    /// shard.set_activities(vec![
+    ///     Activity::playing("Heroes of the Storm"),
+    ///     Activity::listening("Rust"),
+    /// ]);
+    /// ```
+    pub fn set_activities<I: IntoIterator<Item = Activity>>(&self, activities: I) {
+        let activities = activities.into_iter().collect();
+
+        let _ = self.send(ShardRunnerMessage::SetActivities(activities));
+    }
+
     /// Sets the user's full presence information.
     ///
     /// Consider using the individual setters if you only need to modify one of
     /// these.
     ///
+    /// Any number of activities may be given, such as an `Option<Activity>`
+    /// or a `Vec<Activity>`.
+    ///
     /// # Examples
     ///
     /// Set the current user as playing `"Heroes of the Storm"` and being
@@ -181,12 +208,15 @@ impl ShardMessenger {
     /// #     try_main().unwrap();
     /// # }
     /// ```
-    pub fn set_presence(&self, activity: Option<Activity>, mut status: OnlineStatus) {
+    pub fn set_presence<I>(&self, activities: I, mut status: OnlineStatus)
+        where I: IntoIterator<Item = Activity> {
         if status == OnlineStatus::Offline {
             status = OnlineStatus::Invisible;
         }
 
-        let _ = self.send(ShardRunnerMessage::SetPresence(status, activity));
+        let activities = activities.into_iter().collect();
+
+        let _ = self.send(ShardRunnerMessage::SetPresence(status, activities));
     }
 
     /// Sets the user's current online status.
@@ -252,9 +282,37 @@ impl ShardMessenger {
         let _ = self.send(ShardRunnerMessage::Message(message));
     }
 
+    /// Registers a filter with the shard runner, which sends the messages
+    /// received by the shard that pass it to the filter's collector.
+    ///
+    /// Prefer using a collector, such as [`CollectReply`], over this.
+    ///
+    /// [`CollectReply`]: ../../../collector/struct.CollectReply.html
+    #[cfg(feature = "collector")]
+    pub fn set_message_filter(&self, filter: MessageFilter) {
+        let _ = self.send(ShardRunnerMessage::SetMessageFilter(filter));
+    }
+
+    /// Registers a filter with the shard runner, which sends the reactions
+    /// added and removed on the shard that pass it to the filter's collector.
+    ///
+    /// Prefer using a collector, such as [`CollectReaction`], over this.
+    ///
+    /// [`CollectReaction`]: ../../../collector/struct.CollectReaction.html
+    #[cfg(feature = "collector")]
+    pub fn set_reaction_filter(&self, filter: ReactionFilter) {
+        let _ = self.send(ShardRunnerMessage::SetReactionFilter(filter));
+    }
+
     #[inline]
     fn send(&self, msg: ShardRunnerMessage)
         -> Result<(), SendError<InterMessage>> {
         self.tx.send(InterMessage::Client(Box::new(ShardClientMessage::Runner(msg))))
     }
 }
+
+impl AsRef<ShardMessenger> for ShardMessenger {
+    fn as_ref(&self) -> &ShardMessenger {
+        self
+    }
+}
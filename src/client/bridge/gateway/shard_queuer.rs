@@ -25,9 +25,10 @@ use super::{
     ShardRunnerOptions,
 };
 use threadpool::ThreadPool;
-use typemap::ShareMap;
+use crate::client::{ErrorContext, ErrorHook, EventQueue, TypeMap};
 use crate::gateway::ConnectionStage;
-use log::{info, warn};
+use crate::model::gateway::BotGateway;
+use log::{debug, info, warn};
 
 #[cfg(feature = "voice")]
 use crate::client::bridge::voice::ClientVoiceManager;
@@ -35,6 +36,9 @@ use crate::client::bridge::voice::ClientVoiceManager;
 use crate::framework::Framework;
 
 const WAIT_BETWEEN_BOOTS_IN_SECONDS: u64 = 5;
+// How long starting shards must have failed before the recommended number of
+// shards is queried again when autosharding.
+const REQUERY_SHARDS_AFTER_SECONDS: u64 = 60 * 10;
 
 /// The shard queuer is a simple loop that runs indefinitely to manage the
 /// startup of shards.
@@ -48,7 +52,7 @@ pub struct ShardQueuer<H: EventHandler + Send + Sync + 'static,
     /// dispatching.
     ///
     /// [`Client::data`]: ../../struct.Client.html#structfield.data
-    pub data: Arc<RwLock<ShareMap>>,
+    pub data: Arc<RwLock<TypeMap>>,
     /// A reference to an `EventHandler`, such as the one given to the
     /// [`Client`].
     ///
@@ -87,12 +91,46 @@ pub struct ShardQueuer<H: EventHandler + Send + Sync + 'static,
     /// [`Client`]: ../../struct.Client.html
     /// [`Client::threadpool`]: ../../struct.Client.html#structfield.threadpool
     pub threadpool: ThreadPool,
+    /// The limits on running event handlers in the threadpool, to give shard
+    /// runners.
+    pub event_queue: Arc<EventQueue>,
+    /// The hook to report errors to, to give shard runners.
+    pub error_hook: ErrorHook,
     /// A copy of the client's voice manager.
     #[cfg(feature = "voice")]
     pub voice_manager: Arc<Mutex<ClientVoiceManager>>,
     /// A copy of the URI to use to connect to the gateway.
     pub ws_url: Arc<Mutex<String>>,
     pub cache_and_http: Arc<CacheAndHttp>,
+    pub(super) autoshard: Option<Autoshard>,
+    // When starting a shard began to fail, reset after querying the
+    // recommended number of shards.
+    pub(super) failing_since: Option<Instant>,
+}
+
+// What is known about the gateway while autosharding.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct Autoshard {
+    // The total number of shards the shards are started with.
+    shards: u64,
+    // The number of sessions that can still be started until `resets_at`.
+    remaining: u64,
+    resets_at: Instant,
+    // Whether `remaining` was counted down since it was queried.
+    estimated: bool,
+}
+
+impl Autoshard {
+    fn new(info: &BotGateway) -> Self {
+        let limit = &info.session_start_limit;
+
+        Autoshard {
+            shards: info.shards,
+            remaining: limit.remaining,
+            resets_at: Instant::now() + Duration::from_millis(limit.reset_after),
+            estimated: false,
+        }
+    }
 }
 
 impl<H: EventHandler + Send + Sync + 'static,
@@ -109,6 +147,9 @@ impl<H: EventHandler + Send + Sync + 'static,
     /// that time has passed
     /// 3. Start the shard by ID
     ///
+    /// While autosharding, starting shards is paused when no more sessions
+    /// may be started until the session start limit resets.
+    ///
     /// If a [`ShardQueuerMessage::Shutdown`] is received, this will return and
     /// the loop will be over.
     ///
@@ -131,12 +172,19 @@ impl<H: EventHandler + Send + Sync + 'static,
                 Ok(ShardQueuerMessage::Start(id, total)) => {
                     self.checked_start(id.0, total.0);
                 },
+                Ok(ShardQueuerMessage::Autoshard(info)) => {
+                    self.autoshard = Some(Autoshard::new(&info));
+                },
                 Err(RecvTimeoutError::Disconnected) => {
                     // If the sender half has disconnected then the queuer's
                     // lifespan has passed and can shutdown.
                     break;
                 },
                 Err(RecvTimeoutError::Timeout) => {
+                    if self.queue.is_empty() || !self.has_session_start() {
+                        continue;
+                    }
+
                     if let Some((id, total)) = self.queue.pop_front() {
                         self.checked_start(id, total);
                     }
@@ -166,24 +214,159 @@ impl<H: EventHandler + Send + Sync + 'static,
     }
 
     fn checked_start(&mut self, id: u64, total: u64) {
+        if let Some(autoshard) = self.autoshard {
+            if autoshard.shards != total {
+                debug!("Not starting shard {} of outdated total {}", id, total);
+
+                return;
+            }
+
+            if self.check_recommended_shards() {
+                return;
+            }
+
+            if !self.has_session_start() {
+                info!("Queueing start of shard {} until sessions can be started", id);
+
+                self.queue.push_back((id, total));
+
+                return;
+            }
+        }
+
         self.check_last_start();
 
-        if let Err(why) = self.start(id, total) {
-            warn!("Err starting shard {}: {:?}", id, why);
-            info!("Re-queueing start of shard {}", id);
+        match self.start(id, total) {
+            Ok(()) => {
+                self.failing_since = None;
+
+                if let Some(ref mut autoshard) = self.autoshard {
+                    autoshard.remaining = autoshard.remaining.saturating_sub(1);
+                    autoshard.estimated = true;
+                }
+            },
+            Err(why) => {
+                warn!("Err starting shard {}: {:?}", id, why);
+                info!("Re-queueing start of shard {}", id);
+
+                self.error_hook.report(ErrorContext::ShardStart {
+                    shard_id: id,
+                    error: &why,
+                });
 
-            self.queue.push_back((id, total));
+                self.failing_since.get_or_insert_with(Instant::now);
+                self.queue.push_back((id, total));
+            },
         }
 
         self.last_start = Some(Instant::now());
     }
 
+    // Whether a session may be started. Once the remaining sessions are
+    // exhausted, the session start limit is queried again, as the count is
+    // only an estimate.
+    fn has_session_start(&mut self) -> bool {
+        let autoshard = match self.autoshard {
+            Some(autoshard) => autoshard,
+            None => return true,
+        };
+
+        if autoshard.remaining > 0 {
+            return true;
+        }
+
+        let now = Instant::now();
+
+        if !autoshard.estimated && now < autoshard.resets_at {
+            return false;
+        }
+
+        self.query_gateway();
+
+        match self.autoshard {
+            Some(autoshard) if autoshard.remaining == 0 => {
+                let pause = if autoshard.resets_at > now {
+                    autoshard.resets_at - now
+                } else {
+                    Duration::from_secs(0)
+                };
+
+                warn!("Session start limit exhausted, pausing shard starts for {:?}", pause);
+
+                false
+            },
+            _ => true,
+        }
+    }
+
+    // Queries the recommended number of shards if starting shards has been
+    // failing for a long time, telling the manager to restart all shards if
+    // it changed.
+    //
+    // Returns whether the shards are being restarted.
+    fn check_recommended_shards(&mut self) -> bool {
+        let requery_after = Duration::from_secs(REQUERY_SHARDS_AFTER_SECONDS);
+
+        match self.failing_since {
+            Some(since) if since.elapsed() >= requery_after => {},
+            _ => return false,
+        }
+
+        self.failing_since = None;
+
+        let (old, new) = match (self.autoshard, self.query_gateway()) {
+            (Some(autoshard), Some(new)) if autoshard.shards != new.shards => (autoshard.shards, new),
+            _ => return false,
+        };
+
+        info!("Recommended shards changed from {} to {}, restarting all shards", old, new.shards);
+
+        #[cfg(feature = "voice")]
+        self.voice_manager.lock().set_shard_count(new.shards);
+
+        // Starts of the previous total of shards are ignored from now on.
+        if let Some(ref mut autoshard) = self.autoshard {
+            autoshard.shards = new.shards;
+        }
+
+        self.queue.clear();
+        let _ = self.manager_tx.send(ShardManagerMessage::Reshard(new.shards));
+
+        true
+    }
+
+    // Queries the session start limit and recommended number of shards,
+    // keeping the previous total of shards until the manager restarts them.
+    fn query_gateway(&mut self) -> Option<Autoshard> {
+        let new = match self.cache_and_http.http.get_bot_gateway() {
+            Ok(info) => Autoshard::new(&info),
+            Err(why) => {
+                warn!("Err querying the session start limit: {:?}", why);
+
+                self.error_hook.report(ErrorContext::Http {
+                    error: &why,
+                });
+
+                return None;
+            },
+        };
+
+        if let Some(ref mut autoshard) = self.autoshard {
+            *autoshard = Autoshard {
+                shards: autoshard.shards,
+                ..new
+            };
+        }
+
+        Some(new)
+    }
+
     fn start(&mut self, shard_id: u64, shard_total: u64) -> Result<()> {
         let shard_info = [shard_id, shard_total];
 
         let shard = Shard::new(
             Arc::clone(&self.ws_url),
-            &self.cache_and_http.http.token,
+            self.cache_and_http.http.token.expose(),
             shard_info,
         )?;
 
@@ -195,6 +378,8 @@ impl<H: EventHandler + Send + Sync + 'static,
             framework: Arc::clone(&self.framework),
             manager_tx: self.manager_tx.clone(),
             threadpool: self.threadpool.clone(),
+            event_queue: Arc::clone(&self.event_queue),
+            error_hook: self.error_hook.clone(),
             #[cfg(feature = "voice")]
             voice_manager: Arc::clone(&self.voice_manager),
             shard,
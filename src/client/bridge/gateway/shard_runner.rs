@@ -1,4 +1,4 @@
-use crate::gateway::{InterMessage, ReconnectType, Shard, ShardAction};
+use crate::gateway::{ConnectionStage, GatewayError, InterMessage, ReconnectType, Shard, ShardAction};
 use crate::internal::prelude::*;
 use crate::internal::ws_impl::{ReceiverExt, SenderExt};
 use crate::model::event::{Event, GatewayEvent};
@@ -27,12 +27,14 @@ use tungstenite::{
     error::Error as TungsteniteError,
     protocol::frame::CloseFrame,
 };
-use typemap::ShareMap;
+use crate::client::{ErrorContext, ErrorHook, EventQueue, TypeMap};
 
 #[cfg(feature = "framework")]
 use crate::framework::Framework;
 #[cfg(feature = "voice")]
 use super::super::voice::ClientVoiceManager;
+#[cfg(feature = "collector")]
+use crate::collector::{MessageFilter, ReactionAction, ReactionFilter};
 use log::{error, debug, warn};
 
 /// A runner for managing a [`Shard`] and its respective WebSocket client.
@@ -40,7 +42,7 @@ use log::{error, debug, warn};
 /// [`Shard`]: ../../../gateway/struct.Shard.html
 pub struct ShardRunner<H: EventHandler + Send + Sync + 'static,
                        RH: RawEventHandler + Send + Sync + 'static> {
-    data: Arc<RwLock<ShareMap>>,
+    data: Arc<RwLock<TypeMap>>,
     event_handler: Option<Arc<H>>,
     raw_event_handler: Option<Arc<RH>>,
     #[cfg(feature = "framework")]
@@ -51,10 +53,18 @@ pub struct ShardRunner<H: EventHandler + Send + Sync + 'static,
     // channel to send messages to the shard runner from the shard manager
     runner_tx: Sender<InterMessage>,
     shard: Shard,
+    // the last stage of the shard that was dispatched
+    stage: ConnectionStage,
     threadpool: ThreadPool,
+    event_queue: Arc<EventQueue>,
+    error_hook: ErrorHook,
     #[cfg(feature = "voice")]
     voice_manager: Arc<Mutex<ClientVoiceManager>>,
     cache_and_http: Arc<CacheAndHttp>,
+    #[cfg(feature = "collector")]
+    message_filters: Vec<MessageFilter>,
+    #[cfg(feature = "collector")]
+    reaction_filters: Vec<ReactionFilter>,
 }
 
 impl<H: EventHandler + Send + Sync + 'static,
@@ -73,11 +83,18 @@ impl<H: EventHandler + Send + Sync + 'static,
             framework: opt.framework,
             manager_tx: opt.manager_tx,
             shard: opt.shard,
+            stage: ConnectionStage::Disconnected,
             threadpool: opt.threadpool,
+            event_queue: opt.event_queue,
+            error_hook: opt.error_hook,
             #[cfg(feature = "voice")]
             voice_manager: opt.voice_manager,
             #[cfg(any(feature = "cache", feature = "http"))]
             cache_and_http: opt.cache_and_http,
+            #[cfg(feature = "collector")]
+            message_filters: Vec::new(),
+            #[cfg(feature = "collector")]
+            reaction_filters: Vec::new(),
         }
     }
 
@@ -110,6 +127,9 @@ impl<H: EventHandler + Send + Sync + 'static,
     pub fn run(&mut self) -> Result<()> {
         debug!("[ShardRunner {:?}] Running", self.shard.shard_info());
 
+        let stage = self.shard.stage();
+        self.set_stage(stage);
+
         loop {
             if !self.recv()? {
                 return Ok(());
@@ -122,35 +142,34 @@ impl<H: EventHandler + Send + Sync + 'static,
                     self.shard.shard_info(),
                 );
 
+                self.report(&Error::Gateway(GatewayError::HeartbeatFailed));
+
                 return self.request_restart();
             }
 
-            let pre = self.shard.stage();
             let (event, action, successful) = self.recv_event();
-            let post = self.shard.stage();
-
-            if post != pre {
-                self.update_manager();
-
-                let e = ClientEvent::ShardStageUpdate(ShardStageUpdateEvent {
-                    new: post,
-                    old: pre,
-                    shard_id: ShardId(self.shard.shard_info()[0]),
-                });
-                self.dispatch(DispatchEvent::Client(e));
-            }
+            let stage = self.shard.stage();
+            self.set_stage(stage);
 
             match action {
                 Some(ShardAction::Reconnect(ReconnectType::Reidentify)) => {
                     return self.request_restart()
                 },
                 Some(other) => {
-                    let _ = self.action(&other);
+                    if let Err(why) = self.action(&other) {
+                        self.report(&why);
+                    }
+
+                    let stage = self.shard.stage();
+                    self.set_stage(stage);
                 },
                 None => {},
             }
 
             if let Some(event) = event {
+                #[cfg(feature = "collector")]
+                self.fill_filters(&event);
+
                 self.dispatch(DispatchEvent::Model(event));
             }
 
@@ -160,6 +179,36 @@ impl<H: EventHandler + Send + Sync + 'static,
         }
     }
 
+    // Sends messages and reactions to the collectors of the filters they pass,
+    // removing filters that are done.
+    //
+    // Filters whose collectors timed out or were dropped are removed on every
+    // event, as an event passing them may never arrive.
+    #[cfg(feature = "collector")]
+    fn fill_filters(&mut self, event: &Event) {
+        retain_filters(&mut self.message_filters, |filter| filter.is_alive());
+        retain_filters(&mut self.reaction_filters, |filter| filter.is_alive());
+
+        match *event {
+            Event::MessageCreate(ref event) => {
+                let message = Arc::new(event.message.clone());
+
+                retain_filters(&mut self.message_filters, |filter| filter.send_message(&message));
+            },
+            Event::ReactionAdd(ref event) => {
+                let action = Arc::new(ReactionAction::Added(Arc::new(event.reaction.clone())));
+
+                retain_filters(&mut self.reaction_filters, |filter| filter.send_reaction(&action));
+            },
+            Event::ReactionRemove(ref event) => {
+                let action = Arc::new(ReactionAction::Removed(Arc::new(event.reaction.clone())));
+
+                retain_filters(&mut self.reaction_filters, |filter| filter.send_reaction(&action));
+            },
+            _ => {},
+        }
+    }
+
     /// Clones the internal copy of the Sender to the shard runner.
     pub(super) fn runner_tx(&self) -> Sender<InterMessage> {
         self.runner_tx.clone()
@@ -180,6 +229,10 @@ impl<H: EventHandler + Send + Sync + 'static,
                 self.request_restart()
             },
             ShardAction::Reconnect(ReconnectType::Resume) => {
+                // The shard reconnects and then resumes without returning in
+                // between, so the connecting stage is dispatched up front.
+                self.set_stage(ConnectionStage::Connecting);
+
                 self.shard.resume()
             },
             ShardAction::Reconnect(ReconnectType::__Nonexhaustive) => unreachable!(),
@@ -196,7 +249,7 @@ impl<H: EventHandler + Send + Sync + 'static,
     // Returns whether the WebSocket client is still active.
     //
     // If true, the WebSocket client was _not_ shutdown. If false, it was.
-    fn checked_shutdown(&mut self, id: ShardId) -> bool {
+    fn checked_shutdown(&mut self, id: ShardId, code: u16) -> bool {
         // First verify the ID so we know for certain this runner is
         // to shutdown.
         if id.0 != self.shard.shard_info()[0] {
@@ -207,7 +260,7 @@ impl<H: EventHandler + Send + Sync + 'static,
 
         // Send a Close Frame to Discord, which allows a bot to "log off"
         let _ = self.shard.client.close(Some(CloseFrame {
-            code: 1000.into(),
+            code: code.into(),
             reason: Cow::from(""),
         }));
 
@@ -227,6 +280,8 @@ impl<H: EventHandler + Send + Sync + 'static,
             }
         }
 
+        self.set_stage(ConnectionStage::Disconnected);
+
         // Inform the manager that shutdown for this shard has finished.
         if let Err(why) = self.manager_tx.send(ShardManagerMessage::ShutdownFinished(id)) {
             warn!(
@@ -249,6 +304,8 @@ impl<H: EventHandler + Send + Sync + 'static,
             &self.raw_event_handler,
             &self.runner_tx,
             &self.threadpool,
+            &self.event_queue,
+            &self.error_hook,
             self.shard.shard_info()[0],
             Arc::clone(&self.cache_and_http),
         );
@@ -265,7 +322,12 @@ impl<H: EventHandler + Send + Sync + 'static,
             InterMessage::Client(value) => match *value {
                     ShardClientMessage::Manager(ShardManagerMessage::Restart(id)) |
                     ShardClientMessage::Manager(ShardManagerMessage::Shutdown(id)) => {
-                        self.checked_shutdown(id)
+                        self.checked_shutdown(id, 1000)
+                    },
+                    ShardClientMessage::Manager(ShardManagerMessage::ShutdownResumable(id)) => {
+                        // Any close code other than 1000 and 1001 keeps the
+                        // session valid for resuming.
+                        self.checked_shutdown(id, 4000)
                     },
                     ShardClientMessage::Manager(ShardManagerMessage::ShutdownAll) => {
                         // This variant should never be received.
@@ -289,6 +351,11 @@ impl<H: EventHandler + Send + Sync + 'static,
                     ShardClientMessage::Manager(ShardManagerMessage::ShutdownFinished(_)) => {
                         // nb: not sent here
 
+                        true
+                    },
+                    ShardClientMessage::Manager(ShardManagerMessage::Reshard(_)) => {
+                        // nb: not sent here
+
                         true
                     },
                 ShardClientMessage::Runner(ShardRunnerMessage::ChunkGuilds { guild_ids, limit, query }) => {
@@ -327,8 +394,13 @@ impl<H: EventHandler + Send + Sync + 'static,
 
                     self.shard.update_presence().is_ok()
                 },
-                ShardClientMessage::Runner(ShardRunnerMessage::SetPresence(status, activity)) => {
-                    self.shard.set_presence(status, activity);
+                ShardClientMessage::Runner(ShardRunnerMessage::SetActivities(activities)) => {
+                    self.shard.set_activities(activities);
+
+                    self.shard.update_presence().is_ok()
+                },
+                ShardClientMessage::Runner(ShardRunnerMessage::SetPresence(status, activities)) => {
+                    self.shard.set_presence(status, activities);
 
                     self.shard.update_presence().is_ok()
                 },
@@ -337,6 +409,20 @@ impl<H: EventHandler + Send + Sync + 'static,
 
                     self.shard.update_presence().is_ok()
                 },
+                #[cfg(feature = "collector")]
+                ShardClientMessage::Runner(ShardRunnerMessage::SetMessageFilter(filter)) => {
+                    retain_filters(&mut self.message_filters, |filter| filter.is_alive());
+                    self.message_filters.push(filter);
+
+                    true
+                },
+                #[cfg(feature = "collector")]
+                ShardClientMessage::Runner(ShardRunnerMessage::SetReactionFilter(filter)) => {
+                    retain_filters(&mut self.reaction_filters, |filter| filter.is_alive());
+                    self.reaction_filters.push(filter);
+
+                    true
+                },
             },
             InterMessage::Json(value) => {
                 // Value must be forwarded over the websocket
@@ -355,24 +441,27 @@ impl<H: EventHandler + Send + Sync + 'static,
                     self.runner_tx.clone(),
                 );
             },
+            Event::GuildDelete(ref event) => {
+                // Stop the voice thread of a guild the bot is no longer in.
+                //
+                // Deletions with `unavailable` set are parsed as
+                // `GuildUnavailable` instead, and handled below.
+                self.voice_manager.lock().remove(event.guild.id);
+            },
+            Event::GuildUnavailable(_) => {
+                // The guild is only affected by an outage, and the voice
+                // connection is kept so that it resumes once it is over.
+            },
             Event::VoiceServerUpdate(ref event) => {
                 if let Some(guild_id) = event.guild_id {
-                    let mut manager = self.voice_manager.lock();
-                    let search = manager.get_mut(guild_id);
-
-                    if let Some(handler) = search {
-                        handler.update_server(&event.endpoint, &event.token);
-                    }
+                    self.voice_manager.lock()
+                        .update_server(guild_id, &event.endpoint, &event.token);
                 }
             },
             Event::VoiceStateUpdate(ref event) => {
                 if let Some(guild_id) = event.guild_id {
-                    let mut manager = self.voice_manager.lock();
-                    let search = manager.get_mut(guild_id);
-
-                    if let Some(handler) = search {
-                        handler.update_state(&event.voice_state);
-                    }
+                    self.voice_manager.lock()
+                        .update_state(guild_id, &event.voice_state);
                 }
             },
             _ => {},
@@ -454,6 +543,8 @@ impl<H: EventHandler + Send + Sync + 'static,
                         if let Err(why) = self.shard.resume() {
                             warn!("Failed to resume: {:?}", why);
 
+                            self.report(&why);
+
                             return (None, None, false);
                         }
                     },
@@ -477,6 +568,8 @@ impl<H: EventHandler + Send + Sync + 'static,
             Err(why) => {
                 error!("Shard handler received err: {:?}", why);
 
+                self.report(&why);
+
                 return (None, None, true);
             },
         };
@@ -500,8 +593,8 @@ impl<H: EventHandler + Send + Sync + 'static,
         (event, action, true)
     }
 
-    fn request_restart(&self) -> Result<()> {
-        self.update_manager();
+    fn request_restart(&mut self) -> Result<()> {
+        self.set_stage(ConnectionStage::Disconnected);
 
         debug!(
             "[ShardRunner {:?}] Requesting restart",
@@ -519,21 +612,62 @@ impl<H: EventHandler + Send + Sync + 'static,
         Ok(())
     }
 
+    // Dispatches a shard stage update and informs the manager if the stage
+    // differs from the one last dispatched.
+    fn set_stage(&mut self, new: ConnectionStage) {
+        let old = self.stage;
+
+        if new == old {
+            return;
+        }
+
+        self.stage = new;
+        self.update_manager();
+
+        let e = ClientEvent::ShardStageUpdate(ShardStageUpdateEvent {
+            new,
+            old,
+            shard_id: ShardId(self.shard.shard_info()[0]),
+        });
+        self.dispatch(DispatchEvent::Client(e));
+    }
+
+    fn report(&self, error: &Error) {
+        self.error_hook.report(ErrorContext::Gateway {
+            shard_id: self.shard.shard_info()[0],
+            error,
+        });
+    }
+
     fn update_manager(&self) {
         let _ = self.manager_tx.send(ShardManagerMessage::ShardUpdate {
             id: ShardId(self.shard.shard_info()[0]),
             latency: self.shard.latency(),
-            stage: self.shard.stage(),
+            stage: self.stage,
         });
     }
 }
 
+// Keeps the filters `f` returns `true` for, not preserving their order.
+#[cfg(feature = "collector")]
+fn retain_filters<T, F>(filters: &mut Vec<T>, mut f: F) where F: FnMut(&mut T) -> bool {
+    let mut i = 0;
+
+    while i < filters.len() {
+        if f(&mut filters[i]) {
+            i += 1;
+        } else {
+            filters.swap_remove(i);
+        }
+    }
+}
+
 /// Options to be passed to [`ShardRunner::new`].
 ///
 /// [`ShardRunner::new`]: struct.ShardRunner.html#method.new
 pub struct ShardRunnerOptions<H: EventHandler + Send + Sync + 'static,
                               RH: RawEventHandler + Send  + Sync + 'static> {
-    pub data: Arc<RwLock<ShareMap>>,
+    pub data: Arc<RwLock<TypeMap>>,
     pub event_handler: Option<Arc<H>>,
     pub raw_event_handler: Option<Arc<RH>>,
     #[cfg(feature = "framework")]
@@ -541,6 +675,8 @@ pub struct ShardRunnerOptions<H: EventHandler + Send + Sync + 'static,
     pub manager_tx: Sender<ShardManagerMessage>,
     pub shard: Shard,
     pub threadpool: ThreadPool,
+    pub event_queue: Arc<EventQueue>,
+    pub error_hook: ErrorHook,
     #[cfg(feature = "voice")]
     pub voice_manager: Arc<Mutex<ClientVoiceManager>>,
     #[cfg(any(feature = "cache", feature = "http"))]
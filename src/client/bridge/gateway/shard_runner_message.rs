@@ -5,6 +5,9 @@ use crate::model::{
 };
 use tungstenite::Message;
 
+#[cfg(feature = "collector")]
+use crate::collector::{MessageFilter, ReactionFilter};
+
 /// A message to send from a shard over a WebSocket.
 // Once we can use `Box` as part of a pattern, we will reconsider boxing.
 #[allow(clippy::large_enum_variant)]
@@ -42,9 +45,18 @@ pub enum ShardRunnerMessage {
     Message(Message),
     /// Indicates that the client is to update the shard's presence's activity.
     SetActivity(Option<Activity>),
+    /// Indicates that the client is to update the shard's presence's
+    /// activities.
+    SetActivities(Vec<Activity>),
     /// Indicates that the client is to update the shard's presence in its
     /// entirity.
-    SetPresence(OnlineStatus, Option<Activity>),
+    SetPresence(OnlineStatus, Vec<Activity>),
     /// Indicates that the client is to update the shard's presence's status.
     SetStatus(OnlineStatus),
+    /// Registers a filter to send messages received by the shard through.
+    #[cfg(feature = "collector")]
+    SetMessageFilter(MessageFilter),
+    /// Registers a filter to send reactions received by the shard through.
+    #[cfg(feature = "collector")]
+    SetReactionFilter(ReactionFilter),
 }
@@ -1,7 +1,10 @@
 use crate::gateway::InterMessage;
 use std::collections::HashMap;
 use std::sync::mpsc::Sender as MpscSender;
-use crate::model::id::{ChannelId, GuildId, UserId};
+use crate::model::{
+    id::{ChannelId, GuildId, UserId},
+    voice::VoiceState,
+};
 use crate::voice::{Handler, Manager};
 use crate::utils;
 
@@ -96,6 +99,28 @@ impl ClientVoiceManager {
         self.user_id = user_id;
     }
 
+    /// Refer to [`Manager::update_server`].
+    ///
+    /// [`Manager::update_server`]: ../../../voice/struct.Manager.html#method.update_server
+    pub fn update_server<G: Into<GuildId>>(&mut self, guild_id: G, endpoint: &Option<String>, token: &str) {
+        let (gid, sid) = self.manager_info(guild_id);
+
+        if let Some(manager) = self.managers.get_mut(&sid) {
+            manager.update_server(gid, endpoint, token);
+        }
+    }
+
+    /// Refer to [`Manager::update_state`].
+    ///
+    /// [`Manager::update_state`]: ../../../voice/struct.Manager.html#method.update_state
+    pub fn update_state<G: Into<GuildId>>(&mut self, guild_id: G, voice_state: &VoiceState) {
+        let (gid, sid) = self.manager_info(guild_id);
+
+        if let Some(manager) = self.managers.get_mut(&sid) {
+            manager.update_state(gid, voice_state);
+        }
+    }
+
     pub fn manager_get(&self, shard_id: u64) -> Option<&Manager> {
         self.managers.get(&shard_id)
     }
@@ -0,0 +1,393 @@
+use super::{
+    bridge::gateway::{ShardManager, ShardManagerOptions},
+    error_hook::ErrorHook,
+    event_queue::EventQueue,
+    validate_token,
+    Client,
+    ClientError,
+    EventHandler,
+    RawEventHandler,
+};
+use crate::internal::prelude::*;
+use crate::utils::token::SecretToken;
+use crate::CacheAndHttp;
+use parking_lot::{Mutex, RwLock};
+use std::sync::Arc;
+use threadpool::ThreadPool;
+use super::type_map::{TypeMap, TypeMapKey};
+
+#[cfg(feature = "framework")]
+use crate::framework::Framework;
+#[cfg(feature = "http")]
+use crate::http::Http;
+#[cfg(feature = "voice")]
+use crate::model::id::UserId;
+#[cfg(feature = "voice")]
+use super::bridge::voice::ClientVoiceManager;
+#[cfg(feature = "cache")]
+use std::time::Duration;
+
+/// Placeholder for an [`EventHandler`] that has not been set.
+///
+/// [`EventHandler`]: trait.EventHandler.html
+#[doc(hidden)]
+pub struct NoEventHandler;
+impl EventHandler for NoEventHandler {}
+
+/// Placeholder for a [`RawEventHandler`] that has not been set.
+///
+/// [`RawEventHandler`]: trait.RawEventHandler.html
+#[doc(hidden)]
+pub struct NoRawEventHandler;
+impl RawEventHandler for NoRawEventHandler {}
+
+/// A builder for a [`Client`], gathering its settings before validating them
+/// and creating it with [`build`].
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # use std::error::Error;
+/// #
+/// # fn try_main() -> Result<(), Box<Error>> {
+/// use serenity::client::{ClientBuilder, Context, EventHandler};
+/// use serenity::model::channel::Message;
+/// use serenity::prelude::TypeMapKey;
+/// use std::env;
+///
+/// struct Handler;
+///
+/// impl EventHandler for Handler {
+///     fn message(&self, ctx: Context, msg: Message) {
+///         if msg.content == "!count" {
+///             let count = ctx.data.read().get::<Counter>().copied().unwrap_or(0);
+///             let _ = msg.channel_id.say(&ctx, count);
+///         }
+///     }
+/// }
+///
+/// struct Counter;
+///
+/// impl TypeMapKey for Counter {
+///     type Value = u64;
+/// }
+///
+/// let mut client = ClientBuilder::new(&env::var("DISCORD_TOKEN")?)
+///     .event_handler(Handler)
+///     .type_map_insert::<Counter>(0)
+///     .threads(10)
+///     .build()?;
+///
+/// client.start()?;
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     try_main().unwrap();
+/// # }
+/// ```
+///
+/// **Note**: Gateway intents are not supported yet, and the shards to run are
+/// still chosen by calling one of the client's `start` methods, such as
+/// [`start_autosharded`], after building it.
+///
+/// [`Client`]: struct.Client.html
+/// [`build`]: #method.build
+/// [`start_autosharded`]: struct.Client.html#method.start_autosharded
+pub struct ClientBuilder<H = NoEventHandler, RH = NoRawEventHandler> {
+    token: SecretToken,
+    event_handler: Option<H>,
+    raw_event_handler: Option<RH>,
+    data: TypeMap,
+    threads: usize,
+    event_queue: EventQueue,
+    #[cfg(feature = "framework")]
+    framework: Option<Box<dyn Framework + Send>>,
+    #[cfg(feature = "cache")]
+    cache_update_timeout: Option<Duration>,
+}
+
+impl ClientBuilder {
+    /// Starts building a client for a bot user with the given token.
+    ///
+    /// Discord has a requirement of prefixing bot tokens with `"Bot "`, which
+    /// is done for you if not already included.
+    pub fn new(token: impl AsRef<str>) -> Self {
+        ClientBuilder::with_handlers(token, None, None)
+    }
+}
+
+impl<H, RH> ClientBuilder<H, RH>
+    where H: EventHandler + Send + Sync + 'static,
+          RH: RawEventHandler + Send + Sync + 'static {
+    pub(super) fn with_handlers(token: impl AsRef<str>, event_handler: Option<H>, raw_event_handler: Option<RH>) -> Self {
+        ClientBuilder {
+            token: SecretToken::new(token.as_ref().trim()),
+            event_handler,
+            raw_event_handler,
+            data: TypeMap::new(),
+            threads: 5,
+            event_queue: EventQueue::default(),
+            #[cfg(feature = "framework")]
+            framework: None,
+            #[cfg(feature = "cache")]
+            cache_update_timeout: None,
+        }
+    }
+
+    /// Sets the handler that parsed events are dispatched to.
+    pub fn event_handler<EH>(self, handler: EH) -> ClientBuilder<EH, RH>
+        where EH: EventHandler + Send + Sync + 'static {
+        ClientBuilder {
+            token: self.token,
+            event_handler: Some(handler),
+            raw_event_handler: self.raw_event_handler,
+            data: self.data,
+            threads: self.threads,
+            event_queue: self.event_queue,
+            #[cfg(feature = "framework")]
+            framework: self.framework,
+            #[cfg(feature = "cache")]
+            cache_update_timeout: self.cache_update_timeout,
+        }
+    }
+
+    /// Sets the handler that raw events are dispatched to.
+    ///
+    /// Refer to [`RawEventHandler`] for how it interacts with the
+    /// [`event_handler`].
+    ///
+    /// [`RawEventHandler`]: trait.RawEventHandler.html
+    /// [`event_handler`]: #method.event_handler
+    pub fn raw_event_handler<REH>(self, handler: REH) -> ClientBuilder<H, REH>
+        where REH: RawEventHandler + Send + Sync + 'static {
+        ClientBuilder {
+            token: self.token,
+            event_handler: self.event_handler,
+            raw_event_handler: Some(handler),
+            data: self.data,
+            threads: self.threads,
+            event_queue: self.event_queue,
+            #[cfg(feature = "framework")]
+            framework: self.framework,
+            #[cfg(feature = "cache")]
+            cache_update_timeout: self.cache_update_timeout,
+        }
+    }
+
+    /// Sets the framework messages are passed through.
+    ///
+    /// Refer to [`Client::with_framework`] for more information.
+    ///
+    /// [`Client::with_framework`]: struct.Client.html#method.with_framework
+    #[cfg(feature = "framework")]
+    pub fn framework<F>(mut self, framework: F) -> Self
+        where F: Framework + Send + 'static {
+        self.framework = Some(Box::new(framework));
+
+        self
+    }
+
+    /// Inserts a value into the client's [`data`] before it is created.
+    ///
+    /// [`data`]: struct.Client.html#structfield.data
+    pub fn type_map_insert<K>(mut self, value: K::Value) -> Self
+        where K: TypeMapKey {
+        self.data.insert::<K>(value);
+
+        self
+    }
+
+    /// Sets the number of threads in the client's [`threadpool`].
+    ///
+    /// **Note**: Defaults to 5.
+    ///
+    /// [`threadpool`]: struct.Client.html#structfield.threadpool
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = threads;
+
+        self
+    }
+
+    /// Sets the limits on how event handlers are run in the threadpool.
+    ///
+    /// Refer to [`EventQueue`] for more information.
+    ///
+    /// [`EventQueue`]: struct.EventQueue.html
+    pub fn event_queue(mut self, queue: EventQueue) -> Self {
+        self.event_queue = queue;
+
+        self
+    }
+
+    /// Sets how long updating the cache may wait for a write-lock.
+    ///
+    /// If the lock cannot be claimed in time, the event is not applied to the
+    /// cache and a deadlock warning is logged. If not set, updating the cache
+    /// waits until it succeeds, potentially deadlocking.
+    #[cfg(feature = "cache")]
+    pub fn cache_update_timeout(mut self, duration: Duration) -> Self {
+        self.cache_update_timeout = Some(duration);
+
+        self
+    }
+
+    /// Validates the settings and creates the client.
+    ///
+    /// This does not connect to the gateway yet; use one of the client's
+    /// `start` methods for that. For REST-only tools, prefer
+    /// [`build_http_only`], which needs no event handler.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ClientError::InvalidToken`] if the token is malformed, as
+    /// per [`validate_token`], a [`ClientError::NoEventHandler`] if neither an
+    /// event handler nor a raw event handler was set, and a
+    /// [`ClientError::NoThreads`] if the threadpool was set to zero threads.
+    ///
+    /// [`build_http_only`]: #method.build_http_only
+    /// [`ClientError::InvalidToken`]: enum.ClientError.html#variant.InvalidToken
+    /// [`ClientError::NoEventHandler`]: enum.ClientError.html#variant.NoEventHandler
+    /// [`ClientError::NoThreads`]: enum.ClientError.html#variant.NoThreads
+    /// [`validate_token`]: fn.validate_token.html
+    pub fn build(self) -> Result<Client> {
+        self.check_token()?;
+
+        if self.event_handler.is_none() && self.raw_event_handler.is_none() {
+            return Err(Error::Client(ClientError::NoEventHandler));
+        }
+
+        if self.threads == 0 {
+            return Err(Error::Client(ClientError::NoThreads));
+        }
+
+        self.build_unchecked()
+    }
+
+    /// Validates the token and creates only the cache and the ratelimited
+    /// HTTP client, for tools that only call the REST API.
+    ///
+    /// Unlike [`build`], no event handler is needed, and neither a threadpool
+    /// nor a shard manager is created. All settings but the token and the
+    /// [`cache_update_timeout`] are ignored.
+    ///
+    /// # Examples
+    ///
+    /// Sending a message without connecting to the gateway:
+    ///
+    /// ```rust,no_run
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// use serenity::client::ClientBuilder;
+    /// use serenity::model::id::ChannelId;
+    /// use std::env;
+    ///
+    /// let cache_and_http = ClientBuilder::new(&env::var("DISCORD_TOKEN")?)
+    ///     .build_http_only()?;
+    ///
+    /// ChannelId(7).say(&cache_and_http.http, "Migration finished")?;
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ClientError::InvalidToken`] if the token is malformed, as
+    /// per [`validate_token`].
+    ///
+    /// [`ClientError::InvalidToken`]: enum.ClientError.html#variant.InvalidToken
+    /// [`build`]: #method.build
+    /// [`cache_update_timeout`]: #method.cache_update_timeout
+    /// [`validate_token`]: fn.validate_token.html
+    pub fn build_http_only(self) -> Result<CacheAndHttp> {
+        self.check_token()?;
+
+        Ok(self.cache_and_http())
+    }
+
+    fn check_token(&self) -> Result<()> {
+        let token = self.token.expose();
+
+        if token.starts_with("Bot ") {
+            validate_token(&token[4..])
+        } else {
+            validate_token(token)
+        }
+    }
+
+    fn cache_and_http(&self) -> CacheAndHttp {
+        let token = self.token.expose();
+        let http = if token.starts_with("Bot ") {
+            Http::new_with_token(token)
+        } else {
+            Http::new_with_token(&format!("Bot {}", token))
+        };
+
+        CacheAndHttp {
+            #[cfg(feature = "cache")]
+            update_cache_timeout: self.cache_update_timeout,
+            ..CacheAndHttp::new(http)
+        }
+    }
+
+    /// Creates the client without validating the settings.
+    pub(super) fn build_unchecked(self) -> Result<Client> {
+        let cache_and_http = Arc::new(self.cache_and_http());
+
+        let name = "serenity client".to_owned();
+        let threadpool = ThreadPool::with_name(name, self.threads);
+        // Retrieved once the client is started, so a client that is never
+        // started makes no requests.
+        let url = Arc::new(Mutex::new(String::new()));
+        let data = Arc::new(RwLock::new(self.data));
+        let event_queue = Arc::new(self.event_queue);
+        let error_hook = ErrorHook::new();
+        let event_handler = self.event_handler.map(Arc::new);
+        let raw_event_handler = self.raw_event_handler.map(Arc::new);
+
+        #[cfg(feature = "framework")]
+        let framework = Arc::new(Mutex::new(self.framework));
+        #[cfg(feature = "voice")]
+        let voice_manager = Arc::new(Mutex::new(ClientVoiceManager::new(
+            0,
+            UserId(0),
+        )));
+
+        let (shard_manager, shard_manager_worker) = {
+            ShardManager::new_with_hooks(ShardManagerOptions {
+                data: &data,
+                event_handler: &event_handler,
+                raw_event_handler: &raw_event_handler,
+                #[cfg(feature = "framework")]
+                framework: &framework,
+                shard_index: 0,
+                shard_init: 0,
+                shard_total: 0,
+                threadpool: threadpool.clone(),
+                #[cfg(feature = "voice")]
+                voice_manager: &voice_manager,
+                ws_url: &url,
+                cache_and_http: &cache_and_http,
+            }, &event_queue, &error_hook)
+        };
+
+        Ok(Client {
+            ws_uri: url,
+            #[cfg(feature = "framework")]
+            framework,
+            data,
+            shard_manager,
+            shard_manager_worker,
+            threadpool,
+            #[cfg(feature = "voice")]
+            voice_manager,
+            cache_and_http,
+            error_hook,
+        })
+    }
+}
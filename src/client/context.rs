@@ -1,12 +1,12 @@
 use crate::client::bridge::gateway::ShardMessenger;
 use crate::gateway::InterMessage;
 use crate::model::prelude::*;
-use parking_lot::RwLock;
+use parking_lot::{RwLock, RwLockReadGuard, RwLockWriteGuard};
 use std::sync::{
     Arc,
     mpsc::Sender,
 };
-use typemap::ShareMap;
+use crate::client::TypeMap;
 #[cfg(feature = "cache")]
 pub use crate::cache::{Cache, CacheRwLock};
 #[cfg(feature = "http")]
@@ -33,7 +33,7 @@ pub struct Context {
     /// information.
     ///
     /// [`Client::data`]: struct.Client.html#structfield.data
-    pub data: Arc<RwLock<ShareMap>>,
+    pub data: Arc<RwLock<TypeMap>>,
     /// The messenger to communicate with the shard runner.
     pub shard: ShardMessenger,
     /// The ID of the shard this context is related to.
@@ -48,7 +48,7 @@ impl Context {
     /// Create a new Context to be passed to an event handler.
     #[cfg(all(feature = "cache", feature = "http"))]
     pub(crate) fn new(
-        data: Arc<RwLock<ShareMap>>,
+        data: Arc<RwLock<TypeMap>>,
         runner_tx: Sender<InterMessage>,
         shard_id: u64,
         cache: Arc<RwLock<Cache>>,
@@ -66,7 +66,7 @@ impl Context {
     /// Create a new Context to be passed to an event handler.
     #[cfg(all(not(feature = "cache"), feature = "http"))]
     pub(crate) fn new(
-        data: Arc<RwLock<ShareMap>>,
+        data: Arc<RwLock<TypeMap>>,
         runner_tx: Sender<InterMessage>,
         shard_id: u64,
         http: Arc<Http>,
@@ -82,7 +82,7 @@ impl Context {
     /// Create a new Context to be passed to an event handler.
     #[cfg(all(feature = "cache", not(feature = "http")))]
     pub(crate) fn new(
-        data: Arc<RwLock<ShareMap>>,
+        data: Arc<RwLock<TypeMap>>,
         runner_tx: Sender<InterMessage>,
         shard_id: u64,
         cache: Arc<RwLock<Cache>>,
@@ -98,7 +98,7 @@ impl Context {
     /// Create a new Context to be passed to an event handler.
     #[cfg(all(not(feature = "cache"), not(feature = "http")))]
     pub(crate) fn new(
-        data: Arc<RwLock<ShareMap>>,
+        data: Arc<RwLock<TypeMap>>,
         runner_tx: Sender<InterMessage>,
         shard_id: u64,
     ) -> Context {
@@ -109,6 +109,34 @@ impl Context {
         }
     }
 
+    /// Locks [`data`] for reading.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let count = ctx.data_read().get::<MessageCount>().copied();
+    /// ```
+    ///
+    /// [`data`]: #structfield.data
+    #[inline]
+    pub fn data_read(&self) -> RwLockReadGuard<'_, TypeMap> {
+        self.data.read()
+    }
+
+    /// Locks [`data`] for writing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// *ctx.data_write().entry::<MessageCount>().or_insert(0) += 1;
+    /// ```
+    ///
+    /// [`data`]: #structfield.data
+    #[inline]
+    pub fn data_write(&self) -> RwLockWriteGuard<'_, TypeMap> {
+        self.data.write()
+    }
+
     /// Sets the current user as being [`Online`]. This maintains the current
     /// activity.
     ///
@@ -315,6 +343,9 @@ impl Context {
 
     /// Sets the current user's presence, providing all fields to be passed.
     ///
+    /// Any number of activities may be given, such as an `Option<Activity>`
+    /// or a `Vec<Activity>`.
+    ///
     /// # Examples
     ///
     /// Setting the current user as having no activity and being [`Idle`]:
@@ -363,11 +394,27 @@ impl Context {
     /// client.start().unwrap();
     /// ```
     ///
+    /// Setting the current user as playing `"Heroes of the Storm"` and
+    /// listening to `"Rust"` at the same time:
+    ///
+    /// ```rust,ignore
+    /// use serenity::model::gateway::Activity;
+    /// use serenity::model::user::OnlineStatus;
+    ///
+    /// let activities = vec![
+    ///     Activity::playing("Heroes of the Storm"),
+    ///     Activity::listening("Rust"),
+    /// ];
+    ///
+    /// context.set_presence(activities, OnlineStatus::Online);
+    /// ```
+    ///
     /// [`DoNotDisturb`]: ../model/user/enum.OnlineStatus.html#variant.DoNotDisturb
     /// [`Idle`]: ../model/user/enum.OnlineStatus.html#variant.Idle
     #[inline]
-    pub fn set_presence(&self, activity: Option<Activity>, status: OnlineStatus) {
-        self.shard.set_presence(activity, status);
+    pub fn set_presence<I>(&self, activities: I, status: OnlineStatus)
+        where I: IntoIterator<Item = Activity> {
+        self.shard.set_presence(activities, status);
     }
 }
 
@@ -382,3 +429,9 @@ impl AsRef<CacheRwLock> for Context {
         &self.cache
     }
 }
+
+impl AsRef<ShardMessenger> for Context {
+    fn as_ref(&self) -> &ShardMessenger {
+        &self.shard
+    }
+}
@@ -1,7 +1,7 @@
 use crate::gateway::InterMessage;
 use crate::model::{
     channel::{Channel, Message},
-    event::Event,
+    event::*,
     guild::Member,
 };
 use std::{sync::{Arc, mpsc::Sender}};
@@ -9,10 +9,13 @@ use parking_lot::{Mutex, RwLock};
 use super::{
     bridge::gateway::event::ClientEvent,
     event_handler::{EventHandler, RawEventHandler},
+    error_hook::{ErrorContext, ErrorHook},
+    event_queue::{EventOrdering, EventQueue},
+    handler_panic::{self, HandlerPanic},
     Context
 };
 use threadpool::ThreadPool;
-use typemap::ShareMap;
+use crate::client::TypeMap;
 
 #[cfg(feature = "http")]
 use crate::http::Http;
@@ -33,7 +36,11 @@ use log::warn;
 
 #[inline]
 #[cfg(feature = "cache")]
-fn update<E: CacheUpdate + fmt::Debug>(cache_and_http: &Arc<CacheAndHttp>, event: &mut E) -> Option<E::Output> {
+fn update<E: CacheUpdate + fmt::Debug>(
+    cache_and_http: &Arc<CacheAndHttp>,
+    pool: &HandlerPool<'_>,
+    event: &mut E,
+) -> Option<E::Output> {
     if let Some(millis_timeout) = cache_and_http.update_cache_timeout {
 
         if let Some(mut lock) = cache_and_http.cache.try_write_for(millis_timeout) {
@@ -41,6 +48,11 @@ fn update<E: CacheUpdate + fmt::Debug>(cache_and_http: &Arc<CacheAndHttp>, event
         } else {
             warn!("[dispatch] Possible deadlock: Couldn't unlock cache to update with event: {:?}", event);
 
+            pool.error_hook.report(ErrorContext::CacheUpdate {
+                shard_id: pool.shard_id,
+                event: pool.kind.clone(),
+            });
+
             None
         }
     } else {
@@ -50,13 +62,13 @@ fn update<E: CacheUpdate + fmt::Debug>(cache_and_http: &Arc<CacheAndHttp>, event
 
 #[inline]
 #[cfg(not(feature = "cache"))]
-fn update<E>(_cache_and_http: &Arc<CacheAndHttp>, _event: &mut E) -> Option<()> {
+fn update<E>(_cache_and_http: &Arc<CacheAndHttp>, _pool: &HandlerPool<'_>, _event: &mut E) -> Option<()> {
     None
 }
 
 #[cfg(all(feature = "cache", feature = "http"))]
 fn context(
-    data: &Arc<RwLock<ShareMap>>,
+    data: &Arc<RwLock<TypeMap>>,
     runner_tx: &Sender<InterMessage>,
     shard_id: u64,
     cache: &Arc<RwLock<Cache>>,
@@ -67,7 +79,7 @@ fn context(
 
 #[cfg(all(feature = "cache", not(feature = "http")))]
 fn context(
-    data: &Arc<RwLock<ShareMap>>,
+    data: &Arc<RwLock<TypeMap>>,
     runner_tx: &Sender<InterMessage>,
     shard_id: u64,
     cache: &Arc<RwLock<Cache>>,
@@ -77,7 +89,7 @@ fn context(
 
 #[cfg(all(not(feature = "cache"), feature = "http"))]
 fn context(
-    data: &Arc<RwLock<ShareMap>>,
+    data: &Arc<RwLock<TypeMap>>,
     runner_tx: &Sender<InterMessage>,
     shard_id: u64,
     http: &Arc<Http>,
@@ -87,7 +99,7 @@ fn context(
 
 #[cfg(not(any(feature = "cache", feature = "http")))]
 fn context(
-    data: &Arc<RwLock<ShareMap>>,
+    data: &Arc<RwLock<TypeMap>>,
     runner_tx: &Sender<InterMessage>,
     shard_id: u64,
 ) -> Context {
@@ -110,20 +122,47 @@ pub(crate) fn dispatch<H: EventHandler + Send + Sync + 'static,
                        RH: RawEventHandler + Send + Sync + 'static>(
     event: DispatchEvent,
     framework: &Arc<Mutex<Option<Box<dyn Framework + Send>>>>,
-    data: &Arc<RwLock<ShareMap>>,
+    data: &Arc<RwLock<TypeMap>>,
     event_handler: &Option<Arc<H>>,
     raw_event_handler: &Option<Arc<RH>>,
     runner_tx: &Sender<InterMessage>,
     threadpool: &ThreadPool,
+    event_queue: &Arc<EventQueue>,
+    error_hook: &ErrorHook,
     shard_id: u64,
     cache_and_http: Arc<CacheAndHttp>,
 ) {
+    let mut pool = HandlerPool {
+        threadpool,
+        queue: event_queue,
+        error_hook,
+        kind: match event {
+            DispatchEvent::Model(ref e) => Some(e.event_type()),
+            _ => None,
+        },
+        lane: match event {
+            DispatchEvent::Model(ref e) => lane(e, event_queue.event_ordering(), &cache_and_http),
+            _ => None,
+        },
+        shard_id,
+        report: None,
+    };
+
     match (event_handler, raw_event_handler) {
         (None, None) => {}, // Do nothing
         (Some(ref h), None) => {
+            let handler = Arc::clone(h);
+            pool.report = Some(Arc::new(move |panic| handler.handler_panic(panic)));
+
+            if let DispatchEvent::Model(Event::Ready(ref event)) = event {
+                if let Some(ref mut framework) = *framework.lock() {
+                    pool.catch(|| framework.ready(&event.ready));
+                }
+            }
+
             match event {
                 DispatchEvent::Model(Event::MessageCreate(mut event)) => {
-                    update(&cache_and_http, &mut event);
+                    update(&cache_and_http, &pool, &mut event);
 
                     #[cfg(not(any(feature = "cache", feature = "http")))]
                     let context = context(data, runner_tx, shard_id);
@@ -138,10 +177,10 @@ pub(crate) fn dispatch<H: EventHandler + Send + Sync + 'static,
                         context.clone(),
                         event.message.clone(),
                         h,
-                        threadpool,
+                        &pool,
                     );
                     if let Some(ref mut framework) = *framework.lock() {
-                        framework.dispatch(context, event.message, threadpool);
+                        pool.catch(|| framework.dispatch(context, event.message, threadpool));
                     }
                 },
                 other => {
@@ -150,7 +189,7 @@ pub(crate) fn dispatch<H: EventHandler + Send + Sync + 'static,
                         data,
                         h,
                         runner_tx,
-                        threadpool,
+                        &pool,
                         shard_id,
                         cache_and_http,
                     );
@@ -158,6 +197,9 @@ pub(crate) fn dispatch<H: EventHandler + Send + Sync + 'static,
             }
         },
         (None, Some(ref rh)) => {
+            let handler = Arc::clone(rh);
+            pool.report = Some(Arc::new(move |panic| handler.handler_panic(panic)));
+
             if let DispatchEvent::Model(e) = event {
                 #[cfg(not(any(feature = "cache", feature = "http")))]
                 let context = context(data, runner_tx, shard_id);
@@ -168,8 +210,10 @@ pub(crate) fn dispatch<H: EventHandler + Send + Sync + 'static,
                 #[cfg(all(feature = "cache", feature = "http"))]
                 let context = context(data, runner_tx, shard_id, &cache_and_http.cache, &cache_and_http.http);
 
+                pool.catch(|| rh.raw_event_before_cache(&context, &e));
+
                 let event_handler = Arc::clone(rh);
-                threadpool.execute(move || {
+                pool.execute(move || {
                     event_handler.raw_event(context, e);
                 });
             }
@@ -183,6 +227,8 @@ pub(crate) fn dispatch<H: EventHandler + Send + Sync + 'static,
                              raw_event_handler,
                              runner_tx,
                              threadpool,
+                             event_queue,
+                             error_hook,
                              shard_id,
                              Arc::clone(&cache_and_http))
             }
@@ -193,6 +239,8 @@ pub(crate) fn dispatch<H: EventHandler + Send + Sync + 'static,
                      &None::<Arc<RH>>,
                      runner_tx,
                      threadpool,
+                     event_queue,
+                     error_hook,
                      shard_id,
                      cache_and_http);
         }
@@ -203,20 +251,41 @@ pub(crate) fn dispatch<H: EventHandler + Send + Sync + 'static,
 pub(crate) fn dispatch<H: EventHandler + Send + Sync + 'static,
                        RH: RawEventHandler + Send + Sync + 'static>(
     event: DispatchEvent,
-    data: &Arc<RwLock<ShareMap>>,
+    data: &Arc<RwLock<TypeMap>>,
     event_handler: &Option<Arc<H>>,
     raw_event_handler: &Option<Arc<RH>>,
     runner_tx: &Sender<InterMessage>,
     threadpool: &ThreadPool,
+    event_queue: &Arc<EventQueue>,
+    error_hook: &ErrorHook,
     shard_id: u64,
     cache_and_http: Arc<CacheAndHttp>,
 ) {
+    let mut pool = HandlerPool {
+        threadpool,
+        queue: event_queue,
+        error_hook,
+        kind: match event {
+            DispatchEvent::Model(ref e) => Some(e.event_type()),
+            _ => None,
+        },
+        lane: match event {
+            DispatchEvent::Model(ref e) => lane(e, event_queue.event_ordering(), &cache_and_http),
+            _ => None,
+        },
+        shard_id,
+        report: None,
+    };
+
     match (event_handler, raw_event_handler) {
         (None, None) => {}, // Do nothing
         (Some(ref h), None) => {
+            let handler = Arc::clone(h);
+            pool.report = Some(Arc::new(move |panic| handler.handler_panic(panic)));
+
             match event {
                 DispatchEvent::Model(Event::MessageCreate(mut event)) => {
-                    update(&cache_and_http, &mut event);
+                    update(&cache_and_http, &pool, &mut event);
 
                     #[cfg(not(any(feature = "cache", feature = "http")))]
                     let context = context(data, runner_tx, shard_id);
@@ -231,7 +300,7 @@ pub(crate) fn dispatch<H: EventHandler + Send + Sync + 'static,
                         context.clone(),
                         event.message.clone(),
                         h,
-                        threadpool,
+                        &pool,
                     );
                 },
                 other => {
@@ -240,7 +309,7 @@ pub(crate) fn dispatch<H: EventHandler + Send + Sync + 'static,
                         data,
                         h,
                         runner_tx,
-                        threadpool,
+                        &pool,
                         shard_id,
                         cache_and_http,
                     );
@@ -248,6 +317,9 @@ pub(crate) fn dispatch<H: EventHandler + Send + Sync + 'static,
             }
         },
         (None, Some(ref rh)) => {
+            let handler = Arc::clone(rh);
+            pool.report = Some(Arc::new(move |panic| handler.handler_panic(panic)));
+
             match event {
                 DispatchEvent::Model(e) => {
                     #[cfg(not(any(feature = "cache", feature = "http")))]
@@ -259,8 +331,10 @@ pub(crate) fn dispatch<H: EventHandler + Send + Sync + 'static,
                     #[cfg(all(feature = "cache", feature = "http"))]
                     let context = context(data, runner_tx, shard_id, &cache_and_http.cache, &cache_and_http.http);
 
+                    pool.catch(|| rh.raw_event_before_cache(&context, &e));
+
                     let event_handler = Arc::clone(rh);
-                    threadpool.execute(move || {
+                    pool.execute(move || {
                         event_handler.raw_event(context, e);
                     });
                 },
@@ -276,6 +350,8 @@ pub(crate) fn dispatch<H: EventHandler + Send + Sync + 'static,
                              raw_event_handler,
                              runner_tx,
                              threadpool,
+                             event_queue,
+                             error_hook,
                              shard_id,
                              Arc::clone(&cache_and_http)),
                 _ => {}
@@ -286,17 +362,132 @@ pub(crate) fn dispatch<H: EventHandler + Send + Sync + 'static,
                      &None::<Arc<RH>>,
                      runner_tx,
                      threadpool,
+                     event_queue,
+                     error_hook,
                      shard_id,
                      cache_and_http);
         }
     };
 }
 
+// The threadpool, along with the queue limiting how the handlers of an event
+// are run in it and where panics of the handlers are reported to.
+struct HandlerPool<'a> {
+    threadpool: &'a ThreadPool,
+    queue: &'a Arc<EventQueue>,
+    error_hook: &'a ErrorHook,
+    kind: Option<EventType>,
+    lane: Option<u64>,
+    shard_id: u64,
+    report: Option<Arc<dyn Fn(HandlerPanic) + Send + Sync>>,
+}
+
+impl HandlerPool<'_> {
+    fn execute<F>(&self, job: F) where F: FnOnce() + Send + 'static {
+        let kind = self.kind.clone();
+        let shard_id = self.shard_id;
+        let error_hook = self.error_hook.clone();
+        let report = self.report.clone();
+
+        EventQueue::execute(self.queue, self.threadpool, self.kind.clone(), self.lane, move || {
+            if let Err(panic) = handler_panic::catch(kind.as_ref(), shard_id, job) {
+                report_panic(&error_hook, report.as_ref(), panic);
+            }
+        });
+    }
+
+    // Runs a handler on the current thread, reporting a panic.
+    fn catch<F>(&self, f: F) where F: FnOnce() {
+        if let Err(panic) = handler_panic::catch(self.kind.as_ref(), self.shard_id, f) {
+            report_panic(self.error_hook, self.report.as_ref(), panic);
+        }
+    }
+}
+
+fn report_panic(
+    error_hook: &ErrorHook,
+    report: Option<&Arc<dyn Fn(HandlerPanic) + Send + Sync>>,
+    panic: HandlerPanic,
+) {
+    error_hook.report(ErrorContext::HandlerPanic(&panic));
+
+    if let Some(report) = report {
+        report(panic);
+    }
+}
+
+// The guild or channel the handlers of an event are ordered by, if any.
+#[allow(unused_variables)]
+fn lane(event: &Event, ordering: EventOrdering, cache_and_http: &CacheAndHttp) -> Option<u64> {
+    let (guild_id, channel_id) = match *event {
+        Event::ChannelCreate(ChannelCreateEvent { ref channel })
+        | Event::ChannelDelete(ChannelDeleteEvent { ref channel })
+        | Event::ChannelUpdate(ChannelUpdateEvent { ref channel }) => match *channel {
+            Channel::Guild(ref channel) => {
+                let channel = channel.read();
+
+                (Some(channel.guild_id), Some(channel.id))
+            },
+            Channel::Category(ref category) => (None, Some(category.read().id)),
+            Channel::Private(ref channel) => (None, Some(channel.read().id)),
+            Channel::Group(ref group) => (None, Some(group.read().channel_id)),
+            Channel::__Nonexhaustive => unreachable!(),
+        },
+        Event::ChannelPinsUpdate(ChannelPinsUpdateEvent { channel_id, .. })
+        | Event::ChannelRecipientAdd(ChannelRecipientAddEvent { channel_id, .. })
+        | Event::ChannelRecipientRemove(ChannelRecipientRemoveEvent { channel_id, .. })
+        | Event::MessageDelete(MessageDeleteEvent { channel_id, .. })
+        | Event::MessageDeleteBulk(MessageDeleteBulkEvent { channel_id, .. })
+        | Event::MessageUpdate(MessageUpdateEvent { channel_id, .. })
+        | Event::ReactionRemoveAll(ReactionRemoveAllEvent { channel_id, .. })
+        | Event::TypingStart(TypingStartEvent { channel_id, .. }) => (None, Some(channel_id)),
+        Event::MessageCreate(MessageCreateEvent { ref message }) => (message.guild_id, Some(message.channel_id)),
+        Event::ReactionAdd(ReactionAddEvent { ref reaction })
+        | Event::ReactionRemove(ReactionRemoveEvent { ref reaction }) => (reaction.guild_id, Some(reaction.channel_id)),
+        Event::WebhookUpdate(WebhookUpdateEvent { guild_id, channel_id }) => (Some(guild_id), Some(channel_id)),
+        Event::GuildBanAdd(GuildBanAddEvent { guild_id, .. })
+        | Event::GuildBanRemove(GuildBanRemoveEvent { guild_id, .. })
+        | Event::GuildEmojisUpdate(GuildEmojisUpdateEvent { guild_id, .. })
+        | Event::GuildIntegrationsUpdate(GuildIntegrationsUpdateEvent { guild_id })
+        | Event::GuildMemberAdd(GuildMemberAddEvent { guild_id, .. })
+        | Event::GuildMemberRemove(GuildMemberRemoveEvent { guild_id, .. })
+        | Event::GuildMemberUpdate(GuildMemberUpdateEvent { guild_id, .. })
+        | Event::GuildMembersChunk(GuildMembersChunkEvent { guild_id, .. })
+        | Event::GuildRoleCreate(GuildRoleCreateEvent { guild_id, .. })
+        | Event::GuildRoleDelete(GuildRoleDeleteEvent { guild_id, .. })
+        | Event::GuildRoleUpdate(GuildRoleUpdateEvent { guild_id, .. })
+        | Event::GuildUnavailable(GuildUnavailableEvent { guild_id }) => (Some(guild_id), None),
+        Event::GuildCreate(GuildCreateEvent { ref guild }) => (Some(guild.id), None),
+        Event::GuildDelete(GuildDeleteEvent { ref guild })
+        | Event::GuildUpdate(GuildUpdateEvent { ref guild }) => (Some(guild.id), None),
+        Event::PresenceUpdate(PresenceUpdateEvent { guild_id, .. })
+        | Event::VoiceStateUpdate(VoiceStateUpdateEvent { guild_id, .. }) => (guild_id, None),
+        Event::VoiceServerUpdate(VoiceServerUpdateEvent { guild_id, channel_id, .. }) => (guild_id, channel_id),
+        _ => (None, None),
+    };
+
+    match ordering {
+        EventOrdering::Channel => channel_id.map(|id| id.0),
+        EventOrdering::Guild => {
+            #[cfg(feature = "cache")]
+            let guild_id = guild_id.or_else(|| {
+                let channel = cache_and_http.cache.read().guild_channel(channel_id?)?;
+                let guild_id = channel.read().guild_id;
+
+                Some(guild_id)
+            });
+
+            guild_id.map(|id| id.0).or_else(|| channel_id.map(|id| id.0))
+        },
+        _ => None,
+    }
+}
+
 fn dispatch_message<H>(
     context: Context,
     mut message: Message,
     event_handler: &Arc<H>,
-    threadpool: &ThreadPool,
+    threadpool: &HandlerPool<'_>,
 ) where H: EventHandler + Send + Sync + 'static {
     #[cfg(feature = "model")]
     {
@@ -313,10 +504,10 @@ fn dispatch_message<H>(
 #[allow(clippy::too_many_arguments)]
 fn handle_event<H: EventHandler + Send + Sync + 'static>(
     event: DispatchEvent,
-    data: &Arc<RwLock<ShareMap>>,
+    data: &Arc<RwLock<TypeMap>>,
     event_handler: &Arc<H>,
     runner_tx: &Sender<InterMessage>,
-    threadpool: &ThreadPool,
+    threadpool: &HandlerPool<'_>,
     shard_id: u64,
     cache_and_http: Arc<CacheAndHttp>,
 ) {
@@ -338,7 +529,7 @@ fn handle_event<H: EventHandler + Send + Sync + 'static>(
             });
         }
         DispatchEvent::Model(Event::ChannelCreate(mut event)) => {
-            update(&cache_and_http, &mut event);
+            update(&cache_and_http, threadpool, &mut event);
             // Discord sends both a MessageCreate and a ChannelCreate upon a new message in a private channel.
             // This could potentially be annoying to handle when otherwise wanting to normally take care of a new channel.
             // So therefore, private channels are dispatched to their own handler code.
@@ -369,7 +560,7 @@ fn handle_event<H: EventHandler + Send + Sync + 'static>(
             }
         },
         DispatchEvent::Model(Event::ChannelDelete(mut event)) => {
-            update(&cache_and_http, &mut event);
+            update(&cache_and_http, threadpool, &mut event);
 
             match event.channel {
                 Channel::Private(_) | Channel::Group(_) => {},
@@ -399,7 +590,7 @@ fn handle_event<H: EventHandler + Send + Sync + 'static>(
             });
         },
         DispatchEvent::Model(Event::ChannelRecipientAdd(mut event)) => {
-            update(&cache_and_http, &mut event);
+            update(&cache_and_http, threadpool, &mut event);
 
             let event_handler = Arc::clone(event_handler);
 
@@ -412,7 +603,7 @@ fn handle_event<H: EventHandler + Send + Sync + 'static>(
             });
         },
         DispatchEvent::Model(Event::ChannelRecipientRemove(mut event)) => {
-            update(&cache_and_http, &mut event);
+            update(&cache_and_http, threadpool, &mut event);
 
             let event_handler = Arc::clone(event_handler);
 
@@ -425,17 +616,15 @@ fn handle_event<H: EventHandler + Send + Sync + 'static>(
             });
         },
         DispatchEvent::Model(Event::ChannelUpdate(mut event)) => {
+            #[cfg(feature = "cache")]
+            let before = cache_and_http.cache.as_ref().read().channel(event.channel.id());
+            update(&cache_and_http, threadpool, &mut event);
             let event_handler = Arc::clone(event_handler);
 
             threadpool.execute(move || {
                 feature_cache! {{
-                    let before = cache_and_http.cache.as_ref().read().channel(event.channel.id());
-                    update(&cache_and_http, &mut event);
-
                     event_handler.channel_update(context, before, event.channel);
                 } else {
-                    update(&cache_and_http, &mut event);
-
                     event_handler.channel_update(context, event.channel);
                 }}
             });
@@ -463,7 +652,7 @@ fn handle_event<H: EventHandler + Send + Sync + 'static>(
                 !cache.unavailable_guilds.contains(&event.guild.id)
             };
 
-            update(&cache_and_http, &mut event);
+            update(&cache_and_http, threadpool, &mut event);
 
             #[cfg(feature = "cache")]
             {
@@ -495,7 +684,7 @@ fn handle_event<H: EventHandler + Send + Sync + 'static>(
             });
         },
         DispatchEvent::Model(Event::GuildDelete(mut event)) => {
-            let _full = update(&cache_and_http, &mut event);
+            let _full = update(&cache_and_http, threadpool, &mut event);
             let event_handler = Arc::clone(event_handler);
 
             threadpool.execute(move || {
@@ -507,7 +696,7 @@ fn handle_event<H: EventHandler + Send + Sync + 'static>(
             });
         },
         DispatchEvent::Model(Event::GuildEmojisUpdate(mut event)) => {
-            update(&cache_and_http, &mut event);
+            update(&cache_and_http, threadpool, &mut event);
             let event_handler = Arc::clone(event_handler);
 
             threadpool.execute(move || {
@@ -522,7 +711,7 @@ fn handle_event<H: EventHandler + Send + Sync + 'static>(
             });
         },
         DispatchEvent::Model(Event::GuildMemberAdd(mut event)) => {
-            update(&cache_and_http, &mut event);
+            update(&cache_and_http, threadpool, &mut event);
 
             let event_handler = Arc::clone(event_handler);
 
@@ -531,7 +720,7 @@ fn handle_event<H: EventHandler + Send + Sync + 'static>(
             });
         },
         DispatchEvent::Model(Event::GuildMemberRemove(mut event)) => {
-            let _member = update(&cache_and_http, &mut event);
+            let _member = update(&cache_and_http, threadpool, &mut event);
             let event_handler = Arc::clone(event_handler);
 
             threadpool.execute(move || {
@@ -543,7 +732,7 @@ fn handle_event<H: EventHandler + Send + Sync + 'static>(
             });
         },
         DispatchEvent::Model(Event::GuildMemberUpdate(mut event)) => {
-            let _before = update(&cache_and_http, &mut event);
+            let _before = update(&cache_and_http, threadpool, &mut event);
             let _after: Option<Member> = feature_cache! {{
                 cache_and_http.cache.as_ref().read().member(event.guild_id, event.user.id)
             } else {
@@ -563,7 +752,7 @@ fn handle_event<H: EventHandler + Send + Sync + 'static>(
             });
         },
         DispatchEvent::Model(Event::GuildMembersChunk(mut event)) => {
-            update(&cache_and_http, &mut event);
+            update(&cache_and_http, threadpool, &mut event);
             let event_handler = Arc::clone(event_handler);
 
             threadpool.execute(move || {
@@ -571,7 +760,7 @@ fn handle_event<H: EventHandler + Send + Sync + 'static>(
             });
         },
         DispatchEvent::Model(Event::GuildRoleCreate(mut event)) => {
-            update(&cache_and_http, &mut event);
+            update(&cache_and_http, threadpool, &mut event);
             let event_handler = Arc::clone(event_handler);
 
             threadpool.execute(move || {
@@ -579,7 +768,7 @@ fn handle_event<H: EventHandler + Send + Sync + 'static>(
             });
         },
         DispatchEvent::Model(Event::GuildRoleDelete(mut event)) => {
-            let _role = update(&cache_and_http, &mut event);
+            let _role = update(&cache_and_http, threadpool, &mut event);
             let event_handler = Arc::clone(event_handler);
 
             threadpool.execute(move || {
@@ -591,7 +780,7 @@ fn handle_event<H: EventHandler + Send + Sync + 'static>(
             });
         },
         DispatchEvent::Model(Event::GuildRoleUpdate(mut event)) => {
-            let _before = update(&cache_and_http, &mut event);
+            let _before = update(&cache_and_http, threadpool, &mut event);
             let event_handler = Arc::clone(event_handler);
 
             threadpool.execute(move || {
@@ -603,7 +792,7 @@ fn handle_event<H: EventHandler + Send + Sync + 'static>(
             });
         },
         DispatchEvent::Model(Event::GuildUnavailable(mut event)) => {
-            update(&cache_and_http, &mut event);
+            update(&cache_and_http, threadpool, &mut event);
             let event_handler = Arc::clone(event_handler);
 
             threadpool.execute(move || {
@@ -611,20 +800,18 @@ fn handle_event<H: EventHandler + Send + Sync + 'static>(
             });
         },
         DispatchEvent::Model(Event::GuildUpdate(mut event)) => {
+            #[cfg(feature = "cache")]
+            let before = cache_and_http.cache.as_ref().read()
+                .guilds
+                .get(&event.guild.id)
+                .cloned();
+            update(&cache_and_http, threadpool, &mut event);
             let event_handler = Arc::clone(event_handler);
 
             threadpool.execute(move || {
                 feature_cache! {{
-                    let before = cache_and_http.cache.as_ref().read()
-                        .guilds
-                        .get(&event.guild.id)
-                        .cloned();
-                    update(&cache_and_http, &mut event);
-
                     event_handler.guild_update(context, before, event.guild);
                 } else {
-                    update(&cache_and_http, &mut event);
-
                     event_handler.guild_update(context, event.guild);
                 }}
             });
@@ -646,7 +833,7 @@ fn handle_event<H: EventHandler + Send + Sync + 'static>(
             });
         },
         DispatchEvent::Model(Event::MessageUpdate(mut event)) => {
-            let _before = update(&cache_and_http, &mut event);
+            let _before = update(&cache_and_http, threadpool, &mut event);
             let event_handler = Arc::clone(event_handler);
 
             threadpool.execute(move || {
@@ -659,7 +846,7 @@ fn handle_event<H: EventHandler + Send + Sync + 'static>(
             });
         },
         DispatchEvent::Model(Event::PresencesReplace(mut event)) => {
-            update(&cache_and_http, &mut event);
+            update(&cache_and_http, threadpool, &mut event);
             let event_handler = Arc::clone(event_handler);
 
             threadpool.execute(move || {
@@ -667,7 +854,7 @@ fn handle_event<H: EventHandler + Send + Sync + 'static>(
             });
         },
         DispatchEvent::Model(Event::PresenceUpdate(mut event)) => {
-            update(&cache_and_http, &mut event);
+            update(&cache_and_http, threadpool, &mut event);
 
             let event_handler = Arc::clone(event_handler);
 
@@ -675,7 +862,9 @@ fn handle_event<H: EventHandler + Send + Sync + 'static>(
                 event_handler.presence_update(context, event);
             });
         },
-        DispatchEvent::Model(Event::ReactionAdd(event)) => {
+        DispatchEvent::Model(Event::ReactionAdd(mut event)) => {
+            update(&cache_and_http, threadpool, &mut event);
+
             let event_handler = Arc::clone(event_handler);
 
             threadpool.execute(move || {
@@ -697,7 +886,7 @@ fn handle_event<H: EventHandler + Send + Sync + 'static>(
             });
         },
         DispatchEvent::Model(Event::Ready(mut event)) => {
-            update(&cache_and_http, &mut event);
+            update(&cache_and_http, threadpool, &mut event);
             let event_handler = Arc::clone(&event_handler);
 
             threadpool.execute(move || {
@@ -722,7 +911,7 @@ fn handle_event<H: EventHandler + Send + Sync + 'static>(
             });
         },
         DispatchEvent::Model(Event::UserUpdate(mut event)) => {
-            let _before = update(&cache_and_http, &mut event);
+            let _before = update(&cache_and_http, threadpool, &mut event);
             let event_handler = Arc::clone(event_handler);
 
             threadpool.execute(move || {
@@ -741,7 +930,7 @@ fn handle_event<H: EventHandler + Send + Sync + 'static>(
             });
         },
         DispatchEvent::Model(Event::VoiceStateUpdate(mut event)) => {
-            let _before = update(&cache_and_http, &mut event);
+            let _before = update(&cache_and_http, threadpool, &mut event);
             let event_handler = Arc::clone(event_handler);
 
             threadpool.execute(move || {
@@ -30,6 +30,15 @@ pub enum Error {
     /// When all shards that the client is responsible for have shutdown with an
     /// error.
     Shutdown,
+    /// When a [`ClientBuilder`] is built without an event handler or a raw
+    /// event handler, leaving nothing to dispatch events to.
+    ///
+    /// [`ClientBuilder`]: struct.ClientBuilder.html
+    NoEventHandler,
+    /// When a [`ClientBuilder`] is built with a threadpool of zero threads.
+    ///
+    /// [`ClientBuilder`]: struct.ClientBuilder.html
+    NoThreads,
     #[doc(hidden)]
     __Nonexhaustive,
 }
@@ -44,6 +53,8 @@ impl StdError for Error {
             Error::InvalidToken => "The provided token was invalid",
             Error::ShardBootFailure => "Failed to (re-)boot a shard",
             Error::Shutdown => "The clients shards shutdown",
+            Error::NoEventHandler => "No event handler was provided",
+            Error::NoThreads => "The threadpool must have at least one thread",
             Error::__Nonexhaustive => unreachable!(),
         }
     }
@@ -0,0 +1,95 @@
+use crate::internal::prelude::*;
+use crate::model::event::EventType;
+use parking_lot::RwLock;
+use std::{
+    fmt::{Debug, Formatter, Result as FmtResult},
+    sync::Arc,
+};
+use super::HandlerPanic;
+
+/// An error that occurred while the client was running on its own, rather
+/// than in a call made by the user, passed to the hook set via
+/// [`Client::on_error`].
+///
+/// [`Client::on_error`]: struct.Client.html#method.on_error
+#[derive(Debug)]
+pub enum ErrorContext<'a> {
+    /// An error of a shard's connection to the gateway, such as a failed
+    /// heartbeat or resume, or a session rejected by Discord.
+    Gateway {
+        shard_id: u64,
+        error: &'a Error,
+    },
+    /// Starting a shard failed, such as due to the gateway being unreachable.
+    /// The shard's start is retried.
+    ShardStart {
+        shard_id: u64,
+        error: &'a Error,
+    },
+    /// A request the client made on its own failed, such as querying the
+    /// session start limit while autosharding.
+    Http {
+        error: &'a Error,
+    },
+    /// An event of the type was not applied to the cache, as the cache could
+    /// not be locked within the [`cache_update_timeout`].
+    ///
+    /// [`cache_update_timeout`]: struct.ClientBuilder.html#method.cache_update_timeout
+    CacheUpdate {
+        shard_id: u64,
+        event: Option<EventType>,
+    },
+    /// An event handler or raw event handler panicked, including while
+    /// passing a message to the framework.
+    ///
+    /// **Note**: Panics of commands of the [`StandardFramework`] are reported
+    /// to its [`on_handler_panic`] hook instead.
+    ///
+    /// [`StandardFramework`]: ../framework/standard/struct.StandardFramework.html
+    /// [`on_handler_panic`]: ../framework/standard/struct.StandardFramework.html#method.on_handler_panic
+    HandlerPanic(&'a HandlerPanic),
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+type Hook = dyn Fn(ErrorContext<'_>) + Send + Sync;
+
+/// A handle to the hook errors are reported to, shared by the client with its
+/// shards.
+///
+/// Usually set via [`Client::on_error`].
+///
+/// [`Client::on_error`]: struct.Client.html#method.on_error
+#[derive(Clone, Default)]
+pub struct ErrorHook(Arc<RwLock<Option<Arc<Hook>>>>);
+
+impl ErrorHook {
+    /// Creates a handle without a hook set.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the hook, replacing the previous one.
+    pub fn set<F>(&self, hook: F)
+        where F: Fn(ErrorContext<'_>) + Send + Sync + 'static {
+        *self.0.write() = Some(Arc::new(hook));
+    }
+
+    /// Passes the error to the hook, if one is set.
+    pub fn report(&self, context: ErrorContext<'_>) {
+        let hook = self.0.read().clone();
+
+        if let Some(hook) = hook {
+            hook(context);
+        }
+    }
+}
+
+impl Debug for ErrorHook {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("ErrorHook")
+            .field("set", &self.0.read().is_some())
+            .finish()
+    }
+}
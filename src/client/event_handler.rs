@@ -5,7 +5,7 @@ use std::{
     collections::HashMap,
     sync::Arc
 };
-use super::context::Context;
+use super::{context::Context, handler_panic::HandlerPanic};
 use crate::client::bridge::gateway::event::*;
 
 /// The core trait for handling events by serenity.
@@ -265,6 +265,10 @@ pub trait EventHandler {
 
     /// Dispatched when a shard's connection stage is updated
     ///
+    /// This includes the shard connecting, identifying, resuming, becoming
+    /// connected, and becoming disconnected when it is shut down or about to
+    /// be restarted, making it suitable for tracking the health of shards.
+    ///
     /// Provides the context of the shard and the event information about the update.
     fn shard_stage_update(&self, _ctx: Context, _: ShardStageUpdateEvent) {}
 
@@ -311,10 +315,39 @@ pub trait EventHandler {
     ///
     /// Provides the guild's id and the channel's id the webhook belongs in.
     fn webhook_update(&self, _ctx: Context, _guild_id: GuildId, _belongs_to_channel_id: ChannelId) {}
+
+    /// Dispatched when one of the other methods panicked.
+    ///
+    /// The panic is caught, so the shard keeps running. Panics of commands
+    /// are instead passed to the framework's [`on_handler_panic`] hook.
+    ///
+    /// Provides information about the panic and the event being handled.
+    ///
+    /// [`on_handler_panic`]: ../framework/standard/struct.StandardFramework.html#method.on_handler_panic
+    fn handler_panic(&self, _panic: HandlerPanic) {}
 }
 
 /// This core trait for handling raw events
 pub trait RawEventHandler {
     /// Dispatched when any event occurs
     fn raw_event(&self, _ctx: Context, _ev: Event) {}
+
+    /// Dispatched when any event occurs, before the cache is updated with it.
+    ///
+    /// Unlike [`raw_event`], which runs in the threadpool at some point after
+    /// the event was received, this is called on the shard's thread right
+    /// away. This makes the state from before the event observable, such as
+    /// the old content of a message on a `MessageUpdate`.
+    ///
+    /// **Note**: No further events of the shard are handled until this
+    /// returns, so keep it short; forward the event elsewhere for any longer
+    /// work.
+    ///
+    /// [`raw_event`]: #method.raw_event
+    fn raw_event_before_cache(&self, _ctx: &Context, _ev: &Event) {}
+
+    /// Dispatched when one of the other methods panicked.
+    ///
+    /// The panic is caught, so the shard keeps running.
+    fn handler_panic(&self, _panic: HandlerPanic) {}
 }
@@ -0,0 +1,417 @@
+use crate::model::event::EventType;
+use parking_lot::{Condvar, Mutex};
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::{Debug, Formatter, Result as FmtResult},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+use threadpool::ThreadPool;
+use log::warn;
+
+/// What to do with an event when the queue of event handlers waiting for a
+/// thread is full.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum OverflowPolicy {
+    /// Wait until there is room in the queue.
+    ///
+    /// This stalls the shard that received the event, so no further events of
+    /// it are received until then.
+    Block,
+    /// Drop the handler that has been waiting the longest to make room.
+    DropOldest,
+    /// Drop the handler of the event that was just received.
+    DropNewest,
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+/// Which events the handlers of must run one after another, in the order the
+/// events were received.
+///
+/// Events of different guilds or channels are still handled in parallel.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum EventOrdering {
+    /// Handlers of all events may run in parallel, so a handler of an event
+    /// may run before or alongside one of an event received earlier.
+    Unordered,
+    /// Handlers of events of the same channel run in order.
+    ///
+    /// Events without a channel, such as member updates, are not ordered.
+    Channel,
+    /// Handlers of events of the same guild run in order.
+    ///
+    /// Events only carrying the ID of their channel, such as message
+    /// deletions, are assigned to the channel's guild via the cache. Without
+    /// the `cache` feature, or if the channel is not cached, they are ordered
+    /// with the other events of their channel instead. Events outside of
+    /// guilds are ordered per channel.
+    Guild,
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+impl Default for EventOrdering {
+    fn default() -> Self {
+        EventOrdering::Unordered
+    }
+}
+
+/// Limits on how [`EventHandler`] and [`RawEventHandler`] callbacks are run in
+/// the client's threadpool, so that a slow handler cannot make events pile up
+/// without bound.
+///
+/// By default, nothing is limited and handlers of events are not ordered.
+///
+/// **Note**: Commands of the framework are not affected by these limits.
+///
+/// # Examples
+///
+/// Allowing up to 1000 handlers to wait for a thread, dropping the oldest of
+/// them when more arrive, and running at most 2 handlers of typing events at
+/// once:
+///
+/// ```rust,no_run
+/// # use std::error::Error;
+/// #
+/// # fn try_main() -> Result<(), Box<Error>> {
+/// use serenity::client::{ClientBuilder, EventHandler, EventQueue, OverflowPolicy};
+/// use serenity::model::event::EventType;
+/// use std::env;
+///
+/// struct Handler;
+///
+/// impl EventHandler for Handler {}
+///
+/// let queue = EventQueue::new()
+///     .max_queued(1000, OverflowPolicy::DropOldest)
+///     .concurrency_limit(EventType::TypingStart, 2);
+///
+/// let mut client = ClientBuilder::new(&env::var("DISCORD_TOKEN")?)
+///     .event_handler(Handler)
+///     .event_queue(queue)
+///     .build()?;
+/// # Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     try_main().unwrap();
+/// # }
+/// ```
+///
+/// Handling the events of each guild in order, so that a message's deletion
+/// is never handled before its creation:
+///
+/// ```rust
+/// use serenity::client::{EventOrdering, EventQueue};
+///
+/// let queue = EventQueue::new().ordering(EventOrdering::Guild);
+/// ```
+///
+/// [`EventHandler`]: trait.EventHandler.html
+/// [`RawEventHandler`]: trait.RawEventHandler.html
+#[derive(Debug, Default)]
+pub struct EventQueue {
+    max_queued: Option<(usize, OverflowPolicy)>,
+    concurrency: HashMap<EventType, usize>,
+    ordering: EventOrdering,
+    state: Mutex<State>,
+    changed: Condvar,
+}
+
+type Job = Box<dyn FnOnce() + Send>;
+
+#[derive(Default)]
+struct State {
+    // cancellation flags of the handlers waiting for a thread, oldest first
+    queued: VecDeque<Arc<AtomicBool>>,
+    running: HashMap<EventType, usize>,
+    // handlers waiting for the one of their guild or channel that is running
+    lanes: HashMap<u64, VecDeque<Job>>,
+}
+
+impl Debug for State {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("State")
+            .field("queued", &self.queued.len())
+            .field("running", &self.running)
+            .field("lanes", &self.lanes.len())
+            .finish()
+    }
+}
+
+impl EventQueue {
+    /// Creates a queue without any limits.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how many handlers may wait for a thread at once, and what happens
+    /// to further events while that many are waiting.
+    ///
+    /// A `max` of 0 is treated as 1.
+    pub fn max_queued(mut self, max: usize, policy: OverflowPolicy) -> Self {
+        self.max_queued = Some((max.max(1), policy));
+
+        self
+    }
+
+    /// Sets how many handlers of the event type may run at once.
+    ///
+    /// Further handlers of that type wait for one to finish, occupying a
+    /// thread while doing so.
+    ///
+    /// A `limit` of 0 is treated as 1.
+    pub fn concurrency_limit(mut self, kind: EventType, limit: usize) -> Self {
+        self.concurrency.insert(kind, limit.max(1));
+
+        self
+    }
+
+    /// Sets which events are handled one after another.
+    ///
+    /// Refer to [`EventOrdering`] for the options.
+    ///
+    /// [`EventOrdering`]: enum.EventOrdering.html
+    pub fn ordering(mut self, ordering: EventOrdering) -> Self {
+        self.ordering = ordering;
+
+        self
+    }
+
+    #[inline]
+    pub(crate) fn event_ordering(&self) -> EventOrdering {
+        self.ordering
+    }
+
+    /// Runs the handler of an event of the given type in the threadpool,
+    /// subject to the limits.
+    ///
+    /// Handlers given the same `lane` run in the order they were given.
+    pub(crate) fn execute<F>(
+        queue: &Arc<Self>,
+        threadpool: &ThreadPool,
+        kind: Option<EventType>,
+        lane: Option<u64>,
+        job: F,
+    ) where F: FnOnce() + Send + 'static {
+        let cancelled = Arc::new(AtomicBool::new(false));
+
+        {
+            let mut state = queue.state.lock();
+
+            if let Some((max, policy)) = queue.max_queued {
+                if state.queued.len() >= max {
+                    match policy {
+                        OverflowPolicy::Block => {
+                            while state.queued.len() >= max {
+                                queue.changed.wait(&mut state);
+                            }
+                        },
+                        OverflowPolicy::DropOldest => {
+                            if let Some(oldest) = state.queued.pop_front() {
+                                oldest.store(true, Ordering::SeqCst);
+                            }
+
+                            warn!("Event queue is full, dropping the oldest event handler");
+                        },
+                        OverflowPolicy::DropNewest => {
+                            warn!("Event queue is full, dropping the event handler");
+
+                            return;
+                        },
+                        OverflowPolicy::__Nonexhaustive => unreachable!(),
+                    }
+                }
+            }
+
+            state.queued.push_back(Arc::clone(&cancelled));
+        }
+
+        let run = {
+            let queue = Arc::clone(queue);
+
+            move || Self::run(&queue, &cancelled, kind, job)
+        };
+
+        let lane = match lane {
+            Some(lane) => lane,
+            None => return threadpool.execute(run),
+        };
+
+        {
+            let mut state = queue.state.lock();
+
+            if let Some(waiting) = state.lanes.get_mut(&lane) {
+                waiting.push_back(Box::new(run));
+
+                return;
+            }
+
+            state.lanes.insert(lane, VecDeque::new());
+        }
+
+        let queue = Arc::clone(queue);
+
+        // The handlers of the lane given while this one runs are run by the
+        // same thread afterwards, keeping their order.
+        threadpool.execute(move || {
+            let mut next: Job = Box::new(run);
+
+            loop {
+                next();
+
+                let mut state = queue.state.lock();
+
+                match state.lanes.get_mut(&lane).and_then(VecDeque::pop_front) {
+                    Some(job) => next = job,
+                    None => {
+                        state.lanes.remove(&lane);
+
+                        break;
+                    },
+                }
+            }
+        });
+    }
+
+    fn run<F>(queue: &Self, cancelled: &Arc<AtomicBool>, kind: Option<EventType>, job: F)
+        where F: FnOnce() {
+        let _running = {
+            let mut state = queue.state.lock();
+
+            // The flag was already removed from the queue when the handler was
+            // dropped.
+            if cancelled.load(Ordering::SeqCst) {
+                return;
+            }
+
+            if let Some(pos) = state.queued.iter().position(|c| Arc::ptr_eq(c, cancelled)) {
+                state.queued.remove(pos);
+            }
+
+            queue.changed.notify_all();
+
+            match kind.and_then(|kind| queue.concurrency.get(&kind).map(|&limit| (kind, limit))) {
+                Some((kind, limit)) => {
+                    while state.running.get(&kind).map_or(false, |&running| running >= limit) {
+                        queue.changed.wait(&mut state);
+                    }
+
+                    *state.running.entry(kind.clone()).or_insert(0) += 1;
+
+                    Some(Running {
+                        queue,
+                        kind,
+                    })
+                },
+                None => None,
+            }
+        };
+
+        job();
+    }
+}
+
+// Releases a handler's slot of its event type once dropped, even if the
+// handler panicked.
+struct Running<'a> {
+    queue: &'a EventQueue,
+    kind: EventType,
+}
+
+impl Drop for Running<'_> {
+    fn drop(&mut self) {
+        let mut state = self.queue.state.lock();
+
+        if let Some(running) = state.running.get_mut(&self.kind) {
+            *running -= 1;
+        }
+
+        self.queue.changed.notify_all();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{EventQueue, OverflowPolicy};
+    use parking_lot::Mutex;
+    use std::{
+        sync::{mpsc, Arc},
+        thread,
+        time::Duration,
+    };
+    use threadpool::ThreadPool;
+
+    // Runs two handlers while the only thread is busy and the queue has room
+    // for one, returning which of them ran.
+    fn overflow(policy: OverflowPolicy) -> Vec<u8> {
+        let queue = Arc::new(EventQueue::new().max_queued(1, policy));
+        let threadpool = ThreadPool::new(1);
+        let ran = Arc::new(Mutex::new(Vec::new()));
+
+        let (started_tx, started_rx) = mpsc::channel();
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+
+        EventQueue::execute(&queue, &threadpool, None, None, move || {
+            let _ = started_tx.send(());
+            let _ = release_rx.recv();
+        });
+        started_rx.recv().unwrap();
+
+        for i in 0..2 {
+            let ran = Arc::clone(&ran);
+
+            EventQueue::execute(&queue, &threadpool, None, None, move || ran.lock().push(i));
+        }
+
+        release_tx.send(()).unwrap();
+        threadpool.join();
+
+        let ran = ran.lock().clone();
+        ran
+    }
+
+    #[test]
+    fn drop_newest() {
+        assert_eq!(overflow(OverflowPolicy::DropNewest), vec![0]);
+    }
+
+    #[test]
+    fn drop_oldest() {
+        assert_eq!(overflow(OverflowPolicy::DropOldest), vec![1]);
+    }
+
+    #[test]
+    fn lanes_keep_order() {
+        let queue = Arc::new(EventQueue::new());
+        let threadpool = ThreadPool::new(4);
+        let ran = Arc::new(Mutex::new(Vec::new()));
+
+        for i in 0..50u64 {
+            let ran = Arc::clone(&ran);
+
+            EventQueue::execute(&queue, &threadpool, None, Some(i % 2), move || {
+                // Later handlers of a lane would overtake this one if they
+                // were run in parallel.
+                thread::sleep(Duration::from_millis(50 - i));
+                ran.lock().push(i);
+            });
+        }
+
+        threadpool.join();
+
+        let ran = ran.lock();
+
+        for lane in 0..2 {
+            let order = ran.iter().filter(|&&i| i % 2 == lane).cloned().collect::<Vec<_>>();
+
+            assert_eq!(order, (0..50).filter(|i| i % 2 == lane).collect::<Vec<_>>());
+        }
+
+        assert!(queue.state.lock().lanes.is_empty());
+    }
+}
@@ -0,0 +1,85 @@
+use crate::model::event::EventType;
+use std::{
+    any::Any,
+    fmt::{Display, Formatter, Result as FmtResult},
+    panic::{self, AssertUnwindSafe},
+};
+use log::error;
+
+/// Information about a panic caught while running an event handler or a
+/// command.
+///
+/// The panic is caught so the shard and the threads of the client keep
+/// running; this is then passed to [`EventHandler::handler_panic`],
+/// [`RawEventHandler::handler_panic`], or the framework's
+/// [`on_handler_panic`] hook.
+///
+/// [`EventHandler::handler_panic`]: trait.EventHandler.html#method.handler_panic
+/// [`RawEventHandler::handler_panic`]: trait.RawEventHandler.html#method.handler_panic
+/// [`on_handler_panic`]: ../framework/standard/struct.StandardFramework.html#method.on_handler_panic
+#[derive(Clone, Debug)]
+pub struct HandlerPanic {
+    /// The message the handler panicked with, if it was a string.
+    pub message: Option<String>,
+    /// The type of the event being handled, or `None` for events created by
+    /// the client itself, such as shard stage updates.
+    pub event: Option<EventType>,
+    /// The ID of the shard that received the event.
+    pub shard_id: u64,
+}
+
+impl HandlerPanic {
+    pub(crate) fn new(payload: Box<dyn Any + Send>, event: Option<EventType>, shard_id: u64) -> Self {
+        let message = match payload.downcast::<String>() {
+            Ok(message) => Some(*message),
+            Err(payload) => payload.downcast_ref::<&str>().map(|message| message.to_string()),
+        };
+
+        HandlerPanic {
+            message,
+            event,
+            shard_id,
+        }
+    }
+}
+
+impl Display for HandlerPanic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self.message {
+            Some(ref message) => write!(f, "handler panicked: {}", message),
+            None => f.write_str("handler panicked"),
+        }
+    }
+}
+
+/// Runs `f`, catching and logging a panic instead of unwinding further.
+pub(crate) fn catch<F, R>(event: Option<&EventType>, shard_id: u64, f: F) -> Result<R, HandlerPanic>
+    where F: FnOnce() -> R {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| {
+        let panic = HandlerPanic::new(payload, event.cloned(), shard_id);
+
+        error!("[Shard {}] Caught panic handling {:?}: {}", shard_id, panic.event, panic);
+
+        panic
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::catch;
+    use crate::model::event::EventType;
+
+    #[test]
+    fn catches_message() {
+        let panic = catch(Some(&EventType::TypingStart), 3, || panic!("failed {}", 1)).unwrap_err();
+
+        assert_eq!(panic.message.as_ref().map(String::as_str), Some("failed 1"));
+        assert_eq!(panic.event, Some(EventType::TypingStart));
+        assert_eq!(panic.shard_id, 3);
+
+        let panic = catch(None, 0, || panic!("static")).unwrap_err();
+
+        assert_eq!(panic.message.as_ref().map(String::as_str), Some("static"));
+        assert_eq!(catch(None, 0, || 7).ok(), Some(7));
+    }
+}
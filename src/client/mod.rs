@@ -20,17 +20,33 @@
 
 pub mod bridge;
 
+mod builder;
 mod context;
 mod dispatch;
 mod error;
+mod error_hook;
 mod event_handler;
+mod event_queue;
+pub(crate) mod handler_panic;
+mod type_map;
 
 pub use self::{
+    builder::ClientBuilder,
     context::Context,
     error::Error as ClientError,
+    error_hook::{ErrorContext, ErrorHook},
     event_handler::{EventHandler, RawEventHandler},
+    event_queue::{EventOrdering, EventQueue, OverflowPolicy},
+    handler_panic::HandlerPanic,
+    type_map::{Entry as TypeMapEntry, TypeMap, TypeMapKey},
 };
 
+/// The map formerly used for [`Client::data`].
+///
+/// [`Client::data`]: struct.Client.html#structfield.data
+#[deprecated(note = "use `TypeMap` instead")]
+pub type ShareMap = TypeMap;
+
 #[cfg(any(feature = "cache", feature = "http"))]
 pub use crate::CacheAndHttp;
 
@@ -44,10 +60,10 @@ pub use crate::cache::{Cache, CacheRwLock};
 use crate::internal::prelude::*;
 use parking_lot::Mutex;
 use parking_lot::RwLock;
-use self::bridge::gateway::{ShardManager, ShardManagerMonitor, ShardManagerOptions};
+use self::bridge::gateway::{ShardManager, ShardManagerMonitor, ShutdownHandle};
 use std::sync::Arc;
 use threadpool::ThreadPool;
-use typemap::ShareMap;
+use crate::utils::token;
 use log::{error, debug, info};
 
 #[cfg(feature = "framework")]
@@ -56,14 +72,8 @@ use crate::framework::Framework;
 use crate::model::id::UserId;
 #[cfg(feature = "voice")]
 use self::bridge::voice::ClientVoiceManager;
-#[cfg(feature = "http")]
-use crate::http::Http;
-#[cfg(feature = "cache")]
 use std::time::Duration;
 
-struct DummyRawEventHandler;
-impl RawEventHandler for DummyRawEventHandler {}
-
 /// The Client is the way to be able to start sending authenticated requests
 /// over the REST API, as well as initializing a WebSocket connection through
 /// [`Shard`]s. Refer to the [documentation on using sharding][sharding docs]
@@ -108,8 +118,8 @@ impl RawEventHandler for DummyRawEventHandler {}
 /// [`Event::MessageCreate`]: ../model/event/enum.Event.html#variant.MessageCreate
 /// [sharding docs]: ../index.html#sharding
 pub struct Client {
-    /// A ShareMap which requires types to be Send + Sync. This is a map that
-    /// can be safely shared across contexts.
+    /// A [`TypeMap`] which requires types to be Send + Sync. This is a map
+    /// that can be safely shared across contexts.
     ///
     /// The purpose of the data field is to be accessible and persistent across
     /// contexts; that is, data can be modified by one context, and will persist
@@ -130,7 +140,7 @@ pub struct Client {
     /// - [`Event::MessageUpdate`]
     ///
     /// ```rust,ignore
-    /// // Of note, this imports `typemap`'s `Key` as `TypeMapKey`.
+    /// // Of note, this imports `TypeMap` and `TypeMapKey`.
     /// use serenity::prelude::*;
     /// use serenity::model::*;
     /// use std::collections::HashMap;
@@ -182,12 +192,13 @@ pub struct Client {
     /// Refer to [example 05] for an example on using the `data` field.
     ///
     /// [`Context::data`]: struct.Context.html#structfield.data
+    /// [`TypeMap`]: struct.TypeMap.html
     /// [`Event::MessageCreate`]: ../model/event/enum.Event.html#variant.MessageCreate
     /// [`Event::MessageDelete`]: ../model/event/enum.Event.html#variant.MessageDelete
     /// [`Event::MessageDeleteBulk`]: ../model/event/enum.Event.html#variant.MessageDeleteBulk
     /// [`Event::MessageUpdate`]: ../model/event/enum.Event.html#variant.MessageUpdate
     /// [example 05]: https://github.com/serenity-rs/serenity/tree/current/examples/05_command_framework
-    pub data: Arc<RwLock<ShareMap>>,
+    pub data: Arc<RwLock<TypeMap>>,
     /// A vector of all active shards that have received their [`Event::Ready`]
     /// payload, and have dispatched to [`on_ready`] if an event handler was
     /// configured.
@@ -306,8 +317,18 @@ pub struct Client {
     ///
     /// This is wrapped in an `Arc<Mutex<T>>` so all shards will have an updated
     /// value available.
+    ///
+    /// **Note**: This is empty until the client is started.
     pub ws_uri: Arc<Mutex<String>>,
+    /// The cache and the ratelimited HTTP client shared by all shards.
+    ///
+    /// These are usable before the client is started. Tools that only need
+    /// the REST API can create them alone, without a client, via
+    /// [`ClientBuilder::build_http_only`].
+    ///
+    /// [`ClientBuilder::build_http_only`]: struct.ClientBuilder.html#method.build_http_only
     pub cache_and_http: Arc<CacheAndHttp>,
+    error_hook: ErrorHook,
 }
 
 impl Client {
@@ -342,79 +363,18 @@ impl Client {
     /// ```
     pub fn new<H>(token: impl AsRef<str>, handler: H) -> Result<Self>
         where H: EventHandler + Send + Sync + 'static {
-
-        Self::new_with_handlers(token.as_ref(), Some(handler), None::<DummyRawEventHandler>)
+        ClientBuilder::new(token).event_handler(handler).build_unchecked()
     }
     /// Creates a client with an optional Handler. If you pass `None`, events are never parsed, but
     /// they can be received by registering a RawHandler.
+    ///
+    /// Prefer [`ClientBuilder`], which also validates its settings.
+    ///
+    /// [`ClientBuilder`]: struct.ClientBuilder.html
     pub fn new_with_handlers<H, RH>(token: impl AsRef<str>, handler: Option<H>, raw_handler: Option<RH>) -> Result<Self>
         where H: EventHandler + Send + Sync + 'static,
               RH: RawEventHandler + Send + Sync + 'static {
-        let token = token.as_ref().trim();
-
-        let token = if token.starts_with("Bot ") {
-            token.to_string()
-        } else {
-            format!("Bot {}", token)
-        };
-
-        let http = Http::new_with_token(&token);
-
-        let name = "serenity client".to_owned();
-        let threadpool = ThreadPool::with_name(name, 5);
-        let url = Arc::new(Mutex::new(http.get_gateway()?.url));
-        let data = Arc::new(RwLock::new(ShareMap::custom()));
-        let event_handler = handler.map(Arc::new);
-        let raw_event_handler = raw_handler.map(Arc::new);
-
-        #[cfg(feature = "framework")]
-        let framework = Arc::new(Mutex::new(None));
-        #[cfg(feature = "voice")]
-        let voice_manager = Arc::new(Mutex::new(ClientVoiceManager::new(
-            0,
-            UserId(0),
-        )));
-
-        let cache_and_http = Arc::new(CacheAndHttp {
-            #[cfg(feature = "cache")]
-            cache: Arc::new(RwLock::new(Cache::default())),
-            #[cfg(feature = "cache")]
-            update_cache_timeout: None,
-            #[cfg(feature = "http")]
-            http: Arc::new(http),
-            __nonexhaustive: (),
-        });
-
-        let (shard_manager, shard_manager_worker) = {
-            ShardManager::new(ShardManagerOptions {
-                data: &data,
-                event_handler: &event_handler,
-                raw_event_handler: &raw_event_handler,
-                #[cfg(feature = "framework")]
-                framework: &framework,
-                shard_index: 0,
-                shard_init: 0,
-                shard_total: 0,
-                threadpool: threadpool.clone(),
-                #[cfg(feature = "voice")]
-                voice_manager: &voice_manager,
-                ws_url: &url,
-                cache_and_http: &cache_and_http,
-            })
-        };
-
-        Ok(Client {
-            ws_uri: url,
-            #[cfg(feature = "framework")]
-            framework,
-            data,
-            shard_manager,
-            shard_manager_worker,
-            threadpool,
-            #[cfg(feature = "voice")]
-            voice_manager,
-            cache_and_http,
-        })
+        ClientBuilder::with_handlers(token, handler, raw_handler).build_unchecked()
     }
 
     /// Creates a Client for a bot user and sets a cache update timeout.
@@ -454,68 +414,12 @@ impl Client {
     #[cfg(all(feature = "cache", feature = "http"))]
     pub fn new_with_cache_update_timeout<H>(token: impl AsRef<str>, handler: H, duration: Option<Duration>) -> Result<Self>
         where H: EventHandler + Send + Sync + 'static {
-        let token = token.as_ref().trim();
-
-        let token = if token.starts_with("Bot ") {
-            token.to_string()
-        } else {
-            format!("Bot {}", token)
-        };
-
-        let http = Http::new_with_token(&token);
-
-        let name = "serenity client".to_owned();
-        let threadpool = ThreadPool::with_name(name, 5);
-        let url = Arc::new(Mutex::new(http.get_gateway()?.url));
-        let data = Arc::new(RwLock::new(ShareMap::custom()));
-        let event_handler = Some(Arc::new(handler));
-
-        #[cfg(feature = "framework")]
-        let framework = Arc::new(Mutex::new(None));
-        #[cfg(feature = "voice")]
-        let voice_manager = Arc::new(Mutex::new(ClientVoiceManager::new(
-            0,
-            UserId(0),
-        )));
-
-        let cache_and_http = Arc::new(CacheAndHttp {
-            cache: Arc::new(RwLock::new(Cache::default())),
-            update_cache_timeout: duration,
-            #[cfg(feature = "http")]
-            http: Arc::new(http),
-            __nonexhaustive: (),
-        });
+        let builder = ClientBuilder::new(token).event_handler(handler);
 
-        let (shard_manager, shard_manager_worker) = {
-            ShardManager::new(ShardManagerOptions {
-                data: &data,
-                event_handler: &event_handler,
-                raw_event_handler: &None::<Arc<DummyRawEventHandler>>,
-                #[cfg(feature = "framework")]
-                framework: &framework,
-                shard_index: 0,
-                shard_init: 0,
-                shard_total: 0,
-                threadpool: threadpool.clone(),
-                #[cfg(feature = "voice")]
-                voice_manager: &voice_manager,
-                ws_url: &url,
-                cache_and_http: &cache_and_http,
-            })
-        };
-
-        Ok(Client {
-            ws_uri: url,
-            #[cfg(feature = "framework")]
-            framework,
-            data,
-            shard_manager,
-            shard_manager_worker,
-            threadpool,
-            #[cfg(feature = "voice")]
-            voice_manager,
-            cache_and_http,
-        })
+        match duration {
+            Some(duration) => builder.cache_update_timeout(duration),
+            None => builder,
+        }.build_unchecked()
     }
 
     /// Sets a framework to be used with the client. All message events will be
@@ -640,6 +544,56 @@ impl Client {
         *self.framework.lock() = Some(Box::new(f));
     }
 
+    /// Sets a hook that errors occurring while the client runs are reported
+    /// to, replacing the previous one.
+    ///
+    /// This covers errors of the shards' gateway connections, shards failing
+    /// to start, requests the client makes on its own, events that could not
+    /// be applied to the cache, and panics of event handlers, so that they can
+    /// be forwarded to an error tracker in a single place.
+    /// Refer to [`ErrorContext`] for the details passed along.
+    ///
+    /// The hook is called on the thread the error occurred on, which may be a
+    /// shard's thread, so it should not block or panic.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use serenity::prelude::EventHandler;
+    /// # use std::error::Error;
+    /// #
+    /// # struct Handler;
+    /// #
+    /// # impl EventHandler for Handler {}
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// use serenity::client::{Client, ErrorContext};
+    /// use std::env;
+    ///
+    /// let mut client = Client::new(&env::var("DISCORD_TOKEN")?, Handler)?;
+    ///
+    /// client.on_error(|context| match context {
+    ///     ErrorContext::Gateway { shard_id, error } => {
+    ///         eprintln!("Shard {} failed: {:?}", shard_id, error);
+    ///     },
+    ///     ErrorContext::HandlerPanic(panic) => eprintln!("{}", panic),
+    ///     other => eprintln!("{:?}", other),
+    /// });
+    ///
+    /// client.start()?;
+    /// # Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`ErrorContext`]: enum.ErrorContext.html
+    pub fn on_error<F>(&self, hook: F)
+        where F: Fn(ErrorContext<'_>) + Send + Sync + 'static {
+        self.error_hook.set(hook);
+    }
+
     /// Establish the connection and start listening for events.
     ///
     /// This will start receiving events in a loop and start dispatching the
@@ -696,6 +650,13 @@ impl Client {
     /// from the API - determined by Discord - and then open a number of shards
     /// equivalent to that amount.
     ///
+    /// Shards are only started while the bot's session start limit allows it;
+    /// once it is exhausted, starting further shards is paused until the limit
+    /// resets, rather than having their sessions invalidated. If starting
+    /// shards keeps failing for a long time, such as during a network outage,
+    /// the recommended number of shards is retrieved again, and all shards are
+    /// restarted if it changed.
+    ///
     /// Refer to the [Gateway documentation][gateway docs] for more information
     /// on effectively using sharding.
     ///
@@ -737,11 +698,18 @@ impl Client {
     /// [gateway docs]: ../gateway/index.html#sharding
     #[cfg(feature = "http")]
     pub fn start_autosharded(&mut self) -> Result<()> {
-        let (x, y) = {
-            let res = self.cache_and_http.http.get_bot_gateway()?;
+        let info = self.cache_and_http.http.get_bot_gateway()?;
+        let (x, y) = (info.shards as u64 - 1, info.shards as u64);
+
+        {
+            let mut url = self.ws_uri.lock();
+
+            if url.is_empty() {
+                *url = info.url.clone();
+            }
+        }
 
-            (res.shards as u64 - 1, res.shards as u64)
-        };
+        self.shard_manager.lock().set_autosharding(info);
 
         self.start_connection([0, x, y])
     }
@@ -954,6 +922,56 @@ impl Client {
         self.start_connection([range[0], range[1], total_shards])
     }
 
+    /// Shuts down all shards gracefully, waiting up to `timeout` for running
+    /// event handlers and commands to finish.
+    ///
+    /// Returns whether they all finished before the timeout.
+    ///
+    /// As the client is borrowed by the `start` methods while they run, this
+    /// is a shorthand for use around them; to shut down a running client,
+    /// take a [`shutdown_handle`] before starting it instead.
+    ///
+    /// [`shutdown_handle`]: #method.shutdown_handle
+    pub fn shutdown(&self, timeout: Duration) -> bool {
+        ShardManager::shutdown_all_gracefully(&self.shard_manager, timeout)
+    }
+
+    /// Returns a cloneable handle that gracefully shuts the client down while
+    /// it runs, such as from another thread or a command.
+    ///
+    /// # Examples
+    ///
+    /// Shut down after an hour, giving running commands up to 30 seconds to
+    /// finish:
+    ///
+    /// ```rust,no_run
+    /// # use serenity::prelude::*;
+    /// # struct Handler;
+    /// #
+    /// # impl EventHandler for Handler {}
+    /// # let mut client = Client::new("token", Handler).unwrap();
+    /// #
+    /// use std::{thread, time::Duration};
+    ///
+    /// let handle = client.shutdown_handle();
+    ///
+    /// thread::spawn(move || {
+    ///     thread::sleep(Duration::from_secs(60 * 60));
+    ///
+    ///     handle.shutdown(Duration::from_secs(30));
+    /// });
+    ///
+    /// let _ = client.start();
+    /// ```
+    ///
+    /// The handle may also be stored in the client's [`data`] to shut down
+    /// from a command.
+    ///
+    /// [`data`]: #structfield.data
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        ShutdownHandle::new(&self.shard_manager)
+    }
+
     // Shard data layout is:
     // 0: first shard number to initialize
     // 1: shard number to initialize up to and including
@@ -979,6 +997,12 @@ impl Client {
             self.voice_manager.lock().set_user_id(user.id);
         }
 
+        let fetch_url = self.ws_uri.lock().is_empty();
+
+        if fetch_url {
+            *self.ws_uri.lock() = self.cache_and_http.http.get_gateway()?.url;
+        }
+
         {
             let mut manager = self.shard_manager.lock();
 
@@ -1018,6 +1042,9 @@ impl Client {
 /// - The second part of the token is at least 6 characters long;
 /// - The token does not contain any whitespace prior to or after the token.
 ///
+/// Refer to [`utils::token::validate`] to also check that the token contains
+/// a user Id.
+///
 /// # Examples
 ///
 /// Validate that a token is valid and that a number of invalid tokens are
@@ -1047,29 +1074,9 @@ impl Client {
 /// The type of failure is not specified.
 ///
 /// [`ClientError::InvalidToken`]: enum.ClientError.html#variant.InvalidToken
+/// [`utils::token::validate`]: ../utils/token/fn.validate.html
 pub fn validate_token(token: impl AsRef<str>) -> Result<()> {
-    let token = token.as_ref();
-
-    if token.is_empty() {
-        return Err(Error::Client(ClientError::InvalidToken));
-    }
-
-    let parts: Vec<&str> = token.split('.').collect();
-
-    // Check that the token has a total of 3 parts.
-    if parts.len() != 3 {
-        return Err(Error::Client(ClientError::InvalidToken));
-    }
-
-    // Check that the second part is at least 6 characters long.
-    if parts[1].len() < 6 {
-        return Err(Error::Client(ClientError::InvalidToken));
-    }
-
-    // Check that there is no whitespace before/after the token.
-    if token.trim() != token {
-        return Err(Error::Client(ClientError::InvalidToken));
-    }
-
-    Ok(())
+    token::check_structure(token.as_ref())
+        .map(|_| ())
+        .map_err(|_| Error::Client(ClientError::InvalidToken))
 }
@@ -0,0 +1,250 @@
+//! A map storing at most one value per key type, used for [`Client::data`].
+//!
+//! [`Client::data`]: ../struct.Client.html#structfield.data
+
+use std::{
+    any::{Any, TypeId},
+    collections::hash_map::{Entry as HashMapEntry, HashMap},
+    fmt::{Debug, Formatter, Result as FmtResult},
+    marker::PhantomData,
+};
+
+/// A key into a [`TypeMap`], associating the key type with the type of its
+/// value.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity::prelude::TypeMapKey;
+/// use std::collections::HashMap;
+///
+/// struct CommandCounter;
+///
+/// impl TypeMapKey for CommandCounter {
+///     type Value = HashMap<String, u64>;
+/// }
+/// ```
+///
+/// [`TypeMap`]: struct.TypeMap.html
+pub trait TypeMapKey: Any {
+    /// The type of the value stored under this key.
+    type Value: Any + Send + Sync;
+}
+
+/// A map storing at most one value for each [`TypeMapKey`], where the type of
+/// the value is determined by the key.
+///
+/// All values must be `Send + Sync`, so the map can be shared across the
+/// threads events are dispatched on.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity::prelude::{TypeMap, TypeMapKey};
+///
+/// struct Counter;
+///
+/// impl TypeMapKey for Counter {
+///     type Value = u64;
+/// }
+///
+/// let mut map = TypeMap::new();
+/// map.insert::<Counter>(0);
+///
+/// *map.get_mut::<Counter>().unwrap() += 1;
+///
+/// assert_eq!(map.get::<Counter>(), Some(&1));
+/// ```
+///
+/// [`TypeMapKey`]: trait.TypeMapKey.html
+#[derive(Default)]
+pub struct TypeMap(HashMap<TypeId, Box<dyn Any + Send + Sync>>);
+
+impl TypeMap {
+    /// Creates an empty map.
+    #[inline]
+    pub fn new() -> Self {
+        TypeMap(HashMap::new())
+    }
+
+    /// Whether a value is stored for the key.
+    #[inline]
+    pub fn contains_key<K: TypeMapKey>(&self) -> bool {
+        self.0.contains_key(&TypeId::of::<K>())
+    }
+
+    /// Stores a value for the key, returning the value previously stored for
+    /// it, if any.
+    pub fn insert<K: TypeMapKey>(&mut self, value: K::Value) -> Option<K::Value> {
+        self.0
+            .insert(TypeId::of::<K>(), Box::new(value))
+            .and_then(|old| old.downcast().ok())
+            .map(|old| *old)
+    }
+
+    /// Retrieves a reference to the value stored for the key.
+    pub fn get<K: TypeMapKey>(&self) -> Option<&K::Value> {
+        self.0
+            .get(&TypeId::of::<K>())
+            .and_then(|value| value.downcast_ref())
+    }
+
+    /// Retrieves a mutable reference to the value stored for the key.
+    pub fn get_mut<K: TypeMapKey>(&mut self) -> Option<&mut K::Value> {
+        self.0
+            .get_mut(&TypeId::of::<K>())
+            .and_then(|value| value.downcast_mut())
+    }
+
+    /// Removes the value stored for the key, returning it.
+    pub fn remove<K: TypeMapKey>(&mut self) -> Option<K::Value> {
+        self.0
+            .remove(&TypeId::of::<K>())
+            .and_then(|value| value.downcast().ok())
+            .map(|value| *value)
+    }
+
+    /// Gets the key's entry for in-place manipulation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity::prelude::{TypeMap, TypeMapKey};
+    ///
+    /// struct Counter;
+    ///
+    /// impl TypeMapKey for Counter {
+    ///     type Value = u64;
+    /// }
+    ///
+    /// let mut map = TypeMap::new();
+    ///
+    /// *map.entry::<Counter>().or_insert(0) += 1;
+    /// *map.entry::<Counter>().or_insert(0) += 1;
+    ///
+    /// assert_eq!(map.get::<Counter>(), Some(&2));
+    /// ```
+    #[inline]
+    pub fn entry<K: TypeMapKey>(&mut self) -> Entry<'_, K> {
+        Entry {
+            inner: self.0.entry(TypeId::of::<K>()),
+            _key: PhantomData,
+        }
+    }
+
+    /// The number of values stored.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether no values are stored.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Debug for TypeMap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("TypeMap")
+            .field("len", &self.0.len())
+            .finish()
+    }
+}
+
+/// A key's entry in a [`TypeMap`], retrieved via [`TypeMap::entry`].
+///
+/// [`TypeMap`]: struct.TypeMap.html
+/// [`TypeMap::entry`]: struct.TypeMap.html#method.entry
+pub struct Entry<'a, K: TypeMapKey> {
+    inner: HashMapEntry<'a, TypeId, Box<dyn Any + Send + Sync>>,
+    _key: PhantomData<K>,
+}
+
+impl<'a, K: TypeMapKey> Entry<'a, K> {
+    /// Stores `default` if no value is stored yet, returning a mutable
+    /// reference to the stored value.
+    #[inline]
+    pub fn or_insert(self, default: K::Value) -> &'a mut K::Value {
+        self.or_insert_with(|| default)
+    }
+
+    /// Stores the result of `default` if no value is stored yet, returning a
+    /// mutable reference to the stored value.
+    pub fn or_insert_with<F>(self, default: F) -> &'a mut K::Value
+        where F: FnOnce() -> K::Value {
+        self.inner
+            .or_insert_with(|| Box::new(default()))
+            .downcast_mut()
+            .expect("TypeMap value has the type of its key's value")
+    }
+
+    /// Calls `f` with the stored value, if there is one.
+    pub fn and_modify<F>(mut self, f: F) -> Self
+        where F: FnOnce(&mut K::Value) {
+        if let HashMapEntry::Occupied(ref mut entry) = self.inner {
+            if let Some(value) = entry.get_mut().downcast_mut() {
+                f(value);
+            }
+        }
+
+        self
+    }
+}
+
+impl<'a, K> Entry<'a, K>
+    where K: TypeMapKey,
+          K::Value: Default {
+    /// Stores the value's default if no value is stored yet, returning a
+    /// mutable reference to the stored value.
+    #[inline]
+    pub fn or_default(self) -> &'a mut K::Value {
+        self.or_insert_with(K::Value::default)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{TypeMap, TypeMapKey};
+
+    struct Counter;
+
+    impl TypeMapKey for Counter {
+        type Value = u64;
+    }
+
+    struct Name;
+
+    impl TypeMapKey for Name {
+        type Value = String;
+    }
+
+    #[test]
+    fn keys_are_independent() {
+        let mut map = TypeMap::new();
+
+        assert!(map.insert::<Counter>(1).is_none());
+        assert!(map.insert::<Name>("serenity".to_string()).is_none());
+
+        assert_eq!(map.insert::<Counter>(2), Some(1));
+        assert_eq!(map.get::<Counter>(), Some(&2));
+        assert_eq!(map.get::<Name>().map(String::as_str), Some("serenity"));
+        assert_eq!(map.len(), 2);
+
+        assert_eq!(map.remove::<Counter>(), Some(2));
+        assert!(!map.contains_key::<Counter>());
+        assert!(map.contains_key::<Name>());
+    }
+
+    #[test]
+    fn entry() {
+        let mut map = TypeMap::new();
+
+        map.entry::<Counter>().and_modify(|c| *c += 1).or_default();
+        assert_eq!(map.get::<Counter>(), Some(&0));
+
+        map.entry::<Counter>().and_modify(|c| *c += 1).or_default();
+        assert_eq!(map.get::<Counter>(), Some(&1));
+    }
+}
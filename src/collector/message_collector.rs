@@ -0,0 +1,512 @@
+use crate::client::bridge::gateway::ShardMessenger;
+use crate::model::{
+    channel::Message,
+    id::{ChannelId, GuildId, UserId},
+};
+use std::{
+    fmt::{Debug, Formatter, Result as FmtResult},
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc,
+        Weak,
+    },
+    time::{Duration, Instant},
+};
+
+type Filter = dyn Fn(&Arc<Message>) -> bool + Send + Sync;
+
+#[derive(Clone, Default)]
+struct FilterOptions {
+    filter_limit: Option<u32>,
+    collect_limit: Option<u32>,
+    channel_id: Option<ChannelId>,
+    guild_id: Option<GuildId>,
+    author_id: Option<UserId>,
+    filter: Option<Arc<Filter>>,
+}
+
+/// A filter of the messages received by a shard, sending the messages passing
+/// it to a collector, such as [`CollectReply`].
+///
+/// It is registered with the shard's runner via
+/// [`ShardMessenger::set_message_filter`].
+///
+/// [`CollectReply`]: struct.CollectReply.html
+/// [`ShardMessenger::set_message_filter`]: ../../client/bridge/gateway/struct.ShardMessenger.html#method.set_message_filter
+#[derive(Clone)]
+pub struct MessageFilter {
+    filtered: u32,
+    collected: u32,
+    options: FilterOptions,
+    sender: Sender<Arc<Message>>,
+    deadline: Option<Instant>,
+    collector: Weak<()>,
+}
+
+impl MessageFilter {
+    /// Creates a filter, returning it along with the receiver of the messages
+    /// passing it and a handle the collector keeps to signal it is waiting.
+    fn new(
+        options: FilterOptions,
+        deadline: Option<Instant>,
+    ) -> (Self, Receiver<Arc<Message>>, Arc<()>) {
+        let (sender, receiver) = mpsc::channel();
+        let collector = Arc::new(());
+
+        let filter = MessageFilter {
+            filtered: 0,
+            collected: 0,
+            options,
+            sender,
+            deadline,
+            collector: Arc::downgrade(&collector),
+        };
+
+        (filter, receiver, collector)
+    }
+
+    /// Sends the message to the collector if it passes the filter.
+    ///
+    /// Returns whether the filter should be kept, which is not the case once
+    /// it is done or its collector is gone.
+    pub(crate) fn send_message(&mut self, message: &Arc<Message>) -> bool {
+        if !self.is_alive() {
+            return false;
+        }
+
+        if self.is_passing_constraints(message) {
+            self.filtered += 1;
+
+            if self.options.filter.as_ref().map_or(true, |f| f(message)) {
+                self.collected += 1;
+
+                if self.sender.send(Arc::clone(message)).is_err() {
+                    return false;
+                }
+            }
+        }
+
+        !self.is_done()
+    }
+
+    /// Whether the collector is still waiting for messages, which is not the
+    /// case once it timed out or was dropped.
+    pub(crate) fn is_alive(&self) -> bool {
+        self.deadline.map_or(true, |deadline| Instant::now() < deadline)
+            && self.collector.upgrade().is_some()
+    }
+
+    fn is_passing_constraints(&self, message: &Message) -> bool {
+        self.options.channel_id.map_or(true, |id| id == message.channel_id)
+            && self.options.guild_id.map_or(true, |id| Some(id) == message.guild_id)
+            && self.options.author_id.map_or(true, |id| id == message.author.id)
+    }
+
+    fn is_done(&self) -> bool {
+        self.options.filter_limit.map_or(false, |limit| self.filtered >= limit)
+            || self.options.collect_limit.map_or(false, |limit| self.collected >= limit)
+    }
+}
+
+impl Debug for MessageFilter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("MessageFilter")
+            .field("filtered", &self.filtered)
+            .field("collected", &self.collected)
+            .field("channel_id", &self.options.channel_id)
+            .field("guild_id", &self.options.guild_id)
+            .field("author_id", &self.options.author_id)
+            .finish()
+    }
+}
+
+/// Waits for a single message matching the given criteria, such as a reply to
+/// a prompt.
+///
+/// Usually created via [`Message::await_reply`].
+///
+/// # Examples
+///
+/// Asking for a user's favourite colour, giving them 30 seconds to reply:
+///
+/// ```rust,no_run
+/// use serenity::model::channel::Message;
+/// use serenity::prelude::*;
+/// use std::time::Duration;
+///
+/// struct Handler;
+///
+/// impl EventHandler for Handler {
+///     fn message(&self, ctx: Context, msg: Message) {
+///         if msg.content != "!colour" {
+///             return;
+///         }
+///
+///         let _ = msg.reply(&ctx, "What is your favourite colour?");
+///
+///         match msg.await_reply(&ctx).timeout(Duration::from_secs(30)).wait() {
+///             Some(answer) => {
+///                 let _ = answer.reply(&ctx, format!("{} is a nice colour!", answer.content));
+///             },
+///             None => {
+///                 let _ = msg.reply(&ctx, "No answer within 30 seconds.");
+///             },
+///         }
+///     }
+/// }
+/// ```
+///
+/// [`Message::await_reply`]: ../../model/channel/struct.Message.html#method.await_reply
+pub struct CollectReply<'a> {
+    shard: &'a ShardMessenger,
+    options: FilterOptions,
+    timeout: Option<Duration>,
+}
+
+impl<'a> CollectReply<'a> {
+    /// Starts waiting for a message received by the shard of the messenger,
+    /// such as the [`Context`] of an event handler.
+    ///
+    /// [`Context`]: ../../client/struct.Context.html
+    pub fn new(shard_messenger: &'a impl AsRef<ShardMessenger>) -> Self {
+        CollectReply {
+            shard: shard_messenger.as_ref(),
+            options: FilterOptions::default(),
+            timeout: None,
+        }
+    }
+
+    /// Only considers messages in the channel.
+    pub fn channel_id<C: Into<ChannelId>>(mut self, channel_id: C) -> Self {
+        self.options.channel_id = Some(channel_id.into());
+
+        self
+    }
+
+    /// Only considers messages in the guild.
+    pub fn guild_id<G: Into<GuildId>>(mut self, guild_id: G) -> Self {
+        self.options.guild_id = Some(guild_id.into());
+
+        self
+    }
+
+    /// Only considers messages by the user.
+    pub fn author_id<U: Into<UserId>>(mut self, author_id: U) -> Self {
+        self.options.author_id = Some(author_id.into());
+
+        self
+    }
+
+    /// Only accepts messages for which `filter` returns `true`.
+    pub fn filter<F>(mut self, filter: F) -> Self
+        where F: Fn(&Arc<Message>) -> bool + Send + Sync + 'static {
+        self.options.filter = Some(Arc::new(filter));
+
+        self
+    }
+
+    /// Stops waiting after `limit` messages were considered, passed to the
+    /// [`filter`] or not.
+    ///
+    /// [`filter`]: #method.filter
+    pub fn filter_limit(mut self, limit: u32) -> Self {
+        self.options.filter_limit = Some(limit);
+
+        self
+    }
+
+    /// Stops waiting once `duration` has passed.
+    ///
+    /// **Note**: Without a timeout, this waits until a message is accepted or
+    /// the shard is shut down.
+    pub fn timeout(mut self, duration: Duration) -> Self {
+        self.timeout = Some(duration);
+
+        self
+    }
+
+    /// Blocks the current thread until the first accepted message is
+    /// received, returning it.
+    ///
+    /// Returns `None` if waiting stopped before, due to the [`timeout`] or
+    /// [`filter_limit`], or as the shard was shut down.
+    ///
+    /// [`filter_limit`]: #method.filter_limit
+    /// [`timeout`]: #method.timeout
+    pub fn wait(mut self) -> Option<Arc<Message>> {
+        self.options.collect_limit = Some(1);
+
+        MessageCollector::new(self.shard, self.options, self.timeout).next()
+    }
+}
+
+/// A builder for a [`MessageCollector`], setting the criteria of the messages
+/// to collect and when to stop collecting.
+///
+/// # Examples
+///
+/// A quiz, giving the author of the command 30 seconds to answer up to 3
+/// questions:
+///
+/// ```rust,no_run
+/// use serenity::collector::MessageCollectorBuilder;
+/// use serenity::model::channel::Message;
+/// use serenity::prelude::*;
+/// use std::time::Duration;
+///
+/// struct Handler;
+///
+/// impl EventHandler for Handler {
+///     fn message(&self, ctx: Context, msg: Message) {
+///         if msg.content != "!quiz" {
+///             return;
+///         }
+///
+///         let questions = [("1 + 1?", "2"), ("2 * 3?", "6"), ("10 / 2?", "5")];
+///         let _ = msg.reply(&ctx, questions[0].0);
+///
+///         let answers = MessageCollectorBuilder::new(&ctx)
+///             .author_id(msg.author.id)
+///             .channel_id(msg.channel_id)
+///             .collect_limit(questions.len() as u32)
+///             .timeout(Duration::from_secs(30))
+///             .build();
+///
+///         let mut score = 0;
+///
+///         for (i, answer) in answers.enumerate() {
+///             if answer.content == questions[i].1 {
+///                 score += 1;
+///             }
+///
+///             if let Some(next) = questions.get(i + 1) {
+///                 let _ = msg.reply(&ctx, next.0);
+///             }
+///         }
+///
+///         let _ = msg.reply(&ctx, format!("You scored {}/{}.", score, questions.len()));
+///     }
+/// }
+/// ```
+///
+/// [`MessageCollector`]: struct.MessageCollector.html
+pub struct MessageCollectorBuilder<'a> {
+    shard: &'a ShardMessenger,
+    options: FilterOptions,
+    timeout: Option<Duration>,
+}
+
+impl<'a> MessageCollectorBuilder<'a> {
+    /// Starts building a collector of the messages received by the shard of
+    /// the messenger, such as the [`Context`] of an event handler.
+    ///
+    /// [`Context`]: ../../client/struct.Context.html
+    pub fn new(shard_messenger: &'a impl AsRef<ShardMessenger>) -> Self {
+        MessageCollectorBuilder {
+            shard: shard_messenger.as_ref(),
+            options: FilterOptions::default(),
+            timeout: None,
+        }
+    }
+
+    /// Only considers messages in the channel.
+    pub fn channel_id<C: Into<ChannelId>>(mut self, channel_id: C) -> Self {
+        self.options.channel_id = Some(channel_id.into());
+
+        self
+    }
+
+    /// Only considers messages in the guild.
+    pub fn guild_id<G: Into<GuildId>>(mut self, guild_id: G) -> Self {
+        self.options.guild_id = Some(guild_id.into());
+
+        self
+    }
+
+    /// Only considers messages by the user.
+    pub fn author_id<U: Into<UserId>>(mut self, author_id: U) -> Self {
+        self.options.author_id = Some(author_id.into());
+
+        self
+    }
+
+    /// Only collects messages for which `filter` returns `true`, such as
+    /// messages with certain content.
+    pub fn filter<F>(mut self, filter: F) -> Self
+        where F: Fn(&Arc<Message>) -> bool + Send + Sync + 'static {
+        self.options.filter = Some(Arc::new(filter));
+
+        self
+    }
+
+    /// Stops collecting after `limit` messages were considered, passed to the
+    /// [`filter`] or not.
+    ///
+    /// [`filter`]: #method.filter
+    pub fn filter_limit(mut self, limit: u32) -> Self {
+        self.options.filter_limit = Some(limit);
+
+        self
+    }
+
+    /// Stops collecting after `limit` messages were collected.
+    pub fn collect_limit(mut self, limit: u32) -> Self {
+        self.options.collect_limit = Some(limit);
+
+        self
+    }
+
+    /// Stops collecting once `duration` has passed since building the
+    /// collector.
+    ///
+    /// **Note**: Without any limit, messages are collected until the
+    /// collector is dropped or the shard is shut down.
+    pub fn timeout(mut self, duration: Duration) -> Self {
+        self.timeout = Some(duration);
+
+        self
+    }
+
+    /// Starts collecting messages.
+    pub fn build(self) -> MessageCollector {
+        MessageCollector::new(self.shard, self.options, self.timeout)
+    }
+}
+
+/// An iterator over the collected messages, blocking until the next one is
+/// received.
+///
+/// The iterator ends once a limit set via the [`MessageCollectorBuilder`] is
+/// reached, or once the shard is shut down. Dropping it stops collecting.
+///
+/// [`MessageCollectorBuilder`]: struct.MessageCollectorBuilder.html
+#[derive(Debug)]
+pub struct MessageCollector {
+    receiver: Receiver<Arc<Message>>,
+    deadline: Option<Instant>,
+    _alive: Arc<()>,
+}
+
+impl MessageCollector {
+    fn new(shard: &ShardMessenger, options: FilterOptions, timeout: Option<Duration>) -> Self {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        let (filter, receiver, alive) = MessageFilter::new(options, deadline);
+        shard.set_message_filter(filter);
+
+        MessageCollector {
+            receiver,
+            deadline,
+            _alive: alive,
+        }
+    }
+
+    /// Stops collecting, returning the messages that were received but not
+    /// yet yielded by the iterator.
+    pub fn stop(self) -> Vec<Arc<Message>> {
+        self.receiver.try_iter().collect()
+    }
+}
+
+impl Iterator for MessageCollector {
+    type Item = Arc<Message>;
+
+    fn next(&mut self) -> Option<Arc<Message>> {
+        match self.deadline {
+            Some(deadline) => {
+                let now = Instant::now();
+
+                if now >= deadline {
+                    return None;
+                }
+
+                self.receiver.recv_timeout(deadline - now).ok()
+            },
+            None => self.receiver.recv().ok(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::{channel::Message, id::{ChannelId, UserId}};
+    use serde_json;
+    use std::{sync::Arc, time::{Duration, Instant}};
+    use super::{FilterOptions, MessageFilter};
+
+    fn message(channel_id: u64, author_id: u64, content: &str) -> Arc<Message> {
+        let mut message: Message = serde_json::from_str(
+            include_str!("../../tests/resources/message_create_1.json"),
+        ).unwrap();
+        message.channel_id = ChannelId(channel_id);
+        message.author.id = UserId(author_id);
+        message.content = content.to_string();
+
+        Arc::new(message)
+    }
+
+    #[test]
+    fn test_send_message_constraints() {
+        let options = FilterOptions {
+            channel_id: Some(ChannelId(1)),
+            author_id: Some(UserId(2)),
+            filter: Some(Arc::new(|msg: &Arc<Message>| msg.content == "yes")),
+            ..Default::default()
+        };
+        let (mut filter, receiver, _alive) = MessageFilter::new(options, None);
+
+        assert!(filter.send_message(&message(3, 2, "yes")));
+        assert!(filter.send_message(&message(1, 3, "yes")));
+        assert!(filter.send_message(&message(1, 2, "no")));
+        assert!(filter.send_message(&message(1, 2, "yes")));
+
+        let received: Vec<_> = receiver.try_iter().map(|msg| msg.content.clone()).collect();
+        assert_eq!(received, vec!["yes".to_string()]);
+        assert_eq!(filter.filtered, 2);
+        assert_eq!(filter.collected, 1);
+    }
+
+    #[test]
+    fn test_send_message_limits() {
+        let options = FilterOptions {
+            collect_limit: Some(2),
+            ..Default::default()
+        };
+        let (mut filter, receiver, _alive) = MessageFilter::new(options, None);
+
+        assert!(filter.send_message(&message(1, 2, "a")));
+        assert!(!filter.send_message(&message(1, 2, "b")));
+        assert_eq!(receiver.try_iter().count(), 2);
+
+        let options = FilterOptions {
+            filter_limit: Some(1),
+            filter: Some(Arc::new(|_: &Arc<Message>| false)),
+            ..Default::default()
+        };
+        let (mut filter, receiver, _alive) = MessageFilter::new(options, None);
+
+        assert!(!filter.send_message(&message(1, 2, "a")));
+        assert_eq!(receiver.try_iter().count(), 0);
+    }
+
+    #[test]
+    fn test_send_message_dead_collector() {
+        let (mut filter, receiver, alive) = MessageFilter::new(FilterOptions::default(), None);
+        drop(alive);
+
+        assert!(!filter.is_alive());
+        assert!(!filter.send_message(&message(1, 2, "a")));
+        assert_eq!(receiver.try_iter().count(), 0);
+
+        let deadline = Instant::now() - Duration::from_secs(1);
+        let (mut filter, receiver, _alive) = MessageFilter::new(FilterOptions::default(), Some(deadline));
+
+        assert!(!filter.is_alive());
+        assert!(!filter.send_message(&message(1, 2, "a")));
+        assert_eq!(receiver.try_iter().count(), 0);
+
+        let (mut filter, receiver, _alive) = MessageFilter::new(FilterOptions::default(), None);
+        drop(receiver);
+
+        assert!(!filter.send_message(&message(1, 2, "a")));
+    }
+}
@@ -0,0 +1,23 @@
+//! Collectors wait for events received by a shard that match a filter, such as
+//! a reply to a prompt, without having to route the events from an
+//! [`EventHandler`] by hand.
+//!
+//! A collector registers its filter with the [`ShardRunner`] of the shard it
+//! is given the [`ShardMessenger`] of, which sends each matching event to the
+//! collector. The filter is removed again with the next event the shard
+//! receives once the collector is done, timed out or dropped.
+//!
+//! [`EventHandler`]: ../client/trait.EventHandler.html
+//! [`ShardMessenger`]: ../client/bridge/gateway/struct.ShardMessenger.html
+//! [`ShardRunner`]: ../client/bridge/gateway/struct.ShardRunner.html
+
+pub mod message_collector;
+pub mod reaction_collector;
+
+pub use self::message_collector::{
+    CollectReply,
+    MessageCollector,
+    MessageCollectorBuilder,
+    MessageFilter,
+};
+pub use self::reaction_collector::{CollectReaction, ReactionAction, ReactionFilter};
@@ -0,0 +1,416 @@
+use crate::client::bridge::gateway::ShardMessenger;
+use crate::model::{
+    channel::Reaction,
+    id::{ChannelId, GuildId, MessageId, UserId},
+};
+use std::{
+    fmt::{Debug, Formatter, Result as FmtResult},
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc,
+        Weak,
+    },
+    time::{Duration, Instant},
+};
+
+type Filter = dyn Fn(&Arc<Reaction>) -> bool + Send + Sync;
+
+/// A reaction being added to or removed from a message.
+#[derive(Clone, Debug)]
+pub enum ReactionAction {
+    Added(Arc<Reaction>),
+    Removed(Arc<Reaction>),
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+impl ReactionAction {
+    /// The reaction that was added or removed.
+    pub fn as_inner_ref(&self) -> &Arc<Reaction> {
+        match *self {
+            ReactionAction::Added(ref reaction) | ReactionAction::Removed(ref reaction) => reaction,
+            ReactionAction::__Nonexhaustive => unreachable!(),
+        }
+    }
+
+    /// Whether the reaction was added.
+    pub fn is_added(&self) -> bool {
+        match *self {
+            ReactionAction::Added(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Whether the reaction was removed.
+    pub fn is_removed(&self) -> bool {
+        match *self {
+            ReactionAction::Removed(_) => true,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct FilterOptions {
+    filter_limit: Option<u32>,
+    collect_limit: Option<u32>,
+    channel_id: Option<ChannelId>,
+    guild_id: Option<GuildId>,
+    message_id: Option<MessageId>,
+    author_id: Option<UserId>,
+    filter: Option<Arc<Filter>>,
+    added: bool,
+    removed: bool,
+}
+
+impl Default for FilterOptions {
+    fn default() -> Self {
+        FilterOptions {
+            filter_limit: None,
+            collect_limit: None,
+            channel_id: None,
+            guild_id: None,
+            message_id: None,
+            author_id: None,
+            filter: None,
+            added: true,
+            removed: false,
+        }
+    }
+}
+
+/// A filter of the reactions added and removed on a shard, sending the ones
+/// passing it to a collector, such as [`CollectReaction`].
+///
+/// It is registered with the shard's runner via
+/// [`ShardMessenger::set_reaction_filter`].
+///
+/// [`CollectReaction`]: struct.CollectReaction.html
+/// [`ShardMessenger::set_reaction_filter`]: ../../client/bridge/gateway/struct.ShardMessenger.html#method.set_reaction_filter
+#[derive(Clone)]
+pub struct ReactionFilter {
+    filtered: u32,
+    collected: u32,
+    options: FilterOptions,
+    sender: Sender<Arc<ReactionAction>>,
+    deadline: Option<Instant>,
+    collector: Weak<()>,
+}
+
+impl ReactionFilter {
+    /// Creates a filter, returning it along with the receiver of the reactions
+    /// passing it and a handle the collector keeps to signal it is waiting.
+    fn new(
+        options: FilterOptions,
+        deadline: Option<Instant>,
+    ) -> (Self, Receiver<Arc<ReactionAction>>, Arc<()>) {
+        let (sender, receiver) = mpsc::channel();
+        let collector = Arc::new(());
+
+        let filter = ReactionFilter {
+            filtered: 0,
+            collected: 0,
+            options,
+            sender,
+            deadline,
+            collector: Arc::downgrade(&collector),
+        };
+
+        (filter, receiver, collector)
+    }
+
+    /// Sends the reaction to the collector if it passes the filter.
+    ///
+    /// Returns whether the filter should be kept, which is not the case once
+    /// it is done or its collector is gone.
+    pub(crate) fn send_reaction(&mut self, action: &Arc<ReactionAction>) -> bool {
+        if !self.is_alive() {
+            return false;
+        }
+
+        if self.is_passing_constraints(action) {
+            self.filtered += 1;
+
+            if self.options.filter.as_ref().map_or(true, |f| f(action.as_inner_ref())) {
+                self.collected += 1;
+
+                if self.sender.send(Arc::clone(action)).is_err() {
+                    return false;
+                }
+            }
+        }
+
+        !self.is_done()
+    }
+
+    /// Whether the collector is still waiting for reactions, which is not the
+    /// case once it timed out or was dropped.
+    pub(crate) fn is_alive(&self) -> bool {
+        self.deadline.map_or(true, |deadline| Instant::now() < deadline)
+            && self.collector.upgrade().is_some()
+    }
+
+    fn is_passing_constraints(&self, action: &ReactionAction) -> bool {
+        let reaction = action.as_inner_ref();
+
+        (if action.is_added() { self.options.added } else { self.options.removed })
+            && self.options.channel_id.map_or(true, |id| id == reaction.channel_id)
+            && self.options.guild_id.map_or(true, |id| Some(id) == reaction.guild_id)
+            && self.options.message_id.map_or(true, |id| id == reaction.message_id)
+            && self.options.author_id.map_or(true, |id| id == reaction.user_id)
+    }
+
+    fn is_done(&self) -> bool {
+        self.options.filter_limit.map_or(false, |limit| self.filtered >= limit)
+            || self.options.collect_limit.map_or(false, |limit| self.collected >= limit)
+    }
+}
+
+impl Debug for ReactionFilter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("ReactionFilter")
+            .field("filtered", &self.filtered)
+            .field("collected", &self.collected)
+            .field("channel_id", &self.options.channel_id)
+            .field("guild_id", &self.options.guild_id)
+            .field("message_id", &self.options.message_id)
+            .field("author_id", &self.options.author_id)
+            .finish()
+    }
+}
+
+/// Waits for a single reaction matching the given criteria to be added, or
+/// removed if enabled via [`removed`].
+///
+/// Usually created via [`Message::await_reaction`].
+///
+/// # Examples
+///
+/// Asking the author of a message for confirmation by reacting:
+///
+/// ```rust,no_run
+/// use serenity::model::channel::{Message, ReactionType};
+/// use serenity::prelude::*;
+/// use std::time::Duration;
+///
+/// struct Handler;
+///
+/// impl EventHandler for Handler {
+///     fn message(&self, ctx: Context, msg: Message) {
+///         if msg.content != "!delete-all" {
+///             return;
+///         }
+///
+///         let prompt = match msg.reply(&ctx, "React to confirm.") {
+///             Ok(prompt) => prompt,
+///             Err(_) => return,
+///         };
+///
+///         let confirmed = prompt.await_reaction(&ctx)
+///             .author_id(msg.author.id)
+///             .timeout(Duration::from_secs(10))
+///             .wait()
+///             .is_some();
+///
+///         let _ = msg.reply(&ctx, if confirmed { "Deleting." } else { "Cancelled." });
+///     }
+/// }
+/// ```
+///
+/// [`Message::await_reaction`]: ../../model/channel/struct.Message.html#method.await_reaction
+/// [`removed`]: #method.removed
+pub struct CollectReaction<'a> {
+    shard: &'a ShardMessenger,
+    options: FilterOptions,
+    timeout: Option<Duration>,
+}
+
+impl<'a> CollectReaction<'a> {
+    /// Starts waiting for a reaction received by the shard of the messenger,
+    /// such as the [`Context`] of an event handler.
+    ///
+    /// [`Context`]: ../../client/struct.Context.html
+    pub fn new(shard_messenger: &'a impl AsRef<ShardMessenger>) -> Self {
+        CollectReaction {
+            shard: shard_messenger.as_ref(),
+            options: FilterOptions::default(),
+            timeout: None,
+        }
+    }
+
+    /// Only considers reactions in the channel.
+    pub fn channel_id<C: Into<ChannelId>>(mut self, channel_id: C) -> Self {
+        self.options.channel_id = Some(channel_id.into());
+
+        self
+    }
+
+    /// Only considers reactions in the guild.
+    pub fn guild_id<G: Into<GuildId>>(mut self, guild_id: G) -> Self {
+        self.options.guild_id = Some(guild_id.into());
+
+        self
+    }
+
+    /// Only considers reactions on the message.
+    pub fn message_id<M: Into<MessageId>>(mut self, message_id: M) -> Self {
+        self.options.message_id = Some(message_id.into());
+
+        self
+    }
+
+    /// Only considers reactions by the user.
+    pub fn author_id<U: Into<UserId>>(mut self, author_id: U) -> Self {
+        self.options.author_id = Some(author_id.into());
+
+        self
+    }
+
+    /// Sets whether added reactions are considered.
+    ///
+    /// **Note**: Defaults to `true`.
+    pub fn added(mut self, added: bool) -> Self {
+        self.options.added = added;
+
+        self
+    }
+
+    /// Sets whether removed reactions are considered.
+    ///
+    /// **Note**: Defaults to `false`.
+    pub fn removed(mut self, removed: bool) -> Self {
+        self.options.removed = removed;
+
+        self
+    }
+
+    /// Only accepts reactions for which `filter` returns `true`.
+    pub fn filter<F>(mut self, filter: F) -> Self
+        where F: Fn(&Arc<Reaction>) -> bool + Send + Sync + 'static {
+        self.options.filter = Some(Arc::new(filter));
+
+        self
+    }
+
+    /// Stops waiting after `limit` reactions were considered, passed to the
+    /// [`filter`] or not.
+    ///
+    /// [`filter`]: #method.filter
+    pub fn filter_limit(mut self, limit: u32) -> Self {
+        self.options.filter_limit = Some(limit);
+
+        self
+    }
+
+    /// Stops waiting once `duration` has passed.
+    ///
+    /// **Note**: Without a timeout, this waits until a reaction is accepted or
+    /// the shard is shut down.
+    pub fn timeout(mut self, duration: Duration) -> Self {
+        self.timeout = Some(duration);
+
+        self
+    }
+
+    /// Blocks the current thread until the first accepted reaction is
+    /// received, returning it.
+    ///
+    /// Returns `None` if waiting stopped before, due to the [`timeout`] or
+    /// [`filter_limit`], or as the shard was shut down.
+    ///
+    /// [`filter_limit`]: #method.filter_limit
+    /// [`timeout`]: #method.timeout
+    pub fn wait(mut self) -> Option<Arc<ReactionAction>> {
+        self.options.collect_limit = Some(1);
+
+        let deadline = self.timeout.map(|timeout| Instant::now() + timeout);
+        let (filter, receiver, _alive) = ReactionFilter::new(self.options, deadline);
+        self.shard.set_reaction_filter(filter);
+
+        match self.timeout {
+            Some(duration) => receiver.recv_timeout(duration).ok(),
+            None => receiver.recv().ok(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::{channel::Reaction, id::{MessageId, UserId}};
+    use serde_json;
+    use std::{sync::Arc, time::{Duration, Instant}};
+    use super::{FilterOptions, ReactionAction, ReactionFilter};
+
+    fn reaction(message_id: u64, user_id: u64) -> Arc<Reaction> {
+        let mut reaction: Reaction = serde_json::from_str(
+            include_str!("../../tests/resources/message_reaction_add_1.json"),
+        ).unwrap();
+        reaction.message_id = MessageId(message_id);
+        reaction.user_id = UserId(user_id);
+
+        Arc::new(reaction)
+    }
+
+    fn added(message_id: u64, user_id: u64) -> Arc<ReactionAction> {
+        Arc::new(ReactionAction::Added(reaction(message_id, user_id)))
+    }
+
+    fn removed(message_id: u64, user_id: u64) -> Arc<ReactionAction> {
+        Arc::new(ReactionAction::Removed(reaction(message_id, user_id)))
+    }
+
+    #[test]
+    fn test_send_reaction_constraints() {
+        let options = FilterOptions {
+            message_id: Some(MessageId(1)),
+            author_id: Some(UserId(2)),
+            ..Default::default()
+        };
+        let (mut filter, receiver, _alive) = ReactionFilter::new(options, None);
+
+        assert!(filter.send_reaction(&added(3, 2)));
+        assert!(filter.send_reaction(&added(1, 3)));
+        assert!(filter.send_reaction(&removed(1, 2)));
+        assert!(filter.send_reaction(&added(1, 2)));
+
+        let received: Vec<_> = receiver.try_iter().collect();
+        assert_eq!(received.len(), 1);
+        assert!(received[0].is_added());
+        assert_eq!(filter.filtered, 1);
+    }
+
+    #[test]
+    fn test_send_reaction_removed() {
+        let options = FilterOptions {
+            added: false,
+            removed: true,
+            collect_limit: Some(1),
+            ..Default::default()
+        };
+        let (mut filter, receiver, _alive) = ReactionFilter::new(options, None);
+
+        assert!(filter.send_reaction(&added(1, 2)));
+        assert!(!filter.send_reaction(&removed(1, 2)));
+
+        let received: Vec<_> = receiver.try_iter().collect();
+        assert_eq!(received.len(), 1);
+        assert!(received[0].is_removed());
+    }
+
+    #[test]
+    fn test_send_reaction_dead_collector() {
+        let (mut filter, receiver, alive) = ReactionFilter::new(FilterOptions::default(), None);
+        drop(alive);
+
+        assert!(!filter.is_alive());
+        assert!(!filter.send_reaction(&added(1, 2)));
+        assert_eq!(receiver.try_iter().count(), 0);
+
+        let deadline = Instant::now() - Duration::from_secs(1);
+        let (mut filter, receiver, _alive) = ReactionFilter::new(FilterOptions::default(), Some(deadline));
+
+        assert!(!filter.is_alive());
+        assert!(!filter.send_reaction(&added(1, 2)));
+        assert_eq!(receiver.try_iter().count(), 0);
+    }
+}
@@ -2,11 +2,41 @@
 
 /// The maximum length of the textual size of an embed.
 pub const EMBED_MAX_LENGTH: u16 = 6000;
+/// The maximum number of unicode code points allowed within an embed's title.
+pub const EMBED_TITLE_LIMIT: u16 = 256;
+/// The maximum number of unicode code points allowed within an embed's
+/// description.
+pub const EMBED_DESCRIPTION_LIMIT: u16 = 4096;
+/// The maximum number of fields allowed within an embed.
+pub const EMBED_FIELD_COUNT_LIMIT: u16 = 25;
+/// The maximum number of unicode code points allowed within an embed field's
+/// name.
+pub const EMBED_FIELD_NAME_LIMIT: u16 = 256;
+/// The maximum number of unicode code points allowed within an embed field's
+/// value.
+pub const EMBED_FIELD_VALUE_LIMIT: u16 = 1024;
+/// The maximum number of unicode code points allowed within an embed footer's
+/// text.
+pub const EMBED_FOOTER_TEXT_LIMIT: u16 = 2048;
+/// The maximum number of unicode code points allowed within an embed author's
+/// name.
+pub const EMBED_AUTHOR_NAME_LIMIT: u16 = 256;
+/// The maximum number of embeds allowed within a message.
+pub const EMBED_COUNT_LIMIT: u16 = 10;
+/// The maximum number of unicode code points allowed within a channel's name.
+pub const CHANNEL_NAME_LIMIT: u16 = 100;
+/// The maximum number of unicode code points allowed within a channel's topic.
+pub const CHANNEL_TOPIC_LIMIT: u16 = 1024;
+/// The maximum number of seconds allowed for a channel's slow mode rate.
+pub const CHANNEL_RATE_LIMIT: u16 = 21600;
+/// The maximum number of users allowed to be set as a voice channel's user
+/// limit.
+pub const CHANNEL_USER_LIMIT: u16 = 99;
+/// The maximum number of unicode code points allowed within a role's name.
+pub const ROLE_NAME_LIMIT: u16 = 100;
 /// The gateway version used by the library. The gateway URI is retrieved via
 /// the REST API.
 pub const GATEWAY_VERSION: u8 = 6;
-/// The voice gateway version used by the library.
-pub const VOICE_GATEWAY_VERSION: u8 = 3;
 /// The large threshold to send on identify.
 pub const LARGE_THRESHOLD: u8 = 250;
 /// The maximum unicode code points allowed within a message by Discord.
@@ -245,3 +275,73 @@ pub mod close_codes {
     /// Cannot reconnect.
     pub const SHARDING_REQUIRED: u16 = 4011;
 }
+
+pub mod voice_close_codes {
+    /// An invalid voice OP Code was sent.
+    ///
+    /// Can resume.
+    pub const UNKNOWN_OPCODE: u16 = 4001;
+    /// An invalid payload was sent.
+    ///
+    /// Can resume.
+    pub const DECODE_ERROR: u16 = 4002;
+    /// A payload was sent prior to identifying.
+    ///
+    /// Cannot resume.
+    pub const NOT_AUTHENTICATED: u16 = 4003;
+    /// The token sent with the identify payload was incorrect.
+    ///
+    /// Cannot resume.
+    pub const AUTHENTICATION_FAILED: u16 = 4004;
+    /// More than one identify payload was sent.
+    ///
+    /// Cannot resume.
+    pub const ALREADY_AUTHENTICATED: u16 = 4005;
+    /// The session is no longer valid.
+    ///
+    /// Cannot resume.
+    pub const SESSION_NO_LONGER_VALID: u16 = 4006;
+    /// A session timed out.
+    ///
+    /// Cannot resume.
+    pub const SESSION_TIMEOUT: u16 = 4009;
+    /// The voice server to connect to was not found.
+    ///
+    /// Cannot resume.
+    pub const SERVER_NOT_FOUND: u16 = 4011;
+    /// An unknown protocol was selected.
+    ///
+    /// Cannot resume.
+    pub const UNKNOWN_PROTOCOL: u16 = 4012;
+    /// The client was disconnected from the channel, such as by being kicked
+    /// or the channel being deleted.
+    ///
+    /// Cannot resume.
+    pub const DISCONNECTED: u16 = 4014;
+    /// The voice server crashed.
+    ///
+    /// Can resume.
+    pub const VOICE_SERVER_CRASHED: u16 = 4015;
+    /// An unknown encryption mode was selected.
+    ///
+    /// Cannot resume.
+    pub const UNKNOWN_ENCRYPTION_MODE: u16 = 4016;
+
+    /// Whether a voice session closed with the given code may be resumed.
+    ///
+    /// Closes without a code, such as those from network failures, may be.
+    pub fn is_resumable(code: Option<u16>) -> bool {
+        match code {
+            Some(NOT_AUTHENTICATED)
+                | Some(AUTHENTICATION_FAILED)
+                | Some(ALREADY_AUTHENTICATED)
+                | Some(SESSION_NO_LONGER_VALID)
+                | Some(SESSION_TIMEOUT)
+                | Some(SERVER_NOT_FOUND)
+                | Some(UNKNOWN_PROTOCOL)
+                | Some(DISCONNECTED)
+                | Some(UNKNOWN_ENCRYPTION_MODE) => false,
+            _ => true,
+        }
+    }
+}
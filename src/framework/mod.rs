@@ -83,7 +83,7 @@ pub mod standard;
 pub use self::standard::StandardFramework;
 
 use crate::client::Context;
-use crate::model::channel::Message;
+use crate::model::{channel::Message, gateway::Ready};
 use threadpool::ThreadPool;
 use std::sync::Arc;
 
@@ -95,6 +95,13 @@ use std::sync::Arc;
 /// so that you may run your commands in separate threads.
 pub trait Framework {
     fn dispatch(&mut self, _: Context, _: Message, _: &ThreadPool);
+
+    /// Called when a shard receives a READY event, before the event handler's
+    /// `ready` is dispatched.
+    ///
+    /// Does nothing by default.
+    #[inline]
+    fn ready(&mut self, _: &Ready) {}
 }
 
 impl<F: Framework + ?Sized> Framework for Box<F> {
@@ -102,6 +109,11 @@ impl<F: Framework + ?Sized> Framework for Box<F> {
     fn dispatch(&mut self, ctx: Context, msg: Message, threadpool: &ThreadPool) {
         (**self).dispatch(ctx, msg, threadpool);
     }
+
+    #[inline]
+    fn ready(&mut self, ready: &Ready) {
+        (**self).ready(ready);
+    }
 }
 
 impl<T: Framework + ?Sized> Framework for Arc<T> {
@@ -111,6 +123,13 @@ impl<T: Framework + ?Sized> Framework for Arc<T> {
             (*s).dispatch(ctx, msg, threadpool)
         }
     }
+
+    #[inline]
+    fn ready(&mut self, ready: &Ready) {
+        if let Some(s) = Arc::get_mut(self) {
+            (*s).ready(ready)
+        }
+    }
 }
 
 impl<'a, F: Framework + ?Sized> Framework for &'a mut F {
@@ -118,4 +137,9 @@ impl<'a, F: Framework + ?Sized> Framework for &'a mut F {
     fn dispatch(&mut self, ctx: Context, msg: Message, threadpool: &ThreadPool) {
         (**self).dispatch(ctx, msg, threadpool);
     }
+
+    #[inline]
+    fn ready(&mut self, ready: &Ready) {
+        (**self).ready(ready);
+    }
 }
@@ -149,6 +149,15 @@ fn lex(stream: &mut UnicodeStream<'_>, delims: &[&Delimiter]) -> Option<Token> {
     let start = stream.offset();
 
     'outer: while !stream.at_end() {
+        // Keep the quoted value of a named argument (`key="a b"`) whole.
+        if stream.current()? == "\"" && stream.source()[..stream.offset()].ends_with('=') {
+            stream.next();
+            stream.take_until(|s| s == "\"");
+            stream.next();
+
+            continue;
+        }
+
         for delim in delims {
             match delim {
                 Delimiter::Single(c) => {
@@ -178,6 +187,17 @@ fn remove_quotes(s: &str) -> &str {
     s
 }
 
+/// Location of a named argument within the "arguments queue".
+#[derive(Debug, Clone, Copy)]
+struct Named {
+    /// Position of the first token belonging to the named argument.
+    pos: usize,
+    /// Amount of tokens the named argument spans.
+    count: usize,
+    /// Span of the value, if one was given.
+    value: Option<(usize, usize)>,
+}
+
 #[derive(Debug, Clone, Copy)]
 enum State {
     None,
@@ -640,6 +660,37 @@ impl Args {
         }
     }
 
+    /// By starting from the current offset, parse and consume arguments for as long as they
+    /// can be parsed, stopping at the first one that can't.
+    ///
+    /// The argument that failed to parse, and any that follow it, are left untouched.
+    /// Surrounding quotations are removed before parsing.
+    ///
+    /// # Examples
+    ///
+    /// Parse any amount of mentioned users, followed by a duration and a reason:
+    ///
+    /// ```rust
+    /// use serenity::framework::standard::{Args, Delimiter};
+    /// use serenity::model::id::UserId;
+    ///
+    /// let mut args = Args::new("<@1> <@2> <@3> 10m spam", &[Delimiter::Single(' ')]);
+    ///
+    /// let users = args.greedy::<UserId>().collect::<Vec<_>>();
+    ///
+    /// assert_eq!(users, [UserId(1), UserId(2), UserId(3)]);
+    /// assert_eq!(args.single::<String>().unwrap(), "10m");
+    /// assert_eq!(args.rest(), "spam");
+    /// ```
+    #[inline]
+    pub fn greedy<T: FromStr>(&mut self) -> Greedy<'_, T> {
+        Greedy {
+            args: self,
+            done: false,
+            _marker: PhantomData,
+        }
+    }
+
     /// Return an iterator over all unmodified arguments.
     ///
     /// # Examples
@@ -763,6 +814,138 @@ impl Args {
         Ok(parsed)
     }
 
+    fn find_named(&self, key: &str, take_next: bool) -> Option<Named> {
+        for (pos, token) in self.args.iter().enumerate() {
+            if token.kind != TokenKind::Argument {
+                continue;
+            }
+
+            let (start, end) = token.span;
+            let arg = &self.message[start..end];
+
+            let (is_flag, name) = if arg.starts_with("--") {
+                (true, &arg[2..])
+            } else {
+                (false, arg)
+            };
+
+            if !name.starts_with(key) {
+                continue;
+            }
+
+            let after = &name[key.len()..];
+
+            // `key=value` or `--key=value`
+            if after.starts_with('=') {
+                let value_start = end - after.len() + 1;
+
+                return Some(Named {
+                    pos,
+                    count: 1,
+                    value: Some((value_start, end)),
+                });
+            }
+
+            // `--key` or `--key value`
+            if is_flag && after.is_empty() {
+                let next = self.args.get(pos + 1).filter(|t| {
+                    let (start, end) = t.span;
+                    take_next && !self.message[start..end].starts_with("--")
+                });
+
+                return Some(Named {
+                    pos,
+                    count: if next.is_some() { 2 } else { 1 },
+                    value: next.map(|t| t.span),
+                });
+            }
+        }
+
+        None
+    }
+
+    fn remove_named(&mut self, named: Named) {
+        let Named { pos, count, .. } = named;
+
+        self.args.drain(pos..pos + count);
+
+        if pos < self.offset {
+            self.offset -= std::cmp::min(count, self.offset - pos);
+        }
+    }
+
+    /// Search for a named argument given as `--key value`, `--key=value` or `key=value`,
+    /// parse its value, and remove it from the "arguments queue".
+    ///
+    /// A value containing delimiters must be quoted, as in `--key "a b"` or `key="a b"`.
+    /// Quotations surrounding the value are removed.
+    ///
+    /// # Note
+    /// The removal is irreversible, and only happens if the parse was successful.
+    /// Returns [`Error::Eos`] if no argument named `key` with a value is present.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity::framework::standard::{Args, Delimiter};
+    ///
+    /// let mut args = Args::new("spam --count 5 reason=\"too loud\"", &[Delimiter::Single(' ')]);
+    ///
+    /// assert_eq!(args.named::<u32>("count").unwrap(), 5);
+    /// assert_eq!(args.named::<String>("reason").unwrap(), "too loud");
+    /// assert!(args.named::<u32>("limit").is_err());
+    /// assert_eq!(args.single::<String>().unwrap(), "spam");
+    /// ```
+    ///
+    /// [`Error::Eos`]: enum.Error.html#variant.Eos
+    pub fn named<T: FromStr>(&mut self, key: &str) -> Result<T, T::Err> {
+        let named = self.find_named(key, true).ok_or(Error::Eos)?;
+        let (start, end) = named.value.ok_or(Error::Eos)?;
+
+        let parsed = T::from_str(remove_quotes(&self.message[start..end]))?;
+
+        self.remove_named(named);
+
+        Ok(parsed)
+    }
+
+    /// Search for a flag given as `--key`, `--key=value` or `key=value`,
+    /// parse it, and remove it from the "arguments queue".
+    ///
+    /// A bare `--key` is parsed as if its value was `true`. Unlike [`named`],
+    /// the argument following a bare flag is never taken as its value.
+    ///
+    /// # Note
+    /// The removal is irreversible, and only happens if the parse was successful.
+    /// Returns [`Error::Eos`] if no flag named `key` is present.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity::framework::standard::{Args, Delimiter};
+    ///
+    /// let mut args = Args::new("--force ban --silent=false", &[Delimiter::Single(' ')]);
+    ///
+    /// assert!(args.flag::<bool>("force").unwrap());
+    /// assert!(!args.flag::<bool>("silent").unwrap());
+    /// assert!(args.flag::<bool>("dry-run").is_err());
+    /// assert_eq!(args.single::<String>().unwrap(), "ban");
+    /// assert!(args.is_empty());
+    /// ```
+    ///
+    /// [`named`]: #method.named
+    /// [`Error::Eos`]: enum.Error.html#variant.Eos
+    pub fn flag<T: FromStr>(&mut self, key: &str) -> Result<T, T::Err> {
+        let named = self.find_named(key, false).ok_or(Error::Eos)?;
+        let value = named.value.map_or("true", |(start, end)| remove_quotes(&self.message[start..end]));
+
+        let parsed = T::from_str(value)?;
+
+        self.remove_named(named);
+
+        Ok(parsed)
+    }
+
     /// Get the original, unmodified message passed to the command.
     #[inline]
     pub fn message(&self) -> &str {
@@ -775,6 +958,32 @@ impl Args {
         self.remains().unwrap_or_default()
     }
 
+    /// Starting from the offset, return the remainder of available arguments,
+    /// stripped of its quotations if the remainder is a single quoted argument.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity::framework::standard::{Args, Delimiter};
+    ///
+    /// let mut args = Args::new(r#"#general "hello world""#, &[Delimiter::Single(' ')]);
+    ///
+    /// args.advance();
+    ///
+    /// assert_eq!(args.rest_quoted(), "hello world");
+    /// assert_eq!(args.rest(), r#""hello world""#);
+    /// ```
+    #[inline]
+    pub fn rest_quoted(&self) -> &str {
+        let rest = self.rest();
+
+        if self.remaining() == 1 && self.args[self.offset].kind == TokenKind::QuotedArgument {
+            remove_quotes(rest)
+        } else {
+            rest
+        }
+    }
+
     /// Starting from the offset, return the remainder of available arguments.
     ///
     /// Returns `None` if there are no remaining arguments.
@@ -796,6 +1005,7 @@ impl Args {
     ///
     /// The value returned is to be assumed to stay static.
     /// However, if `find` was called previously, and was successful, then the value is substracted by one.
+    /// Likewise, a successful `named` or `flag` substracts the amount of arguments it removed.
     #[inline]
     pub fn len(&self) -> usize {
         self.args.len()
@@ -877,6 +1087,38 @@ impl<'a, T: FromStr> Iterator for Iter<'a, T> {
     }
 }
 
+/// Parse and consume arguments for as long as they can be parsed, as an iterator.
+///
+/// Created by [`Args::greedy`].
+///
+/// [`Args::greedy`]: struct.Args.html#method.greedy
+pub struct Greedy<'a, T: FromStr> {
+    args: &'a mut Args,
+    done: bool,
+    _marker: PhantomData<T>,
+}
+
+impl<'a, T: FromStr> Iterator for Greedy<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        match self.args.quoted().parse::<T>() {
+            Ok(arg) => {
+                self.args.advance();
+                Some(arg)
+            }
+            Err(_) => {
+                self.done = true;
+                None
+            }
+        }
+    }
+}
+
 /// Access to all of the arguments, as an iterator.
 #[derive(Debug)]
 pub struct RawArguments<'a> {
@@ -903,3 +1145,42 @@ impl<'a> Iterator for RawArguments<'a> {
         Some(s)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Args, Delimiter};
+
+    fn args(message: &str) -> Args {
+        Args::new(message, &[Delimiter::Single(' ')])
+    }
+
+    #[test]
+    fn named_quoted_value() {
+        let mut args = args("spam reason=\"too loud\" --by \"a mod\"");
+
+        assert_eq!(args.len(), 4);
+        assert_eq!(args.named::<String>("reason").unwrap(), "too loud");
+        assert_eq!(args.named::<String>("by").unwrap(), "a mod");
+        assert_eq!(args.single::<String>().unwrap(), "spam");
+        assert!(args.is_empty());
+    }
+
+    #[test]
+    fn named_unquoted_value() {
+        let mut args = args("spam reason=too loud --by mod");
+
+        assert_eq!(args.named::<String>("reason").unwrap(), "too");
+        assert_eq!(args.named::<String>("by").unwrap(), "mod");
+        assert_eq!(args.single::<String>().unwrap(), "spam");
+        assert_eq!(args.single::<String>().unwrap(), "loud");
+        assert!(args.is_empty());
+    }
+
+    #[test]
+    fn named_missing_quote() {
+        let mut args = args("spam reason=\"too loud");
+
+        assert_eq!(args.named::<String>("reason").unwrap(), "\"too loud");
+        assert_eq!(args.single::<String>().unwrap(), "spam");
+    }
+}
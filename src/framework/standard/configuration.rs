@@ -2,8 +2,12 @@ use super::Delimiter;
 use crate::client::Context;
 use crate::model::{channel::Message, id::{UserId, GuildId, ChannelId}};
 use std::collections::HashSet;
+use std::time::Duration;
 
 type DynamicPrefixHook = dyn Fn(&mut Context, &Message) -> Option<String> + Send + Sync + 'static;
+type DynamicPrefixListHook = dyn Fn(&mut Context, &Message) -> Vec<String> + Send + Sync + 'static;
+type LocaleHook = dyn Fn(&mut Context, &Message) -> Option<String> + Send + Sync + 'static;
+type TranslateHook = dyn Fn(&str, &str) -> Option<String> + Send + Sync + 'static;
 
 /// A configuration struct for deciding whether the framework
 /// should allow optional whitespace between prefixes, group prefixes and command names.
@@ -103,24 +107,44 @@ pub struct Configuration {
     #[doc(hidden)]
     pub blocked_users: HashSet<UserId>,
     #[doc(hidden)]
+    pub blocked_channels: HashSet<ChannelId>,
+    #[doc(hidden)]
+    pub allowed_guilds: HashSet<GuildId>,
+    #[doc(hidden)]
+    pub allowed_users: HashSet<UserId>,
+    #[doc(hidden)]
     pub allowed_channels: HashSet<ChannelId>,
     #[doc(hidden)]
     pub disabled_commands: HashSet<String>,
     #[doc(hidden)]
     pub dynamic_prefixes: Vec<Box<DynamicPrefixHook>>,
     #[doc(hidden)]
+    pub dynamic_prefix_list: Option<Box<DynamicPrefixListHook>>,
+    #[doc(hidden)]
+    pub locale: Option<Box<LocaleHook>>,
+    #[doc(hidden)]
+    pub translate: Option<Box<TranslateHook>>,
+    #[doc(hidden)]
     pub ignore_bots: bool,
     #[doc(hidden)]
     pub ignore_webhooks: bool,
     #[doc(hidden)]
     pub on_mention: Option<String>,
     #[doc(hidden)]
+    pub on_mention_self: bool,
+    #[doc(hidden)]
     pub owners: HashSet<UserId>,
     #[doc(hidden)]
     pub prefixes: Vec<String>,
     #[doc(hidden)]
+    pub max_levenshtein_distance: usize,
+    #[doc(hidden)]
     pub no_dm_prefix: bool,
     #[doc(hidden)]
+    pub usage_on_argument_error: bool,
+    #[doc(hidden)]
+    pub typing_after: Option<Duration>,
+    #[doc(hidden)]
     pub delimiters: Vec<Delimiter>,
     #[doc(hidden)]
     pub case_insensitive: bool,
@@ -174,7 +198,9 @@ impl Configuration {
         self
     }
 
-       /// HashSet of channels Ids where commands will be working.
+    /// HashSet of channels Ids where commands will be working.
+    ///
+    /// If not empty, commands in all other channels are ignored.
     ///
     /// **Note**: Defaults to an empty HashSet.
     ///
@@ -200,6 +226,89 @@ impl Configuration {
         self
     }
 
+    /// HashSet of guild Ids where commands will be working.
+    ///
+    /// If not empty, commands in all other guilds are ignored. Commands in
+    /// direct messages are unaffected.
+    ///
+    /// **Note**: Defaults to an empty HashSet.
+    ///
+    /// # Examples
+    ///
+    /// Create a HashSet in-place:
+    ///
+    /// ```rust,no_run
+    /// # use serenity::prelude::*;
+    /// # struct Handler;
+    /// #
+    /// # impl EventHandler for Handler {}
+    /// # let mut client = Client::new("token", Handler).unwrap();
+    /// use serenity::model::id::GuildId;
+    /// use serenity::framework::StandardFramework;
+    ///
+    /// client.with_framework(StandardFramework::new().configure(|c| c
+    ///     .allowed_guilds(vec![GuildId(7), GuildId(77)].into_iter().collect())));
+    /// ```
+    pub fn allowed_guilds(&mut self, guilds: HashSet<GuildId>) -> &mut Self {
+        self.allowed_guilds = guilds;
+
+        self
+    }
+
+    /// HashSet of user Ids whose commands will be working.
+    ///
+    /// If not empty, commands of all other users are ignored.
+    ///
+    /// **Note**: Defaults to an empty HashSet.
+    ///
+    /// # Examples
+    ///
+    /// Create a HashSet in-place:
+    ///
+    /// ```rust,no_run
+    /// # use serenity::prelude::*;
+    /// # struct Handler;
+    /// #
+    /// # impl EventHandler for Handler {}
+    /// # let mut client = Client::new("token", Handler).unwrap();
+    /// use serenity::model::id::UserId;
+    /// use serenity::framework::StandardFramework;
+    ///
+    /// client.with_framework(StandardFramework::new().configure(|c| c
+    ///     .allowed_users(vec![UserId(7), UserId(77)].into_iter().collect())));
+    /// ```
+    pub fn allowed_users(&mut self, users: HashSet<UserId>) -> &mut Self {
+        self.allowed_users = users;
+
+        self
+    }
+
+    /// HashSet of channel Ids where commands will be ignored.
+    ///
+    /// **Note**: Defaults to an empty HashSet.
+    ///
+    /// # Examples
+    ///
+    /// Create a HashSet in-place:
+    ///
+    /// ```rust,no_run
+    /// # use serenity::prelude::*;
+    /// # struct Handler;
+    /// #
+    /// # impl EventHandler for Handler {}
+    /// # let mut client = Client::new("token", Handler).unwrap();
+    /// use serenity::model::id::ChannelId;
+    /// use serenity::framework::StandardFramework;
+    ///
+    /// client.with_framework(StandardFramework::new().configure(|c| c
+    ///     .blocked_channels(vec![ChannelId(7), ChannelId(77)].into_iter().collect())));
+    /// ```
+    pub fn blocked_channels(&mut self, channels: HashSet<ChannelId>) -> &mut Self {
+        self.blocked_channels = channels;
+
+        self
+    }
+
     /// HashSet of guild Ids where commands will be ignored.
     ///
     /// **Note**: Defaults to an empty HashSet.
@@ -337,6 +446,44 @@ impl Configuration {
         self
     }
 
+    /// Sets multiple prefixes to respond to dynamically based on conditions.
+    ///
+    /// Each hook is tried in order; the first prefix that matches the message
+    /// is used. This allows a guild to have several prefixes active at once,
+    /// for example ones loaded from a database keyed by the guild's Id.
+    ///
+    /// **Note**: Refer to [`dynamic_prefix`] for the default value.
+    ///
+    /// # Examples
+    ///
+    /// Respond to `"!"` and `"?"` in one guild, and fall back to the
+    /// inherited prefix everywhere else:
+    ///
+    /// ```rust,no_run
+    /// # use serenity::prelude::*;
+    /// # struct Handler;
+    /// #
+    /// # impl EventHandler for Handler {}
+    /// # let mut client = Client::new("token", Handler).unwrap();
+    /// use serenity::framework::StandardFramework;
+    /// use serenity::client::Context;
+    /// use serenity::model::{channel::Message, id::GuildId};
+    ///
+    /// fn guild_prefix(msg: &Message, prefix: &str) -> Option<String> {
+    ///     if msg.guild_id == Some(GuildId(381880193251409931)) {
+    ///         Some(prefix.to_string())
+    ///     } else {
+    ///         None
+    ///     }
+    /// }
+    ///
+    /// client.with_framework(StandardFramework::new()
+    ///     .configure(|c| c.prefix("~").dynamic_prefixes(
+    ///         ["!", "?"].iter().map(|&p| move |_: &mut Context, msg: &Message| guild_prefix(msg, p))
+    ///     )));
+    /// ```
+    ///
+    /// [`dynamic_prefix`]: #method.dynamic_prefix
     #[inline]
     pub fn dynamic_prefixes<F, I: IntoIterator<Item = F>>(&mut self, iter: I) -> &mut Self
     where
@@ -350,6 +497,125 @@ impl Configuration {
         self
     }
 
+    /// Sets a function returning any number of prefixes to respond to,
+    /// such as all of the prefixes a guild has stored in a database.
+    ///
+    /// The prefixes are tried in order after those of [`dynamic_prefixes`],
+    /// and the first that matches the message is used, so a prefix must come
+    /// before any shorter prefix it starts with. Return an empty vector to
+    /// fall back to the inherited prefix.
+    ///
+    /// **Note**: Defaults to no dynamic prefix list.
+    ///
+    /// # Examples
+    ///
+    /// Respond to `"!"` and `"?"` in one guild, and to the inherited prefix
+    /// everywhere else:
+    ///
+    /// ```rust,no_run
+    /// # use serenity::prelude::*;
+    /// # struct Handler;
+    /// #
+    /// # impl EventHandler for Handler {}
+    /// # let mut client = Client::new("token", Handler).unwrap();
+    /// use serenity::framework::StandardFramework;
+    /// use serenity::model::id::GuildId;
+    ///
+    /// client.with_framework(StandardFramework::new()
+    ///     .configure(|c| c.prefix("~").dynamic_prefix_list(|_, msg| {
+    ///         if msg.guild_id == Some(GuildId(381880193251409931)) {
+    ///             vec!["!".to_string(), "?".to_string()]
+    ///         } else {
+    ///             Vec::new()
+    ///         }
+    ///     })));
+    /// ```
+    ///
+    /// [`dynamic_prefixes`]: #method.dynamic_prefixes
+    pub fn dynamic_prefix_list<F>(&mut self, dynamic_prefix_list: F) -> &mut Self
+    where
+        F: Fn(&mut Context, &Message) -> Vec<String> + Send + Sync + 'static,
+    {
+        self.dynamic_prefix_list = Some(Box::new(dynamic_prefix_list));
+
+        self
+    }
+
+    /// Sets how to translate the texts the framework sends by itself, such as
+    /// the usage shown by [`usage_on_argument_error`].
+    ///
+    /// `locale` returns the locale of a message, for instance that of its guild
+    /// or author. `translate` is given that locale and the key of a text, and
+    /// returns the text to use instead of the English default, which may have
+    /// been loaded at runtime. If either returns `None`, the default is used.
+    ///
+    /// | Key          | Default                          |
+    /// |--------------|----------------------------------|
+    /// | `usage_text` | ``Usage: `{invoked} {usage}` ``  |
+    ///
+    /// The same `translate` function can be passed to
+    /// [`HelpOptions::localised`] and [`DispatchError::localised`], to
+    /// translate the help command and dispatch errors.
+    ///
+    /// **Note**: Defaults to no translation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use serenity::prelude::*;
+    /// # struct Handler;
+    /// #
+    /// # impl EventHandler for Handler {}
+    /// # let mut client = Client::new("token", Handler).unwrap();
+    /// use serenity::framework::StandardFramework;
+    /// use serenity::model::id::GuildId;
+    ///
+    /// fn translate(locale: &str, key: &str) -> Option<String> {
+    ///     match (locale, key) {
+    ///         ("de", "usage_text") => Some("Verwendung: `{invoked} {usage}`".to_string()),
+    ///         _ => None,
+    ///     }
+    /// }
+    ///
+    /// client.with_framework(StandardFramework::new()
+    ///     .configure(|c| c
+    ///         .usage_on_argument_error(true)
+    ///         .localisation(|_, msg| {
+    ///             if msg.guild_id == Some(GuildId(381880193251409931)) {
+    ///                 Some("de".to_string())
+    ///             } else {
+    ///                 None
+    ///             }
+    ///         }, translate)));
+    /// ```
+    ///
+    /// [`DispatchError::localised`]: enum.DispatchError.html#method.localised
+    /// [`HelpOptions::localised`]: struct.HelpOptions.html#method.localised
+    /// [`usage_on_argument_error`]: #method.usage_on_argument_error
+    pub fn localisation<L, T>(&mut self, locale: L, translate: T) -> &mut Self
+    where
+        L: Fn(&mut Context, &Message) -> Option<String> + Send + Sync + 'static,
+        T: Fn(&str, &str) -> Option<String> + Send + Sync + 'static,
+    {
+        self.locale = Some(Box::new(locale));
+        self.translate = Some(Box::new(translate));
+
+        self
+    }
+
+    /// Translates the text of `key` into the locale of `msg`, falling back to
+    /// `default`.
+    pub(crate) fn localise(&self, ctx: &mut Context, msg: &Message, key: &str, default: &str) -> String {
+        let (locale, translate) = match (&self.locale, &self.translate) {
+            (Some(locale), Some(translate)) => (locale, translate),
+            _ => return default.to_string(),
+        };
+
+        locale(ctx, msg)
+            .and_then(|locale| translate(&locale, key))
+            .unwrap_or_else(|| default.to_string())
+    }
+
     /// Whether the bot should respond to other bots.
     ///
     /// For example, if this is set to false, then the bot will respond to any
@@ -399,6 +665,35 @@ impl Configuration {
         self
     }
 
+    /// Whether or not to respond to commands initiated with a mention of the
+    /// bot itself, such as `@Bot help`.
+    ///
+    /// The bot's Id is resolved once the client is ready, and replaces any
+    /// value given to [`on_mention`].
+    ///
+    /// **Note**: Defaults to `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use serenity::prelude::*;
+    /// # struct Handler;
+    /// #
+    /// # impl EventHandler for Handler {}
+    /// # let mut client = Client::new("token", Handler).unwrap();
+    /// use serenity::framework::StandardFramework;
+    ///
+    /// client.with_framework(StandardFramework::new()
+    ///     .configure(|c| c.on_mention_self(true).prefix("~")));
+    /// ```
+    ///
+    /// [`on_mention`]: #method.on_mention
+    pub fn on_mention_self(&mut self, b: bool) -> &mut Self {
+        self.on_mention_self = b;
+
+        self
+    }
+
     /// A `HashSet` of user Ids checks won't apply to.
     ///
     /// **Note**: Defaults to an empty HashSet.
@@ -518,6 +813,70 @@ impl Configuration {
         self
     }
 
+    /// If not 0, the framework looks for commands whose names are at most this
+    /// many edits away from an unrecognised command, and passes them to the
+    /// [unrecognised command hook] as suggestions.
+    ///
+    /// Commands hidden from help, or that the author would fail to pass the
+    /// requirements or checks of, are not suggested. Suggestions are only
+    /// searched for with the `cache` feature enabled, and are searched for on
+    /// the threadpool along with the hook.
+    ///
+    /// **Note**: Defaults to `0`.
+    ///
+    /// [unrecognised command hook]: struct.StandardFramework.html#method.unrecognised_command
+    pub fn max_levenshtein_distance(&mut self, distance: usize) -> &mut Self {
+        self.max_levenshtein_distance = distance;
+
+        self
+    }
+
+    /// Whether to reply with a command's usage when it is given too few or too
+    /// many arguments, such as ``Usage: `!ban <user> [reason]` ``.
+    ///
+    /// Only commands with a [`usage`] reply. The [dispatch error hook] is still
+    /// called afterwards.
+    ///
+    /// **Note**: Defaults to `false`.
+    ///
+    /// [`usage`]: struct.CommandOptions.html#structfield.usage
+    /// [dispatch error hook]: struct.StandardFramework.html#method.on_dispatch_error
+    pub fn usage_on_argument_error(&mut self, b: bool) -> &mut Self {
+        self.usage_on_argument_error = b;
+
+        self
+    }
+
+    /// Shows the bot as typing in the channel while a command runs, once it
+    /// has been running for longer than `duration`.
+    ///
+    /// Commands finishing quicker than that do not show typing at all.
+    ///
+    /// # Examples
+    ///
+    /// Show typing for commands taking longer than half a second:
+    ///
+    /// ```rust,no_run
+    /// # use serenity::prelude::*;
+    /// # struct Handler;
+    /// #
+    /// # impl EventHandler for Handler {}
+    /// # let mut client = Client::new("token", Handler).unwrap();
+    /// #
+    /// use serenity::framework::StandardFramework;
+    /// use std::time::Duration;
+    ///
+    /// client.with_framework(StandardFramework::new()
+    ///     .configure(|c| c.typing_after(Duration::from_millis(500))));
+    /// ```
+    ///
+    /// **Note**: Defaults to not showing typing.
+    pub fn typing_after(&mut self, duration: Duration) -> &mut Self {
+        self.typing_after = Some(duration);
+
+        self
+    }
+
     /// Sets a single delimiter to be used when splitting the content after a command.
     ///
     /// **Note**: Defaults to a vector with a single element of `' '`.
@@ -583,8 +942,9 @@ impl Configuration {
     /// Whether the framework shouldn't care about the user's input if it's:
     /// `~command`, `~Command`, or `~COMMAND`.
     ///
-    /// Setting this to `true` will result in *all* command names to be case
-    /// insensitive.
+    /// Setting this to `true` will result in *all* prefixes and command names
+    /// to be case insensitive, so `!help`, `!Help` and `!HELP` are all treated
+    /// the same.
     ///
     /// **Note**: Defaults to `false`.
     pub fn case_insensitivity(&mut self, cs: bool) -> &mut Self {
@@ -602,15 +962,24 @@ impl Default for Configuration {
     /// - **by_space** to `true`
     /// - **blocked_guilds** to an empty HashSet
     /// - **blocked_users** to an empty HashSet,
+    /// - **blocked_channels** to an empty HashSet,
+    /// - **allowed_guilds** to an empty HashSet,
+    /// - **allowed_users** to an empty HashSet,
     /// - **allowed_channels** to an empty HashSet,
     /// - **case_insensitive** to `false`
     /// - **delimiters** to `vec![' ']`
     /// - **disabled_commands** to an empty HashSet
     /// - **dynamic_prefixes** to an empty vector
+    /// - **dynamic_prefix_list** to `None`
+    /// - **localisation** to none
     /// - **ignore_bots** to `true`
     /// - **ignore_webhooks** to `true`
+    /// - **max_levenshtein_distance** to `0`
     /// - **no_dm_prefix** to `false`
+    /// - **usage_on_argument_error** to `false`
+    /// - **typing_after** to `None`
     /// - **on_mention** to `false`
+    /// - **on_mention_self** to `false`
     /// - **owners** to an empty HashSet
     /// - **prefix** to an empty vector
     fn default() -> Configuration {
@@ -620,15 +989,25 @@ impl Default for Configuration {
             by_space: true,
             blocked_guilds: HashSet::default(),
             blocked_users: HashSet::default(),
+            blocked_channels: HashSet::default(),
+            allowed_guilds: HashSet::default(),
+            allowed_users: HashSet::default(),
             allowed_channels: HashSet::default(),
             case_insensitive: false,
             delimiters: vec![Delimiter::Single(' ')],
             disabled_commands: HashSet::default(),
             dynamic_prefixes: Vec::new(),
+            dynamic_prefix_list: None,
+            locale: None,
+            translate: None,
             ignore_bots: true,
             ignore_webhooks: true,
+            max_levenshtein_distance: 0,
             no_dm_prefix: false,
+            usage_on_argument_error: false,
+            typing_after: None,
             on_mention: None,
+            on_mention_self: false,
             owners: HashSet::default(),
             prefixes: vec![],
         }
@@ -0,0 +1,113 @@
+use std::{
+    future::Future,
+    mem::{self, ManuallyDrop},
+    sync::Arc,
+    task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+    thread::{self, Thread},
+};
+
+// A waker unparking the thread driving a future, built by hand rather than
+// via `std::task::Wake` to support older compilers. Its data is a pointer
+// obtained from `Arc::<Thread>::into_raw`.
+static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, wake, wake_by_ref, drop);
+
+fn raw_waker(thread: Arc<Thread>) -> RawWaker {
+    RawWaker::new(Arc::into_raw(thread) as *const (), &VTABLE)
+}
+
+unsafe fn clone(data: *const ()) -> RawWaker {
+    // The waker being cloned keeps its own reference.
+    let thread = ManuallyDrop::new(Arc::from_raw(data as *const Thread));
+
+    raw_waker(Arc::clone(&thread))
+}
+
+unsafe fn wake(data: *const ()) {
+    Arc::from_raw(data as *const Thread).unpark();
+}
+
+unsafe fn wake_by_ref(data: *const ()) {
+    (*(data as *const Thread)).unpark();
+}
+
+unsafe fn drop(data: *const ()) {
+    mem::drop(Arc::from_raw(data as *const Thread));
+}
+
+/// Drives a future to completion on the current thread, parking the thread
+/// while the future is waiting to be woken.
+///
+/// This is used to run `async fn` commands, which are driven on the
+/// threadpool worker that the command was dispatched to. Futures awaited
+/// inside of a command must therefore not rely on being polled by a specific
+/// runtime.
+///
+/// **Note**: The worker stays occupied until the command has finished, just
+/// like for a synchronous command, so awaiting does not free it up to run
+/// other commands or event handlers in the meantime. Raise the number of
+/// threads of the client if commands spend long waiting.
+///
+/// # Examples
+///
+/// Declaring an asynchronous command:
+///
+/// ```rust,ignore
+/// use serenity::framework::standard::{CommandResult, macros::command};
+///
+/// #[command]
+/// async fn ping(ctx: &mut Context, msg: &Message) -> CommandResult {
+///     let content = fetch_pong().await?;
+///     msg.channel_id.say(&ctx.http, content)?;
+///
+///     Ok(())
+/// }
+/// ```
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = Box::pin(future);
+    let waker = unsafe { Waker::from_raw(raw_waker(Arc::new(thread::current()))) };
+    let mut context = Context::from_waker(&waker);
+
+    loop {
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::block_on;
+    use std::{
+        future::Future,
+        pin::Pin,
+        task::{Context, Poll},
+        thread,
+    };
+
+    /// A future that is pending until it has been polled a number of times,
+    /// waking itself from another thread each time.
+    struct Countdown(u8);
+
+    impl Future for Countdown {
+        type Output = &'static str;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            if self.0 == 0 {
+                return Poll::Ready("done");
+            }
+
+            self.0 -= 1;
+            let waker = cx.waker().clone();
+            thread::spawn(move || waker.wake());
+
+            Poll::Pending
+        }
+    }
+
+    #[test]
+    fn test_block_on() {
+        assert_eq!(block_on(async { 1 + 1 }), 2);
+        assert_eq!(block_on(Countdown(3)), "done");
+    }
+}
@@ -51,13 +51,32 @@
 //! The same can be accomplished with no embeds by substituting `with_embeds`
 //! with the [`plain`] function.
 //!
+//! # Customisation
+//!
+//! The output of both functions is adjusted through the [`HelpOptions`] given
+//! to the `#[help]` attribute:
+//!
+//! - every label and text shown, such as `usage_label` or
+//! `command_not_found_text`, can be replaced;
+//! - `lacking_permissions`, `lacking_role`, `lacking_ownership`,
+//! `lacking_conditions` and `wrong_channel` decide whether commands the user
+//! cannot run are shown, struck through or hidden;
+//! - `group_order` decides the order groups are listed in.
+//!
+//! Layouts beyond these, such as formatting each field with a template, are
+//! not provided. Instead, call [`create_customised_help_data`] and render the
+//! returned [`CustomisedHelpData`] in whatever format is desired.
+//!
+//! [`create_customised_help_data`]: fn.create_customised_help_data.html
+//! [`CustomisedHelpData`]: enum.CustomisedHelpData.html
+//! [`HelpOptions`]: ../struct.HelpOptions.html
 //! [`plain`]: fn.plain.html
 //! [`with_embeds`]: fn.with_embeds.html
 
 #[cfg(all(feature = "cache", feature = "http"))]
 use super::{
     Args, CommandGroup, CommandOptions,
-    CommandResult, has_correct_roles, HelpBehaviour, HelpOptions,
+    CommandResult, has_correct_roles, HelpBehaviour, HelpOptions, HelpOrder,
     has_correct_permissions, OnlyIn,
     structures::Command as InternalCommand,
 };
@@ -88,6 +107,7 @@ use log::warn;
 macro_rules! format_command_name {
     ($behaviour:expr, $command_name:expr) => {
         match $behaviour {
+            HelpBehaviour::Lock => format!("`{}` 🔒", $command_name),
             HelpBehaviour::Strike => format!("~~`{}`~~", $command_name),
             HelpBehaviour::Nothing => format!("`{}`", $command_name),
             HelpBehaviour::Hide => continue,
@@ -133,7 +153,7 @@ pub struct Command<'a> {
     availability: &'a str,
     description: Option<&'static str>,
     usage: Option<&'static str>,
-    usage_sample: Option<&'static str>,
+    usage_sample: Vec<&'static str>,
     checks: Vec<String>,
 }
 
@@ -389,11 +409,68 @@ fn check_command_behaviour(
     HelpBehaviour::Nothing
 }
 
+/// Whether the author of `msg` passes the checks of a command, and of its
+/// group and that group's ancestors, that are meant to be run for help.
+///
+/// `groups` lists the command's group and its ancestors, starting with the
+/// outermost.
+#[cfg(all(feature = "cache", feature = "http"))]
+pub(crate) fn passes_help_checks(
+    context: &mut Context,
+    msg: &Message,
+    groups: &[&CommandGroup],
+    options: &CommandOptions,
+) -> bool {
+    let mut args = Args::new("", &[]);
+
+    groups
+        .iter()
+        .flat_map(|group| group.options.checks.iter())
+        .chain(options.checks.iter())
+        .filter(|check| check.check_in_help)
+        .all(|check| (check.function)(context, msg, &mut args, options).is_success())
+}
+
+/// Like `check_command_behaviour`, but additionally runs the checks of the
+/// command and of the groups it is nested in that are meant to be evaluated in
+/// help.
+///
+/// `groups` lists the command's group and its ancestors, starting with the
+/// outermost.
+#[cfg(all(feature = "cache", feature = "http"))]
+fn check_full_command_behaviour(
+    context: &Context,
+    msg: &Message,
+    groups: &[&CommandGroup],
+    command: &InternalCommand,
+    owners: &HashSet<UserId>,
+    help_options: &HelpOptions,
+) -> HelpBehaviour {
+    let behaviour = check_command_behaviour(
+        msg,
+        &command.options,
+        owners,
+        help_options,
+        &context.cache,
+    );
+
+    if behaviour != HelpBehaviour::Nothing {
+        return behaviour;
+    }
+
+    if passes_help_checks(&mut context.clone(), msg, groups, &command.options) {
+        HelpBehaviour::Nothing
+    } else {
+        help_options.lacking_conditions
+    }
+}
+
 #[cfg(all(feature = "cache", feature = "http"))]
 #[allow(clippy::too_many_arguments)]
 fn nested_group_command_search<'a>(
-    cache: &CacheRwLock,
+    context: &Context,
     groups: &[&'static CommandGroup],
+    ancestors: &[&'static CommandGroup],
     name: &mut String,
     help_options: &'a HelpOptions,
     msg: &Message,
@@ -404,12 +481,15 @@ fn nested_group_command_search<'a>(
         let group = *group;
         let mut found: Option<&'static InternalCommand> = None;
 
+        let mut group_chain = ancestors.to_vec();
+        group_chain.push(group);
+
         let group_behaviour = check_command_behaviour(
                 &msg,
                 &group.options,
                 &owners,
                 &help_options,
-                &cache,
+                &context.cache,
         );
 
         match &group_behaviour {
@@ -445,12 +525,13 @@ fn nested_group_command_search<'a>(
 
             if search_command_name_matched.is_some() {
 
-                if HelpBehaviour::Nothing == check_command_behaviour(
+                if HelpBehaviour::Nothing == check_full_command_behaviour(
+                    &context,
                     &msg,
-                    &command.options,
+                    &group_chain,
+                    &command,
                     &owners,
                     &help_options,
-                    &cache,
                 ) {
                     found = Some(command);
                 } else {
@@ -467,12 +548,13 @@ fn nested_group_command_search<'a>(
                 let levenshtein_distance = levenshtein_distance(&command_name, &name);
 
                 if levenshtein_distance <= help_options.max_levenshtein_distance
-                    && HelpBehaviour::Nothing == check_command_behaviour(
+                    && HelpBehaviour::Nothing == check_full_command_behaviour(
+                        &context,
                         &msg,
-                        &command.options,
+                        &group_chain,
+                        &command,
                         &owners,
                         &help_options,
-                        &cache,
                     )
                 {
                     similar_commands.push(SuggestedCommandName {
@@ -507,7 +589,7 @@ fn nested_group_command_search<'a>(
                 .options
                 .checks
                 .iter()
-                .chain(group.options.checks.iter())
+                .chain(group_chain.iter().rev().flat_map(|group| group.options.checks.iter()))
                 .filter_map(|check| {
                     if check.display_in_help {
                         Some(check.name.to_string())
@@ -527,14 +609,15 @@ fn nested_group_command_search<'a>(
                     aliases: options.names[1..].to_vec(),
                     availability: available_text,
                     usage: options.usage,
-                    usage_sample: options.example,
+                    usage_sample: options.examples.to_vec(),
                 },
             });
         }
 
         match nested_group_command_search(
-            cache,
+            context,
             &group.sub_groups,
+            &group_chain,
             name,
             help_options,
             msg,
@@ -554,7 +637,7 @@ fn nested_group_command_search<'a>(
 /// returns similar commands.
 #[cfg(feature = "cache")]
 fn fetch_single_command<'a>(
-    cache: impl AsRef<CacheRwLock>,
+    context: &Context,
     groups: &[&'static CommandGroup],
     name: &str,
     help_options: &'a HelpOptions,
@@ -562,12 +645,12 @@ fn fetch_single_command<'a>(
     owners: &HashSet<UserId>,
 ) -> Result<CustomisedHelpData<'a>, Vec<SuggestedCommandName>> {
     let mut similar_commands: Vec<SuggestedCommandName> = Vec::new();
-    let cache = cache.as_ref();
     let mut name = name.to_string();
 
     match nested_group_command_search(
-        &cache,
+        &context,
         &groups,
+        &[],
         &mut name,
         &help_options,
         &msg,
@@ -586,11 +669,13 @@ fn fill_eligible_commands<'a>(
     commands: &[&'static InternalCommand],
     owners: &HashSet<UserId>,
     help_options: &'a HelpOptions,
-    group: &'a CommandGroup,
+    groups: &[&'a CommandGroup],
     msg: &Message,
     to_fill: &mut GroupCommandsPair,
     highest_formatter: &mut HelpBehaviour,
 ) {
+    let group = groups[groups.len() - 1];
+
     to_fill.name = group.help_name;
     to_fill.prefixes = group.options.prefixes.to_vec();
 
@@ -628,12 +713,13 @@ fn fill_eligible_commands<'a>(
             }
         }
 
-        let command_behaviour = check_command_behaviour(
+        let command_behaviour = check_full_command_behaviour(
+            context,
             msg,
-            &command.options,
+            groups,
+            command,
             owners,
             help_options,
-            &context.cache,
         );
 
         let name = format_command_name!(command_behaviour, &name);
@@ -643,6 +729,8 @@ fn fill_eligible_commands<'a>(
 
 /// Tries to fetch all commands visible to the user within a group and
 /// its sub-groups.
+///
+/// `groups` lists the group and its ancestors, starting with the outermost.
 #[cfg(feature = "cache")]
 #[allow(clippy::too_many_arguments)]
 fn fetch_all_eligible_commands_in_group<'a>(
@@ -650,10 +738,11 @@ fn fetch_all_eligible_commands_in_group<'a>(
     commands: &[&'static InternalCommand],
     owners: &HashSet<UserId>,
     help_options: &'a HelpOptions,
-    group: &'a CommandGroup,
+    groups: &[&'a CommandGroup],
     msg: &Message,
     highest_formatter: HelpBehaviour,
 ) -> GroupCommandsPair {
+    let group = groups[groups.len() - 1];
     let mut group_with_cmds = GroupCommandsPair::default();
     let mut highest_formatter = highest_formatter;
 
@@ -662,7 +751,7 @@ fn fetch_all_eligible_commands_in_group<'a>(
         &commands,
         &owners,
         &help_options,
-        &group,
+        groups,
         &msg,
         &mut group_with_cmds,
         &mut highest_formatter,
@@ -675,12 +764,15 @@ fn fetch_all_eligible_commands_in_group<'a>(
             continue;
         }
 
+        let mut sub_group_chain = groups.to_vec();
+        sub_group_chain.push(sub_group);
+
         let grouped_cmd = fetch_all_eligible_commands_in_group(
             &context,
             &sub_group.commands,
             &owners,
             &help_options,
-            &sub_group,
+            &sub_group_chain,
             &msg,
             highest_formatter,
         );
@@ -706,31 +798,54 @@ fn create_command_group_commands_pair_from_groups<'a>(
     for group in groups {
         let group = *group;
 
-        let group_with_cmds = create_single_group(&context, group, &owners, &msg, &help_options);
+        let group_with_cmds = create_single_group(&context, group, &[], &owners, &msg, &help_options);
 
         if !group_with_cmds.command_names.is_empty() {
             listed_groups.push(group_with_cmds);
         }
     }
 
+    sort_groups(&mut listed_groups, help_options.group_order);
+
     listed_groups
 }
 
+/// Sorts groups and, recursively, their sub-groups according to `order`.
+#[cfg(feature = "cache")]
+fn sort_groups(groups: &mut [GroupCommandsPair], order: HelpOrder) {
+    match order {
+        HelpOrder::Declaration => return,
+        HelpOrder::Alphabetical => groups.sort_by(|a, b| a.name.cmp(b.name)),
+        HelpOrder::__Nonexhaustive => unreachable!(),
+    }
+
+    for group in groups {
+        sort_groups(&mut group.sub_groups, order);
+    }
+}
+
 /// Fetches a single group with its commands.
+///
+/// `ancestors` lists the groups the group is nested in, starting with the
+/// outermost.
 #[cfg(feature = "cache")]
 fn create_single_group(
     context: &Context,
     group: &CommandGroup,
+    ancestors: &[&CommandGroup],
     owners: &HashSet<UserId>,
     msg: &Message,
     help_options: &HelpOptions,
 ) -> GroupCommandsPair {
+    let mut groups = ancestors.to_vec();
+    groups.push(group);
+
     let mut group_with_cmds = fetch_all_eligible_commands_in_group(
         &context,
         &group.commands,
         &owners,
         &help_options,
-        &group,
+        &groups,
         &msg,
         HelpBehaviour::Nothing,
     );
@@ -768,6 +883,32 @@ pub fn searched_lowercase<'a>(
     help_options: &'a HelpOptions,
     msg: &Message,
     searched_named_lowercase: &mut String,
+) -> Option<CustomisedHelpData<'a>> {
+    searched_lowercase_nested(
+        context,
+        group,
+        &[],
+        owners,
+        args,
+        help_options,
+        msg,
+        searched_named_lowercase,
+    )
+}
+
+/// Like `searched_lowercase`, but for a group nested in `ancestors`, starting
+/// with the outermost, whose checks apply to its commands as well.
+#[cfg(feature = "cache")]
+#[allow(clippy::too_many_arguments)]
+fn searched_lowercase_nested<'a>(
+    context: &Context,
+    group: &CommandGroup,
+    ancestors: &[&CommandGroup],
+    owners: &HashSet<UserId>,
+    args: &'a Args,
+    help_options: &'a HelpOptions,
+    msg: &Message,
+    searched_named_lowercase: &mut String,
 ) -> Option<CustomisedHelpData<'a>> {
     let is_prefixless_group = {
         group.options.prefixes.is_empty()
@@ -792,7 +933,7 @@ pub fn searched_lowercase<'a>(
 
     if is_prefixless_group || is_word_prefix {
         let single_group =
-            create_single_group(&context, &group, owners, &msg, &help_options);
+            create_single_group(&context, &group, ancestors, owners, &msg, &help_options);
 
         if !single_group.command_names.is_empty() {
             return Some(CustomisedHelpData::GroupedCommands {
@@ -806,11 +947,15 @@ pub fn searched_lowercase<'a>(
             });
         }
     } else if progressed || group.options.prefixes.is_empty() {
+        let mut group_chain = ancestors.to_vec();
+        group_chain.push(group);
+
         for sub_group in group.sub_groups {
 
-            if let Some(found_set) = searched_lowercase(
+            if let Some(found_set) = searched_lowercase_nested(
                 context,
                 sub_group,
+                &group_chain,
                 owners,
                 args,
                 help_options,
@@ -838,12 +983,10 @@ pub fn create_customised_help_data<'a>(
     help_options: &'a HelpOptions,
     msg: &Message,
 ) -> CustomisedHelpData<'a> {
-    let cache = &context.cache;
-
     if !args.is_empty() {
         let name = args.message();
 
-        return match fetch_single_command(&cache, &groups, &name, &help_options, &msg, owners) {
+        return match fetch_single_command(&context, &groups, &name, &help_options, &msg, owners) {
             Ok(single_command) => single_command,
             Err(suggestions) => {
                 let mut searched_named_lowercase = name.to_lowercase().to_string();
@@ -1080,12 +1223,19 @@ fn send_single_command_embed(
                 embed.field(&help_options.usage_label, full_usage_text, true);
             }
 
-            if let Some(ref example) = command.usage_sample {
-                let full_example_text = if let Some(first_prefix) = command.group_prefixes.get(0) {
-                    format!("`{} {} {}`", first_prefix, command.name, example)
-                } else {
-                    format!("`{} {}`", command.name, example)
-                };
+            if !command.usage_sample.is_empty() {
+                let full_example_text = command
+                    .usage_sample
+                    .iter()
+                    .map(|example| {
+                        if let Some(first_prefix) = command.group_prefixes.get(0) {
+                            format!("`{} {} {}`", first_prefix, command.name, example)
+                        } else {
+                            format!("`{} {}`", command.name, example)
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n");
 
                 embed.field(&help_options.usage_sample_label, full_example_text, true);
             }
@@ -1312,7 +1462,7 @@ fn single_command_to_plain_string(help_options: &HelpOptions, command: &Command<
         }
     }
 
-    if let Some(ref example) = command.usage_sample {
+    for example in &command.usage_sample {
         if let Some(first_prefix) = command.group_prefixes.get(0) {
             let _ = writeln!(
                 result,
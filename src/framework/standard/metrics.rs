@@ -0,0 +1,56 @@
+use crate::model::id::{ChannelId, GuildId, UserId};
+use std::time::Duration;
+
+/// Information about a single command invocation, handed to [`Metrics`] once
+/// the command has finished.
+///
+/// [`Metrics`]: trait.Metrics.html
+#[derive(Clone, Debug)]
+pub struct CommandMetrics<'a> {
+    /// The name the command was dispatched under; the first of its names, or
+    /// the name the help command was invoked with.
+    pub name: &'a str,
+    /// The name of the group the command belongs to, or `None` for the help
+    /// command.
+    pub group: Option<&'a str>,
+    /// The guild the command was invoked in, if any.
+    pub guild_id: Option<GuildId>,
+    /// The channel the command was invoked in.
+    pub channel_id: ChannelId,
+    /// The user who invoked the command.
+    pub author_id: UserId,
+    /// How long the command took to run.
+    pub duration: Duration,
+    /// Whether the command returned `Ok`.
+    pub success: bool,
+}
+
+/// A sink for data about commands the framework has dispatched, for instance
+/// to graph command usage.
+///
+/// Set one with [`StandardFramework::metrics`].
+///
+/// # Examples
+///
+/// Counting how often each command is used:
+///
+/// ```rust
+/// use serenity::framework::standard::{CommandMetrics, Metrics};
+/// use std::collections::HashMap;
+/// use std::sync::Mutex;
+///
+/// #[derive(Default)]
+/// struct Usage(Mutex<HashMap<String, u64>>);
+///
+/// impl Metrics for Usage {
+///     fn command_finished(&self, data: &CommandMetrics<'_>) {
+///         *self.0.lock().unwrap().entry(data.name.to_string()).or_insert(0) += 1;
+///     }
+/// }
+/// ```
+///
+/// [`StandardFramework::metrics`]: struct.StandardFramework.html#method.metrics
+pub trait Metrics: Send + Sync {
+    /// Called after a command has run, with the outcome of the invocation.
+    fn command_finished(&self, data: &CommandMetrics<'_>);
+}
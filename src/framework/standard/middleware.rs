@@ -0,0 +1,33 @@
+use super::{Args, Command, CommandResult};
+use crate::client::Context;
+use crate::model::channel::Message;
+use std::sync::Arc;
+
+pub(crate) type Middleware = dyn Fn(&mut Context, &Message, Args, Next<'_>) -> CommandResult + Send + Sync + 'static;
+
+/// The remainder of a chain of [middleware], ending in the command itself.
+///
+/// [middleware]: struct.StandardFramework.html#method.middleware
+pub struct Next<'a> {
+    pub(crate) middleware: &'a [Arc<Middleware>],
+    pub(crate) command: &'static Command,
+}
+
+impl<'a> Next<'a> {
+    /// The command being invoked.
+    #[inline]
+    pub fn command(&self) -> &'static Command {
+        self.command
+    }
+
+    /// Runs the next middleware, or the command if there is none left.
+    pub fn run(self, ctx: &mut Context, msg: &Message, args: Args) -> CommandResult {
+        match self.middleware.split_first() {
+            Some((middleware, rest)) => middleware(ctx, msg, args, Next {
+                middleware: rest,
+                command: self.command,
+            }),
+            None => (self.command.fun)(ctx, msg, args),
+        }
+    }
+}
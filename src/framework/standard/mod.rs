@@ -5,29 +5,41 @@ pub mod macros {
 
 mod args;
 mod configuration;
+mod executor;
+mod metrics;
+mod middleware;
 mod parse;
 mod structures;
 
-pub use args::{Args, Delimiter, Error as ArgError, Iter, RawArguments};
+pub use args::{Args, Delimiter, Error as ArgError, Greedy, Iter, RawArguments};
 pub use configuration::{Configuration, WithWhiteSpace};
+pub use executor::block_on;
+pub use metrics::{CommandMetrics, Metrics};
+pub use middleware::Next;
 pub use structures::*;
 
 use structures::buckets::{Bucket, Ratelimit};
-pub use structures::buckets::BucketBuilder;
+pub use structures::buckets::{BucketBuilder, LimitedFor};
 
+use middleware::Middleware;
 use parse::{ParseError, Invoke};
 use parse::map::{CommandMap, GroupMap, Map};
 
 use super::Framework;
-use crate::client::Context;
+use crate::client::{handler_panic, Context, HandlerPanic};
+use crate::http::Typing;
 use crate::model::{
-    channel::{Channel, Message},
+    channel::Message,
+    event::EventType,
+    gateway::Ready,
     permissions::Permissions,
 };
 
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
+use parking_lot::{Mutex, RwLock};
 use threadpool::ThreadPool;
 use uwl::{UnicodeStream, StrExt};
 
@@ -37,6 +49,8 @@ use crate::cache::CacheRwLock;
 use crate::model::guild::{Guild, Member};
 #[cfg(feature = "cache")]
 use crate::internal::RwLockExt;
+#[cfg(all(feature = "cache", feature = "http"))]
+use std::collections::HashSet;
 
 /// An enum representing all possible fail conditions under which a command won't
 /// be executed.
@@ -49,11 +63,12 @@ pub enum DispatchError {
     Ratelimited(i64),
     /// When the requested command is disabled in bot configuration.
     CommandDisabled(String),
-    /// When the user is blocked in bot configuration.
+    /// When the user is blocked, or not allowed, in bot configuration.
     BlockedUser,
-    /// When the guild or its owner is blocked in bot configuration.
+    /// When the guild or its owner is blocked, or the guild is not allowed, in
+    /// bot configuration.
     BlockedGuild,
-    /// When the channel blocked in bot configuration.
+    /// When the channel is blocked, or not allowed, in bot configuration.
     BlockedChannel,
     /// When the requested command can only be used in a direct message or group
     /// channel.
@@ -63,9 +78,12 @@ pub enum DispatchError {
     OnlyForGuilds,
     /// When the requested command can only be used by bot owners.
     OnlyForOwners,
+    /// When the requested command can only be used in NSFW channels.
+    OnlyForNsfw,
     /// When the requested command requires one role.
     LackingRole,
-    /// When the command requester lacks specific required permissions.
+    /// When the command requester lacks specific required permissions. The
+    /// attached value is the permissions the requester is missing.
     LackingPermissions(Permissions),
     /// When there are too few arguments.
     NotEnoughArguments { min: u16, given: usize },
@@ -80,12 +98,101 @@ pub enum DispatchError {
     __Nonexhaustive,
 }
 
+impl DispatchError {
+    /// Describes the error in a message fit to be sent to the user, translated
+    /// into `locale`.
+    ///
+    /// `translate` is given the locale and the key of the message, such as
+    /// `"ratelimited"`, and returns the text to use instead of the English
+    /// default, which may have been loaded at runtime. Placeholders in the
+    /// text, such as `{seconds}`, are filled in afterwards.
+    ///
+    /// | Error                  | Key                    | Placeholders        |
+    /// |------------------------|------------------------|---------------------|
+    /// | `CheckFailed`          | `check_failed`         | `{check}`           |
+    /// | `Ratelimited`          | `ratelimited`          | `{seconds}`         |
+    /// | `CommandDisabled`      | `command_disabled`     | `{command}`         |
+    /// | `BlockedUser`          | `blocked_user`         |                     |
+    /// | `BlockedGuild`         | `blocked_guild`        |                     |
+    /// | `BlockedChannel`       | `blocked_channel`      |                     |
+    /// | `OnlyForDM`            | `only_for_dm`          |                     |
+    /// | `OnlyForGuilds`        | `only_for_guilds`      |                     |
+    /// | `OnlyForOwners`        | `only_for_owners`      |                     |
+    /// | `OnlyForNsfw`          | `only_for_nsfw`        |                     |
+    /// | `LackingRole`          | `lacking_role`         |                     |
+    /// | `LackingPermissions`   | `lacking_permissions`  |                     |
+    /// | `NotEnoughArguments`   | `not_enough_arguments` | `{min}`, `{given}`  |
+    /// | `TooManyArguments`     | `too_many_arguments`   | `{max}`, `{given}`  |
+    /// | `IgnoredBot`           | `ignored_bot`          |                     |
+    /// | `WebhookAuthor`        | `webhook_author`       |                     |
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity::framework::standard::DispatchError;
+    ///
+    /// fn translate(locale: &str, key: &str) -> Option<String> {
+    ///     match (locale, key) {
+    ///         ("de", "ratelimited") => Some("Versuche es in {seconds} Sekunden erneut.".to_string()),
+    ///         _ => None,
+    ///     }
+    /// }
+    ///
+    /// let error = DispatchError::Ratelimited(5);
+    ///
+    /// assert_eq!(error.localised("de", translate), "Versuche es in 5 Sekunden erneut.");
+    /// assert_eq!(error.localised("en", translate), "Try this again in 5 seconds.");
+    /// ```
+    pub fn localised<F>(&self, locale: &str, translate: F) -> String
+    where
+        F: Fn(&str, &str) -> Option<String>,
+    {
+        use self::DispatchError::*;
+
+        let (key, default) = match self {
+            CheckFailed(..) => ("check_failed", "The `{check}` check failed."),
+            Ratelimited(_) => ("ratelimited", "Try this again in {seconds} seconds."),
+            CommandDisabled(_) => ("command_disabled", "The `{command}` command is disabled."),
+            BlockedUser => ("blocked_user", "You are not allowed to use commands."),
+            BlockedGuild => ("blocked_guild", "Commands are not allowed in this guild."),
+            BlockedChannel => ("blocked_channel", "Commands are not allowed in this channel."),
+            OnlyForDM => ("only_for_dm", "This command can only be used in direct messages."),
+            OnlyForGuilds => ("only_for_guilds", "This command can only be used in guilds."),
+            OnlyForOwners => ("only_for_owners", "This command can only be used by the bot's owners."),
+            OnlyForNsfw => ("only_for_nsfw", "This command can only be used in NSFW channels."),
+            LackingRole => ("lacking_role", "You lack the role required to use this command."),
+            LackingPermissions(_) => ("lacking_permissions", "You lack the permissions required to use this command."),
+            NotEnoughArguments { .. } => ("not_enough_arguments", "Need {min} arguments, but only got {given}."),
+            TooManyArguments { .. } => ("too_many_arguments", "Max arguments allowed is {max}, but got {given}."),
+            IgnoredBot => ("ignored_bot", "Bots cannot use commands."),
+            WebhookAuthor => ("webhook_author", "Webhooks cannot use commands."),
+            __Nonexhaustive => unreachable!(),
+        };
+
+        let text = translate(locale, key).unwrap_or_else(|| default.to_string());
+
+        match self {
+            CheckFailed(name, _) => text.replace("{check}", name),
+            Ratelimited(seconds) => text.replace("{seconds}", &seconds.to_string()),
+            CommandDisabled(name) => text.replace("{command}", name),
+            NotEnoughArguments { min, given } => text
+                .replace("{min}", &min.to_string())
+                .replace("{given}", &given.to_string()),
+            TooManyArguments { max, given } => text
+                .replace("{max}", &max.to_string())
+                .replace("{given}", &given.to_string()),
+            _ => text,
+        }
+    }
+}
+
 pub type DispatchHook = dyn Fn(&mut Context, &Message, DispatchError) + Send + Sync + 'static;
 type BeforeHook = dyn Fn(&mut Context, &Message, &str) -> bool + Send + Sync + 'static;
-type AfterHook = dyn Fn(&mut Context, &Message, &str, Result<(), CommandError>) + Send + Sync + 'static;
-type UnrecognisedHook = dyn Fn(&mut Context, &Message, &str) + Send + Sync + 'static;
+type AfterHook = dyn Fn(&mut Context, &Message, &str, Duration, Result<(), CommandError>) + Send + Sync + 'static;
+type UnrecognisedHook = dyn Fn(&mut Context, &Message, &str, &[&str]) + Send + Sync + 'static;
 type NormalMessageHook = dyn Fn(&mut Context, &Message) + Send + Sync + 'static;
 type PrefixOnlyHook = dyn Fn(&mut Context, &Message) + Send + Sync + 'static;
+type HandlerPanicHook = dyn Fn(&mut Context, &Message, &str, &HandlerPanic) + Send + Sync + 'static;
 
 /// A utility for easily managing dispatches to commands.
 ///
@@ -95,14 +202,17 @@ type PrefixOnlyHook = dyn Fn(&mut Context, &Message) + Send + Sync + 'static;
 #[derive(Default)]
 pub struct StandardFramework {
     groups: Vec<(&'static CommandGroup, Map)>,
-    buckets: HashMap<String, Bucket>,
+    buckets: Arc<Mutex<HashMap<String, Bucket>>>,
     before: Option<Arc<BeforeHook>>,
     after: Option<Arc<AfterHook>>,
     dispatch: Option<Arc<DispatchHook>>,
     unrecognised_command: Option<Arc<UnrecognisedHook>>,
     normal_message: Option<Arc<NormalMessageHook>>,
     prefix_only: Option<Arc<PrefixOnlyHook>>,
-    config: Configuration,
+    handler_panic: Option<Arc<HandlerPanicHook>>,
+    metrics: Option<Arc<dyn Metrics>>,
+    middleware: Vec<Arc<Middleware>>,
+    config: Arc<RwLock<Configuration>>,
     help: Option<&'static HelpCommand>,
     /// Whether the framework has been "initialized".
     ///
@@ -156,15 +266,58 @@ impl StandardFramework {
     /// [`Configuration::default`]: struct.Configuration.html#method.default
     /// [`prefix`]: struct.Configuration.html#method.prefix
     /// [allowing whitespace between prefixes]: struct.Configuration.html#method.with_whitespace
-    pub fn configure<F>(mut self, f: F) -> Self
+    pub fn configure<F>(self, f: F) -> Self
     where
         F: FnOnce(&mut Configuration) -> &mut Configuration,
     {
-        f(&mut self.config);
+        f(&mut self.config.write());
 
         self
     }
 
+    /// Returns a handle to the framework's configuration, allowing it to be
+    /// changed at runtime without recreating the [`Client`].
+    ///
+    /// **Note**: The configuration is read-locked while a message is being
+    /// parsed. Do not modify it from within a dynamic prefix or the dispatch
+    /// error hook, as that would deadlock.
+    ///
+    /// # Examples
+    ///
+    /// Store the handle in the client's data, so that a command can change the
+    /// prefix later on:
+    ///
+    /// ```rust,no_run
+    /// # use serenity::prelude::*;
+    /// # struct Handler;
+    /// #
+    /// # impl EventHandler for Handler {}
+    /// # let mut client = Client::new("token", Handler).unwrap();
+    /// #
+    /// use serenity::framework::standard::{Configuration, StandardFramework};
+    /// use std::sync::Arc;
+    ///
+    /// struct FrameworkConfig;
+    ///
+    /// impl TypeMapKey for FrameworkConfig {
+    ///     type Value = Arc<RwLock<Configuration>>;
+    /// }
+    ///
+    /// let framework = StandardFramework::new().configure(|c| c.prefix("~"));
+    ///
+    /// client.data.write().insert::<FrameworkConfig>(framework.configuration_handle());
+    /// client.with_framework(framework);
+    ///
+    /// // Later, for instance inside of an owner-only command:
+    /// let data = client.data.read();
+    /// data.get::<FrameworkConfig>().unwrap().write().prefix("!");
+    /// ```
+    ///
+    /// [`Client`]: ../../client/struct.Client.html
+    pub fn configuration_handle(&self) -> Arc<RwLock<Configuration>> {
+        Arc::clone(&self.config)
+    }
+
     /// Defines a bucket with `delay` between each command, and the `limit` of uses
     /// per `time_span`.
     ///
@@ -193,8 +346,61 @@ impl StandardFramework {
     /// client.with_framework(StandardFramework::new()
     ///     .bucket("basic", |b| b.delay(2).time_span(10).limit(3)));
     /// ```
+    ///
+    /// Share a bucket between everyone in a guild, letting the bot's owners
+    /// bypass it:
+    ///
+    /// ```rust,no_run
+    /// # use serenity::prelude::*;
+    /// # struct Handler;
+    /// #
+    /// # impl EventHandler for Handler {}
+    /// # let mut client = Client::new("token", Handler).unwrap();
+    /// #
+    /// use serenity::framework::standard::{LimitedFor, StandardFramework};
+    ///
+    /// client.with_framework(StandardFramework::new()
+    ///     .bucket("guild", |b| b
+    ///         .time_span(60)
+    ///         .limit(10)
+    ///         .limit_for(LimitedFor::Guild)
+    ///         .exempt_owners(true)));
+    /// ```
+    ///
+    /// Tell users how long they have to wait when a command is delayed:
+    ///
+    /// ```rust,no_run
+    /// # use serenity::prelude::*;
+    /// # struct Handler;
+    /// #
+    /// # impl EventHandler for Handler {}
+    /// # let mut client = Client::new("token", Handler).unwrap();
+    /// #
+    /// use serenity::framework::standard::StandardFramework;
+    ///
+    /// client.with_framework(StandardFramework::new()
+    ///     .bucket("basic", |b| b.delay(5).delay_action(|ctx, msg, seconds| {
+    ///         let _ = msg.reply(ctx, &format!("Try this again in {} seconds.", seconds));
+    ///     })));
+    /// ```
+    ///
+    /// Only count invocations that succeed, so that mistyped arguments do not
+    /// put a user on cooldown:
+    ///
+    /// ```rust,no_run
+    /// # use serenity::prelude::*;
+    /// # struct Handler;
+    /// #
+    /// # impl EventHandler for Handler {}
+    /// # let mut client = Client::new("token", Handler).unwrap();
+    /// #
+    /// use serenity::framework::standard::StandardFramework;
+    ///
+    /// client.with_framework(StandardFramework::new()
+    ///     .bucket("basic", |b| b.delay(30).charge_only_on_success(true)));
+    /// ```
     #[inline]
-    pub fn bucket<F>(mut self, name: &str, f: F) -> Self
+    pub fn bucket<F>(self, name: &str, f: F) -> Self
     where
         F: FnOnce(&mut BucketBuilder) -> &mut BucketBuilder
     {
@@ -206,43 +412,63 @@ impl StandardFramework {
             delay,
             time_span,
             limit,
+            limited_for,
+            exempt_owners,
+            charge_only_on_success,
             check,
+            delay_action,
         } = builder;
 
-        self.buckets.insert(
+        self.buckets.lock().insert(
             name.to_string(),
             Bucket {
                 ratelimit: Ratelimit {
                     delay,
                     limit: Some((time_span, limit)),
                 },
-                users: HashMap::new(),
-                check,
+                limited_for,
+                exempt_owners,
+                charge_only_on_success,
+                targets: HashMap::new(),
+                check: check.map(Arc::from),
+                delay_action: delay_action.map(Arc::from),
             },
         );
 
         self
     }
 
-    fn should_fail_common(&self, msg: &Message) -> Option<DispatchError> {
-        if self.config.ignore_bots && msg.author.bot {
+    fn should_fail_common(
+        &self,
+        config: &Configuration,
+        ctx: &Context,
+        msg: &Message,
+    ) -> Option<DispatchError> {
+        if config.ignore_bots && msg.author.bot {
             return Some(DispatchError::IgnoredBot);
         }
 
-        if self.config.ignore_webhooks && msg.webhook_id.is_some() {
+        if config.ignore_webhooks && msg.webhook_id.is_some() {
             return Some(DispatchError::WebhookAuthor);
         }
 
+        // Owners may bypass the block and allow lists with `owner_privilege`,
+        // which is only known once the command has been parsed.
+        if !config.owners.contains(&msg.author.id) {
+            return blocked(config, ctx, msg);
+        }
+
         None
     }
 
     fn should_fail(
         &mut self,
+        config: &Configuration,
         ctx: &mut Context,
         msg: &Message,
         args: &mut Args,
         command: &'static CommandOptions,
-        group: &'static GroupOptions,
+        groups: &[&'static CommandGroup],
     ) -> Option<DispatchError> {
         if let Some(min) = command.min_args {
             if args.len() < min as usize {
@@ -262,51 +488,64 @@ impl StandardFramework {
             }
         }
 
-        if (group.owner_privilege && command.owner_privilege)
-            && self.config.owners.contains(&msg.author.id)
+        #[cfg(feature = "cache")]
         {
-            return None;
-        }
+            if command.nsfw_only {
+                let is_nsfw = msg.channel_id
+                    .to_channel_cached(&ctx.cache)
+                    .map_or(false, |c| c.is_nsfw());
 
-        if self.config.blocked_users.contains(&msg.author.id) {
-            return Some(DispatchError::BlockedUser);
+                if !is_nsfw {
+                    return Some(DispatchError::OnlyForNsfw);
+                }
+            }
         }
 
-        #[cfg(feature = "cache")]
+        if (groups.iter().all(|g| g.options.owner_privilege) && command.owner_privilege)
+            && config.owners.contains(&msg.author.id)
         {
-            if let Some(Channel::Guild(chan)) = msg.channel_id.to_channel_cached(&ctx.cache) {
-                let guild_id = chan.with(|c| c.guild_id);
+            return None;
+        }
 
-                if self.config.blocked_guilds.contains(&guild_id) {
-                    return Some(DispatchError::BlockedGuild);
-                }
+        let is_owner = config.owners.contains(&msg.author.id);
 
-                if let Some(guild) = guild_id.to_guild_cached(&ctx.cache) {
-                    if self.config.blocked_users.contains(&guild.with(|g| g.owner_id)) {
-                        return Some(DispatchError::BlockedGuild);
-                    }
-                }
+        // Owners were let through `should_fail_common` until their privilege
+        // for the command was known.
+        if is_owner {
+            if let Some(error) = blocked(config, ctx, msg) {
+                return Some(error);
             }
         }
 
-        if !self.config.allowed_channels.is_empty() &&
-           !self.config.allowed_channels.contains(&msg.channel_id) {
-            return Some(DispatchError::BlockedChannel);
-        }
+        // Only hold the lock while taking a ticket, so that the bucket's
+        // check and delay action may take as long as they need.
+        let bucket = bucket_of(command, groups).and_then(|name| {
+            let mut buckets = self.buckets.lock();
+            let bucket = buckets.get_mut(name)?;
 
-        if let Some(ref mut bucket) = command.bucket.as_ref().and_then(|b| self.buckets.get_mut(*b)) {
-            let rate_limit = bucket.take(msg.author.id.0);
+            let key = bucket.limited_for.key(msg.guild_id, msg.channel_id, msg.author.id);
+            let rate_limit = if bucket.is_exempt(is_owner) { 0 } else { bucket.take(key) };
 
-            let apply = bucket.check.as_ref().map_or(true, |check| {
+            Some((rate_limit, bucket.check.clone(), bucket.delay_action.clone()))
+        });
+
+        if let Some((rate_limit, check, delay_action)) = bucket {
+            let apply = check.map_or(true, |check| {
                 (check)(ctx, msg.guild_id, msg.channel_id, msg.author.id)
             });
 
             if apply && rate_limit > 0 {
+                if let Some(delay_action) = delay_action {
+                    delay_action(ctx, msg, rate_limit);
+                }
+
                 return Some(DispatchError::Ratelimited(rate_limit));
             }
         }
 
-        for check in group.checks.iter().chain(command.checks.iter()) {
+        let group_checks = groups.iter().flat_map(|g| g.options.checks.iter());
+
+        for check in group_checks.chain(command.checks.iter()) {
             let res = (check.function)(ctx, msg, args, command);
 
             if let CheckResult::Failure(r) = res {
@@ -459,6 +698,96 @@ impl StandardFramework {
         self
     }
 
+    /// Adds a middleware wrapped around the execution of every command, after
+    /// the [`before`] hook has allowed it.
+    ///
+    /// Middleware runs in the order it was added, the first being the
+    /// outermost. Each decides whether to go on by calling [`Next::run`], and
+    /// may do work before and after it. Not calling it short-circuits the
+    /// command, and the middleware's result is used in its place.
+    ///
+    /// # Examples
+    ///
+    /// Refuse commands in a maintenance mode, and log how long the others took:
+    ///
+    /// ```rust,no_run
+    /// # use serenity::prelude::*;
+    /// # struct Handler;
+    /// #
+    /// # impl EventHandler for Handler {}
+    /// # let mut client = Client::new("token", Handler).unwrap();
+    /// #
+    /// use serenity::framework::standard::{Next, StandardFramework};
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use std::time::Instant;
+    ///
+    /// static MAINTENANCE: AtomicBool = AtomicBool::new(false);
+    ///
+    /// client.with_framework(StandardFramework::new()
+    ///     .middleware(|ctx, msg, args, next: Next<'_>| {
+    ///         if MAINTENANCE.load(Ordering::Relaxed) {
+    ///             return Err("The bot is under maintenance.".into());
+    ///         }
+    ///
+    ///         next.run(ctx, msg, args)
+    ///     })
+    ///     .middleware(|ctx, msg, args, next: Next<'_>| {
+    ///         let name = next.command().options.names[0];
+    ///         let start = Instant::now();
+    ///         let res = next.run(ctx, msg, args);
+    ///
+    ///         println!("{} took {:?}", name, start.elapsed());
+    ///
+    ///         res
+    ///     }));
+    /// ```
+    ///
+    /// [`before`]: #method.before
+    /// [`Next::run`]: struct.Next.html#method.run
+    pub fn middleware<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&mut Context, &Message, Args, Next<'_>) -> CommandResult + Send + Sync + 'static,
+    {
+        self.middleware.push(Arc::new(f));
+
+        self
+    }
+
+    /// Sets the [`Metrics`] sink that is told about every command the
+    /// framework runs, including how long it took and whether it succeeded.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use serenity::prelude::*;
+    /// # struct Handler;
+    /// #
+    /// # impl EventHandler for Handler {}
+    /// # let mut client = Client::new("token", Handler).unwrap();
+    /// #
+    /// use serenity::framework::standard::{CommandMetrics, Metrics, StandardFramework};
+    ///
+    /// struct Log;
+    ///
+    /// impl Metrics for Log {
+    ///     fn command_finished(&self, data: &CommandMetrics<'_>) {
+    ///         println!("{} took {:?} (success: {})", data.name, data.duration, data.success);
+    ///     }
+    /// }
+    ///
+    /// client.with_framework(StandardFramework::new().metrics(Log));
+    /// ```
+    ///
+    /// [`Metrics`]: trait.Metrics.html
+    pub fn metrics<M>(mut self, metrics: M) -> Self
+    where
+        M: Metrics + 'static,
+    {
+        self.metrics = Some(Arc::new(metrics));
+
+        self
+    }
+
     /// Specify the function to be called prior to every command's execution.
     /// If that function returns true, the command will be executed.
     ///
@@ -518,7 +847,8 @@ impl StandardFramework {
     }
 
     /// Specify the function to be called after every command's execution.
-    /// Fourth argument exists if command returned an error which you can handle.
+    /// Fourth argument is how long the command took to execute.
+    /// Fifth argument is the command's result, holding the error if it returned one.
     ///
     /// # Examples
     ///
@@ -534,24 +864,65 @@ impl StandardFramework {
     /// use serenity::framework::StandardFramework;
     ///
     /// client.with_framework(StandardFramework::new()
-    ///     .after(|ctx, msg, cmd_name, error| {
+    ///     .after(|ctx, msg, cmd_name, elapsed, error| {
     ///         //  Print out an error if it happened
     ///         if let Err(why) = error {
-    ///             println!("Error in {}: {:?}", cmd_name, why);
+    ///             println!("Error in {} after {:?}: {:?}", cmd_name, elapsed, why);
     ///         }
     ///     }));
     /// ```
     pub fn after<F>(mut self, f: F) -> Self
     where
-        F: Fn(&mut Context, &Message, &str, Result<(), CommandError>) + Send + Sync + 'static,
+        F: Fn(&mut Context, &Message, &str, Duration, Result<(), CommandError>) + Send + Sync + 'static,
     {
         self.after = Some(Arc::new(f));
 
         self
     }
 
+    /// Specify the function to be called when a command panicked.
+    ///
+    /// The panic is caught, so the shard keeps running. Afterwards, the
+    /// [`after`] hook receives the panic as the command's error, which can be
+    /// told apart from other errors with `error.is::<HandlerPanic>()`.
+    ///
+    /// # Examples
+    ///
+    /// Apologising for a command that panicked:
+    ///
+    /// ```rust,no_run
+    /// # use serenity::prelude::*;
+    /// # struct Handler;
+    /// #
+    /// # impl EventHandler for Handler {}
+    /// # let mut client = Client::new("token", Handler).unwrap();
+    /// #
+    /// use serenity::framework::StandardFramework;
+    ///
+    /// client.with_framework(StandardFramework::new()
+    ///     .on_handler_panic(|ctx, msg, cmd_name, panic| {
+    ///         println!("{} panicked: {}", cmd_name, panic);
+    ///
+    ///         let _ = msg.channel_id.say(&ctx.http, "Something went wrong.");
+    ///     }));
+    /// ```
+    ///
+    /// [`after`]: #method.after
+    pub fn on_handler_panic<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&mut Context, &Message, &str, &HandlerPanic) + Send + Sync + 'static,
+    {
+        self.handler_panic = Some(Arc::new(f));
+
+        self
+    }
+
     /// Specify the function to be called if no command could be dispatched.
     ///
+    /// Besides the name that was not recognised, the function receives the
+    /// names of similar commands, closest first, if
+    /// [`Configuration::max_levenshtein_distance`] is set.
+    ///
     /// # Examples
     ///
     /// Using `unrecognised_command`:
@@ -566,13 +937,20 @@ impl StandardFramework {
     /// use serenity::framework::StandardFramework;
     ///
     /// client.with_framework(StandardFramework::new()
-    ///     .unrecognised_command(|_ctx, msg, unrecognised_command_name| {
+    ///     .configure(|c| c.max_levenshtein_distance(2))
+    ///     .unrecognised_command(|_ctx, msg, unrecognised_command_name, suggestions| {
     ///        println!("A user named {:?} tried to executute an unknown command: {}", msg.author.name, unrecognised_command_name);
+    ///
+    ///        if let Some(suggestion) = suggestions.first() {
+    ///            println!("Did they mean `{}`?", suggestion);
+    ///        }
     ///     }));
     /// ```
+    ///
+    /// [`Configuration::max_levenshtein_distance`]: struct.Configuration.html#method.max_levenshtein_distance
     pub fn unrecognised_command<F>(mut self, f: F) -> Self
     where
-        F: Fn(&mut Context, &Message, &str) + Send + Sync + 'static,
+        F: Fn(&mut Context, &Message, &str, &[&str]) + Send + Sync + 'static,
     {
         self.unrecognised_command = Some(Arc::new(f));
 
@@ -621,12 +999,37 @@ impl StandardFramework {
 }
 
 impl Framework for StandardFramework {
+    fn ready(&mut self, ready: &Ready) {
+        let mut config = self.config.write();
+
+        if config.on_mention_self {
+            config.on_mention = Some(ready.user.id.to_string());
+        }
+    }
+
     fn dispatch(&mut self, mut ctx: Context, msg: Message, threadpool: &ThreadPool) {
+        let config = Arc::clone(&self.config);
+        let config = config.read();
+
         let mut stream = UnicodeStream::new(&msg.content);
 
         stream.take_while(|s| s.is_whitespace());
 
-        let prefix = parse::prefix(&mut ctx, &msg, &mut stream, &self.config);
+        // Ignored messages are filtered out before any parsing work, and are
+        // only reported to the dispatch hook if they are addressed to the bot.
+        if let Some(error) = self.should_fail_common(&config, &ctx, &msg) {
+            if let Some(dispatch) = &self.dispatch {
+                let prefix = parse::prefix(&mut ctx, &msg, &mut stream, &config);
+
+                if prefix.is_some() || (config.no_dm_prefix && msg.is_private()) {
+                    dispatch(&mut ctx, &msg, error);
+                }
+            }
+
+            return;
+        }
+
+        let prefix = parse::prefix(&mut ctx, &msg, &mut stream, &config);
 
         if prefix.is_some() && stream.rest().is_empty() {
 
@@ -642,7 +1045,7 @@ impl Framework for StandardFramework {
             return;
         }
 
-        if prefix.is_none() && !(self.config.no_dm_prefix && msg.is_private()) {
+        if prefix.is_none() && !(config.no_dm_prefix && msg.is_private()) {
 
             if let Some(normal) = &self.normal_message {
                 let normal = Arc::clone(&normal);
@@ -656,21 +1059,12 @@ impl Framework for StandardFramework {
             return;
         }
 
-        if let Some(error) = self.should_fail_common(&msg) {
-
-            if let Some(dispatch) = &self.dispatch {
-                dispatch(&mut ctx, &msg, error);
-            }
-
-            return;
-        }
-
         let invocation = parse::command(
             &ctx,
             &msg,
             &mut stream,
             &self.groups,
-            &self.config,
+            &config,
             self.help.as_ref().map(|h| h.options.names),
         );
 
@@ -682,8 +1076,26 @@ impl Framework for StandardFramework {
                         let unrecognised_command = Arc::clone(&unrecognised_command);
                         let mut ctx = ctx.clone();
                         let msg = msg.clone();
+                        #[cfg(all(feature = "cache", feature = "http"))]
+                        let config = Arc::clone(&self.config);
+                        #[cfg(all(feature = "cache", feature = "http"))]
+                        let groups = self.groups.iter().map(|(group, _)| *group).collect::<Vec<_>>();
+
                         threadpool.execute(move || {
-                            unrecognised_command(&mut ctx, &msg, &unreg);
+                            #[cfg(all(feature = "cache", feature = "http"))]
+                            let suggestions = {
+                                let config = config.read();
+
+                                if config.max_levenshtein_distance > 0 {
+                                    suggest_commands(&groups, &config, &mut ctx, &msg, &unreg)
+                                } else {
+                                    Vec::new()
+                                }
+                            };
+                            #[cfg(not(all(feature = "cache", feature = "http")))]
+                            let suggestions = Vec::new();
+
+                            unrecognised_command(&mut ctx, &msg, &unreg, &suggestions);
                         });
                     }
                 }
@@ -710,11 +1122,13 @@ impl Framework for StandardFramework {
 
         match invoke {
             Invoke::Help(name) => {
-                let args = Args::new(stream.rest(), &self.config.delimiters);
+                let args = Args::new(stream.rest(), &config.delimiters);
 
                 let before = self.before.clone();
                 let after = self.after.clone();
-                let owners = self.config.owners.clone();
+                let on_panic = self.handler_panic.clone();
+                let metrics = self.metrics.clone();
+                let owners = config.owners.clone();
 
                 let groups = self.groups.iter().map(|(g, _)| *g).collect::<Vec<_>>();
 
@@ -730,18 +1144,41 @@ impl Framework for StandardFramework {
                         }
                     }
 
-                    let res = (help.fun)(&mut ctx, &msg, args, help.options, &groups, owners);
+                    let start = Instant::now();
+                    let res = run_caught(&mut ctx, &msg, name, on_panic, |ctx| {
+                        (help.fun)(ctx, &msg, args, help.options, &groups, owners)
+                    });
+                    let elapsed = start.elapsed();
+
+                    if let Some(metrics) = metrics {
+                        metrics.command_finished(&CommandMetrics {
+                            name,
+                            group: None,
+                            guild_id: msg.guild_id,
+                            channel_id: msg.channel_id,
+                            author_id: msg.author.id,
+                            duration: elapsed,
+                            success: res.is_ok(),
+                        });
+                    }
 
                     if let Some(after) = after {
-                        after(&mut ctx, &msg, name, res);
+                        after(&mut ctx, &msg, name, elapsed, res);
                     }
                 });
             }
             Invoke::Command { command, group } => {
+                let top_groups = self.groups.iter().map(|(g, _)| *g).collect::<Vec<_>>();
+                let mut groups = Vec::new();
+
+                if !ancestry(&top_groups, group, &mut groups) {
+                    groups.push(group);
+                }
+
                 let mut args = {
                     use std::borrow::Cow;
 
-                    let mut delims = Cow::Borrowed(&self.config.delimiters);
+                    let mut delims = Cow::Borrowed(&config.delimiters);
 
                     // If user has configured the command's own delimiters, use those instead.
                     if !command.options.delimiters.is_empty() {
@@ -764,8 +1201,31 @@ impl Framework for StandardFramework {
                 };
 
                 if let Some(error) =
-                    self.should_fail(&mut ctx, &msg, &mut args, &command.options, &group.options)
+                    self.should_fail(&config, &mut ctx, &msg, &mut args, &command.options, &groups)
                 {
+                    let is_argument_error = match error {
+                        DispatchError::NotEnoughArguments { .. }
+                        | DispatchError::TooManyArguments { .. } => true,
+                        _ => false,
+                    };
+
+                    if let (true, true, Some(usage)) =
+                        (config.usage_on_argument_error, is_argument_error, command.options.usage)
+                    {
+                        // Everything that was typed before the arguments, such as `!ban`.
+                        let invoked = msg.content[..msg.content.len() - stream.rest().len()].trim();
+                        let text = config
+                            .localise(&mut ctx, &msg, "usage_text", "Usage: `{invoked} {usage}`")
+                            .replace("{invoked}", invoked)
+                            .replace("{usage}", usage);
+                        let http = Arc::clone(&ctx.http);
+                        let channel_id = msg.channel_id;
+
+                        threadpool.execute(move || {
+                            let _ = channel_id.say(&http, text);
+                        });
+                    }
+
                     if let Some(dispatch) = &self.dispatch {
                         dispatch(&mut ctx, &msg, error);
                     }
@@ -775,19 +1235,69 @@ impl Framework for StandardFramework {
 
                 let before = self.before.clone();
                 let after = self.after.clone();
+                let on_panic = self.handler_panic.clone();
+                let metrics = self.metrics.clone();
+                let middleware = self.middleware.clone();
+                let buckets = Arc::clone(&self.buckets);
+                let bucket = bucket_of(&command.options, &groups);
+                let is_owner = config.owners.contains(&msg.author.id);
+                let typing_after = config.typing_after;
                 let msg = msg.clone();
                 let name = &command.options.names[0];
                 threadpool.execute(move || {
+                    let refund = || {
+                        let mut buckets = buckets.lock();
+
+                        if let Some(bucket) = bucket.and_then(|b| buckets.get_mut(b)) {
+                            if bucket.charge_only_on_success && !bucket.is_exempt(is_owner) {
+                                let key = bucket.limited_for.key(msg.guild_id, msg.channel_id, msg.author.id);
+                                bucket.refund(key);
+                            }
+                        }
+                    };
+
                     if let Some(before) = before {
                         if !before(&mut ctx, &msg, name) {
+                            refund();
+
                             return;
                         }
                     }
 
-                    let res = (command.fun)(&mut ctx, &msg, args);
+                    let typing = typing_after.map(|delay| {
+                        Typing::start_after(Arc::clone(&ctx.http), msg.channel_id.0, delay)
+                    });
+
+                    let start = Instant::now();
+                    let next = Next {
+                        middleware: &middleware,
+                        command,
+                    };
+                    let res = run_caught(&mut ctx, &msg, name, on_panic, |ctx| next.run(ctx, &msg, args));
+                    let elapsed = start.elapsed();
+
+                    if let Some(typing) = typing {
+                        typing.stop();
+                    }
+
+                    if res.is_err() {
+                        refund();
+                    }
+
+                    if let Some(metrics) = metrics {
+                        metrics.command_finished(&CommandMetrics {
+                            name,
+                            group: Some(group.name),
+                            guild_id: msg.guild_id,
+                            channel_id: msg.channel_id,
+                            author_id: msg.author.id,
+                            duration: elapsed,
+                            success: res.is_ok(),
+                        });
+                    }
 
                     if let Some(after) = after {
-                        after(&mut ctx, &msg, name, res);
+                        after(&mut ctx, &msg, name, elapsed, res);
                     }
                 });
             }
@@ -795,6 +1305,166 @@ impl Framework for StandardFramework {
     }
 }
 
+/// Runs a command, turning a panic into an error after passing it to the
+/// `on_panic` hook.
+fn run_caught<F>(
+    ctx: &mut Context,
+    msg: &Message,
+    name: &str,
+    on_panic: Option<Arc<HandlerPanicHook>>,
+    f: F,
+) -> CommandResult
+where
+    F: FnOnce(&mut Context) -> CommandResult,
+{
+    let shard_id = ctx.shard_id;
+
+    match handler_panic::catch(Some(&EventType::MessageCreate), shard_id, || f(ctx)) {
+        Ok(res) => res,
+        Err(panic) => {
+            if let Some(on_panic) = on_panic {
+                on_panic(ctx, msg, name, &panic);
+            }
+
+            Err(CommandError::new(panic))
+        },
+    }
+}
+
+/// Finds the names of commands similar to `name`, closest first, that the
+/// author of `msg` would be allowed to invoke.
+#[cfg(all(feature = "cache", feature = "http"))]
+fn suggest_commands(
+    groups: &[&'static CommandGroup],
+    config: &Configuration,
+    ctx: &mut Context,
+    msg: &Message,
+    name: &str,
+) -> Vec<&'static str> {
+    fn collect(
+        group: &'static CommandGroup,
+        ancestors: &mut Vec<&'static CommandGroup>,
+        config: &Configuration,
+        ctx: &mut Context,
+        msg: &Message,
+        name: &str,
+        found: &mut Vec<(usize, &'static str)>,
+    ) {
+        if parse::check_discrepancy(ctx, msg, config, &group.options).is_err() {
+            return;
+        }
+
+        ancestors.push(group);
+
+        for command in group.commands {
+            let options = command.options;
+
+            if !options.help_available {
+                continue;
+            }
+
+            let distance = options.names
+                .iter()
+                .map(|n| help_commands::levenshtein_distance(name, n))
+                .min();
+
+            let distance = match distance {
+                Some(d) if d <= config.max_levenshtein_distance => d,
+                _ => continue,
+            };
+
+            if parse::check_discrepancy(ctx, msg, config, &options).is_err() {
+                continue;
+            }
+
+            if help_commands::passes_help_checks(ctx, msg, ancestors, options) {
+                found.push((distance, options.names[0]));
+            }
+        }
+
+        for sub_group in group.sub_groups {
+            collect(sub_group, ancestors, config, ctx, msg, name, found);
+        }
+
+        ancestors.pop();
+    }
+
+    let mut found = Vec::new();
+
+    for group in groups {
+        collect(group, &mut Vec::new(), config, ctx, msg, name, &mut found);
+    }
+
+    found.sort_by_key(|&(distance, _)| distance);
+
+    let mut names: Vec<&'static str> = found.into_iter().map(|(_, n)| n).collect();
+    let mut seen = HashSet::new();
+    names.retain(|n| seen.insert(*n));
+
+    names
+}
+
+/// Fills `path` with the groups leading up to, and including, `target`.
+///
+/// Returns whether `target` was found.
+fn ancestry(
+    groups: &[&'static CommandGroup],
+    target: &'static CommandGroup,
+    path: &mut Vec<&'static CommandGroup>,
+) -> bool {
+    for group in groups {
+        path.push(*group);
+
+        if std::ptr::eq(*group, target) || ancestry(group.sub_groups, target, path) {
+            return true;
+        }
+
+        path.pop();
+    }
+
+    false
+}
+
+/// Checks the message against the configured block and allow lists.
+#[cfg_attr(not(feature = "cache"), allow(unused_variables))]
+fn blocked(config: &Configuration, ctx: &Context, msg: &Message) -> Option<DispatchError> {
+    if config.blocked_users.contains(&msg.author.id)
+        || (!config.allowed_users.is_empty() && !config.allowed_users.contains(&msg.author.id))
+    {
+        return Some(DispatchError::BlockedUser);
+    }
+
+    if let Some(guild_id) = msg.guild_id {
+        if config.blocked_guilds.contains(&guild_id)
+            || (!config.allowed_guilds.is_empty() && !config.allowed_guilds.contains(&guild_id))
+        {
+            return Some(DispatchError::BlockedGuild);
+        }
+
+        #[cfg(feature = "cache")]
+        {
+            if let Some(guild) = guild_id.to_guild_cached(&ctx.cache) {
+                if config.blocked_users.contains(&guild.with(|g| g.owner_id)) {
+                    return Some(DispatchError::BlockedGuild);
+                }
+            }
+        }
+    }
+
+    if config.blocked_channels.contains(&msg.channel_id)
+        || (!config.allowed_channels.is_empty() && !config.allowed_channels.contains(&msg.channel_id))
+    {
+        return Some(DispatchError::BlockedChannel);
+    }
+
+    None
+}
+
+/// The bucket of the command, or else the one of its nearest group that has one.
+fn bucket_of(command: &CommandOptions, groups: &[&'static CommandGroup]) -> Option<&'static str> {
+    command.bucket.or_else(|| groups.iter().rev().find_map(|g| g.options.bucket))
+}
+
 pub trait CommonOptions {
     fn required_permissions(&self) -> &Permissions;
     fn allowed_roles(&self) -> &'static [&'static str];
@@ -69,9 +69,13 @@ impl ParseMap for CommandMap {
     }
 }
 
+pub type GroupStorage = (&'static CommandGroup, Arc<GroupMap>, Arc<CommandMap>);
+
 #[derive(Debug, Default)]
 pub struct GroupMap {
-    groups: HashMap<&'static str, (&'static CommandGroup, Arc<GroupMap>, Arc<CommandMap>)>,
+    groups: HashMap<&'static str, GroupStorage>,
+    /// Groups without prefixes, whose commands are reachable through the parent's prefix.
+    prefixless: Vec<GroupStorage>,
     min_length: usize,
     max_length: usize,
 }
@@ -84,6 +88,12 @@ impl GroupMap {
             let subgroups_map = Arc::new(Self::new(&group.sub_groups));
             let commands_map = Arc::new(CommandMap::new(&group.commands));
 
+            if group.options.prefixes.is_empty() {
+                map.prefixless.push((*group, subgroups_map, commands_map));
+
+                continue;
+            }
+
             for prefix in group.options.prefixes {
                 let len = prefix.chars().count();
                 map.min_length = std::cmp::min(len, map.min_length);
@@ -95,10 +105,15 @@ impl GroupMap {
 
         map
     }
+
+    #[inline]
+    pub fn prefixless(&self) -> &[GroupStorage] {
+        &self.prefixless
+    }
 }
 
 impl ParseMap for GroupMap {
-    type Storage = (&'static CommandGroup, Arc<GroupMap>, Arc<CommandMap>);
+    type Storage = GroupStorage;
 
     #[inline]
     fn min_length(&self) -> usize {
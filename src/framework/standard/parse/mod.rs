@@ -5,7 +5,7 @@ use uwl::{StrExt, UnicodeStream};
 
 pub mod map;
 
-use map::{CommandMap, GroupMap, ParseMap};
+use map::{CommandMap, GroupMap, GroupStorage, ParseMap};
 
 use std::borrow::Cow;
 
@@ -62,7 +62,7 @@ fn find_prefix<'a>(
     let try_match = |prefix: &str| {
         let peeked = stream.peek_for(prefix.chars().count());
 
-        if prefix == peeked {
+        if to_lowercase(config, prefix) == to_lowercase(config, peeked) {
             Some(peeked)
         } else {
             None
@@ -77,6 +77,12 @@ fn find_prefix<'a>(
         }
     }
 
+    if let Some(f) = &config.dynamic_prefix_list {
+        if let Some(p) = f(ctx, msg).iter().find_map(|p| try_match(p)) {
+            return Some(p);
+        }
+    }
+
     config.prefixes.iter().find_map(|p| try_match(&p))
 }
 
@@ -84,13 +90,15 @@ fn find_prefix<'a>(
 ///
 /// The "prefix" may be one of the following:
 /// - A mention (`<@id>`/`<@!id>`)
-/// - A dynamically constructed prefix ([`Configuration::dynamic_prefix`])
+/// - A dynamically constructed prefix ([`Configuration::dynamic_prefix`] or
+///   [`Configuration::dynamic_prefix_list`])
 /// - A static prefix ([`Configuration::prefix`])
 /// - Nothing
 ///
 /// In all cases, whitespace after the prefix is cleared.
 ///
 /// [`Configuration::dynamic_prefix`]: ../struct.Configuration.html#method.dynamic_prefix
+/// [`Configuration::dynamic_prefix_list`]: ../struct.Configuration.html#method.dynamic_prefix_list
 /// [`Configuration::prefix`]: ../struct.Configuration.html#method.prefix
 pub fn prefix<'a>(
     ctx: &mut Context,
@@ -118,7 +126,7 @@ pub fn prefix<'a>(
 }
 
 /// Checked per valid group or command in the message.
-fn check_discrepancy(
+pub(crate) fn check_discrepancy(
     ctx: &Context,
     msg: &Message,
     config: &Configuration,
@@ -151,9 +159,10 @@ fn check_discrepancy(
             if !perms.contains(*options.required_permissions())
                 && !(options.owner_privilege() && config.owners.contains(&msg.author.id))
             {
-                return Err(DispatchError::LackingPermissions(
-                    *options.required_permissions(),
-                ));
+                let mut missing = *options.required_permissions();
+                missing.remove(perms);
+
+                return Err(DispatchError::LackingPermissions(missing));
             }
 
             if let Some(member) = guild.members.get(&msg.author.id) {
@@ -240,7 +249,7 @@ fn parse_group(
     msg: &Message,
     config: &Configuration,
     map: &GroupMap,
-) -> Result<(&'static CommandGroup, Arc<CommandMap>), ParseError> {
+) -> Result<GroupStorage, ParseError> {
     let (n, o) = try_parse(stream, map, config.by_space, ToString::to_string);
 
     if let Some((group, map, commands)) = o {
@@ -253,11 +262,11 @@ fn parse_group(
         check_discrepancy(ctx, msg, config, &group.options)?;
 
         if map.is_empty() {
-            return Ok((group, commands));
+            return Ok((group, map, commands));
         }
 
         return match parse_group(stream, ctx, msg, config, &map) {
-            Err(ParseError::UnrecognisedCommand(None)) => Ok((group, commands)),
+            Err(ParseError::UnrecognisedCommand(None)) => Ok((group, map, commands)),
             res => res,
         };
     }
@@ -265,6 +274,40 @@ fn parse_group(
     Err(ParseError::UnrecognisedCommand(None))
 }
 
+/// Parse a command of the `group`, or of any of its prefixless sub-groups, however deeply nested.
+fn parse_group_command(
+    stream: &mut UnicodeStream<'_>,
+    ctx: &Context,
+    msg: &Message,
+    config: &Configuration,
+    map: &CommandMap,
+    prefixless: &[GroupStorage],
+    group: &'static CommandGroup,
+) -> Result<Invoke, ParseError> {
+    let start = stream.offset();
+
+    let err = match parse_cmd(stream, ctx, msg, config, map) {
+        Ok(command) => return Ok(Invoke::Command { group, command }),
+        Err(err) => err,
+    };
+
+    for (sub_group, sub_groups, commands) in prefixless {
+        stream.set(start);
+
+        let res = parse_group_command(stream, ctx, msg, config, commands, sub_groups.prefixless(), sub_group);
+
+        if res.is_ok() {
+            check_discrepancy(ctx, msg, config, &sub_group.options)?;
+
+            return res;
+        }
+    }
+
+    stream.set(start);
+
+    Err(err)
+}
+
 #[inline]
 fn handle_command(
     stream: &mut UnicodeStream<'_>,
@@ -272,17 +315,39 @@ fn handle_command(
     msg: &Message,
     config: &Configuration,
     map: &CommandMap,
+    prefixless: &[GroupStorage],
     group: &'static CommandGroup,
 ) -> Result<Invoke, ParseError> {
-    match parse_cmd(stream, ctx, msg, config, map) {
-        Ok(command) => Ok(Invoke::Command { group, command }),
-        Err(err) => match group.options.default_command {
-            Some(command) => Ok(Invoke::Command { group, command }),
+    match parse_group_command(stream, ctx, msg, config, map, prefixless, group) {
+        Ok(invoke) => Ok(invoke),
+        Err(err) => match default_command(group, prefixless) {
+            Some((sub_group, command)) => {
+                if !std::ptr::eq(sub_group, group) {
+                    check_discrepancy(ctx, msg, config, &sub_group.options)?;
+                }
+
+                Ok(Invoke::Command { group: sub_group, command })
+            },
             None => Err(err),
         },
     }
 }
 
+/// Find the default command of the `group`, or else of the first of its prefixless sub-groups,
+/// however deeply nested, to have one.
+fn default_command(
+    group: &'static CommandGroup,
+    prefixless: &[GroupStorage],
+) -> Option<(&'static CommandGroup, &'static Command)> {
+    if let Some(command) = group.options.default_command {
+        return Some((group, command));
+    }
+
+    prefixless
+        .iter()
+        .find_map(|(sub_group, sub_groups, _)| default_command(sub_group, sub_groups.prefixless()))
+}
+
 #[inline]
 fn handle_group(
     stream: &mut UnicodeStream<'_>,
@@ -291,8 +356,9 @@ fn handle_group(
     config: &Configuration,
     map: &GroupMap,
 ) -> Result<Invoke, ParseError> {
-    parse_group(stream, ctx, msg, config, map)
-        .and_then(|(group, map)| handle_command(stream, ctx, msg, config, &map, group))
+    parse_group(stream, ctx, msg, config, map).and_then(|(group, sub_groups, commands)| {
+        handle_command(stream, ctx, msg, config, &commands, sub_groups.prefixless(), group)
+    })
 }
 
 #[derive(Debug)]
@@ -363,7 +429,7 @@ pub fn command(
                     return res;
                 }
 
-                let res = handle_command(stream, ctx, msg, config, commands, group);
+                let res = handle_command(stream, ctx, msg, config, commands, subgroups.prefixless(), group);
 
                 if res.is_ok() {
                     check_discrepancy(ctx, msg, config, &group.options)?;
@@ -387,3 +453,113 @@ pub enum Invoke {
     },
     Help(&'static str),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::permissions::Permissions;
+
+    fn noop(_: &mut Context, _: &Message, _: Args) -> CommandResult {
+        Ok(())
+    }
+
+    static COMMAND_OPTIONS: CommandOptions = CommandOptions {
+        checks: &[],
+        bucket: None,
+        names: &["noop"],
+        desc: None,
+        delimiters: &[],
+        usage: None,
+        examples: &[],
+        min_args: None,
+        max_args: None,
+        allowed_roles: &[],
+        required_permissions: Permissions { bits: 0 },
+        help_available: true,
+        only_in: OnlyIn::None,
+        owners_only: false,
+        owner_privilege: true,
+        nsfw_only: false,
+        sub_commands: &[],
+    };
+
+    static ROOT_DEFAULT: Command = Command { fun: noop, options: &COMMAND_OPTIONS };
+    static NESTED_DEFAULT: Command = Command { fun: noop, options: &COMMAND_OPTIONS };
+
+    const fn group_options(
+        prefixes: &'static [&'static str],
+        default_command: Option<&'static Command>,
+    ) -> GroupOptions {
+        GroupOptions {
+            prefixes,
+            only_in: OnlyIn::None,
+            owners_only: false,
+            owner_privilege: true,
+            help_available: true,
+            allowed_roles: &[],
+            required_permissions: Permissions { bits: 0 },
+            checks: &[],
+            bucket: None,
+            default_command,
+            description: None,
+        }
+    }
+
+    const fn group(
+        name: &'static str,
+        options: &'static GroupOptions,
+        sub_groups: &'static [&'static CommandGroup],
+    ) -> CommandGroup {
+        CommandGroup {
+            help_name: name,
+            name,
+            options,
+            commands: &[],
+            sub_groups,
+        }
+    }
+
+    // `settings` -> (prefixless) `general` -> (prefixless) `display`, which
+    // alone has a default command.
+    static DISPLAY_OPTIONS: GroupOptions = group_options(&[], Some(&NESTED_DEFAULT));
+    static DISPLAY: CommandGroup = group("display", &DISPLAY_OPTIONS, &[]);
+    static GENERAL_OPTIONS: GroupOptions = group_options(&[], None);
+    static GENERAL: CommandGroup = group("general", &GENERAL_OPTIONS, &[&DISPLAY]);
+    static SETTINGS_OPTIONS: GroupOptions = group_options(&["settings"], None);
+    static SETTINGS: CommandGroup = group("settings", &SETTINGS_OPTIONS, &[&GENERAL]);
+
+    // As above, but with a default command of its own.
+    static CONFIG_OPTIONS: GroupOptions = group_options(&["config"], Some(&ROOT_DEFAULT));
+    static CONFIG: CommandGroup = group("config", &CONFIG_OPTIONS, &[&GENERAL]);
+
+    // A prefixed sub-group's default command is only reachable through its prefix.
+    static PREFIXED_OPTIONS: GroupOptions = group_options(&["display"], Some(&NESTED_DEFAULT));
+    static PREFIXED: CommandGroup = group("display", &PREFIXED_OPTIONS, &[]);
+    static OPTIONS_OPTIONS: GroupOptions = group_options(&["options"], None);
+    static OPTIONS: CommandGroup = group("options", &OPTIONS_OPTIONS, &[&PREFIXED]);
+
+    #[test]
+    fn default_command_of_prefixless_sub_group() {
+        let map = GroupMap::new(SETTINGS.sub_groups);
+        let (group, command) = default_command(&SETTINGS, map.prefixless()).unwrap();
+
+        assert!(std::ptr::eq(group, &DISPLAY));
+        assert!(std::ptr::eq(command, &NESTED_DEFAULT));
+    }
+
+    #[test]
+    fn default_command_of_group_first() {
+        let map = GroupMap::new(CONFIG.sub_groups);
+        let (group, command) = default_command(&CONFIG, map.prefixless()).unwrap();
+
+        assert!(std::ptr::eq(group, &CONFIG));
+        assert!(std::ptr::eq(command, &ROOT_DEFAULT));
+    }
+
+    #[test]
+    fn default_command_of_prefixed_sub_group() {
+        let map = GroupMap::new(OPTIONS.sub_groups);
+
+        assert!(default_command(&OPTIONS, map.prefixless()).is_none());
+    }
+}
@@ -1,9 +1,11 @@
 use chrono::Utc;
 use crate::client::Context;
-use crate::model::id::{ChannelId, GuildId, UserId};
+use crate::model::{channel::Message, id::{ChannelId, GuildId, UserId}};
 use std::collections::HashMap;
+use std::sync::Arc;
 
 type Check = dyn Fn(&mut Context, Option<GuildId>, ChannelId, UserId) -> bool + Send + Sync + 'static;
+type DelayAction = dyn Fn(&mut Context, &Message, i64) + Send + Sync + 'static;
 
 pub(crate) struct Ratelimit {
     pub delay: i64,
@@ -13,21 +15,64 @@ pub(crate) struct Ratelimit {
 #[derive(Default)]
 pub(crate) struct MemberRatelimit {
     pub last_time: i64,
+    pub previous_time: Option<i64>,
     pub set_time: i64,
     pub tickets: i32,
 }
 
+/// The scope a bucket's ratelimit is tracked in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LimitedFor {
+    /// Every user has their own ratelimit.
+    User,
+    /// Every channel has its own ratelimit, shared by all users in it.
+    Channel,
+    /// Every guild has its own ratelimit, shared by all users in it.
+    ///
+    /// Outside of guilds, the channel is used instead.
+    Guild,
+    /// A single ratelimit is shared by everyone.
+    Global,
+}
+
+impl Default for LimitedFor {
+    fn default() -> Self {
+        LimitedFor::User
+    }
+}
+
+impl LimitedFor {
+    /// Returns the key this scope tracks the ratelimit under.
+    pub(crate) fn key(self, guild_id: Option<GuildId>, channel_id: ChannelId, user_id: UserId) -> u64 {
+        match self {
+            LimitedFor::User => user_id.0,
+            LimitedFor::Channel => channel_id.0,
+            LimitedFor::Guild => guild_id.map_or(channel_id.0, |id| id.0),
+            LimitedFor::Global => 0,
+        }
+    }
+}
+
 pub(crate) struct Bucket {
     pub ratelimit: Ratelimit,
-    pub users: HashMap<u64, MemberRatelimit>,
-    pub check: Option<Box<Check>>,
+    pub limited_for: LimitedFor,
+    pub exempt_owners: bool,
+    pub charge_only_on_success: bool,
+    pub targets: HashMap<u64, MemberRatelimit>,
+    pub check: Option<Arc<Check>>,
+    pub delay_action: Option<Arc<DelayAction>>,
 }
 
 impl Bucket {
-    pub fn take(&mut self, user_id: u64) -> i64 {
+    /// Whether the invoker is exempt from the bucket's ratelimit.
+    pub fn is_exempt(&self, is_owner: bool) -> bool {
+        self.exempt_owners && is_owner
+    }
+
+    pub fn take(&mut self, key: u64) -> i64 {
         let time = Utc::now().timestamp();
-        let user = self.users
-            .entry(user_id)
+        let user = self.targets
+            .entry(key)
             .or_insert_with(MemberRatelimit::default);
 
         if let Some((timespan, limit)) = self.ratelimit.limit {
@@ -45,11 +90,24 @@ impl Bucket {
             (user.last_time + self.ratelimit.delay) - time
         } else {
             user.tickets += 1;
+            user.previous_time = Some(user.last_time);
             user.last_time = time;
 
             0
         }
     }
+
+    /// Gives back the last ticket taken under `key`, restoring both the ticket
+    /// count and the time of the invocation before it, so that neither the
+    /// limit nor the delay count the refunded invocation.
+    pub fn refund(&mut self, key: u64) {
+        if let Some(user) = self.targets.get_mut(&key) {
+            if let Some(time) = user.previous_time.take() {
+                user.last_time = time;
+                user.tickets = (user.tickets - 1).max(0);
+            }
+        }
+    }
 }
 
 #[derive(Default)]
@@ -57,7 +115,11 @@ pub struct BucketBuilder {
     pub(crate) delay: i64,
     pub(crate) time_span: i64,
     pub(crate) limit: i32,
+    pub(crate) limited_for: LimitedFor,
+    pub(crate) exempt_owners: bool,
+    pub(crate) charge_only_on_success: bool,
     pub(crate) check: Option<Box<Check>>,
+    pub(crate) delay_action: Option<Box<DelayAction>>,
 }
 
 impl BucketBuilder {
@@ -93,6 +155,45 @@ impl BucketBuilder {
         self
     }
 
+    /// The scope the ratelimit applies to, such as per user or per guild.
+    ///
+    /// **Note**: Defaults to [`LimitedFor::User`].
+    ///
+    /// [`LimitedFor::User`]: enum.LimitedFor.html#variant.User
+    #[inline]
+    pub fn limit_for(&mut self, limited_for: LimitedFor) -> &mut Self {
+        self.limited_for = limited_for;
+
+        self
+    }
+
+    /// Whether the bot's owners are exempt from the bucket's ratelimit.
+    ///
+    /// **Note**: Defaults to `false`.
+    #[inline]
+    pub fn exempt_owners(&mut self, exempt: bool) -> &mut Self {
+        self.exempt_owners = exempt;
+
+        self
+    }
+
+    /// Whether a command only uses up its invocation when it returns `Ok`.
+    ///
+    /// If enabled, failed invocations, such as those given bad arguments, and
+    /// invocations cancelled by the framework's `before` hook are refunded,
+    /// counting neither towards the [`limit`] nor the [`delay`].
+    ///
+    /// **Note**: Defaults to `false`.
+    ///
+    /// [`delay`]: #method.delay
+    /// [`limit`]: #method.limit
+    #[inline]
+    pub fn charge_only_on_success(&mut self, b: bool) -> &mut Self {
+        self.charge_only_on_success = b;
+
+        self
+    }
+
     /// Middleware confirming (or denying) that the bucket is eligible to apply.
     /// For instance, to limit the bucket to just one user.
     #[inline]
@@ -104,4 +205,134 @@ impl BucketBuilder {
 
         self
     }
+
+    /// Function called when a command is delayed by this bucket, for instance to
+    /// tell the user how long to wait.
+    ///
+    /// The third argument is the remaining time, in seconds, until the command
+    /// can be used again.
+    ///
+    /// **Note**: The command's dispatch is still reported as
+    /// [`DispatchError::Ratelimited`].
+    ///
+    /// [`DispatchError::Ratelimited`]: ../enum.DispatchError.html#variant.Ratelimited
+    #[inline]
+    pub fn delay_action<F>(&mut self, f: F) -> &mut Self
+    where
+        F: Fn(&mut Context, &Message, i64) + Send + Sync + 'static
+    {
+        self.delay_action = Some(Box::new(f));
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Bucket, LimitedFor, Ratelimit};
+    use crate::model::id::{ChannelId, GuildId, UserId};
+    use std::collections::HashMap;
+
+    fn bucket(delay: i64, time_span: i64, limit: i32) -> Bucket {
+        Bucket {
+            ratelimit: Ratelimit {
+                delay,
+                limit: Some((time_span, limit)),
+            },
+            limited_for: LimitedFor::User,
+            exempt_owners: false,
+            charge_only_on_success: true,
+            targets: HashMap::new(),
+            check: None,
+            delay_action: None,
+        }
+    }
+
+    #[test]
+    fn test_limited_for_key() {
+        let guild_id = Some(GuildId(1));
+        let channel_id = ChannelId(2);
+        let user_id = UserId(3);
+
+        assert_eq!(LimitedFor::User.key(guild_id, channel_id, user_id), 3);
+        assert_eq!(LimitedFor::Channel.key(guild_id, channel_id, user_id), 2);
+        assert_eq!(LimitedFor::Guild.key(guild_id, channel_id, user_id), 1);
+        assert_eq!(LimitedFor::Guild.key(None, channel_id, user_id), 2);
+        assert_eq!(LimitedFor::Global.key(guild_id, channel_id, user_id), 0);
+        assert_eq!(LimitedFor::Global.key(None, ChannelId(4), UserId(5)), 0);
+    }
+
+    #[test]
+    fn test_exempt_owners() {
+        let mut bucket = bucket(30, 0, 0);
+
+        assert!(!bucket.is_exempt(true));
+        assert!(!bucket.is_exempt(false));
+
+        bucket.exempt_owners = true;
+        assert!(bucket.is_exempt(true));
+        assert!(!bucket.is_exempt(false));
+    }
+
+    #[test]
+    fn test_take_delay() {
+        let mut bucket = bucket(30, 0, 0);
+
+        assert_eq!(bucket.take(1), 0);
+
+        let remaining = bucket.take(1);
+        assert!(remaining > 0 && remaining <= 30);
+
+        // Other keys are tracked separately.
+        assert_eq!(bucket.take(2), 0);
+    }
+
+    #[test]
+    fn test_take_limit() {
+        let mut bucket = bucket(0, 60, 1);
+
+        // The time span starts once the limit is first exceeded.
+        assert_eq!(bucket.take(1), 0);
+        assert_eq!(bucket.take(1), 0);
+
+        let remaining = bucket.take(1);
+        assert!(remaining > 0 && remaining <= 60);
+        assert_eq!(bucket.take(2), 0);
+    }
+
+    #[test]
+    fn test_refund_restores_delay() {
+        let mut bucket = bucket(30, 0, 0);
+
+        assert_eq!(bucket.take(1), 0);
+        assert!(bucket.take(1) > 0);
+
+        bucket.refund(1);
+        assert_eq!(bucket.take(1), 0);
+        assert!(bucket.take(1) > 0);
+    }
+
+    #[test]
+    fn test_refund_restores_tickets() {
+        let mut bucket = bucket(0, 60, 2);
+
+        // The time span starts once the limit is first exceeded.
+        assert_eq!(bucket.take(1), 0);
+        assert_eq!(bucket.take(1), 0);
+        assert_eq!(bucket.take(1), 0);
+        assert_eq!(bucket.take(1), 0);
+        assert!(bucket.take(1) > 0);
+
+        bucket.refund(1);
+        assert_eq!(bucket.take(1), 0);
+        assert!(bucket.take(1) > 0);
+    }
+
+    #[test]
+    fn test_refund_unknown_key() {
+        let mut bucket = bucket(30, 0, 0);
+
+        bucket.refund(1);
+        assert!(bucket.targets.is_empty());
+    }
 }
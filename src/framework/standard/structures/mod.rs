@@ -1,6 +1,9 @@
 use std::{
+    any::Any,
+    borrow::Cow,
     collections::HashSet,
     fmt,
+    sync::Arc,
 };
 use crate::client::Context;
 use crate::model::{
@@ -41,8 +44,8 @@ pub struct CommandOptions {
     pub delimiters: &'static [&'static str],
     /// Command usage schema, used by other commands.
     pub usage: Option<&'static str>,
-    /// Example arguments, used by other commands.
-    pub example: Option<&'static str>,
+    /// Examples of arguments, used by other commands.
+    pub examples: &'static [&'static str],
     /// Minimum amount of arguments that should be passed.
     pub min_args: Option<u16>,
     /// Maximum amount of arguments that can be passed.
@@ -59,6 +62,8 @@ pub struct CommandOptions {
     pub owners_only: bool,
     /// Whether the command treats owners as normal users.
     pub owner_privilege: bool,
+    /// Whether the command can only be used in NSFW channels.
+    pub nsfw_only: bool,
     /// Other commands belonging to this command.
     pub sub_commands: &'static [&'static Command],
 }
@@ -73,17 +78,106 @@ pub struct GroupOptions {
     pub allowed_roles: &'static [&'static str],
     pub required_permissions: Permissions,
     pub checks: &'static [&'static Check],
+    /// The bucket of the group's commands, including those of its sub-groups,
+    /// that do not have a bucket of their own.
+    pub bucket: Option<&'static str>,
     pub default_command: Option<&'static Command>,
     pub description: Option<&'static str>,
 }
 
-#[derive(Debug, Clone)]
-pub struct CommandError(pub String);
+/// An error returned by a command.
+///
+/// Any displayable value can be converted into a `CommandError`, keeping its
+/// displayed message. To also retain the value itself, so that a user-defined
+/// error type can be recovered in the [`after`] hook with [`downcast_ref`],
+/// create the error with [`new`] instead.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity::framework::standard::CommandError;
+/// use std::fmt;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum BotError {
+///     NotPlaying,
+/// }
+///
+/// impl fmt::Display for BotError {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         f.write_str("nothing is playing")
+///     }
+/// }
+///
+/// let error = CommandError::new(BotError::NotPlaying);
+///
+/// assert_eq!(error.message, "nothing is playing");
+/// assert_eq!(error.downcast_ref::<BotError>(), Some(&BotError::NotPlaying));
+/// assert!(!error.is::<String>());
+///
+/// let error = CommandError::from(BotError::NotPlaying);
+///
+/// assert_eq!(error.message, "nothing is playing");
+/// assert!(!error.is::<BotError>());
+/// ```
+///
+/// [`after`]: ../struct.StandardFramework.html#method.after
+/// [`downcast_ref`]: #method.downcast_ref
+/// [`new`]: #method.new
+#[derive(Clone)]
+pub struct CommandError {
+    /// The displayed message of the error.
+    pub message: String,
+    error: Option<Arc<dyn Any + Send + Sync>>,
+}
+
+impl CommandError {
+    /// Creates an error from `error`, retaining it for [`downcast_ref`].
+    ///
+    /// Commands can return it with `.map_err(CommandError::new)?`.
+    ///
+    /// [`downcast_ref`]: #method.downcast_ref
+    pub fn new<E: fmt::Display + Send + Sync + 'static>(error: E) -> Self {
+        CommandError {
+            message: error.to_string(),
+            error: Some(Arc::new(error)),
+        }
+    }
+
+    /// Returns a reference to the original error if it was retained by
+    /// [`new`] and is of type `E`.
+    ///
+    /// [`new`]: #method.new
+    #[inline]
+    pub fn downcast_ref<E: Any>(&self) -> Option<&E> {
+        self.error.as_ref().and_then(|error| error.downcast_ref::<E>())
+    }
+
+    /// Whether the original error was retained by [`new`] and is of type
+    /// `E`.
+    ///
+    /// [`new`]: #method.new
+    #[inline]
+    pub fn is<E: Any>(&self) -> bool {
+        self.error.as_ref().map_or(false, |error| error.is::<E>())
+    }
+}
+
+impl fmt::Debug for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CommandError")
+            .field(&self.message)
+            .finish()
+    }
+}
 
 impl<T: fmt::Display> From<T> for CommandError {
     #[inline]
     fn from(d: T) -> Self {
-        CommandError(d.to_string())
+        CommandError {
+            message: d.to_string(),
+            error: None,
+        }
     }
 }
 
@@ -145,11 +239,14 @@ impl PartialEq for HelpCommand {
 /// a command which the user or command fails to meet following criteria :
 /// Lacking required permissions to execute the command.
 /// Lacking required roles to execute the command.
+/// Failing the command's checks.
 /// The command can't be used in the current channel (as in `DM only` or `guild only`).
 #[derive(Copy, Clone, Debug, PartialOrd, Ord, Eq, PartialEq)]
 pub enum HelpBehaviour {
     /// The command will be displayed, hence nothing will be done.
     Nothing,
+    /// Marks a command as locked by applying `{command_name} 🔒`.
+    Lock,
     /// Strikes a command by applying `~~{command_name}~~`.
     Strike,
     /// Does not list a command in the help-menu.
@@ -158,63 +255,78 @@ pub enum HelpBehaviour {
     __Nonexhaustive,
 }
 
+/// Describes the order in which the help-command lists groups and their sub-groups.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum HelpOrder {
+    /// Groups are listed in the order they were declared.
+    Declaration,
+    /// Groups are listed alphabetically by their help name.
+    Alphabetical,
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct HelpOptions {
     /// Which names should the help command use for dispatching.
     /// Defaults to `["help"]`
     pub names: &'static [&'static str],
     /// Suggests a command's name.
-    pub suggestion_text: &'static str,
+    pub suggestion_text: Cow<'static, str>,
     /// If no help is available, this text will be displayed.
-    pub no_help_available_text: &'static str,
+    pub no_help_available_text: Cow<'static, str>,
     /// How to use a command, `{usage_label}: {command_name} {args}`
-    pub usage_label: &'static str,
+    pub usage_label: Cow<'static, str>,
     /// Actual sample label, `{usage_sample_label}: {command_name} {args}`
-    pub usage_sample_label: &'static str,
+    pub usage_sample_label: Cow<'static, str>,
     /// Text labeling ungrouped commands, `{ungrouped_label}: ...`
-    pub ungrouped_label: &'static str,
+    pub ungrouped_label: Cow<'static, str>,
     /// Text labeling the start of the description.
-    pub description_label: &'static str,
+    pub description_label: Cow<'static, str>,
     /// Text labeling grouped commands, `{grouped_label} {group_name}: ...`
-    pub grouped_label: &'static str,
+    pub grouped_label: Cow<'static, str>,
     /// Text labeling a command's alternative names (aliases).
-    pub aliases_label: &'static str,
+    pub aliases_label: Cow<'static, str>,
     /// Text specifying that a command is only usable in a guild.
-    pub guild_only_text: &'static str,
+    pub guild_only_text: Cow<'static, str>,
     /// Text labeling a command's names of checks.
-    pub checks_label: &'static str,
+    pub checks_label: Cow<'static, str>,
     /// Text specifying that a command is only usable in via DM.
-    pub dm_only_text: &'static str,
+    pub dm_only_text: Cow<'static, str>,
     /// Text specifying that a command can be used via DM and in guilds.
-    pub dm_and_guild_text: &'static str,
+    pub dm_and_guild_text: Cow<'static, str>,
     /// Text expressing that a command is available.
-    pub available_text: &'static str,
+    pub available_text: Cow<'static, str>,
     /// Error-message once a command could not be found.
     /// Output-example (without whitespace between both substitutions: `{command_not_found_text}{command_name}`
     /// `{command_name}` describes user's input as in: `{prefix}help {command_name}`.
-    pub command_not_found_text: &'static str,
+    pub command_not_found_text: Cow<'static, str>,
     /// Explains the user on how to use access a single command's details.
-    pub individual_command_tip: &'static str,
+    pub individual_command_tip: Cow<'static, str>,
     /// Explains reasoning behind strikethrough-commands, see fields requiring `HelpBehaviour` for further information.
     /// If `HelpBehaviour::Strike` is unused, this field will evaluate to `None` during creation
     /// inside of the help macro.
     ///
     /// **Note**: Text is only used in direct messages.
-    pub strikethrough_commands_tip_in_dm: Option<&'static str>,
+    pub strikethrough_commands_tip_in_dm: Option<Cow<'static, str>>,
     /// Explains reasoning behind strikethrough-commands, see fields requiring `HelpBehaviour` for further information.
     /// If `HelpBehaviour::Strike` is unused, this field will evaluate to `None` during creation
     /// inside of the help macro.
     ///
     /// **Note**: Text is only used in guilds.
-    pub strikethrough_commands_tip_in_guild: Option<&'static str>,
+    pub strikethrough_commands_tip_in_guild: Option<Cow<'static, str>>,
     /// Announcing a group's prefix as in: {group_prefix} {prefix}.
-    pub group_prefix: &'static str,
+    pub group_prefix: Cow<'static, str>,
     /// If a user lacks required roles, this will treat how these commands will be displayed.
     pub lacking_role: HelpBehaviour,
     /// If a user lacks permissions, this will treat how these commands will be displayed.
     pub lacking_permissions: HelpBehaviour,
     /// If a user lacks ownership, this will treat how these commands will be displayed.
     pub lacking_ownership: HelpBehaviour,
+    /// If a user fails a command's checks, this will treat how these commands will be displayed.
+    ///
+    /// Only checks with `check_in_help` set are evaluated.
+    pub lacking_conditions: HelpBehaviour,
     /// If a user is using the help-command in a channel where a command is not available,
     /// this behaviour will be executed.
     pub wrong_channel: HelpBehaviour,
@@ -226,7 +338,117 @@ pub struct HelpOptions {
     pub max_levenshtein_distance: usize,
     /// Help will use this as prefix to express how deeply nested a command or
     /// group is.
-    pub indention_prefix: &'static str,
+    pub indention_prefix: Cow<'static, str>,
+    /// The order in which groups are listed.
+    pub group_order: HelpOrder,
+}
+
+impl HelpOptions {
+    /// Creates a copy of these options with their texts translated into
+    /// `locale`, for instance the locale of a guild or user.
+    ///
+    /// `translate` is given the locale and the name of the field, such as
+    /// `"usage_label"`, and returns the text to use instead, which may have been
+    /// loaded at runtime. If it returns `None`, the text is kept as is.
+    ///
+    /// # Examples
+    ///
+    /// Use German labels in guilds whose locale is German:
+    ///
+    /// ```rust,no_run
+    /// use serenity::framework::standard::{
+    ///     help_commands, Args, CommandGroup, CommandResult, HelpOptions, macros::help,
+    /// };
+    /// use serenity::model::prelude::{Message, UserId};
+    /// use serenity::prelude::*;
+    /// use std::collections::HashSet;
+    ///
+    /// fn translate(locale: &str, key: &str) -> Option<String> {
+    ///     if locale != "de" {
+    ///         return None;
+    ///     }
+    ///
+    ///     Some(match key {
+    ///         "usage_label" => "Verwendung",
+    ///         "description_label" => "Beschreibung",
+    ///         "command_not_found_text" => "**Fehler**: Befehl `{}` nicht gefunden.",
+    ///         _ => return None,
+    ///     }.to_string())
+    /// }
+    ///
+    /// # fn guild_locale(_: &Message) -> String { "de".to_string() }
+    /// #
+    /// #[help]
+    /// fn my_help(
+    ///    context: &mut Context,
+    ///    msg: &Message,
+    ///    args: Args,
+    ///    help_options: &'static HelpOptions,
+    ///    groups: &[&'static CommandGroup],
+    ///    owners: HashSet<UserId>
+    /// ) -> CommandResult {
+    ///     // For instance loaded from a database.
+    ///     let locale = guild_locale(msg);
+    ///     let options = help_options.localised(&locale, translate);
+    ///
+    /// #   #[cfg(all(feature = "cache", feature = "http"))]
+    /// #   {
+    ///     help_commands::with_embeds(context, msg, args, &options, groups, owners)
+    /// #   }
+    /// #
+    /// #   #[cfg(not(all(feature = "cache", feature = "http")))]
+    /// #   Ok(())
+    /// }
+    /// ```
+    pub fn localised<F>(&self, locale: &str, translate: F) -> HelpOptions
+    where
+        F: Fn(&str, &str) -> Option<String>,
+    {
+        let mut options = self.clone();
+
+        macro_rules! translate {
+            ($($field:ident),*) => {
+                $(
+                    if let Some(text) = translate(locale, stringify!($field)) {
+                        options.$field = Cow::Owned(text);
+                    }
+                )*
+            };
+        }
+
+        translate!(
+            suggestion_text,
+            no_help_available_text,
+            usage_label,
+            usage_sample_label,
+            ungrouped_label,
+            description_label,
+            grouped_label,
+            aliases_label,
+            guild_only_text,
+            checks_label,
+            dm_only_text,
+            dm_and_guild_text,
+            available_text,
+            command_not_found_text,
+            individual_command_tip,
+            group_prefix
+        );
+
+        if options.strikethrough_commands_tip_in_dm.is_some() {
+            if let Some(text) = translate(locale, "strikethrough_commands_tip_in_dm") {
+                options.strikethrough_commands_tip_in_dm = Some(Cow::Owned(text));
+            }
+        }
+
+        if options.strikethrough_commands_tip_in_guild.is_some() {
+            if let Some(text) = translate(locale, "strikethrough_commands_tip_in_guild") {
+                options.strikethrough_commands_tip_in_guild = Some(Cow::Owned(text));
+            }
+        }
+
+        options
+    }
 }
 
 #[derive(Debug, PartialEq)]
@@ -268,3 +490,36 @@ mod levenshtein_tests {
         assert_eq!(HelpBehaviour::Hide, std::cmp::max(HelpBehaviour::Nothing, HelpBehaviour::Hide));
     }
 }
+
+#[cfg(test)]
+mod command_error_tests {
+    use super::{CommandError, CommandResult};
+    use std::error::Error;
+
+    fn fails() -> Result<(), Box<dyn Error>> {
+        Err("no voice connection".into())
+    }
+
+    #[test]
+    fn from_boxed_error() {
+        let command = || -> CommandResult {
+            fails()?;
+
+            Ok(())
+        };
+
+        let error = command().unwrap_err();
+
+        assert_eq!(error.message, "no voice connection");
+        assert!(!error.is::<Box<dyn Error>>());
+    }
+
+    #[test]
+    fn new_retains_error() {
+        let error = CommandError::new(42u8);
+
+        assert_eq!(error.message, "42");
+        assert_eq!(error.downcast_ref::<u8>(), Some(&42));
+        assert!(!error.is::<String>());
+    }
+}
@@ -70,7 +70,7 @@ use tungstenite::client::AutoStream;
 #[cfg(feature = "client")]
 use crate::client::bridge::gateway::ShardClientMessage;
 
-pub type CurrentPresence = (Option<Activity>, OnlineStatus);
+pub type CurrentPresence = (Vec<Activity>, OnlineStatus);
 
 #[cfg(not(feature = "native_tls_backend"))]
 pub type WsClient = WebSocket<rustls::StreamOwned<rustls::ClientSession, std::net::TcpStream>>;
@@ -24,6 +24,7 @@ use tungstenite::{
     error::Error as TungsteniteError,
     protocol::frame::CloseFrame,
 };
+use crate::utils::token::SecretToken;
 use url::Url;
 use log::{error, debug, info, trace, warn};
 
@@ -95,7 +96,8 @@ pub struct Shard {
     // This acts as a timeout to determine if the shard has - for some reason -
     // not started within a decent amount of time.
     pub started: Instant,
-    pub token: String,
+    /// The token used to identify, which is redacted when formatted.
+    pub token: SecretToken,
     ws_url: Arc<Mutex<String>>,
 }
 
@@ -145,7 +147,7 @@ impl Shard {
         let _ = set_client_timeout(&mut client);
         set_client_buffer_sizes(&mut client);
 
-        let current_presence = (None, OnlineStatus::Online);
+        let current_presence = (Vec::new(), OnlineStatus::Online);
         let heartbeat_instants = (None, None);
         let heartbeat_interval = None;
         let last_heartbeat_acknowledged = true;
@@ -163,7 +165,7 @@ impl Shard {
             seq,
             stage,
             started: Instant::now(),
-            token: token.to_string(),
+            token: SecretToken::new(token),
             session_id,
             shard_info,
             ws_url,
@@ -286,12 +288,18 @@ impl Shard {
     /// ```
     #[inline]
     pub fn set_activity(&mut self, activity: Option<Activity>) {
-        self.current_presence.0 = activity;
+        self.set_activities(activity);
     }
 
+    /// Sets the user's current activities, replacing any previous ones.
     #[inline]
-    pub fn set_presence(&mut self, status: OnlineStatus, activity: Option<Activity>) {
-        self.set_activity(activity);
+    pub fn set_activities<I: IntoIterator<Item = Activity>>(&mut self, activities: I) {
+        self.current_presence.0 = activities.into_iter().collect();
+    }
+
+    #[inline]
+    pub fn set_presence<I: IntoIterator<Item = Activity>>(&mut self, status: OnlineStatus, activities: I) {
+        self.set_activities(activities);
         self.set_status(status);
     }
 
@@ -753,7 +761,7 @@ impl Shard {
     // - the time that the last heartbeat sent as being now
     // - the `stage` to `Identifying`
     pub fn identify(&mut self) -> Result<()> {
-        self.client.send_identify(&self.shard_info, &self.token)?;
+        self.client.send_identify(&self.shard_info, self.token.expose(), &self.current_presence)?;
 
         self.heartbeat_instants.0 = Some(Instant::now());
         self.stage = ConnectionStage::Identifying;
@@ -807,7 +815,7 @@ impl Shard {
                     &self.shard_info,
                     session_id,
                     self.seq,
-                    &self.token,
+                    self.token.expose(),
                 )
             },
             None => Err(Error::Gateway(GatewayError::NoSessionId)),
@@ -3,7 +3,7 @@ use crate::constants::{self, OpCode};
 use crate::gateway::{CurrentPresence, WsClient};
 use crate::internal::prelude::*;
 use crate::internal::ws_impl::SenderExt;
-use crate::model::id::GuildId;
+use crate::model::{id::GuildId, user::OnlineStatus};
 use serde_json::json;
 use std::env::consts;
 use log::{debug, trace};
@@ -20,8 +20,12 @@ pub trait WebSocketGatewayClientExt {
     fn send_heartbeat(&mut self, shard_info: &[u64; 2], seq: Option<u64>)
         -> Result<()>;
 
-    fn send_identify(&mut self, shard_info: &[u64; 2], token: &str)
-        -> Result<()>;
+    fn send_identify(
+        &mut self,
+        shard_info: &[u64; 2],
+        token: &str,
+        current_presence: &CurrentPresence,
+    ) -> Result<()>;
 
     fn send_presence_update(
         &mut self,
@@ -68,8 +72,12 @@ impl WebSocketGatewayClientExt for WsClient {
         })).map_err(From::from)
     }
 
-    fn send_identify(&mut self, shard_info: &[u64; 2], token: &str)
-        -> Result<()> {
+    fn send_identify(
+        &mut self,
+        shard_info: &[u64; 2],
+        token: &str,
+        current_presence: &CurrentPresence,
+    ) -> Result<()> {
         debug!("[Shard {:?}] Identifying", shard_info);
 
         self.send_json(&json!({
@@ -79,6 +87,7 @@ impl WebSocketGatewayClientExt for WsClient {
                 "large_threshold": constants::LARGE_THRESHOLD,
                 "shard": shard_info,
                 "token": token,
+                "presence": presence_json(current_presence),
                 "v": constants::GATEWAY_VERSION,
                 "properties": {
                     "$browser": "serenity",
@@ -94,23 +103,11 @@ impl WebSocketGatewayClientExt for WsClient {
         shard_info: &[u64; 2],
         current_presence: &CurrentPresence,
     ) -> Result<()> {
-        let &(ref activity, ref status) = current_presence;
-        let now = Utc::now().timestamp() as u64;
-
         debug!("[Shard {:?}] Sending presence update", shard_info);
 
         self.send_json(&json!({
             "op": OpCode::StatusUpdate.num(),
-            "d": {
-                "afk": false,
-                "since": now,
-                "status": status.name(),
-                "game": activity.as_ref().map(|x| json!({
-                    "name": x.name,
-                    "type": x.kind,
-                    "url": x.url,
-                })),
-            },
+            "d": presence_json(current_presence),
         }))
     }
 
@@ -133,3 +130,48 @@ impl WebSocketGatewayClientExt for WsClient {
         })).map_err(From::from)
     }
 }
+
+/// Builds the presence payload sent when identifying and updating the
+/// presence.
+///
+/// Discord only knows the user to be away from keyboard, along with the time
+/// since they are, while they are [`Idle`].
+///
+/// [`Idle`]: ../model/user/enum.OnlineStatus.html#variant.Idle
+fn presence_json(current_presence: &CurrentPresence) -> Value {
+    let &(ref activities, status) = current_presence;
+    let activities = activities.iter().map(|x| json!({
+        "name": x.name,
+        "type": x.kind,
+        "url": x.url,
+    })).collect::<Vec<_>>();
+    let afk = status == OnlineStatus::Idle;
+    let since = if afk { Some(Utc::now().timestamp_millis()) } else { None };
+
+    json!({
+        "afk": afk,
+        "since": since,
+        "status": status.name(),
+        "game": activities.first(),
+        "activities": activities,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::user::OnlineStatus;
+    use super::presence_json;
+
+    #[test]
+    fn test_presence_json() {
+        let invisible = presence_json(&(Vec::new(), OnlineStatus::Invisible));
+        assert_eq!(invisible["status"], "invisible");
+        assert_eq!(invisible["afk"], false);
+        assert!(invisible["since"].is_null());
+
+        let idle = presence_json(&(Vec::new(), OnlineStatus::Idle));
+        assert_eq!(idle["status"], "idle");
+        assert_eq!(idle["afk"], true);
+        assert!(idle["since"].is_u64());
+    }
+}
@@ -28,11 +28,13 @@ pub mod raw;
 pub mod request;
 pub mod routing;
 
-mod error;
+pub(crate) mod error;
+mod typing;
 
 pub use reqwest::StatusCode;
 pub use self::error::Error as HttpError;
 pub use self::raw::*;
+pub use self::typing::Typing;
 
 use reqwest::{
     Method,
@@ -2,13 +2,14 @@ use crate::constants;
 use reqwest::{
     Client,
     header::{AUTHORIZATION, USER_AGENT, CONTENT_TYPE, HeaderValue, HeaderMap as Headers},
-    multipart::Part,
+    multipart::{Form, Part},
     Response as ReqwestResponse,
     StatusCode,
     Url,
 };
 use crate::internal::prelude::*;
 use crate::model::prelude::*;
+use crate::utils::token::SecretToken;
 use super::{
     ratelimiting::{perform, RateLimit},
     request::Request,
@@ -27,9 +28,16 @@ use std::{
     sync::Arc,
 };
 
+/// A client for Discord's REST API.
+///
+/// Cloning it is cheap, and clones share the same connection pool and
+/// ratelimits.
+#[derive(Clone)]
 pub struct Http {
     client: Client,
-    pub token: String,
+    /// The token used to authorize requests, which is redacted when
+    /// formatted.
+    pub token: SecretToken,
     pub limiter: Arc<Mutex<()>>,
     /// The routes mutex is a HashMap of each [`Route`] and their respective
     /// ratelimit information.
@@ -61,7 +69,7 @@ impl Http {
     pub fn new(client: Client, token: &str) -> Self {
         Http {
             client,
-            token: token.to_string(),
+            token: SecretToken::new(token),
             limiter: Arc::new(Mutex::new(())),
             routes: Arc::new(Mutex::new(HashMap::default())),
         }
@@ -77,7 +85,7 @@ impl Http {
             client: Client::builder()
                 .use_default_tls()
                 .build().expect("Cannot build Reqwest::Client."),
-            token: token.to_string(),
+            token: SecretToken::new(token),
             limiter: Arc::new(Mutex::new(())),
             routes: Arc::new(Mutex::new(HashMap::default())),
         }
@@ -680,6 +688,45 @@ impl Http {
         })
     }
 
+    /// Edits a message by Id, uploading new files alongside it.
+    ///
+    /// The map is sent as the `payload_json` of the request. Existing
+    /// attachments are kept unless an `attachments` array is given, in which
+    /// case only those it lists are kept.
+    ///
+    /// **Note**: Only the author of a message can modify it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an
+    /// [`HttpError::InvalidRequest(PayloadTooLarge)`][`HttpError::InvalidRequest`]
+    /// if the files are too large to send.
+    ///
+    /// [`HttpError::InvalidRequest`]: enum.HttpError.html#variant.InvalidRequest
+    pub fn edit_message_and_attachments<'a, T, It>(&self, channel_id: u64, message_id: u64, map: &Value, new_attachments: It) -> Result<Message>
+        where T: Into<AttachmentType<'a>>, It: IntoIterator<Item=T> {
+        let uri = api!("/channels/{}/messages/{}", channel_id, message_id);
+        let url = match Url::parse(&uri) {
+            Ok(url) => url,
+            Err(_) => return Err(Error::Url(uri)),
+        };
+
+        let multipart = multipart_files(new_attachments)?
+            .text("payload_json", serde_json::to_string(map)?);
+
+        let response = self.client
+            .patch(url)
+            .header(AUTHORIZATION, HeaderValue::from_str(self.token.expose())?)
+            .header(USER_AGENT, HeaderValue::from_static(&constants::USER_AGENT))
+            .multipart(multipart).send()?;
+
+        if !response.status().is_success() {
+            return Err(HttpError::UnsuccessfulRequest(response.into()).into());
+        }
+
+        serde_json::from_reader(response).map_err(From::from)
+    }
+
     /// Edits the current user's nickname for the provided [`Guild`] via its Id.
     ///
     /// Pass `None` to reset the nickname.
@@ -1047,6 +1094,15 @@ impl Http {
         })
     }
 
+    /// Gets guild information with counts.
+    pub fn get_guild_with_counts(&self, guild_id: u64) -> Result<PartialGuild> {
+        self.fire(Request {
+            body: None,
+            headers: None,
+            route: RouteInfo::GetGuildWithCounts { guild_id },
+        })
+    }
+
     /// Gets a guild embed information.
     pub fn get_guild_embed(&self, guild_id: u64) -> Result<GuildEmbed> {
         self.fire(Request {
@@ -1483,35 +1539,7 @@ impl Http {
             Err(_) => return Err(Error::Url(uri)),
         };
 
-        let mut multipart = reqwest::multipart::Form::new();
-        let mut file_num = "0".to_string();
-
-        for file in files {
-
-            match file.into() {
-                AttachmentType::Bytes((bytes, filename)) => {
-                    multipart = multipart
-                        .part(file_num.to_string(), Part::bytes(bytes.to_vec())
-                            .file_name(filename.to_string()));
-                },
-                AttachmentType::File((file, filename)) => {
-                    multipart = multipart
-                        .part(file_num.to_string(),
-                            Part::reader(file.try_clone()?)
-                                .file_name(filename.to_string()));
-                },
-                AttachmentType::Path(path) => {
-                    multipart = multipart
-                        .file(file_num.to_string(), path)?;
-                },
-                AttachmentType::__Nonexhaustive => unreachable!(),
-            }
-
-            unsafe {
-                let vec = file_num.as_mut_vec();
-                vec[0] += 1;
-            }
-        }
+        let mut multipart = multipart_files(files)?;
 
         for (k, v) in map {
             match v {
@@ -1526,7 +1554,7 @@ impl Http {
 
         let response = self.client
             .post(url)
-            .header(AUTHORIZATION, HeaderValue::from_str(&self.token)?)
+            .header(AUTHORIZATION, HeaderValue::from_str(self.token.expose())?)
             .header(USER_AGENT, HeaderValue::from_static(&constants::USER_AGENT))
             .multipart(multipart).send()?;
 
@@ -1735,7 +1763,7 @@ impl Http {
         // If it doesn't and the loop breaks, try one last time.
         for _ in 0..3 {
 
-            match request.build(&self.client, &self.token)?.send() {
+            match request.build(&self.client, self.token.expose())?.send() {
                 Ok(response) => return Ok(response),
                 Err(reqwest_error) => {
                     if let Some(io_error) = reqwest_error.get_ref().and_then(|e| e.downcast_ref::<std::io::Error>()) {
@@ -1750,7 +1778,7 @@ impl Http {
             }
         }
 
-        request.build(&self.client, &self.token)
+        request.build(&self.client, self.token.expose())
             .map_err(Into::into)
             .and_then(|b| Ok(b.send()?))
     }
@@ -1782,9 +1810,45 @@ impl Default for Http {
     fn default() -> Self {
         Self {
             client: Client::builder().build().expect("Cannot build Reqwest::Client."),
-            token: "".to_string(),
+            token: SecretToken::default(),
             limiter: Arc::new(Mutex::new(())),
             routes: Arc::new(Mutex::new(HashMap::default())),
         }
     }
 }
+
+/// Creates a multipart form containing the given files, named by their index.
+fn multipart_files<'a, T, It>(files: It) -> Result<Form>
+    where T: Into<AttachmentType<'a>>, It: IntoIterator<Item=T> {
+    let mut multipart = Form::new();
+    let mut file_num = "0".to_string();
+
+    for file in files {
+
+        match file.into() {
+            AttachmentType::Bytes((bytes, filename)) => {
+                multipart = multipart
+                    .part(file_num.to_string(), Part::bytes(bytes.to_vec())
+                        .file_name(filename.to_string()));
+            },
+            AttachmentType::File((file, filename)) => {
+                multipart = multipart
+                    .part(file_num.to_string(),
+                        Part::reader(file.try_clone()?)
+                            .file_name(filename.to_string()));
+            },
+            AttachmentType::Path(path) => {
+                multipart = multipart
+                    .file(file_num.to_string(), path)?;
+            },
+            AttachmentType::__Nonexhaustive => unreachable!(),
+        }
+
+        unsafe {
+            let vec = file_num.as_mut_vec();
+            vec[0] += 1;
+        }
+    }
+
+    Ok(multipart)
+}
@@ -370,6 +370,10 @@ impl Route {
         format!(api!("/guilds/{}"), guild_id)
     }
 
+    pub fn guild_with_counts(guild_id: u64) -> String {
+        format!(api!("/guilds/{}?with_counts=true"), guild_id)
+    }
+
     pub fn guild_audit_logs(
         guild_id: u64,
         action_type: Option<u8>,
@@ -801,6 +805,9 @@ pub enum RouteInfo<'a> {
     GetGuildEmbed {
         guild_id: u64,
     },
+    GetGuildWithCounts {
+        guild_id: u64,
+    },
     GetGuildIntegrations {
         guild_id: u64,
     },
@@ -1237,6 +1244,11 @@ impl<'a> RouteInfo<'a> {
                 Route::GuildsId(guild_id),
                 Cow::from(Route::guild(guild_id)),
             ),
+            RouteInfo::GetGuildWithCounts { guild_id } => (
+                LightMethod::Get,
+                Route::GuildsId(guild_id),
+                Cow::from(Route::guild_with_counts(guild_id)),
+            ),
             RouteInfo::GetGuildEmbed { guild_id } => (
                 LightMethod::Get,
                 Route::GuildsIdEmbed(guild_id),
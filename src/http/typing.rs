@@ -0,0 +1,144 @@
+use super::Http;
+use parking_lot::Mutex;
+use std::{
+    ptr,
+    sync::{
+        atomic::{AtomicBool, AtomicPtr, Ordering},
+        mpsc::{self, Receiver, RecvTimeoutError, Sender},
+        Arc,
+        Once,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+/// How often the typing indicator is renewed. Discord shows it for a few
+/// seconds after each broadcast.
+const INTERVAL: Duration = Duration::from_secs(4);
+
+/// A channel kept shown as typing by the timer thread.
+struct Entry {
+    http: Arc<Http>,
+    channel_id: u64,
+    next: Instant,
+    stopped: Arc<AtomicBool>,
+}
+
+/// Keeps the current user shown as typing in a channel until it is dropped or
+/// [`stop`]ped.
+///
+/// The indicator is broadcast from a single background thread shared by all
+/// guards, so this works from within blocking code, such as a long-running
+/// command, without spawning a thread for each of them.
+///
+/// Usually created by [`ChannelId::start_typing`].
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # use serenity::http::Http;
+/// # use serenity::model::id::ChannelId;
+/// #
+/// # let http = Http::default();
+/// let typing = ChannelId(7).start_typing(&http);
+///
+/// // Do some lengthy work...
+///
+/// typing.stop();
+/// ```
+///
+/// [`stop`]: #method.stop
+/// [`ChannelId::start_typing`]: ../model/id/struct.ChannelId.html#method.start_typing
+#[derive(Debug)]
+pub struct Typing(Arc<AtomicBool>);
+
+impl Typing {
+    /// Starts broadcasting typing in the channel right away.
+    pub fn start(http: Arc<Http>, channel_id: u64) -> Self {
+        Self::start_after(http, channel_id, Duration::from_secs(0))
+    }
+
+    /// Starts broadcasting typing in the channel once `delay` has passed.
+    ///
+    /// Nothing is broadcast if the guard is dropped before then, making it
+    /// suitable to only show typing for work that turns out to take long.
+    pub fn start_after(http: Arc<Http>, channel_id: u64, delay: Duration) -> Self {
+        let stopped = Arc::new(AtomicBool::new(false));
+
+        let _ = timer().send(Entry {
+            http,
+            channel_id,
+            next: Instant::now() + delay,
+            stopped: Arc::clone(&stopped),
+        });
+
+        Typing(stopped)
+    }
+
+    /// Stops broadcasting typing.
+    ///
+    /// This is the same as dropping the guard.
+    #[inline]
+    pub fn stop(self) {}
+}
+
+impl Drop for Typing {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Returns a sender to the timer thread, starting it on first use.
+fn timer() -> Sender<Entry> {
+    static START: Once = Once::new();
+    static TIMER: AtomicPtr<Mutex<Sender<Entry>>> = AtomicPtr::new(ptr::null_mut());
+
+    START.call_once(|| {
+        let (tx, rx) = mpsc::channel();
+
+        thread::Builder::new()
+            .name("serenity typing".to_string())
+            .spawn(move || run(&rx))
+            .expect("Cannot spawn the typing thread.");
+
+        // Leaked on purpose, as the timer lives for the rest of the program.
+        TIMER.store(Box::into_raw(Box::new(Mutex::new(tx))), Ordering::Release);
+    });
+
+    // The pointer is set once within `call_once`, which has returned by now,
+    // and is never freed.
+    let timer = unsafe { &*TIMER.load(Ordering::Acquire) };
+
+    timer.lock().clone()
+}
+
+/// Broadcasts typing for every entry that is due, sleeping until the next one
+/// is or a new entry arrives.
+fn run(rx: &Receiver<Entry>) {
+    let mut entries: Vec<Entry> = Vec::new();
+
+    loop {
+        let now = Instant::now();
+
+        entries.retain(|entry| !entry.stopped.load(Ordering::Relaxed));
+
+        for entry in entries.iter_mut().filter(|entry| entry.next <= now) {
+            let _ = entry.http.broadcast_typing(entry.channel_id);
+
+            entry.next = now + INTERVAL;
+        }
+
+        let next = entries.iter().map(|entry| entry.next).min();
+
+        let received = match next {
+            Some(next) => rx.recv_timeout(next.saturating_duration_since(Instant::now())),
+            None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+
+        match received {
+            Ok(entry) => entries.push(entry),
+            Err(RecvTimeoutError::Timeout) => {},
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+}
@@ -47,6 +47,37 @@ macro_rules! feature_cache {
 }
 
 macro_rules! enum_number {
+    // Enums with an `Unknown(u64)` variant fall back to it for values not
+    // known to the library, rather than failing to deserialize. The values of
+    // the known variants are retrieved from the enum's `num` method.
+    ($name:ident { $($variant:ident, )* _ => Unknown, }) => {
+        impl From<u64> for $name {
+            fn from(num: u64) -> Self {
+                match num {
+                    $( v if v == $name::$variant.num() => $name::$variant, )*
+                    _ => $name::Unknown(num),
+                }
+            }
+        }
+
+        impl ::serde::Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+                where S: ::serde::Serializer
+            {
+                serializer.serialize_u64(self.num())
+            }
+        }
+
+        impl<'de> ::serde::Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> ::std::result::Result<Self, D::Error>
+                where D: ::serde::Deserializer<'de>
+            {
+                let num = <u64 as ::serde::Deserialize>::deserialize(deserializer)?;
+
+                Ok($name::from(num))
+            }
+        }
+    };
     ($name:ident { $($variant:ident, )* }) => {
         impl ::serde::Serialize for $name {
             fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
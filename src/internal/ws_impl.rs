@@ -1,5 +1,5 @@
 use flate2::read::ZlibDecoder;
-use crate::gateway::WsClient;
+use crate::gateway::{GatewayError, WsClient};
 use crate::internal::prelude::*;
 use serde_json;
 use tungstenite::{
@@ -76,6 +76,9 @@ fn convert_ws_message(message: Option<Message>) -> Result<Option<Value>>{
                 why
             })?
         },
+        Some(Message::Close(frame)) => {
+            return Err(Error::Gateway(GatewayError::Closed(frame.map(|f| f.into_owned()))));
+        },
         // Ping/Pong message behaviour is internally handled by tungstenite.
         _ => None,
     })
@@ -71,6 +71,8 @@ pub mod builder;
 pub mod cache;
 #[cfg(feature = "client")]
 pub mod client;
+#[cfg(feature = "collector")]
+pub mod collector;
 #[cfg(feature = "framework")]
 pub mod framework;
 #[cfg(feature = "gateway")]
@@ -113,6 +115,22 @@ pub struct CacheAndHttp {
     __nonexhaustive: (),
 }
 
+#[cfg(feature = "client")]
+impl CacheAndHttp {
+    /// Creates an empty cache, without a timeout for updating it, alongside
+    /// the given HTTP client.
+    pub fn new(http: Http) -> Self {
+        CacheAndHttp {
+            #[cfg(feature = "cache")]
+            cache: Arc::new(RwLock::new(Cache::default())),
+            #[cfg(feature = "cache")]
+            update_cache_timeout: None,
+            http: Arc::new(http),
+            __nonexhaustive: (),
+        }
+    }
+}
+
 // For the procedural macros defined in `command_attr`; do not remove!
 #[allow(clippy::useless_attribute)]
 #[allow(rust_2018_idioms)]
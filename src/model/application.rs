@@ -1,14 +1,21 @@
 //! Models about OAuth2 applications.
 
+use bitflags::__impl_bitflags;
+use serde::{
+    de::{Deserialize, Deserializer},
+    ser::{Serialize, Serializer},
+};
+use std::result::Result as StdResult;
 use super::{
     id::UserId,
     user::User,
-    utils::default_true
+    utils::{default_true, deserialize_u64, serialize_u64, U64Visitor}
 };
 
 /// Information about a user's application. An application does not necessarily
 /// have an associated bot user.
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct ApplicationInfo {
     /// The bot user associated with the application. See [`BotApplication`] for
     /// more information.
@@ -29,7 +36,7 @@ pub struct ApplicationInfo {
     pub description: String,
     /// A set of bitflags assigned to the application, which represent gated
     /// feature flags that have been enabled for the application.
-    pub flags: Option<u64>,
+    pub flags: Option<ApplicationFlags>,
     /// A hash pointing to the application's icon.
     ///
     /// This is not necessarily equivalent to the bot user's avatar.
@@ -46,12 +53,14 @@ pub struct ApplicationInfo {
     ///
     /// This is not equivalent to the application's bot user's token.
     pub secret: String,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
+    /// The team owning the application, if the application belongs to a
+    /// team.
+    pub team: Option<Team>,
 }
 
 /// Information about an application with an application's bot user.
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct BotApplication {
     /// The unique Id of the bot user.
     pub id: UserId,
@@ -74,12 +83,11 @@ pub struct BotApplication {
     /// **Note**: Keep this information private, as untrusted sources can use it
     /// to perform any action with a bot user.
     pub token: String,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 /// Information about the current application and its owner.
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct CurrentApplicationInfo {
     pub description: String,
     pub icon: Option<String>,
@@ -89,6 +97,142 @@ pub struct CurrentApplicationInfo {
     #[serde(default)] pub rpc_origins: Vec<String>,
     pub bot_public: bool,
     pub bot_require_code_grant: bool,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
+    /// The public flags of the application, such as whether it was approved
+    /// to use the privileged gateway intents.
+    pub flags: Option<ApplicationFlags>,
+    /// The team owning the application, if the application belongs to a
+    /// team. In that case, [`owner`] is a placeholder user for the team.
+    ///
+    /// [`owner`]: #structfield.owner
+    pub team: Option<Team>,
+}
+
+/// The flags of an application.
+#[derive(Copy, PartialEq, Eq, Clone, PartialOrd, Ord, Hash)]
+pub struct ApplicationFlags {
+    pub bits: u64,
+}
+
+__impl_bitflags! {
+    ApplicationFlags: u64 {
+        /// The application is verified and allowed to receive presence
+        /// updates in more than 100 guilds.
+        GATEWAY_PRESENCE = 1 << 12;
+        /// The application may receive presence updates in fewer than 100
+        /// guilds, and is not yet verified.
+        GATEWAY_PRESENCE_LIMITED = 1 << 13;
+        /// The application is verified and allowed to receive guild member
+        /// events in more than 100 guilds.
+        GATEWAY_GUILD_MEMBERS = 1 << 14;
+        /// The application may receive guild member events in fewer than 100
+        /// guilds, and is not yet verified.
+        GATEWAY_GUILD_MEMBERS_LIMITED = 1 << 15;
+        /// The application's verification is pending, having grown past the
+        /// unverified guild limit.
+        VERIFICATION_PENDING_GUILD_LIMIT = 1 << 16;
+        /// The application is embedded within the Discord client.
+        EMBEDDED = 1 << 17;
+    }
+}
+
+impl ApplicationFlags {
+    /// Whether the application may receive presence updates, either as a
+    /// verified or as a limited application.
+    pub fn has_presence_intent(self) -> bool {
+        self.intersects(Self::GATEWAY_PRESENCE | Self::GATEWAY_PRESENCE_LIMITED)
+    }
+
+    /// Whether the application may receive guild member events, either as a
+    /// verified or as a limited application.
+    pub fn has_guild_members_intent(self) -> bool {
+        self.intersects(Self::GATEWAY_GUILD_MEMBERS | Self::GATEWAY_GUILD_MEMBERS_LIMITED)
+    }
+}
+
+impl<'de> Deserialize<'de> for ApplicationFlags {
+    fn deserialize<D>(deserializer: D) -> StdResult<Self, D::Error>
+    where D: Deserializer<'de>
+    {
+        Ok(ApplicationFlags::from_bits_truncate(
+            deserializer.deserialize_u64(U64Visitor)?,
+        ))
+    }
+}
+
+impl Serialize for ApplicationFlags {
+    fn serialize<S>(&self, serializer: S) -> StdResult<S::Ok, S::Error>
+    where S: Serializer
+    {
+        serializer.serialize_u64(self.bits())
+    }
+}
+
+/// A group of developers owning applications together.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct Team {
+    /// The unique Id of the team.
+    #[serde(deserialize_with = "deserialize_u64", serialize_with = "serialize_u64")]
+    pub id: u64,
+    /// A hash of the team's icon.
+    pub icon: Option<String>,
+    /// The name of the team.
+    #[serde(default)]
+    pub name: String,
+    /// The members of the team.
+    pub members: Vec<TeamMember>,
+    /// The Id of the user owning the team.
+    pub owner_user_id: UserId,
+}
+
+/// A member of a [`Team`].
+///
+/// [`Team`]: struct.Team.html
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct TeamMember {
+    /// The state of the user's membership.
+    pub membership_state: MembershipState,
+    /// The permissions of the member, currently always `["*"]`.
+    pub permissions: Vec<String>,
+    /// The Id of the team the user is a member of.
+    #[serde(deserialize_with = "deserialize_u64", serialize_with = "serialize_u64")]
+    pub team_id: u64,
+    /// The user of the member.
+    pub user: User,
+}
+
+/// The state of a [`TeamMember`]'s membership.
+///
+/// [`TeamMember`]: struct.TeamMember.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum MembershipState {
+    /// The user has been invited to the team, but has not accepted yet.
+    Invited,
+    /// The user has accepted the invite and is a member of the team.
+    Accepted,
+    /// A value not yet known to the library, containing the raw value sent
+    /// by Discord.
+    Unknown(u64),
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+enum_number!(
+    MembershipState {
+        Invited,
+        Accepted,
+        _ => Unknown,
+    }
+);
+
+impl MembershipState {
+    pub fn num(self) -> u64 {
+        match self {
+            MembershipState::Invited => 1,
+            MembershipState::Accepted => 2,
+            MembershipState::Unknown(num) => num,
+            MembershipState::__Nonexhaustive => unreachable!(),
+        }
+    }
 }
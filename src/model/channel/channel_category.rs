@@ -13,6 +13,7 @@ use crate::http::Http;
 ///
 /// [`GuildChannel`]: struct.GuildChannel.html
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct ChannelCategory {
     /// Id of this category.
     pub id: ChannelId,
@@ -37,8 +38,6 @@ pub struct ChannelCategory {
     ///
     /// [`GuildChannel`]: struct.GuildChannel.html
     pub permission_overwrites: Vec<PermissionOverwrite>,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 #[cfg(feature = "model")]
@@ -91,6 +90,7 @@ impl ChannelCategory {
 
         let mut edit_channel = EditChannel::default();
         f(&mut edit_channel);
+        edit_channel.validate()?;
         let map = serenity_utils::hashmap_to_json_map(edit_channel.0);
 
         cache_http.http().edit_channel(self.id.0, &map).map(|channel| {
@@ -113,7 +113,6 @@ impl ChannelCategory {
                 name,
                 position,
                 kind,
-                _nonexhaustive: (),
             };
         })
     }
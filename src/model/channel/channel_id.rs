@@ -22,7 +22,9 @@ use crate::http::AttachmentType;
 #[cfg(feature = "model")]
 use crate::utils;
 #[cfg(feature = "http")]
-use crate::http::Http;
+use crate::http::{Http, Typing};
+#[cfg(all(feature = "http", feature = "model"))]
+use std::sync::Arc;
 #[cfg(all(feature = "http", feature = "model"))]
 use serde_json::json;
 
@@ -52,6 +54,31 @@ impl ChannelId {
     #[inline]
     pub fn broadcast_typing(self, http: impl AsRef<Http>) -> Result<()> { http.as_ref().broadcast_typing(self.0) }
 
+    /// Starts broadcasting that the current user is typing to the channel, and
+    /// keeps doing so until the returned [`Typing`] is dropped or stopped.
+    ///
+    /// **Note**: Requires the [Send Messages] permission.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use serenity::model::id::ChannelId;
+    ///
+    /// let typing = ChannelId(7).start_typing(&ctx.http);
+    ///
+    /// // Do some lengthy work...
+    ///
+    /// typing.stop();
+    /// ```
+    ///
+    /// [`Typing`]: ../../http/struct.Typing.html
+    /// [Send Messages]: ../permissions/struct.Permissions.html#associatedconstant.SEND_MESSAGES
+    #[cfg(feature = "http")]
+    #[inline]
+    pub fn start_typing(self, http: impl AsRef<Http>) -> Typing {
+        Typing::start(Arc::new(http.as_ref().clone()), self.0)
+    }
+
     /// Creates a [permission overwrite][`PermissionOverwrite`] for either a
     /// single [`Member`] or [`Role`] within the channel.
     ///
@@ -262,6 +289,7 @@ impl ChannelId {
     pub fn edit<F: FnOnce(&mut EditChannel) -> &mut EditChannel>(self, http: impl AsRef<Http>, f: F) -> Result<GuildChannel> {
         let mut channel = EditChannel::default();
         f(&mut channel);
+        channel.validate()?;
 
         let map = utils::hashmap_to_json_map(channel.0);
 
@@ -289,27 +317,26 @@ impl ChannelId {
     /// [`the limit`]: ../../builder/struct.EditMessage.html#method.content
     #[cfg(all(feature = "utils", feature = "http"))]
     #[inline]
-    pub fn edit_message<F, M>(self, http: impl AsRef<Http>, message_id: M, f: F) -> Result<Message>
-        where F: FnOnce(&mut EditMessage) -> &mut EditMessage, M: Into<MessageId> {
+    pub fn edit_message<'a, F, M>(self, http: impl AsRef<Http>, message_id: M, f: F) -> Result<Message>
+        where for <'b> F: FnOnce(&'b mut EditMessage<'a>) -> &'b mut EditMessage<'a>, M: Into<MessageId> {
         self._edit_message(&http, message_id.into(), f)
     }
 
-    fn _edit_message<F>(self, http: impl AsRef<Http>, message_id: MessageId, f: F) -> Result<Message>
-        where F: FnOnce(&mut EditMessage) -> &mut EditMessage {
+    fn _edit_message<'a, F>(self, http: impl AsRef<Http>, message_id: MessageId, f: F) -> Result<Message>
+        where for <'b> F: FnOnce(&'b mut EditMessage<'a>) -> &'b mut EditMessage<'a> {
         let mut msg = EditMessage::default();
         f(&mut msg);
 
-        if let Some(content) = msg.0.get(&"content") {
-            if let Value::String(ref content) = *content {
-                if let Some(length_over) = Message::overflow_length(content) {
-                    return Err(Error::Model(ModelError::MessageTooLong(length_over)));
-                }
-            }
-        }
-
         let map = utils::hashmap_to_json_map(msg.0);
 
-        http.as_ref().edit_message(self.0, message_id.0, &Value::Object(map))
+        Message::check_content_length(&map)?;
+        Message::check_embed_length(&map)?;
+
+        if msg.1.is_empty() {
+            http.as_ref().edit_message(self.0, message_id.0, &Value::Object(map))
+        } else {
+            http.as_ref().edit_message_and_attachments(self.0, message_id.0, &Value::Object(map), msg.1)
+        }
     }
 
     /// Attempts to find a [`Channel`] by its Id in the cache.
@@ -525,6 +552,31 @@ impl ChannelId {
         })
     }
 
+    /// Sends content of any length in the channel, split over as many messages
+    /// as needed to stay under the message length limit.
+    ///
+    /// Refer to [`utils::split_message`] for how the content is split.
+    ///
+    /// **Note**: Every page is sent as its own message. Showing the pages one
+    /// at a time in a single message, navigated with reactions, is not
+    /// supported.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error that occurs while sending a message. Messages
+    /// sent before it are not deleted.
+    ///
+    /// [`utils::split_message`]: ../../utils/fn.split_message.html
+    #[cfg(feature = "http")]
+    pub fn say_paginated(self, http: impl AsRef<Http>, content: impl AsRef<str>) -> Result<Vec<Message>> {
+        let limit = usize::from(crate::constants::MESSAGE_CODE_LIMIT);
+
+        utils::split_message(content, limit)
+            .into_iter()
+            .map(|page| self.say(&http, page))
+            .collect()
+    }
+
     /// Sends a file along with optional message contents. The filename _must_
     /// be specified.
     ///
@@ -609,8 +661,8 @@ impl ChannelId {
             }
         }
 
-        if let Some(e) = msg.0.remove(&"embed") {
-            msg.0.insert("payload_json", json!({ "embed": e }));
+        if let Some(e) = msg.0.remove(&"embeds") {
+            msg.0.insert("payload_json", json!({ "embeds": e }));
         }
 
         let map = utils::hashmap_to_json_map(msg.0.clone());
@@ -642,21 +694,20 @@ impl ChannelId {
         let mut create_message = CreateMessage::default();
         let msg = f(&mut create_message);
 
+        msg.check_length()?;
+
         if !msg.2.is_empty() {
-            if let Some(e) = msg.0.remove(&"embed") {
+            if let Some(e) = msg.0.remove(&"embeds") {
                 if let Some(c) = msg.0.remove(&"content") {
-                    msg.0.insert("payload_json", json!({ "content": c, "embed": e }));
+                    msg.0.insert("payload_json", json!({ "content": c, "embeds": e }));
                 } else {
-                    msg.0.insert("payload_json", json!({ "embed": e }));
+                    msg.0.insert("payload_json", json!({ "embeds": e }));
                 }
             }
         }
 
         let map = utils::hashmap_to_json_map(msg.0.clone());
 
-        Message::check_content_length(&map)?;
-        Message::check_embed_length(&map)?;
-
         let message = if msg.2.is_empty() {
             http.as_ref().send_message(self.0, &Value::Object(map))?
         } else {
@@ -672,6 +723,34 @@ impl ChannelId {
         Ok(message)
     }
 
+    /// Sets the slow mode rate of the channel, the amount of seconds a user
+    /// has to wait before sending another message. Passing `0` disables slow
+    /// mode.
+    ///
+    /// This is a shorthand for [`edit`]ing the channel with
+    /// [`EditChannel::rate_limit_per_user`].
+    ///
+    /// Requires the [Manage Channel] permission.
+    ///
+    /// # Examples
+    ///
+    /// Enable a 30 second slow mode during a raid:
+    ///
+    /// ```rust,ignore
+    /// // assuming a `channel_id` has been bound
+    ///
+    /// channel_id.set_slowmode(&context, 30);
+    /// ```
+    ///
+    /// [`edit`]: #method.edit
+    /// [`EditChannel::rate_limit_per_user`]: ../../builder/struct.EditChannel.html#method.rate_limit_per_user
+    /// [Manage Channel]: ../permissions/struct.Permissions.html#associatedconstant.MANAGE_CHANNELS
+    #[cfg(all(feature = "utils", feature = "http"))]
+    #[inline]
+    pub fn set_slowmode(self, http: impl AsRef<Http>, seconds: u64) -> Result<GuildChannel> {
+        self.edit(http, |c| c.rate_limit_per_user(seconds))
+    }
+
     /// Unpins a [`Message`] in the channel given by its Id.
     ///
     /// Requires the [Manage Messages] permission.
@@ -16,8 +16,13 @@ use crate::utils;
 /// **Note**: Maximum amount of characters you can put is 256 in a field name,
 /// 1024 in a field value, and 2048 in a description.
 ///
+/// An empty embed of the `"rich"` [`kind`] can be created via its `Default`
+/// implementation, after which its fields can be set.
+///
 /// [slack's attachments]: https://api.slack.com/docs/message-attachments
+/// [`kind`]: #structfield.kind
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct Embed {
     /// Information about the author of the embed.
     pub author: Option<EmbedAuthor>,
@@ -67,8 +72,26 @@ pub struct Embed {
     ///
     /// [`kind`]: #structfield.kind
     pub video: Option<EmbedVideo>,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
+}
+
+impl Default for Embed {
+    fn default() -> Self {
+        Embed {
+            author: None,
+            colour: Default::default(),
+            description: None,
+            fields: Vec::new(),
+            footer: None,
+            image: None,
+            kind: "rich".to_string(),
+            provider: None,
+            thumbnail: None,
+            timestamp: None,
+            title: None,
+            url: None,
+            video: None,
+        }
+    }
 }
 
 #[cfg(feature = "model")]
@@ -104,7 +127,8 @@ impl Embed {
 }
 
 /// An author object in an embed.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct EmbedAuthor {
     /// The URL of the author icon.
     ///
@@ -116,12 +140,11 @@ pub struct EmbedAuthor {
     pub proxy_icon_url: Option<String>,
     /// The URL of the author.
     pub url: Option<String>,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 /// A field object in an embed.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct EmbedField {
     /// Indicator of whether the field should display as inline.
     pub inline: bool,
@@ -133,8 +156,6 @@ pub struct EmbedField {
     ///
     /// The maxiumum length of this field is 1024 unicode codepoints.
     pub value: String,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 impl EmbedField {
@@ -155,13 +176,13 @@ impl EmbedField {
             name,
             value,
             inline,
-            _nonexhaustive: (),
         }
     }
 }
 
 /// Footer information for an embed.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct EmbedFooter {
     /// The URL of the footer icon.
     ///
@@ -171,12 +192,11 @@ pub struct EmbedFooter {
     pub proxy_icon_url: Option<String>,
     /// The associated text with the footer.
     pub text: String,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 /// An image object in an embed.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct EmbedImage {
     /// The height of the image.
     pub height: u64,
@@ -188,23 +208,21 @@ pub struct EmbedImage {
     pub url: String,
     /// The width of the image.
     pub width: u64,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 /// The provider of an embed.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct EmbedProvider {
     /// The name of the provider.
     pub name: String,
     /// The URL of the provider.
     pub url: Option<String>,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 /// The dimensions and URL of an embed thumbnail.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct EmbedThumbnail {
     /// The height of the thumbnail in pixels.
     pub height: u64,
@@ -216,12 +234,11 @@ pub struct EmbedThumbnail {
     pub url: String,
     /// The width of the thumbnail in pixels.
     pub width: u64,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 /// Video information for an embed.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct EmbedVideo {
     /// The height of the video in pixels.
     pub height: u64,
@@ -229,6 +246,4 @@ pub struct EmbedVideo {
     pub url: String,
     /// The width of the video in pixels.
     pub width: u64,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
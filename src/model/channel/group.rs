@@ -24,6 +24,7 @@ use crate::http::Http;
 /// [`Guild`]: ../guild/struct.Guild.html
 /// [`User`]: ../user/struct.User.html
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct Group {
     /// The Id of the group channel.
     #[serde(rename = "id")]
@@ -42,8 +43,6 @@ pub struct Group {
     #[serde(deserialize_with = "deserialize_users",
             serialize_with = "serialize_users")]
     pub recipients: HashMap<UserId, Arc<RwLock<User>>>,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 #[cfg(feature = "model")]
@@ -176,8 +175,8 @@ impl Group {
     /// [`the limit`]: ../../builder/struct.EditMessage.html#method.content
     #[cfg(feature = "http")]
     #[inline]
-    pub fn edit_message<F, M>(&self, http: impl AsRef<Http>, message_id: M, f: F) -> Result<Message>
-        where F: FnOnce(&mut EditMessage) -> &mut EditMessage, M: Into<MessageId> {
+    pub fn edit_message<'a, F, M>(&self, http: impl AsRef<Http>, message_id: M, f: F) -> Result<Message>
+        where for <'b> F: FnOnce(&'b mut EditMessage<'a>) -> &'b mut EditMessage<'a>, M: Into<MessageId> {
         self.channel_id.edit_message(&http, message_id, f)
     }
 
@@ -36,8 +36,9 @@ use crate::http::Http;
 /// Represents a guild's text, news, or voice channel. Some methods are available
 /// only for voice channels and some are only available for text channels.
 /// News channels are a subset of text channels and lack slow mode hence
-/// `slow_mode_rate` will be `None`.
+/// `rate_limit_per_user` will be `None`.
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct GuildChannel {
     /// The unique Id of the channel.
     ///
@@ -96,14 +97,25 @@ pub struct GuildChannel {
     // default to `false`.
     #[serde(default)]
     pub nsfw: bool,
-    /// A rate limit that applies per user and excludes bots.
+    /// The amount of seconds a user has to wait before sending another
+    /// message, also known as slow mode. Bots and users with the
+    /// [Manage Messages] or [Manage Channels] permissions are unaffected.
     ///
     /// **Note**: This is only available for text channels excluding news
     /// channels.
-    #[serde(default, rename = "rate_limit_per_user")]
-    pub slow_mode_rate: Option<u64>,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
+    ///
+    /// [Manage Messages]: ../permissions/struct.Permissions.html#associatedconstant.MANAGE_MESSAGES
+    /// [Manage Channels]: ../permissions/struct.Permissions.html#associatedconstant.MANAGE_CHANNELS
+    #[serde(default, alias = "slow_mode_rate")]
+    pub rate_limit_per_user: Option<u64>,
+}
+
+impl GuildChannel {
+    /// The amount of seconds a user has to wait before sending another
+    /// message.
+    #[deprecated(since="0.7.3", note="Please use the `rate_limit_per_user` field instead.")]
+    #[inline]
+    pub fn slow_mode_rate(&self) -> Option<u64> { self.rate_limit_per_user }
 }
 
 #[cfg(feature = "model")]
@@ -377,6 +389,7 @@ impl GuildChannel {
 
         let mut edit_channel = EditChannel::default();
         f(&mut edit_channel);
+        edit_channel.validate()?;
         let edited = serenity_utils::hashmap_to_json_map(edit_channel.0);
 
         match cache_http.http().edit_channel(self.id.0, &edited) {
@@ -410,8 +423,8 @@ impl GuildChannel {
     /// [`the limit`]: ../../builder/struct.EditMessage.html#method.content
     #[cfg(feature = "http")]
     #[inline]
-    pub fn edit_message<F, M>(&self, http: impl AsRef<Http>, message_id: M, f: F) -> Result<Message>
-        where F: FnOnce(&mut EditMessage) -> &mut EditMessage, M: Into<MessageId> {
+    pub fn edit_message<'a, F, M>(&self, http: impl AsRef<Http>, message_id: M, f: F) -> Result<Message>
+        where for <'b> F: FnOnce(&'b mut EditMessage<'a>) -> &'b mut EditMessage<'a>, M: Into<MessageId> {
         self.id.edit_message(&http, message_id, f)
     }
 
@@ -825,6 +838,52 @@ impl GuildChannel {
         self.id.send_message(&cache_http.http(), f)
     }
 
+    /// Replaces the channel's permission overwrites with the ones of its
+    /// parent [`ChannelCategory`], equivalent to the "Sync Now" button of the
+    /// Discord client.
+    ///
+    /// The category is retrieved from the cache if possible, otherwise it is
+    /// requested via the REST API. The overwrites are then applied in a
+    /// single channel edit.
+    ///
+    /// **Note**: Requires the [Manage Channels] permission.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ModelError::NoCategory`] if the channel does not belong to
+    /// a category, or [`ModelError::InvalidChannelType`] if its parent is not
+    /// a category.
+    ///
+    /// If the `cache` is enabled, returns a [`ModelError::InvalidPermissions`]
+    /// if the current user does not have the required permissions.
+    ///
+    /// [`ChannelCategory`]: struct.ChannelCategory.html
+    /// [`ModelError::InvalidChannelType`]: ../error/enum.Error.html#variant.InvalidChannelType
+    /// [`ModelError::InvalidPermissions`]: ../error/enum.Error.html#variant.InvalidPermissions
+    /// [`ModelError::NoCategory`]: ../error/enum.Error.html#variant.NoCategory
+    /// [Manage Channels]: ../permissions/struct.Permissions.html#associatedconstant.MANAGE_CHANNELS
+    #[cfg(all(feature = "utils", feature = "client", feature = "builder"))]
+    pub fn sync_permissions_with_category(&mut self, cache_http: impl CacheHttp) -> Result<()> {
+        let category_id = self.category_id.ok_or(Error::Model(ModelError::NoCategory))?;
+
+        #[cfg(feature = "cache")]
+        let cached = cache_http.cache()
+            .and_then(|cache| cache.read().categories(category_id))
+            .map(|category| category.read().permission_overwrites.clone());
+        #[cfg(not(feature = "cache"))]
+        let cached: Option<Vec<PermissionOverwrite>> = None;
+
+        let overwrites = match cached {
+            Some(overwrites) => overwrites,
+            None => match cache_http.http().get_channel(category_id.0)? {
+                Channel::Category(category) => category.read().permission_overwrites.clone(),
+                _ => return Err(Error::Model(ModelError::InvalidChannelType)),
+            },
+        };
+
+        self.edit(cache_http, |c| c.permissions(overwrites))
+    }
+
     /// Unpins a [`Message`] in the channel given by its Id.
     ///
     /// Requires the [Manage Messages] permission.
@@ -18,20 +18,15 @@ use serde_json::json;
 use std::sync::Arc;
 #[cfg(all(feature = "cache", feature = "model"))]
 use std::fmt::Write;
-#[cfg(feature = "model")]
 use bitflags::__impl_bitflags;
-#[cfg(feature = "model")]
 use serde::{
     de::{Deserialize, Deserializer},
     ser::{Serialize, Serializer},
 };
-#[cfg(feature = "model")]
 use super::utils::U64Visitor;
+use std::result::Result as StdResult;
 #[cfg(feature = "model")]
-use std::{
-    mem,
-    result::Result as StdResult,
-};
+use std::mem;
 #[cfg(feature = "model")]
 use crate::{
     constants,
@@ -44,10 +39,15 @@ use crate::{
 };
 #[cfg(feature = "http")]
 use crate::http::Http;
+#[cfg(feature = "collector")]
+use crate::client::bridge::gateway::ShardMessenger;
+#[cfg(feature = "collector")]
+use crate::collector::{CollectReaction, CollectReply};
 
 /// A representation of a message over a guild's text channel, a group, or a
 /// private channel.
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct Message {
     /// The unique Id of the message. Can be used to calculate the creation date
     /// of the message.
@@ -64,7 +64,8 @@ pub struct Message {
     pub content: String,
     /// The timestamp of the last time the message was updated, if it was.
     pub edited_timestamp: Option<DateTime<FixedOffset>>,
-    /// Array of embeds sent with the message.
+    /// Array of embeds sent with the message, of which there may be up to 10.
+    #[serde(default)]
     pub embeds: Vec<Embed>,
     /// The Id of the [`Guild`] that the message was sent in. This value will
     /// only be present if this message was received over the gateway.
@@ -109,12 +110,20 @@ pub struct Message {
     pub activity: Option<MessageActivity>,
     /// Sent with Rich Presence-related chat embeds.
     pub application: Option<MessageApplication>,
-    /// Reference data sent with crossposted messages.
+    /// Reference data sent with crossposted messages and replies.
     pub message_reference: Option<MessageReference>,
+    /// The message this message is a reply to, if any.
+    ///
+    /// This is only sent for messages of type [`MessageType::InlineReply`],
+    /// and will be `None` if the referenced message was deleted.
+    ///
+    /// [`MessageType::InlineReply`]: enum.MessageType.html#variant.InlineReply
+    pub referenced_message: Option<Box<Message>>,
     /// Bit flags describing extra features of the message.
     pub flags: Option<MessageFlags>,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
+    /// The stickers sent with the message.
+    #[serde(default)]
+    pub sticker_items: Vec<StickerItem>,
 }
 
 #[cfg(feature = "model")]
@@ -194,7 +203,8 @@ impl Message {
 
     /// Edits this message, replacing the original content with new content.
     ///
-    /// Message editing preserves all unchanged message data.
+    /// Message editing preserves all unchanged message data, including its
+    /// embeds and attachments unless they are replaced or removed.
     ///
     /// Refer to the documentation for [`EditMessage`] for more information
     /// regarding message restrictions and requirements.
@@ -225,8 +235,8 @@ impl Message {
     /// [`EditMessage`]: ../../builder/struct.EditMessage.html
     /// [`the limit`]: ../../builder/struct.EditMessage.html#method.content
     #[cfg(feature = "client")]
-    pub fn edit<F>(&mut self, cache_http: impl CacheHttp, f: F) -> Result<()>
-        where F: FnOnce(&mut EditMessage) -> &mut EditMessage {
+    pub fn edit<'a, F>(&mut self, cache_http: impl CacheHttp, f: F) -> Result<()>
+        where for <'b> F: FnOnce(&'b mut EditMessage<'a>) -> &'b mut EditMessage<'a> {
         #[cfg(feature = "cache")]
         {
             if let Some(cache) = cache_http.cache() {
@@ -243,19 +253,37 @@ impl Message {
             builder.content(&self.content);
         }
 
-        if let Some(embed) = self.embeds.get(0) {
-            let embed = CreateEmbed::from(embed.clone());
-            builder.embed( |e| {
-                *e = embed;
-                e
-            });
+        if !self.embeds.is_empty() {
+            builder.set_embeds(self.embeds.iter().map(CreateEmbed::from).collect());
+        }
+
+        for attachment in &self.attachments {
+            builder.attachment(attachment.id);
         }
 
+        let attachments = builder.0.get("attachments").cloned();
+
         f(&mut builder);
 
+        // Only send the attachments to keep if they were changed, so that
+        // edits not touching them look like any other.
+        if builder.0.get("attachments") == attachments.as_ref() {
+            builder.0.remove("attachments");
+        }
+
         let map = serenity_utils::hashmap_to_json_map(builder.0);
 
-        match cache_http.http().edit_message(self.channel_id.0, self.id.0, &Value::Object(map)) {
+        Message::check_content_length(&map)?;
+        Message::check_embed_length(&map)?;
+
+        let http = cache_http.http();
+        let edited = if builder.1.is_empty() {
+            http.edit_message(self.channel_id.0, self.id.0, &Value::Object(map))
+        } else {
+            http.edit_message_and_attachments(self.channel_id.0, self.id.0, &Value::Object(map), builder.1)
+        };
+
+        match edited {
             Ok(edited) => {
                 mem::replace(self, edited);
 
@@ -586,6 +614,29 @@ impl Message {
         self.guild_id.as_ref().and_then(|guild_id| self.author.nick_in(cache_http, *guild_id))
     }
 
+    /// Waits for the message's author to send another message in the
+    /// channel, such as an answer to a prompt.
+    ///
+    /// Refer to [`CollectReply`] for the further options and an example.
+    ///
+    /// [`CollectReply`]: ../../collector/struct.CollectReply.html
+    #[cfg(feature = "collector")]
+    pub fn await_reply<'a>(&self, shard_messenger: &'a impl AsRef<ShardMessenger>) -> CollectReply<'a> {
+        CollectReply::new(shard_messenger)
+            .channel_id(self.channel_id)
+            .author_id(self.author.id)
+    }
+
+    /// Waits for a reaction to be added to the message.
+    ///
+    /// Refer to [`CollectReaction`] for the further options and an example.
+    ///
+    /// [`CollectReaction`]: ../../collector/struct.CollectReaction.html
+    #[cfg(feature = "collector")]
+    pub fn await_reaction<'a>(&self, shard_messenger: &'a impl AsRef<ShardMessenger>) -> CollectReaction<'a> {
+        CollectReaction::new(shard_messenger).message_id(self.id)
+    }
+
     pub(crate) fn check_content_length(map: &JsonMap) -> Result<()> {
         if let Some(content) = map.get("content") {
             if let Value::String(ref content) = *content {
@@ -599,54 +650,7 @@ impl Message {
     }
 
     pub(crate) fn check_embed_length(map: &JsonMap) -> Result<()> {
-        let embed = match map.get("embed") {
-            Some(&Value::Object(ref value)) => value,
-            _ => return Ok(()),
-        };
-
-        let mut total: usize = 0;
-
-        if let Some(&Value::Object(ref author)) = embed.get("author") {
-            if let Some(&Value::Object(ref name)) = author.get("name") {
-                total += name.len();
-            }
-        }
-
-        if let Some(&Value::String(ref description)) = embed.get("description") {
-            total += description.len();
-        }
-
-        if let Some(&Value::Array(ref fields)) = embed.get("fields") {
-            for field_as_value in fields {
-                if let Value::Object(ref field) = *field_as_value {
-                    if let Some(&Value::String(ref field_name)) = field.get("name") {
-                        total += field_name.len();
-                    }
-
-                    if let Some(&Value::String(ref field_value)) = field.get("value") {
-                        total += field_value.len();
-                    }
-                }
-            }
-        }
-
-        if let Some(&Value::Object(ref footer)) = embed.get("footer") {
-            if let Some(&Value::String(ref text)) = footer.get("text") {
-                total += text.len();
-            }
-        }
-
-        if let Some(&Value::String(ref title)) = embed.get("title") {
-            total += title.len();
-        }
-
-        if total <= constants::EMBED_MAX_LENGTH as usize {
-            Ok(())
-        } else {
-            let overflow = total as u64 - u64::from(constants::EMBED_MAX_LENGTH);
-
-            Err(Error::Model(ModelError::EmbedTooLarge(overflow)))
-        }
+        crate::builder::check_embeds_length(map)
     }
 }
 
@@ -674,6 +678,7 @@ impl<'a> From<&'a Message> for MessageId {
 /// [`count`]: #structfield.count
 /// [reaction type]: enum.ReactionType.html
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct MessageReaction {
     /// The amount of the type of reaction that have been sent for the
     /// associated message.
@@ -683,37 +688,40 @@ pub struct MessageReaction {
     /// The type of reaction.
     #[serde(rename = "emoji")]
     pub reaction_type: ReactionType,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 /// Differentiates between regular and different types of system messages.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
 pub enum MessageType {
     /// A regular message.
-    Regular = 0,
+    Regular,
     /// An indicator that a recipient was added by the author.
-    GroupRecipientAddition = 1,
+    GroupRecipientAddition,
     /// An indicator that a recipient was removed by the author.
-    GroupRecipientRemoval = 2,
+    GroupRecipientRemoval,
     /// An indicator that a call was started by the author.
-    GroupCallCreation = 3,
+    GroupCallCreation,
     /// An indicator that the group name was modified by the author.
-    GroupNameUpdate = 4,
+    GroupNameUpdate,
     /// An indicator that the group icon was modified by the author.
-    GroupIconUpdate = 5,
+    GroupIconUpdate,
     /// An indicator that a message was pinned by the author.
-    PinsAdd = 6,
+    PinsAdd,
     /// An indicator that a member joined the guild.
-    MemberJoin = 7,
+    MemberJoin,
     /// An indicator that someone has boosted the guild.
-    NitroBoost = 8,
+    NitroBoost,
     /// An indicator that the guild has reached nitro tier 1
-    NitroTier1 = 9,
+    NitroTier1,
     /// An indicator that the guild has reached nitro tier 2
-    NitroTier2 = 10,
+    NitroTier2,
     /// An indicator that the guild has reached nitro tier 3
-    NitroTier3 = 11,
+    NitroTier3,
+    /// An indicator that the message is a reply to another message.
+    InlineReply,
+    /// A value not yet known to the library, containing the raw value sent
+    /// by Discord.
+    Unknown(u64),
     #[doc(hidden)]
     __Nonexhaustive,
 }
@@ -732,6 +740,8 @@ enum_number!(
         NitroTier1,
         NitroTier2,
         NitroTier3,
+        InlineReply,
+        _ => Unknown,
     }
 );
 
@@ -752,6 +762,8 @@ impl MessageType {
             NitroTier1 => 9,
             NitroTier2 => 10,
             NitroTier3 => 11,
+            InlineReply => 19,
+            Unknown(num) => num,
             __Nonexhaustive => unreachable!(),
         }
     }
@@ -759,11 +771,14 @@ impl MessageType {
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
 pub enum MessageActivityKind {
-    JOIN = 1,
-    SPECTATE = 2,
-    LISTEN = 3,
+    JOIN,
+    SPECTATE,
+    LISTEN,
     #[allow(non_camel_case_types)]
-    JOIN_REQUEST = 5,
+    JOIN_REQUEST,
+    /// A value not yet known to the library, containing the raw value sent
+    /// by Discord.
+    Unknown(u64),
     #[doc(hidden)]
     __Nonexhaustive,
 }
@@ -774,6 +789,7 @@ enum_number!(
         SPECTATE,
         LISTEN,
         JOIN_REQUEST,
+        _ => Unknown,
     }
 );
 
@@ -786,6 +802,7 @@ impl MessageActivityKind {
             SPECTATE => 2,
             LISTEN => 3,
             JOIN_REQUEST => 5,
+            Unknown(num) => num,
             __Nonexhaustive => unreachable!(),
         }
     }
@@ -793,6 +810,7 @@ impl MessageActivityKind {
 
 /// Rich Presence application information.
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct MessageApplication {
     /// ID of the application.
     pub id: u64,
@@ -804,24 +822,22 @@ pub struct MessageApplication {
     pub icon: Option<String>,
     /// Name of the application.
     pub name: String,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 /// Rich Presence activity information.
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct MessageActivity {
     /// Kind of message activity.
     #[serde(rename = "type")]
     pub kind: MessageActivityKind,
     /// `party_id` from a Rich Presence event.
     pub party_id: Option<String>,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
-/// Reference data sent with crossposted messages.
+/// Reference data sent with crossposted messages and replies.
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct MessageReference {
     /// ID of the originating message.
     pub message_id: Option<MessageId>,
@@ -829,8 +845,6 @@ pub struct MessageReference {
     pub channel_id: ChannelId,
     /// ID of the originating message's guild.
     pub guild_id: Option<GuildId>,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 /// Channel Mention Object
@@ -861,6 +875,12 @@ __impl_bitflags! {
         IS_CROSSPOST = 0b0000_0000_0000_0000_0000_0000_0000_0010;
         /// Do not include any embeds when serializing this message.
         SUPPRESS_EMBEDS = 0b0000_0000_0000_0000_0000_0000_0000_0100;
+        /// The source message for this crosspost has been deleted (via Channel Following).
+        SOURCE_MESSAGE_DELETED = 0b0000_0000_0000_0000_0000_0000_0000_1000;
+        /// This message came from the urgent message system.
+        URGENT = 0b0000_0000_0000_0000_0000_0000_0001_0000;
+        /// This message is only visible to the user who invoked the interaction.
+        EPHEMERAL = 0b0000_0000_0000_0000_0000_0000_0100_0000;
     }
 }
 
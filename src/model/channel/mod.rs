@@ -8,6 +8,8 @@ mod guild_channel;
 mod message;
 mod private_channel;
 mod reaction;
+mod stage_instance;
+mod sticker;
 mod channel_category;
 
 #[cfg(feature = "http")]
@@ -20,6 +22,8 @@ pub use self::guild_channel::*;
 pub use self::message::*;
 pub use self::private_channel::*;
 pub use self::reaction::*;
+pub use self::stage_instance::*;
+pub use self::sticker::*;
 pub use self::channel_category::*;
 
 use crate::{internal::RwLockExt, model::prelude::*};
@@ -316,9 +320,6 @@ impl<'de> Deserialize<'de> for Channel {
         };
 
         match kind {
-            0 | 2 | 5 | 6 => serde_json::from_value::<GuildChannel>(Value::Object(v))
-                .map(|x| Channel::Guild(Arc::new(RwLock::new(x))))
-                .map_err(DeError::custom),
             1 => serde_json::from_value::<PrivateChannel>(Value::Object(v))
                 .map(|x| Channel::Private(Arc::new(RwLock::new(x))))
                 .map_err(DeError::custom),
@@ -328,7 +329,11 @@ impl<'de> Deserialize<'de> for Channel {
             4 => serde_json::from_value::<ChannelCategory>(Value::Object(v))
                 .map(|x| Channel::Category(Arc::new(RwLock::new(x))))
                 .map_err(DeError::custom),
-            _ => Err(DeError::custom("Unknown channel type")),
+            // Every other channel type, including ones unknown to the
+            // library, exists within a guild.
+            _ => serde_json::from_value::<GuildChannel>(Value::Object(v))
+                .map(|x| Channel::Guild(Arc::new(RwLock::new(x))))
+                .map_err(DeError::custom),
         }
     }
 }
@@ -391,51 +396,79 @@ pub enum ChannelType {
     /// An indicator that the channel is a text [`GuildChannel`].
     ///
     /// [`GuildChannel`]: struct.GuildChannel.html
-    Text = 0,
+    Text,
     /// An indicator that the channel is a [`PrivateChannel`].
     ///
     /// [`PrivateChannel`]: struct.PrivateChannel.html
-    Private = 1,
+    Private,
     /// An indicator that the channel is a voice [`GuildChannel`].
     ///
     /// [`GuildChannel`]: struct.GuildChannel.html
-    Voice = 2,
+    Voice,
     /// An indicator that the channel is the channel of a [`Group`].
     ///
     /// [`Group`]: struct.Group.html
-    Group = 3,
+    Group,
     /// An indicator that the channel is the channel of a [`ChannelCategory`].
     ///
     /// [`ChannelCategory`]: struct.ChannelCategory.html
-    Category = 4,
+    Category,
     /// An indicator that the channel is a `NewsChannel`.
     ///
     /// Note: `NewsChannel` is serialized into a [`GuildChannel`]
     ///
     /// [`GuildChannel`]: struct.GuildChannel.html
-    News = 5,
+    News,
     /// An indicator that the channel is a `StoreChannel`
     ///
     /// Note: `StoreChannel` is serialized into a [`GuildChannel`]
     ///
     /// [`GuildChannel`]: struct.GuildChannel.html
-    Store = 6,
+    Store,
+    /// An indicator that the channel is a temporary sub-channel within a
+    /// [`News`] channel.
+    ///
+    /// Note: `NewsThread` is serialized into a [`GuildChannel`]
+    ///
+    /// [`GuildChannel`]: struct.GuildChannel.html
+    /// [`News`]: #variant.News
+    NewsThread,
+    /// An indicator that the channel is a temporary sub-channel within a
+    /// [`Text`] channel.
+    ///
+    /// Note: `PublicThread` is serialized into a [`GuildChannel`]
+    ///
+    /// [`GuildChannel`]: struct.GuildChannel.html
+    /// [`Text`]: #variant.Text
+    PublicThread,
+    /// An indicator that the channel is a temporary sub-channel within a
+    /// [`Text`] channel, only viewable by those invited and those with the
+    /// Manage Threads permission.
+    ///
+    /// Note: `PrivateThread` is serialized into a [`GuildChannel`]
+    ///
+    /// [`GuildChannel`]: struct.GuildChannel.html
+    /// [`Text`]: #variant.Text
+    PrivateThread,
+    /// An indicator that the channel is a `StageChannel`.
+    ///
+    /// Note: `StageChannel` is serialized into a [`GuildChannel`]
+    ///
+    /// [`GuildChannel`]: struct.GuildChannel.html
+    Stage,
+    /// An indicator that the channel is a `ForumChannel`.
+    ///
+    /// Note: `ForumChannel` is serialized into a [`GuildChannel`]
+    ///
+    /// [`GuildChannel`]: struct.GuildChannel.html
+    Forum,
+    /// An indicator that the channel is of a type not yet known to the
+    /// library, containing the raw value sent by Discord.
+    Unknown(u64),
     #[doc(hidden)]
     __Nonexhaustive,
 }
 
-enum_number!(
-    ChannelType {
-        Text,
-        Private,
-        Voice,
-        Group,
-        Category,
-        News,
-        Store,
-    }
-);
-
 impl ChannelType {
     pub fn name(&self) -> &str {
         match *self {
@@ -446,6 +479,12 @@ impl ChannelType {
             ChannelType::Category => "category",
             ChannelType::News => "news",
             ChannelType::Store => "store",
+            ChannelType::NewsThread => "news_thread",
+            ChannelType::PublicThread => "public_thread",
+            ChannelType::PrivateThread => "private_thread",
+            ChannelType::Stage => "stage",
+            ChannelType::Forum => "forum",
+            ChannelType::Unknown(_) => "unknown",
             ChannelType::__Nonexhaustive => unreachable!(),
         }
     }
@@ -459,11 +498,45 @@ impl ChannelType {
             ChannelType::Category => 4,
             ChannelType::News => 5,
             ChannelType::Store => 6,
+            ChannelType::NewsThread => 10,
+            ChannelType::PublicThread => 11,
+            ChannelType::PrivateThread => 12,
+            ChannelType::Stage => 13,
+            ChannelType::Forum => 15,
+            ChannelType::Unknown(num) => num,
             ChannelType::__Nonexhaustive => unreachable!(),
         }
     }
+
+    /// Whether the channel type is one of the thread types.
+    pub fn is_thread(self) -> bool {
+        match self {
+            ChannelType::NewsThread
+            | ChannelType::PublicThread
+            | ChannelType::PrivateThread => true,
+            _ => false,
+        }
+    }
 }
 
+enum_number!(
+    ChannelType {
+        Text,
+        Private,
+        Voice,
+        Group,
+        Category,
+        News,
+        Store,
+        NewsThread,
+        PublicThread,
+        PrivateThread,
+        Stage,
+        Forum,
+        _ => Unknown,
+    }
+);
+
 #[derive(Deserialize, Serialize)]
 struct PermissionOverwriteData {
     allow: Permissions,
@@ -551,7 +624,6 @@ mod test {
                 name: None,
                 owner_id: UserId(2),
                 recipients: HashMap::new(),
-                _nonexhaustive: (),
             }
         }
 
@@ -570,8 +642,7 @@ mod test {
                 topic: None,
                 user_limit: None,
                 nsfw: false,
-                slow_mode_rate: Some(0),
-                _nonexhaustive: (),
+                rate_limit_per_user: Some(0),
             }
         }
 
@@ -587,9 +658,7 @@ mod test {
                     bot: false,
                     discriminator: 1,
                     name: "ab".to_string(),
-                    _nonexhaustive: (),
                 })),
-                _nonexhaustive: (),
             }
         }
 
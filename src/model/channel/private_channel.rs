@@ -22,6 +22,7 @@ use crate::http::Http;
 
 /// A Direct Message text channel with another user.
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct PrivateChannel {
     /// The unique Id of the private channel.
     ///
@@ -45,8 +46,6 @@ pub struct PrivateChannel {
             serialize_with = "serialize_single_recipient",
             rename = "recipients")]
     pub recipient: Arc<RwLock<User>>,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 #[cfg(feature = "model")]
@@ -155,8 +154,8 @@ impl PrivateChannel {
     /// [`the limit`]: ../../builder/struct.EditMessage.html#method.content
     #[cfg(feature = "http")]
     #[inline]
-    pub fn edit_message<F, M>(&self, http: impl AsRef<Http>, message_id: M, f: F) -> Result<Message>
-        where F: FnOnce(&mut EditMessage) -> &mut EditMessage, M: Into<MessageId> {
+    pub fn edit_message<'a, F, M>(&self, http: impl AsRef<Http>, message_id: M, f: F) -> Result<Message>
+        where for <'b> F: FnOnce(&'b mut EditMessage<'a>) -> &'b mut EditMessage<'a>, M: Into<MessageId> {
         self.id.edit_message(&http, message_id, f)
     }
 
@@ -23,6 +23,7 @@ use log::warn;
 
 /// An emoji reaction to a message.
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct Reaction {
     /// The [`Channel`] of the associated [`Message`].
     ///
@@ -31,6 +32,16 @@ pub struct Reaction {
     pub channel_id: ChannelId,
     /// The reactive emoji used.
     pub emoji: ReactionType,
+    /// The Id of the [`Guild`] the reaction was made in, if it was made in
+    /// one.
+    ///
+    /// [`Guild`]: ../guild/struct.Guild.html
+    pub guild_id: Option<GuildId>,
+    /// A partial amount of data about the reacting user's member data, if
+    /// the reaction was added in a guild.
+    ///
+    /// This is not present when a reaction is removed.
+    pub member: Option<PartialMember>,
     /// The Id of the [`Message`] that was reacted to.
     ///
     /// [`Message`]: struct.Message.html
@@ -39,8 +50,6 @@ pub struct Reaction {
     ///
     /// [`User`]: ../user/struct.User.html
     pub user_id: UserId,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 #[cfg(feature = "model")]
@@ -332,6 +341,23 @@ impl ReactionType {
             ReactionType::__Nonexhaustive => unreachable!(),
         }
     }
+
+    /// Returns the URL of the emoji's image if the type is a
+    /// [custom][`ReactionType::Custom`] emoji, a gif if it is animated and a
+    /// png otherwise.
+    ///
+    /// [`ReactionType::Custom`]: enum.ReactionType.html#variant.Custom
+    pub fn url(&self) -> Option<String> {
+        match *self {
+            ReactionType::Custom { animated, id, .. } => {
+                let extension = if animated {"gif"} else {"png"};
+
+                Some(cdn!("/emojis/{}.{}", id, extension))
+            },
+            ReactionType::Unicode(_) => None,
+            ReactionType::__Nonexhaustive => unreachable!(),
+        }
+    }
 }
 
 #[cfg(feature = "model")]
@@ -400,6 +426,9 @@ impl From<String> for ReactionType {
 impl<'a> From<&'a str> for ReactionType {
     /// Creates a `ReactionType` from a string slice.
     ///
+    /// The string is always taken as a unicode emoji. To also accept custom
+    /// emojis, parse the string into a `ReactionType` instead.
+    ///
     /// # Examples
     ///
     /// Creating a `ReactionType` from a `🍎`, modeling a similar API as the
@@ -417,27 +446,97 @@ impl<'a> From<&'a str> for ReactionType {
     fn from(unicode: &str) -> ReactionType { ReactionType::Unicode(unicode.to_string()) }
 }
 
-// TODO: Change this to `!` once it becomes stable.
-#[derive(Debug)]
-pub enum NeverFails {}
+/// An error returned when a string could not be parsed into a
+/// [`ReactionType`].
+///
+/// [`ReactionType`]: enum.ReactionType.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ReactionConversionError;
 
-impl Display for NeverFails {
+impl Display for ReactionConversionError {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
-        write!(f, "never fails")
+        f.write_str("failed to convert from a string to a reaction type")
     }
 }
 
-impl StdError for NeverFails {
-    fn description(&self) -> &str {
-        "never fails"
-    }
-}
+impl StdError for ReactionConversionError {}
+
+/// The former error type of parsing a [`ReactionType`], from when parsing
+/// could not fail.
+///
+/// [`ReactionType`]: enum.ReactionType.html
+#[deprecated(since="0.7.3", note="Please use `ReactionConversionError` instead.")]
+pub type NeverFails = ReactionConversionError;
 
 impl FromStr for ReactionType {
-    type Err = NeverFails;
+    type Err = ReactionConversionError;
 
-    fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
-        Ok(ReactionType::from(s))
+    /// Parses a `ReactionType` from a string slice.
+    ///
+    /// Custom emojis are accepted in their mention form, `<:name:id>` or
+    /// `<a:name:id>` when animated, or as `name:id`. Any other string without
+    /// a `:` is treated as a unicode emoji.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity::model::channel::ReactionType;
+    /// use serenity::model::id::EmojiId;
+    ///
+    /// assert_eq!(
+    ///     "<a:party:600000000000000000>".parse::<ReactionType>().unwrap(),
+    ///     ReactionType::Custom {
+    ///         animated: true,
+    ///         id: EmojiId(600000000000000000),
+    ///         name: Some("party".to_string()),
+    ///     },
+    /// );
+    ///
+    /// assert_eq!(
+    ///     "🍎".parse::<ReactionType>().unwrap(),
+    ///     ReactionType::Unicode("🍎".to_string()),
+    /// );
+    ///
+    /// assert!("<:party:notanid>".parse::<ReactionType>().is_err());
+    /// ```
+    fn from_str(emoji_string: &str) -> StdResult<Self, Self::Err> {
+        if emoji_string.is_empty() {
+            return Err(ReactionConversionError);
+        }
+
+        if !emoji_string.starts_with('<') && !emoji_string.contains(':') {
+            return Ok(ReactionType::Unicode(emoji_string.to_string()));
+        }
+
+        let (animated, emoji) = if emoji_string.starts_with('<') {
+            if !emoji_string.ends_with('>') {
+                return Err(ReactionConversionError);
+            }
+
+            let inner = &emoji_string[1..emoji_string.len() - 1];
+
+            if inner.starts_with("a:") {
+                (true, &inner[2..])
+            } else if inner.starts_with(':') {
+                (false, &inner[1..])
+            } else {
+                return Err(ReactionConversionError);
+            }
+        } else {
+            (false, emoji_string)
+        };
+
+        let mut split = emoji.splitn(2, ':');
+        let name = split.next().unwrap_or("");
+        let id = split.next()
+            .and_then(|id| id.parse::<u64>().ok())
+            .ok_or(ReactionConversionError)?;
+
+        Ok(ReactionType::Custom {
+            animated,
+            id: EmojiId(id),
+            name: if name.is_empty() { None } else { Some(name.to_string()) },
+        })
     }
 }
 
@@ -456,11 +555,16 @@ impl Display for ReactionType {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         match *self {
             ReactionType::Custom {
+                animated,
                 id,
                 ref name,
-                ..
             } => {
                 f.write_char('<')?;
+
+                if animated {
+                    f.write_char('a')?;
+                }
+
                 f.write_char(':')?;
                 f.write_str(name.as_ref().map_or("", |s| s.as_str()))?;
                 f.write_char(':')?;
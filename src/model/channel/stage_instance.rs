@@ -0,0 +1,58 @@
+use crate::model::id::{ChannelId, GuildId, StageInstanceId};
+
+/// A live stage, holding information about the [stage channel] it takes place
+/// in.
+///
+/// [stage channel]: enum.ChannelType.html#variant.Stage
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct StageInstance {
+    /// The Id of the stage instance.
+    pub id: StageInstanceId,
+    /// The Id of the guild the stage instance is in.
+    pub guild_id: GuildId,
+    /// The Id of the stage channel the stage instance is in.
+    pub channel_id: ChannelId,
+    /// The topic of the stage instance.
+    pub topic: String,
+    /// Who can see the stage instance.
+    pub privacy_level: StagePrivacyLevel,
+    /// Whether stage discovery is disabled for the stage instance.
+    #[serde(default)]
+    pub discoverable_disabled: bool,
+}
+
+/// The visibility of a [`StageInstance`].
+///
+/// [`StageInstance`]: struct.StageInstance.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum StagePrivacyLevel {
+    /// The stage instance is visible publicly, such as on stage discovery.
+    Public,
+    /// The stage instance is only visible to guild members.
+    GuildOnly,
+    /// A value not yet known to the library, containing the raw value sent
+    /// by Discord.
+    Unknown(u64),
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+enum_number!(
+    StagePrivacyLevel {
+        Public,
+        GuildOnly,
+        _ => Unknown,
+    }
+);
+
+impl StagePrivacyLevel {
+    pub fn num(self) -> u64 {
+        match self {
+            StagePrivacyLevel::Public => 1,
+            StagePrivacyLevel::GuildOnly => 2,
+            StagePrivacyLevel::Unknown(num) => num,
+            StagePrivacyLevel::__Nonexhaustive => unreachable!(),
+        }
+    }
+}
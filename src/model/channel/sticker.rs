@@ -0,0 +1,50 @@
+use crate::model::id::StickerId;
+
+/// The smallest amount of data required to render a sticker.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct StickerItem {
+    /// The unique ID given to this sticker.
+    pub id: StickerId,
+    /// The name of the sticker.
+    pub name: String,
+    /// The type of sticker format.
+    pub format_type: StickerFormatType,
+}
+
+/// Differentiates between sticker formats.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum StickerFormatType {
+    /// A PNG format sticker.
+    Png,
+    /// An animated PNG format sticker.
+    Apng,
+    /// A LOTTIE format animated sticker.
+    Lottie,
+    /// A value not yet known to the library, containing the raw value sent
+    /// by Discord.
+    Unknown(u64),
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+enum_number!(
+    StickerFormatType {
+        Png,
+        Apng,
+        Lottie,
+        _ => Unknown,
+    }
+);
+
+impl StickerFormatType {
+    pub fn num(self) -> u64 {
+        match self {
+            StickerFormatType::Png => 1,
+            StickerFormatType::Apng => 2,
+            StickerFormatType::Lottie => 3,
+            StickerFormatType::Unknown(num) => num,
+            StickerFormatType::__Nonexhaustive => unreachable!(),
+        }
+    }
+}
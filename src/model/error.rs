@@ -82,8 +82,9 @@ pub enum Error {
     /// When attempting to delete a number of days' worth of messages that is
     /// not allowed.
     DeleteMessageDaysAmount(u8),
-    /// Indicates that the textual content of an embed exceeds the maximum
-    /// length.
+    /// Indicates that the textual content of an embed, or one of its parts,
+    /// exceeds the maximum length. Contains the amount over the exceeded
+    /// limit.
     EmbedTooLarge(u64),
     /// An indication that a [guild][`Guild`] could not be found by
     /// [Id][`GuildId`] in the [`Cache`].
@@ -134,10 +135,35 @@ pub enum Error {
     /// Indicates that the current user is attempting to Direct Message another
     /// bot user, which is disallowed by the API.
     MessagingBot,
+    /// Indicates that a name given to a builder is empty.
+    NameTooShort,
+    /// Indicates that a name given to a builder, such as that of a channel or
+    /// role, is over 100 unicode code points. Contains the amount over the
+    /// limit.
+    NameTooLong(u64),
     /// An indicator that the [`ChannelType`] cannot perform an action.
     ///
     /// [`ChannelType`]: ../channel/enum.ChannelType.html
     InvalidChannelType,
+    /// An indicator that a [`GuildChannel`] does not belong to a category.
+    ///
+    /// [`GuildChannel`]: ../channel/struct.GuildChannel.html
+    NoCategory,
+    /// Indicates that the slow mode rate of a channel is over 21600 seconds.
+    /// Contains the given rate.
+    RateLimitAmount(u64),
+    /// Indicates that the topic of a channel is over 1024 unicode code points.
+    /// Contains the amount over the limit.
+    TopicTooLong(u64),
+    /// Indicates that a message has more than the 10 embeds allowed by
+    /// Discord. Contains the amount of embeds over the limit.
+    TooManyEmbeds(u64),
+    /// Indicates that an embed has more than the 25 fields allowed by
+    /// Discord. Contains the amount of fields over the limit.
+    TooManyEmbedFields(u64),
+    /// Indicates that the user limit of a voice channel is over 99. Contains
+    /// the given limit.
+    UserLimitAmount(u64),
     #[doc(hidden)]
     __Nonexhaustive,
 }
@@ -161,6 +187,14 @@ impl StdError for Error {
             Error::ItemMissing => "The required item is missing from the cache.",
             Error::MessageTooLong(_) => "Message too large.",
             Error::MessagingBot => "Attempted to message another bot user.",
+            Error::NameTooShort => "Name too short.",
+            Error::NameTooLong(_) => "Name too long.",
+            Error::NoCategory => "The channel does not belong to a category.",
+            Error::RateLimitAmount(_) => "Invalid slow mode rate.",
+            Error::TopicTooLong(_) => "Topic too long.",
+            Error::TooManyEmbeds(_) => "Too many embeds.",
+            Error::TooManyEmbedFields(_) => "Too many embed fields.",
+            Error::UserLimitAmount(_) => "Invalid user limit.",
             Error::__Nonexhaustive => unreachable!(),
         }
     }
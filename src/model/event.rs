@@ -9,7 +9,7 @@ use serde::ser::{
 };
 use serde_json;
 use std::collections::HashMap;
-use super::utils::{deserialize_emojis, deserialize_u64};
+use super::utils::{deserialize_emojis, deserialize_f64_as_u64, deserialize_u64};
 use super::prelude::*;
 use crate::constants::{OpCode, VoiceOpCode};
 use crate::internal::prelude::*;
@@ -36,17 +36,16 @@ use std::mem;
 /// [`Guild`]: ../guild/struct.Guild.html
 /// [`PrivateChannel`]: ../channel/struct.PrivateChannel.html
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub struct ChannelCreateEvent {
     /// The channel that was created.
     pub channel: Channel,
-    pub(crate) _nonexhaustive: (),
 }
 
 impl<'de> Deserialize<'de> for ChannelCreateEvent {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
         Ok(Self {
             channel: Channel::deserialize(deserializer)?,
-            _nonexhaustive: (),
         })
     }
 }
@@ -126,9 +125,9 @@ impl CacheUpdate for ChannelCreateEvent {
 }
 
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub struct ChannelDeleteEvent {
     pub channel: Channel,
-    pub(crate) _nonexhaustive: (),
 }
 
 #[cfg(feature = "cache")]
@@ -176,7 +175,6 @@ impl<'de> Deserialize<'de> for ChannelDeleteEvent {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
         Ok(Self {
             channel: Channel::deserialize(deserializer)?,
-            _nonexhaustive: (),
         })
     }
 }
@@ -189,11 +187,10 @@ impl Serialize for ChannelDeleteEvent {
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct ChannelPinsUpdateEvent {
     pub channel_id: ChannelId,
     pub last_pin_timestamp: Option<DateTime<FixedOffset>>,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 #[cfg(feature = "cache")]
@@ -231,11 +228,10 @@ impl CacheUpdate for ChannelPinsUpdateEvent {
 
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct ChannelRecipientAddEvent {
     pub channel_id: ChannelId,
     pub user: User,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 #[cfg(feature = "cache")]
@@ -256,11 +252,10 @@ impl CacheUpdate for ChannelRecipientAddEvent {
 
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct ChannelRecipientRemoveEvent {
     pub channel_id: ChannelId,
     pub user: User,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 #[cfg(feature = "cache")]
@@ -277,9 +272,9 @@ impl CacheUpdate for ChannelRecipientRemoveEvent {
 }
 
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub struct ChannelUpdateEvent {
     pub channel: Channel,
-    pub(crate) _nonexhaustive: (),
 }
 
 #[cfg(feature = "cache")]
@@ -343,7 +338,6 @@ impl<'de> Deserialize<'de> for ChannelUpdateEvent {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
         Ok(Self {
             channel: Channel::deserialize(deserializer)?,
-            _nonexhaustive: (),
         })
     }
 }
@@ -356,25 +350,23 @@ impl Serialize for ChannelUpdateEvent {
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct GuildBanAddEvent {
     pub guild_id: GuildId,
     pub user: User,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct GuildBanRemoveEvent {
     pub guild_id: GuildId,
     pub user: User,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub struct GuildCreateEvent {
     pub guild: Guild,
-    pub(crate) _nonexhaustive: (),
 }
 
 #[cfg(feature = "cache")]
@@ -406,7 +398,6 @@ impl<'de> Deserialize<'de> for GuildCreateEvent {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
         Ok(Self {
             guild: Guild::deserialize(deserializer)?,
-            _nonexhaustive: (),
         })
     }
 }
@@ -419,9 +410,9 @@ impl Serialize for GuildCreateEvent {
 }
 
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub struct GuildDeleteEvent {
     pub guild: PartialGuild,
-    pub(crate) _nonexhaustive: (),
 }
 
 #[cfg(feature = "cache")]
@@ -448,7 +439,6 @@ impl<'de> Deserialize<'de> for GuildDeleteEvent {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
         Ok(Self {
             guild: PartialGuild::deserialize(deserializer)?,
-            _nonexhaustive: (),
         })
     }
 }
@@ -461,11 +451,10 @@ impl Serialize for GuildDeleteEvent {
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct GuildEmojisUpdateEvent {
     #[serde(serialize_with = "serialize_emojis", deserialize_with = "deserialize_emojis")] pub emojis: HashMap<EmojiId, Emoji>,
     pub guild_id: GuildId,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 #[cfg(feature = "cache")]
@@ -484,17 +473,16 @@ impl CacheUpdate for GuildEmojisUpdateEvent {
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct GuildIntegrationsUpdateEvent {
     pub guild_id: GuildId,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub struct GuildMemberAddEvent {
     pub guild_id: GuildId,
     pub member: Member,
-    pub(crate) _nonexhaustive: (),
 }
 
 #[cfg(feature = "cache")]
@@ -532,7 +520,6 @@ impl<'de> Deserialize<'de> for GuildMemberAddEvent {
             guild_id,
             member: Member::deserialize(Value::Object(map))
                 .map_err(DeError::custom)?,
-            _nonexhaustive: (),
         })
     }
 }
@@ -553,11 +540,10 @@ impl Serialize for GuildMemberAddEvent {
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct GuildMemberRemoveEvent {
     pub guild_id: GuildId,
     pub user: User,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 #[cfg(feature = "cache")]
@@ -575,13 +561,14 @@ impl CacheUpdate for GuildMemberRemoveEvent {
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct GuildMemberUpdateEvent {
     pub guild_id: GuildId,
+    pub joined_at: Option<DateTime<FixedOffset>>,
     pub nick: Option<String>,
+    pub premium_since: Option<DateTime<FixedOffset>>,
     pub roles: Vec<RoleId>,
     pub user: User,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 #[cfg(feature = "cache")]
@@ -600,9 +587,14 @@ impl CacheUpdate for GuildMemberUpdateEvent {
                 let item = Some(member.clone());
 
                 member.nick.clone_from(&self.nick);
+                member.premium_since = self.premium_since;
                 member.roles.clone_from(&self.roles);
                 member.user.write().clone_from(&self.user);
 
+                if self.joined_at.is_some() {
+                    member.joined_at = self.joined_at;
+                }
+
                 found = true;
 
                 item
@@ -616,12 +608,12 @@ impl CacheUpdate for GuildMemberUpdateEvent {
                     Member {
                         deaf: false,
                         guild_id: self.guild_id,
-                        joined_at: None,
+                        joined_at: self.joined_at,
                         mute: false,
                         nick: self.nick.clone(),
+                        premium_since: self.premium_since,
                         roles: self.roles.clone(),
                         user: Arc::new(RwLock::new(self.user.clone())),
-                        _nonexhaustive: (),
                     },
                 );
             }
@@ -634,11 +626,10 @@ impl CacheUpdate for GuildMemberUpdateEvent {
 }
 
 #[derive(Clone, Debug, Serialize)]
+#[non_exhaustive]
 pub struct GuildMembersChunkEvent {
     pub guild_id: GuildId,
     pub members: HashMap<UserId, Member>,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 #[cfg(feature = "cache")]
@@ -695,17 +686,15 @@ impl<'de> Deserialize<'de> for GuildMembersChunkEvent {
         Ok(GuildMembersChunkEvent {
             guild_id,
             members,
-            _nonexhaustive: (),
         })
     }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct GuildRoleCreateEvent {
     pub guild_id: GuildId,
     pub role: Role,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 #[cfg(feature = "cache")]
@@ -725,11 +714,10 @@ impl CacheUpdate for GuildRoleCreateEvent {
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct GuildRoleDeleteEvent {
     pub guild_id: GuildId,
     pub role_id: RoleId,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 #[cfg(feature = "cache")]
@@ -745,11 +733,10 @@ impl CacheUpdate for GuildRoleDeleteEvent {
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct GuildRoleUpdateEvent {
     pub guild_id: GuildId,
     pub role: Role,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 #[cfg(feature = "cache")]
@@ -768,10 +755,9 @@ impl CacheUpdate for GuildRoleUpdateEvent {
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct GuildUnavailableEvent {
     #[serde(rename = "id")] pub guild_id: GuildId,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 #[cfg(feature = "cache")]
@@ -787,9 +773,9 @@ impl CacheUpdate for GuildUnavailableEvent {
 }
 
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub struct GuildUpdateEvent {
     pub guild: PartialGuild,
-    pub(crate) _nonexhaustive: (),
 }
 
 #[cfg(feature = "cache")]
@@ -818,7 +804,6 @@ impl<'de> Deserialize<'de> for GuildUpdateEvent {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
         Ok(Self {
             guild: PartialGuild::deserialize(deserializer)?,
-            _nonexhaustive: (),
         })
     }
 }
@@ -831,9 +816,9 @@ impl Serialize for GuildUpdateEvent {
 }
 
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub struct MessageCreateEvent {
     pub message: Message,
-    pub(crate) _nonexhaustive: (),
 }
 
 #[cfg(feature = "cache")]
@@ -842,6 +827,14 @@ impl CacheUpdate for MessageCreateEvent {
     type Output = Message;
 
     fn update(&mut self, cache: &mut Cache) -> Option<Self::Output> {
+        if let (Some(guild_id), Some(member)) = (self.message.guild_id, &self.message.member) {
+            if self.message.webhook_id.is_none() {
+                cache.update_user_entry(&self.message.author);
+            }
+
+            cache.update_partial_member(guild_id, self.message.author.id, member);
+        }
+
         let max = cache.settings().max_messages;
 
         if max == 0 {
@@ -874,7 +867,6 @@ impl<'de> Deserialize<'de> for MessageCreateEvent {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
         Ok(Self {
             message: Message::deserialize(deserializer)?,
-            _nonexhaustive: (),
         })
     }
 }
@@ -887,22 +879,21 @@ impl Serialize for MessageCreateEvent {
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct MessageDeleteBulkEvent {
     pub channel_id: ChannelId,
     pub ids: Vec<MessageId>,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct MessageDeleteEvent {
     pub channel_id: ChannelId,
     #[serde(rename = "id")] pub message_id: MessageId,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct MessageUpdateEvent {
     pub id: MessageId,
     pub channel_id: ChannelId,
@@ -919,8 +910,8 @@ pub struct MessageUpdateEvent {
     pub mention_roles: Option<Vec<RoleId>>,
     pub attachments: Option<Vec<Attachment>>,
     pub embeds: Option<Vec<Value>>,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
+    pub message_reference: Option<MessageReference>,
+    pub referenced_message: Option<Box<Message>>,
 }
 
 #[cfg(feature = "cache")]
@@ -961,6 +952,14 @@ impl CacheUpdate for MessageUpdateEvent {
                     message.pinned = pinned;
                 }
 
+                if let Some(message_reference) = self.message_reference.clone() {
+                    message.message_reference = Some(message_reference);
+                }
+
+                if let Some(referenced_message) = self.referenced_message.clone() {
+                    message.referenced_message = Some(referenced_message);
+                }
+
                 return Some(item);
             }
         }
@@ -970,12 +969,11 @@ impl CacheUpdate for MessageUpdateEvent {
 }
 
 #[derive(Clone, Debug, Serialize)]
+#[non_exhaustive]
 pub struct PresenceUpdateEvent {
     pub guild_id: Option<GuildId>,
     pub presence: Presence,
     pub roles: Option<Vec<RoleId>>,
-    #[serde(skip_serializing)]
-    pub(crate) _nonexhaustive: (),
 }
 
 #[cfg(feature = "cache")]
@@ -1016,9 +1014,9 @@ impl CacheUpdate for PresenceUpdateEvent {
                             joined_at: None,
                             mute: false,
                             nick: self.presence.nick.clone(),
+                            premium_since: None,
                             user: Arc::clone(&user),
                             roles,
-                            _nonexhaustive: (),
                         });
                     }
                 }
@@ -1056,15 +1054,14 @@ impl<'de> Deserialize<'de> for PresenceUpdateEvent {
             guild_id,
             presence,
             roles,
-            _nonexhaustive: (),
         })
     }
 }
 
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub struct PresencesReplaceEvent {
     pub presences: Vec<Presence>,
-    pub(crate) _nonexhaustive: (),
 }
 
 #[cfg(feature = "cache")]
@@ -1092,7 +1089,6 @@ impl<'de> Deserialize<'de> for PresencesReplaceEvent {
 
         Ok(Self {
             presences,
-            _nonexhaustive: (),
         })
     }
 }
@@ -1111,20 +1107,32 @@ impl Serialize for PresencesReplaceEvent {
 }
 
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub struct ReactionAddEvent {
     pub reaction: Reaction,
-    pub(crate) _nonexhaustive: (),
 }
 
 impl<'de> Deserialize<'de> for ReactionAddEvent {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
         Ok(Self {
             reaction: Reaction::deserialize(deserializer)?,
-            _nonexhaustive: (),
         })
     }
 }
 
+#[cfg(feature = "cache")]
+impl CacheUpdate for ReactionAddEvent {
+    type Output = ();
+
+    fn update(&mut self, cache: &mut Cache) -> Option<()> {
+        if let (Some(guild_id), Some(member)) = (self.reaction.guild_id, &self.reaction.member) {
+            cache.update_partial_member(guild_id, self.reaction.user_id, member);
+        }
+
+        None
+    }
+}
+
 impl Serialize for ReactionAddEvent {
     fn serialize<S>(&self, serializer: S) -> StdResult<S::Ok, S::Error>
         where S: Serializer {
@@ -1133,16 +1141,15 @@ impl Serialize for ReactionAddEvent {
 }
 
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub struct ReactionRemoveEvent {
     pub reaction: Reaction,
-    pub(crate) _nonexhaustive: (),
 }
 
 impl<'de> Deserialize<'de> for ReactionRemoveEvent {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
         Ok(Self {
             reaction: Reaction::deserialize(deserializer)?,
-            _nonexhaustive: (),
         })
     }
 }
@@ -1155,18 +1162,17 @@ impl Serialize for ReactionRemoveEvent {
 }
 
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct ReactionRemoveAllEvent {
     pub channel_id: ChannelId,
     pub message_id: MessageId,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 /// The "Ready" event, containing initial ready cache
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub struct ReadyEvent {
     pub ready: Ready,
-    pub(crate) _nonexhaustive: (),
 }
 
 #[cfg(feature = "cache")]
@@ -1214,7 +1220,6 @@ impl<'de> Deserialize<'de> for ReadyEvent {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
         Ok(Self {
             ready: Ready::deserialize(deserializer)?,
-            _nonexhaustive: (),
         })
     }
 }
@@ -1227,33 +1232,30 @@ impl Serialize for ReadyEvent {
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct ResumedEvent {
     #[serde(rename = "_trace")] pub trace: Vec<Option<String>>,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct TypingStartEvent {
     pub channel_id: ChannelId,
     pub timestamp: u64,
     pub user_id: UserId,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct UnknownEvent {
     pub kind: String,
     pub value: Value,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub struct UserUpdateEvent {
     pub current_user: CurrentUser,
-    pub(crate) _nonexhaustive: (),
 }
 
 #[cfg(feature = "cache")]
@@ -1269,7 +1271,6 @@ impl<'de> Deserialize<'de> for UserUpdateEvent {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
         Ok(Self {
             current_user: CurrentUser::deserialize(deserializer)?,
-            _nonexhaustive: (),
         })
     }
 }
@@ -1282,20 +1283,19 @@ impl Serialize for UserUpdateEvent {
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct VoiceServerUpdateEvent {
     pub channel_id: Option<ChannelId>,
     pub endpoint: Option<String>,
     pub guild_id: Option<GuildId>,
     pub token: String,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub struct VoiceStateUpdateEvent {
     pub guild_id: Option<GuildId>,
     pub voice_state: VoiceState,
-    pub(crate) _nonexhaustive: (),
 }
 
 #[cfg(feature = "cache")]
@@ -1337,7 +1337,6 @@ impl<'de> Deserialize<'de> for VoiceStateUpdateEvent {
             guild_id,
             voice_state: VoiceState::deserialize(Value::Object(map))
                 .map_err(DeError::custom)?,
-            _nonexhaustive: (),
         })
     }
 }
@@ -1360,11 +1359,10 @@ impl Serialize for VoiceStateUpdateEvent {
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct WebhookUpdateEvent {
     pub channel_id: ChannelId,
     pub guild_id: GuildId,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -1567,6 +1565,53 @@ pub enum Event {
     __Nonexhaustive,
 }
 
+impl Event {
+    /// The type of the event.
+    pub fn event_type(&self) -> EventType {
+        match *self {
+            Event::ChannelCreate(_) => EventType::ChannelCreate,
+            Event::ChannelDelete(_) => EventType::ChannelDelete,
+            Event::ChannelPinsUpdate(_) => EventType::ChannelPinsUpdate,
+            Event::ChannelRecipientAdd(_) => EventType::ChannelRecipientAdd,
+            Event::ChannelRecipientRemove(_) => EventType::ChannelRecipientRemove,
+            Event::ChannelUpdate(_) => EventType::ChannelUpdate,
+            Event::GuildBanAdd(_) => EventType::GuildBanAdd,
+            Event::GuildBanRemove(_) => EventType::GuildBanRemove,
+            Event::GuildCreate(_) => EventType::GuildCreate,
+            Event::GuildDelete(_) => EventType::GuildDelete,
+            Event::GuildEmojisUpdate(_) => EventType::GuildEmojisUpdate,
+            Event::GuildIntegrationsUpdate(_) => EventType::GuildIntegrationsUpdate,
+            Event::GuildMemberAdd(_) => EventType::GuildMemberAdd,
+            Event::GuildMemberRemove(_) => EventType::GuildMemberRemove,
+            Event::GuildMemberUpdate(_) => EventType::GuildMemberUpdate,
+            Event::GuildMembersChunk(_) => EventType::GuildMembersChunk,
+            Event::GuildRoleCreate(_) => EventType::GuildRoleCreate,
+            Event::GuildRoleDelete(_) => EventType::GuildRoleDelete,
+            Event::GuildRoleUpdate(_) => EventType::GuildRoleUpdate,
+            Event::GuildUnavailable(_) => EventType::GuildUnavailable,
+            Event::GuildUpdate(_) => EventType::GuildUpdate,
+            Event::MessageCreate(_) => EventType::MessageCreate,
+            Event::MessageDelete(_) => EventType::MessageDelete,
+            Event::MessageDeleteBulk(_) => EventType::MessageDeleteBulk,
+            Event::MessageUpdate(_) => EventType::MessageUpdate,
+            Event::PresenceUpdate(_) => EventType::PresenceUpdate,
+            Event::PresencesReplace(_) => EventType::PresencesReplace,
+            Event::ReactionAdd(_) => EventType::ReactionAdd,
+            Event::ReactionRemove(_) => EventType::ReactionRemove,
+            Event::ReactionRemoveAll(_) => EventType::ReactionRemoveAll,
+            Event::Ready(_) => EventType::Ready,
+            Event::Resumed(_) => EventType::Resumed,
+            Event::TypingStart(_) => EventType::TypingStart,
+            Event::UserUpdate(_) => EventType::UserUpdate,
+            Event::VoiceStateUpdate(_) => EventType::VoiceStateUpdate,
+            Event::VoiceServerUpdate(_) => EventType::VoiceServerUpdate,
+            Event::WebhookUpdate(_) => EventType::WebhookUpdate,
+            Event::Unknown(ref unknown) => EventType::Other(unknown.kind.clone()),
+            Event::__Nonexhaustive => unreachable!(),
+        }
+    }
+}
+
 /// Deserializes a `serde_json::Value` into an `Event`.
 ///
 /// The given `EventType` is used to determine what event to deserialize into.
@@ -1688,7 +1733,6 @@ pub fn deserialize_event_with_type(kind: EventType, v: Value) -> Result<Event> {
         EventType::Other(kind) => Event::Unknown(UnknownEvent {
             kind: kind.to_owned(),
             value: v,
-            _nonexhaustive: (),
         }),
         EventType::__Nonexhaustive => unreachable!(),
     })
@@ -1703,7 +1747,7 @@ pub fn deserialize_event_with_type(kind: EventType, v: Value) -> Result<Event> {
 /// [`EventType::ChannelCreate`].
 ///
 /// [`EventType::ChannelCreate`]: enum.EventType.html#variant.ChannelCreate
-#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
 pub enum EventType {
     /// Indicator that a channel create payload was received.
     ///
@@ -1997,84 +2041,91 @@ impl<'de> Deserialize<'de> for EventType {
 }
 
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct VoiceHeartbeat {
     pub nonce: u64,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 #[derive(Clone, Copy, Debug, Serialize)]
+#[non_exhaustive]
 pub struct VoiceHeartbeatAck {
     pub nonce: u64,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 impl<'de> Deserialize<'de> for VoiceHeartbeatAck {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
         deserialize_u64(deserializer)
-            .map(|nonce| Self { nonce, _nonexhaustive: () })
+            .map(|nonce| Self { nonce })
     }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct VoiceReady {
+    /// Erroneous since voice gateway v4, and ignored in favour of
+    /// [`VoiceHello::heartbeat_interval`].
+    ///
+    /// [`VoiceHello::heartbeat_interval`]: struct.VoiceHello.html#structfield.heartbeat_interval
+    #[serde(default)]
     pub heartbeat_interval: u64,
     pub modes: Vec<String>,
     pub ip: String, 
     pub port: u16,
     pub ssrc: u32,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct VoiceHello {
+    /// The interval in milliseconds at which to heartbeat.
+    #[serde(deserialize_with = "deserialize_f64_as_u64")]
     pub heartbeat_interval: u64,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct VoiceSessionDescription {
     pub mode: String,
     pub secret_key: Vec<u8>,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
+    /// The audio codec in use, sent since voice gateway v4.
+    #[serde(default)]
+    pub audio_codec: Option<String>,
+    /// The Id of the media session, sent since voice gateway v4.
+    #[serde(default)]
+    pub media_session_id: Option<String>,
+    /// The video codec in use, sent since voice gateway v4.
+    #[serde(default)]
+    pub video_codec: Option<String>,
 }
 
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct VoiceSpeaking {
-    pub speaking: bool,
+    pub speaking: SpeakingState,
     pub ssrc: u32,
     pub user_id: UserId,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct VoiceResume {
     pub server_id: String,
     pub session_id: String,
     pub token: String,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct VoiceClientConnect {
     pub audio_ssrc: u32,
     pub user_id: UserId,
     pub video_ssrc: u32,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct VoiceClientDisconnect {
     pub user_id: UserId,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 /// A representation of data received for [`voice`] events.
@@ -16,6 +16,7 @@ use bitflags::bitflags;
 ///
 /// This is only applicable to bot users.
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct BotGateway {
     /// Information describing how many gateway sessions you can initiate within
     /// a ratelimit period.
@@ -25,12 +26,11 @@ pub struct BotGateway {
     pub shards: u64,
     /// The gateway to connect to.
     pub url: String,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 /// Representation of an activity that a [`User`] is performing.
 #[derive(Clone, Debug, Serialize)]
+#[non_exhaustive]
 pub struct Activity {
     /// The ID of the application for the activity.
     pub application_id: Option<ApplicationId>,
@@ -60,12 +60,35 @@ pub struct Activity {
     /// [`ActivityType::Streaming`]: enum.ActivityType.html#variant.Streaming
     /// [`kind`]: #structfield.kind
     pub url: Option<String>,
-    #[serde(skip_serializing)]
-    pub(crate) _nonexhaustive: (),
 }
 
 #[cfg(feature = "model")]
 impl Activity {
+    /// Creates an `Activity` of the given kind and name, with all other
+    /// fields left empty.
+    ///
+    /// Prefer [`playing`], [`streaming`] and [`listening`] where applicable.
+    ///
+    /// [`playing`]: #method.playing
+    /// [`streaming`]: #method.streaming
+    /// [`listening`]: #method.listening
+    pub fn new(kind: ActivityType, name: &str) -> Activity {
+        Activity {
+            application_id: None,
+            assets: None,
+            details: None,
+            flags: None,
+            instance: None,
+            kind,
+            name: name.to_string(),
+            party: None,
+            secrets: None,
+            state: None,
+            timestamps: None,
+            url: None,
+        }
+    }
+
     /// Creates a `Game` struct that appears as a `Playing <name>` status.
     ///
     /// **Note**: Maximum `name` length is 128.
@@ -94,21 +117,7 @@ impl Activity {
     /// # fn main() {}
     /// ```
     pub fn playing(name: &str) -> Activity {
-        Activity {
-            application_id: None,
-            assets: None,
-            details: None,
-            flags: None,
-            instance: None,
-            kind: ActivityType::Playing,
-            name: name.to_string(),
-            party: None,
-            secrets: None,
-            state: None,
-            timestamps: None,
-            url: None,
-            _nonexhaustive: (),
-        }
+        Activity::new(ActivityType::Playing, name)
     }
 
     /// Creates an `Activity` struct that appears as a `Streaming <name>`
@@ -143,19 +152,8 @@ impl Activity {
     /// ```
     pub fn streaming(name: &str, url: &str) -> Activity {
         Activity {
-            application_id: None,
-            assets: None,
-            details: None,
-            flags: None,
-            instance: None,
-            kind: ActivityType::Streaming,
-            name: name.to_string(),
-            party: None,
-            secrets: None,
-            state: None,
-            timestamps: None,
             url: Some(url.to_string()),
-            _nonexhaustive: (),
+            ..Activity::new(ActivityType::Streaming, name)
         }
     }
 
@@ -187,21 +185,14 @@ impl Activity {
     /// # fn main() {}
     /// ```
     pub fn listening(name: &str) -> Activity {
-        Activity {
-            application_id: None,
-            assets: None,
-            details: None,
-            flags: None,
-            instance: None,
-            kind: ActivityType::Listening,
-            name: name.to_string(),
-            party: None,
-            secrets: None,
-            state: None,
-            timestamps: None,
-            url: None,
-            _nonexhaustive: (),
-        }
+        Activity::new(ActivityType::Listening, name)
+    }
+
+    /// Creates a `Game` struct that appears as a `Competing in <name>` status.
+    ///
+    /// **Note**: Maximum `name` length is 128.
+    pub fn competing(name: &str) -> Activity {
+        Activity::new(ActivityType::Competing, name)
     }
 }
 
@@ -268,13 +259,13 @@ impl<'de> Deserialize<'de> for Activity {
             state,
             timestamps,
             url,
-            _nonexhaustive: (),
         })
     }
 }
 
 /// The assets for an activity.
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct ActivityAssets {
     /// The ID for a large asset of the activity, usually a snowflake.
     pub large_image: Option<String>,
@@ -284,8 +275,6 @@ pub struct ActivityAssets {
     pub small_image: Option<String>,
     /// Text displayed when hovering over the small image of the activity.
     pub small_text: Option<String>,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 bitflags! {
@@ -309,17 +298,17 @@ bitflags! {
 
 /// Information about an activity's party.
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct ActivityParty {
     /// The ID of the party.
     pub id: Option<String>,
     /// Used to show the party's current and maximum size.
     pub size: Option<[u64; 2]>,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 /// Secrets for an activity.
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct ActivitySecrets {
     /// The secret for joining a party.
     pub join: Option<String>,
@@ -328,18 +317,25 @@ pub struct ActivitySecrets {
     pub match_: Option<String>,
     /// The secret for spectating an activity.
     pub spectate: Option<String>,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 #[derive(Clone, Copy, Debug)]
 pub enum ActivityType {
     /// An indicator that the user is playing a game.
-    Playing = 0,
+    Playing,
     /// An indicator that the user is streaming to a service.
-    Streaming = 1,
+    Streaming,
     /// An indicator that the user is listening to something.
-    Listening = 2,
+    Listening,
+    /// An indicator that the user is watching something.
+    Watching,
+    /// An indicator that the user has set a custom status.
+    Custom,
+    /// An indicator that the user is competing in something.
+    Competing,
+    /// A value not yet known to the library, containing the raw value sent
+    /// by Discord.
+    Unknown(u64),
     #[doc(hidden)]
     __Nonexhaustive,
 }
@@ -349,6 +345,10 @@ enum_number!(
         Playing,
         Streaming,
         Listening,
+        Watching,
+        Custom,
+        Competing,
+        _ => Unknown,
     }
 );
 
@@ -360,6 +360,10 @@ impl ActivityType {
             Playing => 0,
             Streaming => 1,
             Listening => 2,
+            Watching => 3,
+            Custom => 4,
+            Competing => 5,
+            Unknown(num) => num,
             __Nonexhaustive => unreachable!(),
         }
     }
@@ -375,22 +379,28 @@ impl Default for ActivityType {
 ///
 /// [`BotGateway`]: struct.BotGateway.html
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct Gateway {
     /// The gateway to connect to.
     pub url: String,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 /// Information detailing the current online status of a [`User`].
 ///
 /// [`User`]: ../user/struct.User.html
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub struct Presence {
     /// The activity that a [`User`] is performing.
     ///
     /// [`User`]: struct.User.html
     pub activity: Option<Activity>,
+    /// All activities that a [`User`] is performing, the first of which is
+    /// the [`activity`].
+    ///
+    /// [`User`]: struct.User.html
+    /// [`activity`]: #structfield.activity
+    pub activities: Vec<Activity>,
     /// The date of the last presence update.
     pub last_modified: Option<u64>,
     /// The nickname of the member, if applicable.
@@ -402,7 +412,6 @@ pub struct Presence {
     pub user_id: UserId,
     /// The associated user instance.
     pub user: Option<Arc<RwLock<User>>>,
-    pub(crate) _nonexhaustive: (),
 }
 
 impl<'de> Deserialize<'de> for Presence {
@@ -428,11 +437,17 @@ impl<'de> Deserialize<'de> for Presence {
             (user_id, None)
         };
 
+        let activities = match map.remove("activities") {
+            Some(v) => serde_json::from_value::<Option<Vec<Activity>>>(v)
+                .map_err(DeError::custom)?
+                .unwrap_or_default(),
+            None => Vec::new(),
+        };
         let activity = match map.remove("game") {
             Some(v) => serde_json::from_value::<Option<Activity>>(v)
                 .map_err(DeError::custom)?,
             None => None,
-        };
+        }.or_else(|| activities.first().cloned());
         let last_modified = match map.remove("last_modified") {
             Some(v) => serde_json::from_value::<Option<u64>>(v)
                 .map_err(DeError::custom)?,
@@ -450,12 +465,12 @@ impl<'de> Deserialize<'de> for Presence {
 
         Ok(Presence {
             activity,
+            activities,
             last_modified,
             nick,
             status,
             user,
             user_id,
-            _nonexhaustive: (),
         })
     }
 }
@@ -468,8 +483,9 @@ impl Serialize for Presence {
             id: u64,
         }
 
-        let mut state = serializer.serialize_struct("Presence", 5)?;
+        let mut state = serializer.serialize_struct("Presence", 6)?;
         state.serialize_field("game", &self.activity)?;
+        state.serialize_field("activities", &self.activities)?;
         state.serialize_field("last_modified", &self.last_modified)?;
         state.serialize_field("nick", &self.nick)?;
         state.serialize_field("status", &self.status)?;
@@ -488,6 +504,7 @@ impl Serialize for Presence {
 
 /// An initial set of information given after IDENTIFYing to the gateway.
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct Ready {
     pub guilds: Vec<GuildStatus>,
     #[serde(default, serialize_with = "serialize_presences", deserialize_with = "deserialize_presences")]
@@ -501,13 +518,12 @@ pub struct Ready {
     pub user: CurrentUser,
     #[serde(rename = "v")]
     pub version: u64,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 /// Information describing how many gateway sessions you can initiate within a
 /// ratelimit period.
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct SessionStartLimit {
     /// The number of sessions that you can still initiate within the current
     /// ratelimit period.
@@ -516,14 +532,11 @@ pub struct SessionStartLimit {
     pub reset_after: u64,
     /// The total number of session starts within the ratelimit period allowed.
     pub total: u64,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 /// Timestamps of when a user started and/or is ending their activity.
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct ActivityTimestamps {
     pub end: Option<u64>,
     pub start: Option<u64>,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
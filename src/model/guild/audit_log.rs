@@ -10,7 +10,6 @@ use serde::ser::Serializer;
 use super::super::prelude::*;
 use std::{
     collections::HashMap,
-    mem::transmute,
     fmt
 };
 
@@ -41,24 +40,28 @@ pub enum Action {
     Webhook(ActionWebhook),
     Emoji(ActionEmoji),
     MessageDelete,
+    /// An action not yet known to the library, containing the raw value sent
+    /// by Discord.
+    Unknown(u64),
     #[doc(hidden)]
     __Nonexhaustive,
 }
 
 impl Action {
-    pub fn num(&self) -> u8 {
+    pub fn num(&self) -> u64 {
         use self::Action::*;
 
         match *self {
             GuildUpdate => 1,
-            Action::Channel(ref x) => x.num(),
-            Action::ChannelOverwrite(ref x) => x.num(),
-            Action::Member(ref x) => x.num(),
-            Action::Role(ref x) => x.num(),
-            Action::Invite(ref x) => x.num(),
-            Action::Webhook(ref x) => x.num(),
-            Action::Emoji(ref x) => x.num(),
+            Action::Channel(ref x) => u64::from(x.num()),
+            Action::ChannelOverwrite(ref x) => u64::from(x.num()),
+            Action::Member(ref x) => u64::from(x.num()),
+            Action::Role(ref x) => u64::from(x.num()),
+            Action::Invite(ref x) => u64::from(x.num()),
+            Action::Webhook(ref x) => u64::from(x.num()),
+            Action::Emoji(ref x) => u64::from(x.num()),
             Action::MessageDelete => 72,
+            Action::Unknown(num) => num,
             Action::__Nonexhaustive => unreachable!(),
         }
     }
@@ -200,8 +203,8 @@ impl ActionWebhook {
 #[repr(u8)]
 pub enum ActionEmoji {
     Create = 60,
-    Delete = 61,
-    Update = 62,
+    Update = 61,
+    Delete = 62,
     #[doc(hidden)]
     __Nonexhaustive,
 }
@@ -226,14 +229,15 @@ pub struct Change {
 }
 
 #[derive(Debug)]
+#[non_exhaustive]
 pub struct AuditLogs {
     pub entries: HashMap<AuditLogEntryId, AuditLogEntry>,
     pub webhooks: Vec<Webhook>,
     pub users: Vec<User>,
-    pub(crate) _nonexhaustive: (),
 }
 
 #[derive(Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct AuditLogEntry {
     /// Determines to what entity an [`action`] was used on.
     ///
@@ -258,11 +262,10 @@ pub struct AuditLogEntry {
     pub id: AuditLogEntryId,
     /// Some optional data assosiated with this entry.
     pub options: Option<Options>,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 #[derive(Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct Options {
     /// Number of days after which inactive members were kicked.
     #[serde(default, with = "option_u64_handler")]
@@ -285,8 +288,6 @@ pub struct Options {
     /// Name of the role if type is "role"
     #[serde(default)]
     pub role_name: Option<String>,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 mod u64_handler {
@@ -372,24 +373,39 @@ mod action_handler {
             type Value = Action;
 
             fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
-                formatter.write_str("an integer between 1 to 72")
+                formatter.write_str("a positive integer")
             }
 
             // NOTE: Serde internally delegates number types below `u64` to it.
             fn visit_u64<E: de::Error>(self, value: u64) -> StdResult<Action, E> {
-                let value = value as u8;
-
                 Ok(match value {
                     1 => Action::GuildUpdate,
-                    10..=12 => Action::Channel(unsafe { transmute(value) }),
-                    13..=15 => Action::ChannelOverwrite(unsafe { transmute(value) }),
-                    20..=25 => Action::Member(unsafe { transmute(value) }),
-                    30..=32 => Action::Role(unsafe { transmute(value) }),
-                    40..=42 => Action::Invite(unsafe { transmute(value) }),
-                    50..=52 => Action::Webhook(unsafe { transmute(value) }),
-                    60..=62 => Action::Emoji(unsafe { transmute(value) }),
+                    10 => Action::Channel(ActionChannel::Create),
+                    11 => Action::Channel(ActionChannel::Update),
+                    12 => Action::Channel(ActionChannel::Delete),
+                    13 => Action::ChannelOverwrite(ActionChannelOverwrite::Create),
+                    14 => Action::ChannelOverwrite(ActionChannelOverwrite::Update),
+                    15 => Action::ChannelOverwrite(ActionChannelOverwrite::Delete),
+                    20 => Action::Member(ActionMember::Kick),
+                    21 => Action::Member(ActionMember::Prune),
+                    22 => Action::Member(ActionMember::BanAdd),
+                    23 => Action::Member(ActionMember::BanRemove),
+                    24 => Action::Member(ActionMember::Update),
+                    25 => Action::Member(ActionMember::RoleUpdate),
+                    30 => Action::Role(ActionRole::Create),
+                    31 => Action::Role(ActionRole::Update),
+                    32 => Action::Role(ActionRole::Delete),
+                    40 => Action::Invite(ActionInvite::Create),
+                    41 => Action::Invite(ActionInvite::Update),
+                    42 => Action::Invite(ActionInvite::Delete),
+                    50 => Action::Webhook(ActionWebhook::Create),
+                    51 => Action::Webhook(ActionWebhook::Update),
+                    52 => Action::Webhook(ActionWebhook::Delete),
+                    60 => Action::Emoji(ActionEmoji::Create),
+                    61 => Action::Emoji(ActionEmoji::Update),
+                    62 => Action::Emoji(ActionEmoji::Delete),
                     72 => Action::MessageDelete,
-                    _ => return Err(E::custom(format!("Unexpected action number: {}", value))),
+                    _ => Action::Unknown(value),
                 })
             }
         }
@@ -401,7 +417,7 @@ mod action_handler {
         action: &Action,
         serializer: S,
     ) -> StdResult<S::Ok, S::Error> {
-        serializer.serialize_u8(action.num())
+        serializer.serialize_u64(action.num())
     }
 }
 impl<'de> Deserialize<'de> for AuditLogs {
@@ -462,7 +478,6 @@ impl<'de> Deserialize<'de> for AuditLogs {
                         .collect(),
                     webhooks: webhooks.unwrap(),
                     users: users.unwrap(),
-                    _nonexhaustive: (),
                 })
             }
         }
@@ -5,6 +5,7 @@ use std::fmt::{
     Write as FmtWrite
 };
 use super::super::id::{EmojiId, RoleId};
+use super::super::utils::default_true;
 
 #[cfg(all(feature = "cache", feature = "model"))]
 use serde_json::json;
@@ -25,6 +26,7 @@ use crate::http::raw::Http;
 /// or via an integration. Emojis created using the API only work within the
 /// guild it was created in.
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct Emoji {
     /// Whether the emoji is animated.
     #[serde(default)]
@@ -46,8 +48,12 @@ pub struct Emoji {
     ///
     /// [`Role`]: struct.Role.html
     pub roles: Vec<RoleId>,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
+    /// Whether the emoji can be used, which may be `false` when the guild
+    /// lost the [`PremiumTier`] required for it.
+    ///
+    /// [`PremiumTier`]: enum.PremiumTier.html
+    #[serde(default = "default_true")]
+    pub available: bool,
 }
 
 #[cfg(feature = "model")]
@@ -135,6 +141,37 @@ impl Emoji {
         }
     }
 
+    /// Edits the emoji by restricting it to the given [`Role`]s. Passing no
+    /// roles removes the restriction, allowing everyone to use the emoji.
+    ///
+    /// **Note**: The [Manage Emojis] permission is required.
+    ///
+    /// [`Role`]: struct.Role.html
+    /// [Manage Emojis]: ../permissions/struct.Permissions.html#associatedconstant.MANAGE_EMOJIS
+    #[cfg(all(feature = "cache", feature = "http"))]
+    pub fn edit_roles<T, It>(&mut self, cache_and_http: T, roles: It) -> Result<()>
+    where T: AsRef<CacheRwLock> + AsRef<Http>, It: IntoIterator<Item=RoleId> {
+        match self.find_guild_id(&cache_and_http) {
+            Some(guild_id) => {
+                let roles = roles.into_iter().map(|id| id.0).collect::<Vec<u64>>();
+                let map = json!({
+                    "roles": roles,
+                });
+
+                match AsRef::<Http>::as_ref(&cache_and_http)
+                    .edit_emoji(guild_id.0, self.id.0, &map) {
+                    Ok(emoji) => {
+                        mem::replace(self, emoji);
+
+                        Ok(())
+                    },
+                    Err(why) => Err(why),
+                }
+            },
+            None => Err(Error::Model(ModelError::ItemMissing)),
+        }
+    }
+
     /// Finds the [`Guild`] that owns the emoji by looking through the Cache.
     ///
     /// [`Guild`]: struct.Guild.html
@@ -221,9 +258,10 @@ impl Display for Emoji {
     /// Formats the emoji into a string that will cause Discord clients to
     /// render the emoji.
     ///
-    /// This is in the format of: `<:NAME:EMOJI_ID>`.
+    /// This is in the format of: `<:NAME:EMOJI_ID>`, or `<a:NAME:EMOJI_ID>`
+    /// if the emoji is animated.
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
-        f.write_str("<:")?;
+        f.write_str(if self.animated { "<a:" } else { "<:" })?;
         f.write_str(&self.name)?;
         FmtWrite::write_char(f, ':')?;
         Display::fmt(&self.id, f)?;
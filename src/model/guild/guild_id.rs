@@ -134,6 +134,7 @@ impl GuildId {
     pub fn create_channel(self, http: impl AsRef<Http>, f: impl FnOnce(&mut CreateChannel) -> &mut CreateChannel) -> Result<GuildChannel> {
         let mut builder = CreateChannel::default();
         f(&mut builder);
+        builder.validate()?;
 
         let map = utils::hashmap_to_json_map(builder.0);
 
@@ -209,6 +210,7 @@ impl GuildId {
     where F: FnOnce(&mut EditRole) -> &mut EditRole {
         let mut edit_role = EditRole::default();
         f(&mut edit_role);
+        edit_role.validate()?;
         let map = utils::hashmap_to_json_map(edit_role.0);
 
         let role = http.as_ref().create_role(self.0, &map)?;
@@ -329,6 +331,29 @@ impl GuildId {
         http.as_ref().edit_emoji(self.0, emoji_id.0, &map)
     }
 
+    /// Restricts an [`Emoji`] in the guild to the given [`Role`]s. Passing no
+    /// roles removes the restriction.
+    ///
+    /// Also see [`Emoji::edit_roles`] if you have the `cache` and `methods`
+    /// features enabled.
+    ///
+    /// Requires the [Manage Emojis] permission.
+    ///
+    /// [`Emoji`]: ../guild/struct.Emoji.html
+    /// [`Emoji::edit_roles`]: ../guild/struct.Emoji.html#method.edit_roles
+    /// [`Role`]: ../guild/struct.Role.html
+    /// [Manage Emojis]: ../permissions/struct.Permissions.html#associatedconstant.MANAGE_EMOJIS
+    #[cfg(feature = "http")]
+    pub fn edit_emoji_roles<E, It>(self, http: impl AsRef<Http>, emoji_id: E, roles: It) -> Result<Emoji>
+        where E: Into<EmojiId>, It: IntoIterator<Item=RoleId> {
+        let roles = roles.into_iter().map(|id| id.0).collect::<Vec<u64>>();
+        let map = json!({
+            "roles": roles,
+        });
+
+        http.as_ref().edit_emoji(self.0, emoji_id.into().0, &map)
+    }
+
     /// Edits the properties of member of the guild, such as muting or
     /// nicknaming them.
     ///
@@ -400,6 +425,7 @@ impl GuildId {
         where F: FnOnce(&mut EditRole) -> &mut EditRole {
         let mut edit_role = EditRole::default();
         f(&mut edit_role);
+        edit_role.validate()?;
         let map = utils::hashmap_to_json_map(edit_role.0);
 
         http.as_ref().edit_role(self.0, role_id.0, &map)
@@ -454,6 +480,18 @@ impl GuildId {
     #[inline]
     pub fn to_partial_guild(self, http: impl AsRef<Http>) -> Result<PartialGuild> {http.as_ref().get_guild(self.0) }
 
+    /// Requests [`PartialGuild`] over REST API, including the
+    /// [`approximate_member_count`] and [`approximate_presence_count`].
+    ///
+    /// [`PartialGuild`]: ../guild/struct.PartialGuild.html
+    /// [`approximate_member_count`]: ../guild/struct.PartialGuild.html#structfield.approximate_member_count
+    /// [`approximate_presence_count`]: ../guild/struct.PartialGuild.html#structfield.approximate_presence_count
+    #[cfg(feature = "http")]
+    #[inline]
+    pub fn to_partial_guild_with_counts(self, http: impl AsRef<Http>) -> Result<PartialGuild> {
+        http.as_ref().get_guild_with_counts(self.0)
+    }
+
     /// Gets all integration of the guild.
     ///
     /// This performs a request over the REST API.
@@ -628,29 +666,14 @@ impl GuildId {
         http.as_ref().edit_guild_channel_positions(self.0, &Value::Array(items))
     }
 
-    /// Returns the Id of the shard associated with the guild.
+    /// Returns the Id of the shard associated with the guild, given either the
+    /// total number of shards in use or the cache to retrieve it from.
     ///
-    /// When the cache is enabled this will automatically retrieve the total
-    /// number of shards.
+    /// This is calculated as `(guild_id >> 22) % shard_count`.
     ///
-    /// **Note**: When the cache is enabled, this function unlocks the cache to
-    /// retrieve the total number of shards in use. If you already have the
-    /// total, consider using [`utils::shard_id`].
-    ///
-    /// [`utils::shard_id`]: ../../utils/fn.shard_id.html
-    #[cfg(all(feature = "cache", feature = "utils"))]
-    #[inline]
-    pub fn shard_id(self, cache: impl AsRef<CacheRwLock>) -> u64 {
-        crate::utils::shard_id(self.0, cache.as_ref().read().shard_count)
-    }
-
-    /// Returns the Id of the shard associated with the guild.
-    ///
-    /// When the cache is enabled this will automatically retrieve the total
-    /// number of shards.
-    ///
-    /// When the cache is not enabled, the total number of shards being used
-    /// will need to be passed.
+    /// **Note**: When given the cache, this function unlocks it to retrieve the
+    /// total number of shards in use. If you already have the total, consider
+    /// passing it instead.
     ///
     /// # Examples
     ///
@@ -665,9 +688,11 @@ impl GuildId {
     ///
     /// assert_eq!(guild_id.shard_id(17), 7);
     /// ```
-    #[cfg(all(feature = "utils", not(feature = "cache")))]
+    #[cfg(feature = "utils")]
     #[inline]
-    pub fn shard_id(self, shard_count: u64) -> u64 { crate::utils::shard_id(self.0, shard_count) }
+    pub fn shard_id(self, shard_count: impl utils::ShardCount) -> u64 {
+        utils::shard_id(self.0, shard_count.shard_count())
+    }
 
     /// Starts an integration sync for the given integration Id.
     ///
@@ -2,20 +2,49 @@ use super::*;
 
 /// Various information about integrations.
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct Integration {
     pub id: IntegrationId,
     pub account: IntegrationAccount,
     pub enabled: bool,
-    #[serde(rename = "expire_behaviour")] pub expire_behaviour: u64,
-    pub expire_grace_period: u64,
+    /// The behaviour of expiring subscribers.
+    ///
+    /// This is `None` for bot integrations.
+    #[serde(rename = "expire_behavior", alias = "expire_behaviour")]
+    pub expire_behaviour: Option<u64>,
+    /// The grace period in days before expiring subscribers.
+    ///
+    /// This is `None` for bot integrations.
+    pub expire_grace_period: Option<u64>,
+    #[serde(rename = "type")]
     pub kind: String,
     pub name: String,
-    pub role_id: RoleId,
-    pub synced_at: u64,
-    pub syncing: bool,
-    pub user: User,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
+    /// The Id of the role that the integration uses for subscribers.
+    ///
+    /// This is `None` for bot integrations.
+    pub role_id: Option<RoleId>,
+    /// When the integration was last synced.
+    ///
+    /// This is `None` for bot integrations.
+    pub synced_at: Option<DateTime<FixedOffset>>,
+    /// Whether the integration is syncing.
+    ///
+    /// This is `None` for bot integrations.
+    pub syncing: Option<bool>,
+    /// The user for the integration.
+    ///
+    /// This is `None` for bot integrations.
+    pub user: Option<User>,
+    /// Whether emoticons should be synced for the integration, only present
+    /// for Twitch integrations.
+    pub enable_emoticons: Option<bool>,
+    /// Whether the integration has been revoked.
+    pub revoked: Option<bool>,
+    /// The number of subscribers of the integration.
+    pub subscriber_count: Option<u64>,
+    /// The bot application of the integration, only present for bot
+    /// integrations.
+    pub application: Option<IntegrationApplication>,
 }
 
 impl From<Integration> for IntegrationId {
@@ -25,9 +54,23 @@ impl From<Integration> for IntegrationId {
 
 /// Integration account object.
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct IntegrationAccount {
     pub id: String,
     pub name: String,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
+}
+
+/// The bot application of an [`Integration`].
+///
+/// [`Integration`]: struct.Integration.html
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct IntegrationApplication {
+    pub id: ApplicationId,
+    pub name: String,
+    pub icon: Option<String>,
+    pub description: String,
+    pub summary: String,
+    /// The bot user of the application.
+    pub bot: Option<User>,
 }
@@ -58,6 +58,7 @@ impl BanOptions for (u8, String) {
 
 /// Information about a member of a guild.
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct Member {
     /// Indicator of whether the member can hear in voice channels.
     pub deaf: bool,
@@ -71,14 +72,15 @@ pub struct Member {
     ///
     /// Can't be longer than 32 characters.
     pub nick: Option<String>,
+    /// Timestamp representing the date since the member has been boosting
+    /// the guild, if they are.
+    pub premium_since: Option<DateTime<FixedOffset>>,
     /// Vector of Ids of [`Role`](struct.Role.html)s given to the member.
     pub roles: Vec<RoleId>,
     /// Attached User struct.
     #[serde(deserialize_with = "deserialize_sync_user",
             serialize_with = "serialize_sync_user")]
     pub user: Arc<RwLock<User>>,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 #[cfg(feature = "model")]
@@ -500,20 +502,35 @@ impl Display for Member {
 
 /// A partial amount of data for a member.
 ///
-/// This is used in [`Message`]s from [`Guild`]s.
+/// This is used in [`Message`]s and [`Reaction`]s from [`Guild`]s.
 ///
 /// [`Guild`]: struct.Guild.html
 /// [`Message`]: ../channel/struct.Message.html
+/// [`Reaction`]: ../channel/struct.Reaction.html
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct PartialMember {
     /// Indicator of whether the member can hear in voice channels.
+    #[serde(default)]
     pub deaf: bool,
     /// Timestamp representing the date when the member joined.
     pub joined_at: Option<DateTime<FixedOffset>>,
     /// Indicator of whether the member can speak in voice channels.
+    #[serde(default)]
     pub mute: bool,
+    /// The member's nickname, if present.
+    pub nick: Option<String>,
+    /// Timestamp representing the date since the member has been boosting
+    /// the guild, if they are.
+    pub premium_since: Option<DateTime<FixedOffset>>,
     /// Vector of Ids of [`Role`]s given to the member.
     pub roles: Vec<RoleId>,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
+    /// The user the member data belongs to.
+    ///
+    /// This is only present on [`Reaction`]s, as a [`Message`]'s author
+    /// already holds the user.
+    ///
+    /// [`Message`]: ../channel/struct.Message.html
+    /// [`Reaction`]: ../channel/struct.Reaction.html
+    pub user: Option<User>,
 }
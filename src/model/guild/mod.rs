@@ -8,6 +8,7 @@ mod partial_guild;
 mod role;
 mod audit_log;
 mod premium_tier;
+mod welcome_screen;
 
 #[cfg(feature = "http")]
 use crate::http::CacheHttp;
@@ -19,6 +20,7 @@ pub use self::partial_guild::*;
 pub use self::role::*;
 pub use self::audit_log::*;
 pub use self::premium_tier::*;
+pub use self::welcome_screen::*;
 
 use chrono::{DateTime, FixedOffset};
 use crate::model::prelude::*;
@@ -27,6 +29,8 @@ use super::utils::*;
 
 #[cfg(all(feature = "cache", feature = "model"))]
 use crate::cache::CacheRwLock;
+#[cfg(all(feature = "model", feature = "utils"))]
+use crate::utils::ShardCount;
 #[cfg(all(feature = "cache", feature = "model"))]
 use parking_lot::RwLock;
 #[cfg(all(feature = "http", feature = "model"))]
@@ -55,6 +59,7 @@ pub struct Ban {
 
 /// Information about a Discord guild, such as channels, emojis, etc.
 #[derive(Clone, Debug, Serialize)]
+#[non_exhaustive]
 pub struct Guild {
     /// Id of a voice channel that's considered the AFK channel.
     pub afk_channel_id: Option<ChannelId>,
@@ -141,6 +146,10 @@ pub struct Guild {
     /// If the [`"InviteSplash"`] feature is enabled, this can be used to generate
     /// a URL to a splash image.
     pub splash: Option<String>,
+    /// An identifying hash of the guild's discovery splash image.
+    ///
+    /// This is only present for guilds with the `DISCOVERABLE` feature.
+    pub discovery_splash: Option<String>,
     /// The ID of the channel to which system messages are sent.
     pub system_channel_id: Option<ChannelId>,
     /// Indicator of the current verification level of the guild.
@@ -165,8 +174,35 @@ pub struct Guild {
     /// The preferred locale of this guild only set if guild has the "DISCOVERABLE"
     /// feature, defaults to en-US.
     pub preferred_locale: String,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
+    /// The live [`StageInstance`]s of the guild's stage channels.
+    ///
+    /// [`StageInstance`]: ../channel/struct.StageInstance.html
+    #[serde(default)]
+    pub stage_instances: Vec<StageInstance>,
+    /// The maximum number of members for the guild.
+    pub max_members: Option<u64>,
+    /// The maximum number of presences for the guild, `None` if the default
+    /// limit applies.
+    pub max_presences: Option<u64>,
+    /// The maximum number of users in a video channel.
+    pub max_video_channel_users: Option<u64>,
+    /// The approximate number of members in the guild.
+    ///
+    /// This is only present when the guild was fetched with counts, see
+    /// [`GuildId::to_partial_guild_with_counts`].
+    ///
+    /// [`GuildId::to_partial_guild_with_counts`]: ../id/struct.GuildId.html#method.to_partial_guild_with_counts
+    pub approximate_member_count: Option<u64>,
+    /// The approximate number of non-offline members in the guild.
+    ///
+    /// This is only present when the guild was fetched with counts, see
+    /// [`GuildId::to_partial_guild_with_counts`].
+    ///
+    /// [`GuildId::to_partial_guild_with_counts`]: ../id/struct.GuildId.html#method.to_partial_guild_with_counts
+    pub approximate_presence_count: Option<u64>,
+    /// The welcome screen of the guild, if it has the
+    /// `WELCOME_SCREEN_ENABLED` feature.
+    pub welcome_screen: Option<GuildWelcomeScreen>,
 }
 
 #[cfg(feature = "model")]
@@ -811,6 +847,21 @@ impl Guild {
         }
     }
 
+    /// Returns the formatted URL of the guild's banner image, if one exists.
+    ///
+    /// Animated banners will produce a GIF URL.
+    pub fn banner_url(&self) -> Option<String> {
+        self.banner.as_ref().map(|banner| {
+            let ext = if banner.starts_with("a_") {
+                "gif"
+            } else {
+                "webp"
+            };
+
+            cdn!("/banners/{}/{}.{}?size=1024", self.id, banner, ext)
+        })
+    }
+
     /// Returns the formatted URL of the guild's icon, if one exists.
     pub fn icon_url(&self) -> Option<String> {
         self.icon
@@ -1524,27 +1575,14 @@ impl Guild {
         self.id.reorder_channels(&http, channels)
     }
 
-    /// Returns the Id of the shard associated with the guild.
+    /// Returns the Id of the shard associated with the guild, given either the
+    /// total number of shards in use or the cache to retrieve it from.
     ///
-    /// When the cache is enabled this will automatically retrieve the total
-    /// number of shards.
+    /// This is calculated as `(guild_id >> 22) % shard_count`.
     ///
-    /// **Note**: When the cache is enabled, this function unlocks the cache to
-    /// retrieve the total number of shards in use. If you already have the
-    /// total, consider using [`utils::shard_id`].
-    ///
-    /// [`utils::shard_id`]: ../../utils/fn.shard_id.html
-    #[cfg(all(feature = "cache", feature = "utils"))]
-    #[inline]
-    pub fn shard_id(&self, cache: impl AsRef<CacheRwLock>) -> u64 { self.id.shard_id(&cache) }
-
-    /// Returns the Id of the shard associated with the guild.
-    ///
-    /// When the cache is enabled this will automatically retrieve the total
-    /// number of shards.
-    ///
-    /// When the cache is not enabled, the total number of shards being used
-    /// will need to be passed.
+    /// **Note**: When given the cache, this function unlocks it to retrieve the
+    /// total number of shards in use. If you already have the total, consider
+    /// passing it instead.
     ///
     /// # Examples
     ///
@@ -1558,15 +1596,23 @@ impl Guild {
     ///
     /// assert_eq!(guild.shard_id(17), 7);
     /// ```
-    #[cfg(all(feature = "utils", not(feature = "cache")))]
+    #[cfg(feature = "utils")]
     #[inline]
-    pub fn shard_id(&self, shard_count: u64) -> u64 { self.id.shard_id(shard_count) }
+    pub fn shard_id(&self, shard_count: impl ShardCount) -> u64 { self.id.shard_id(shard_count) }
 
     /// Returns the formatted URL of the guild's splash image, if one exists.
     pub fn splash_url(&self) -> Option<String> {
-        self.icon
+        self.splash
+            .as_ref()
+            .map(|splash| cdn!("/splashes/{}/{}.webp?size=1024", self.id, splash))
+    }
+
+    /// Returns the formatted URL of the guild's discovery splash image, if one
+    /// exists.
+    pub fn discovery_splash_url(&self) -> Option<String> {
+        self.discovery_splash
             .as_ref()
-            .map(|icon| format!(cdn!("/splashes/{}/{}.webp"), self.id, icon))
+            .map(|splash| cdn!("/discovery-splashes/{}/{}.webp?size=1024", self.id, splash))
     }
 
     /// Starts an integration sync for the given integration Id.
@@ -1819,6 +1865,10 @@ impl<'de> Deserialize<'de> for Guild {
             Some(v) => Option::<String>::deserialize(v).map_err(DeError::custom)?,
             None => None,
         };
+        let discovery_splash = match map.remove("discovery_splash") {
+            Some(v) => Option::<String>::deserialize(v).map_err(DeError::custom)?,
+            None => None,
+        };
         let system_channel_id = match map.remove("system_channel_id") {
             Some(v) => Option::<ChannelId>::deserialize(v).map_err(DeError::custom)?,
             None => None,
@@ -1855,6 +1905,34 @@ impl<'de> Deserialize<'de> for Guild {
             .ok_or_else(|| DeError::custom("expected preferred locale"))
             .and_then(String::deserialize)
             .map_err(DeError::custom)?;
+        let stage_instances = match map.remove("stage_instances") {
+            Some(v) => Vec::<StageInstance>::deserialize(v).map_err(DeError::custom)?,
+            None => Vec::new(),
+        };
+        let max_members = match map.remove("max_members") {
+            Some(v) => Option::<u64>::deserialize(v).map_err(DeError::custom)?,
+            None => None,
+        };
+        let max_presences = match map.remove("max_presences") {
+            Some(v) => Option::<u64>::deserialize(v).map_err(DeError::custom)?,
+            None => None,
+        };
+        let max_video_channel_users = match map.remove("max_video_channel_users") {
+            Some(v) => Option::<u64>::deserialize(v).map_err(DeError::custom)?,
+            None => None,
+        };
+        let approximate_member_count = match map.remove("approximate_member_count") {
+            Some(v) => Option::<u64>::deserialize(v).map_err(DeError::custom)?,
+            None => None,
+        };
+        let approximate_presence_count = match map.remove("approximate_presence_count") {
+            Some(v) => Option::<u64>::deserialize(v).map_err(DeError::custom)?,
+            None => None,
+        };
+        let welcome_screen = match map.remove("welcome_screen") {
+            Some(v) => Option::<GuildWelcomeScreen>::deserialize(v).map_err(DeError::custom)?,
+            None => None,
+        };
 
         Ok(Self {
             afk_channel_id,
@@ -1878,6 +1956,7 @@ impl<'de> Deserialize<'de> for Guild {
             region,
             roles,
             splash,
+            discovery_splash,
             system_channel_id,
             verification_level,
             voice_states,
@@ -1887,7 +1966,13 @@ impl<'de> Deserialize<'de> for Guild {
             banner,
             vanity_url_code,
             preferred_locale,
-            _nonexhaustive: (),
+            stage_instances,
+            max_members,
+            max_presences,
+            max_video_channel_users,
+            approximate_member_count,
+            approximate_presence_count,
+            welcome_screen,
         })
     }
 }
@@ -2003,9 +2088,9 @@ impl From<u64> for GuildContainer {
 impl InviteGuild {
     /// Returns the formatted URL of the guild's splash image, if one exists.
     pub fn splash_url(&self) -> Option<String> {
-        self.icon
+        self.splash_hash
             .as_ref()
-            .map(|icon| format!(cdn!("/splashes/{}/{}.webp"), self.id, icon))
+            .map(|splash| cdn!("/splashes/{}/{}.webp?size=1024", self.id, splash))
     }
 }
 
@@ -2052,9 +2137,12 @@ impl GuildStatus {
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
 pub enum DefaultMessageNotificationLevel {
     /// Receive notifications for everything.
-    All = 0,
+    All,
     /// Receive only mentions.
-    Mentions = 1,
+    Mentions,
+    /// A value not yet known to the library, containing the raw value sent
+    /// by Discord.
+    Unknown(u64),
     #[doc(hidden)]
     __Nonexhaustive,
 }
@@ -2063,6 +2151,7 @@ enum_number!(
     DefaultMessageNotificationLevel {
         All,
         Mentions,
+        _ => Unknown,
     }
 );
 
@@ -2071,6 +2160,7 @@ impl DefaultMessageNotificationLevel {
         match self {
             DefaultMessageNotificationLevel::All => 0,
             DefaultMessageNotificationLevel::Mentions => 1,
+            DefaultMessageNotificationLevel::Unknown(num) => num,
             DefaultMessageNotificationLevel::__Nonexhaustive => unreachable!(),
         }
     }
@@ -2080,11 +2170,14 @@ impl DefaultMessageNotificationLevel {
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
 pub enum ExplicitContentFilter {
     /// Don't scan any messages.
-    None = 0,
+    None,
     /// Scan messages from members without a role.
-    WithoutRole = 1,
+    WithoutRole,
     /// Scan messages sent by all members.
-    All = 2,
+    All,
+    /// A value not yet known to the library, containing the raw value sent
+    /// by Discord.
+    Unknown(u64),
     #[doc(hidden)]
     __Nonexhaustive,
 }
@@ -2094,6 +2187,7 @@ enum_number!(
         None,
         WithoutRole,
         All,
+        _ => Unknown,
     }
 );
 
@@ -2103,6 +2197,7 @@ impl ExplicitContentFilter {
             ExplicitContentFilter::None => 0,
             ExplicitContentFilter::WithoutRole => 1,
             ExplicitContentFilter::All => 2,
+            ExplicitContentFilter::Unknown(num) => num,
             ExplicitContentFilter::__Nonexhaustive => unreachable!(),
         }
     }
@@ -2112,9 +2207,12 @@ impl ExplicitContentFilter {
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
 pub enum MfaLevel {
     /// MFA is disabled.
-    None = 0,
+    None,
     /// MFA is enabled.
-    Elevated = 1,
+    Elevated,
+    /// A value not yet known to the library, containing the raw value sent
+    /// by Discord.
+    Unknown(u64),
     #[doc(hidden)]
     __Nonexhaustive,
 }
@@ -2123,6 +2221,7 @@ enum_number!(
     MfaLevel {
         None,
         Elevated,
+        _ => Unknown,
     }
 );
 
@@ -2131,6 +2230,7 @@ impl MfaLevel {
         match self {
             MfaLevel::None => 0,
             MfaLevel::Elevated => 1,
+            MfaLevel::Unknown(num) => num,
             MfaLevel::__Nonexhaustive => unreachable!(),
         }
     }
@@ -2159,6 +2259,8 @@ pub enum Region {
     #[serde(rename = "vip-us-west")] VipUsWest,
     #[doc(hidden)]
     __Nonexhaustive,
+    /// A region not yet known to the library.
+    #[serde(other, rename = "unknown")] Unknown,
 }
 
 impl Region {
@@ -2182,6 +2284,7 @@ impl Region {
             Region::VipAmsterdam => "vip-amsterdam",
             Region::VipUsEast => "vip-us-east",
             Region::VipUsWest => "vip-us-west",
+            Region::Unknown => "unknown",
             Region::__Nonexhaustive => unreachable!(),
         }
     }
@@ -2194,15 +2297,18 @@ impl Region {
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
 pub enum VerificationLevel {
     /// Does not require any verification.
-    None = 0,
+    None,
     /// Must have a verified email on the user's Discord account.
-    Low = 1,
+    Low,
     /// Must also be a registered user on Discord for longer than 5 minutes.
-    Medium = 2,
+    Medium,
     /// Must also be a member of the guild for longer than 10 minutes.
-    High = 3,
+    High,
     /// Must have a verified phone on the user's Discord account.
-    Higher = 4,
+    Higher,
+    /// A value not yet known to the library, containing the raw value sent
+    /// by Discord.
+    Unknown(u64),
     #[doc(hidden)]
     __Nonexhaustive,
 }
@@ -2214,6 +2320,7 @@ enum_number!(
         Medium,
         High,
         Higher,
+        _ => Unknown,
     }
 );
 
@@ -2225,6 +2332,7 @@ impl VerificationLevel {
             VerificationLevel::Medium => 2,
             VerificationLevel::High => 3,
             VerificationLevel::Higher => 4,
+            VerificationLevel::Unknown(num) => num,
             VerificationLevel::__Nonexhaustive => unreachable!(),
         }
     }
@@ -2246,7 +2354,6 @@ mod test {
                 bot: true,
                 discriminator: 1432,
                 name: "test".to_string(),
-                _nonexhaustive: (),
             }
         }
 
@@ -2263,9 +2370,9 @@ mod test {
                 joined_at: Some(dt),
                 mute: false,
                 nick: Some("aaaa".to_string()),
+                premium_since: None,
                 roles: vec1,
                 user: u,
-                _nonexhaustive: (),
             }
         }
 
@@ -2308,6 +2415,7 @@ mod test {
                 region: "NA".to_string(),
                 roles: hm5,
                 splash: Some("asdf".to_string()),
+                discovery_splash: None,
                 verification_level: VerificationLevel::None,
                 voice_states: hm6,
                 description: None,
@@ -2319,7 +2427,13 @@ mod test {
                 banner: None,
                 vanity_url_code: Some("bruhmoment".to_string()),
                 preferred_locale: "en-US".to_string(),
-                _nonexhaustive: (),
+                stage_instances: vec![],
+                max_members: None,
+                max_presences: None,
+                max_video_channel_users: None,
+                approximate_member_count: None,
+                approximate_presence_count: None,
+                welcome_screen: None,
             }
         }
 
@@ -2342,5 +2456,17 @@ mod test {
 
             assert_eq!(lhs, gen_member().display_name());
         }
+
+        #[test]
+        fn splash_url_uses_splash_hash() {
+            let guild = gen();
+
+            assert_eq!(
+                guild.splash_url().unwrap(),
+                "https://cdn.discordapp.com/splashes/1/asdf.webp?size=1024",
+            );
+            assert!(guild.discovery_splash_url().is_none());
+            assert!(guild.banner_url().is_none());
+        }
     }
 }
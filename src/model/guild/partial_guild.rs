@@ -7,14 +7,15 @@ use super::super::utils::{deserialize_emojis, deserialize_roles, deserialize_u64
 use crate::builder::{CreateChannel, EditGuild, EditMember, EditRole};
 #[cfg(feature = "http")]
 use crate::http::Http;
-#[cfg(all(feature = "cache", feature = "utils", feature = "client"))]
-use crate::cache::CacheRwLock;
+#[cfg(all(feature = "model", feature = "utils"))]
+use crate::utils::ShardCount;
 
 /// Partial information about a [`Guild`]. This does not include information
 /// like member data.
 ///
 /// [`Guild`]: struct.Guild.html
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct PartialGuild {
     pub id: GuildId,
     pub afk_channel_id: Option<ChannelId>,
@@ -36,6 +37,7 @@ pub struct PartialGuild {
     pub region: String,
     #[serde(serialize_with = "serialize_roles", deserialize_with = "deserialize_roles")] pub roles: HashMap<RoleId, Role>,
     pub splash: Option<String>,
+    pub discovery_splash: Option<String>,
     pub verification_level: VerificationLevel,
     pub description: Option<String>,
     pub premium_tier: PremiumTier,
@@ -44,8 +46,26 @@ pub struct PartialGuild {
     pub premium_subscription_count: u64,
     pub banner: Option<String>,
     pub vanity_url_code: Option<String>,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
+    pub max_members: Option<u64>,
+    pub max_presences: Option<u64>,
+    pub max_video_channel_users: Option<u64>,
+    /// The approximate number of members in the guild.
+    ///
+    /// This is only present when the guild was fetched with counts, see
+    /// [`GuildId::to_partial_guild_with_counts`].
+    ///
+    /// [`GuildId::to_partial_guild_with_counts`]: ../id/struct.GuildId.html#method.to_partial_guild_with_counts
+    pub approximate_member_count: Option<u64>,
+    /// The approximate number of non-offline members in the guild.
+    ///
+    /// This is only present when the guild was fetched with counts, see
+    /// [`GuildId::to_partial_guild_with_counts`].
+    ///
+    /// [`GuildId::to_partial_guild_with_counts`]: ../id/struct.GuildId.html#method.to_partial_guild_with_counts
+    pub approximate_presence_count: Option<u64>,
+    /// The welcome screen of the guild, if it has the
+    /// `WELCOME_SCREEN_ENABLED` feature.
+    pub welcome_screen: Option<GuildWelcomeScreen>,
 }
 
 #[cfg(feature = "model")]
@@ -335,6 +355,21 @@ impl PartialGuild {
     #[inline]
     pub fn kick<U: Into<UserId>>(&self, http: impl AsRef<Http>, user_id: U) -> Result<()> { self.id.kick(&http, user_id) }
 
+    /// Returns the formatted URL of the guild's banner image, if one exists.
+    ///
+    /// Animated banners will produce a GIF URL.
+    pub fn banner_url(&self) -> Option<String> {
+        self.banner.as_ref().map(|banner| {
+            let ext = if banner.starts_with("a_") {
+                "gif"
+            } else {
+                "webp"
+            };
+
+            cdn!("/banners/{}/{}.{}?size=1024", self.id, banner, ext)
+        })
+    }
+
     /// Returns a formatted URL of the guild's icon, if the guild has an icon.
     pub fn icon_url(&self) -> Option<String> {
         self.icon
@@ -408,27 +443,14 @@ impl PartialGuild {
     #[cfg(feature = "http")]
     pub fn prune_count(&self, http: impl AsRef<Http>, days: u16) -> Result<GuildPrune> { self.id.prune_count(&http, days) }
 
-    /// Returns the Id of the shard associated with the guild.
+    /// Returns the Id of the shard associated with the guild, given either the
+    /// total number of shards in use or the cache to retrieve it from.
     ///
-    /// When the cache is enabled this will automatically retrieve the total
-    /// number of shards.
+    /// This is calculated as `(guild_id >> 22) % shard_count`.
     ///
-    /// **Note**: When the cache is enabled, this function unlocks the cache to
-    /// retrieve the total number of shards in use. If you already have the
-    /// total, consider using [`utils::shard_id`].
-    ///
-    /// [`utils::shard_id`]: ../../utils/fn.shard_id.html
-    #[cfg(all(feature = "cache", feature = "utils", feature = "client"))]
-    #[inline]
-    pub fn shard_id(&self, cache: impl AsRef<CacheRwLock>) -> u64 { self.id.shard_id(cache) }
-
-    /// Returns the Id of the shard associated with the guild.
-    ///
-    /// When the cache is enabled this will automatically retrieve the total
-    /// number of shards.
-    ///
-    /// When the cache is not enabled, the total number of shards being used
-    /// will need to be passed.
+    /// **Note**: When given the cache, this function unlocks it to retrieve the
+    /// total number of shards in use. If you already have the total, consider
+    /// passing it instead.
     ///
     /// # Examples
     ///
@@ -442,15 +464,23 @@ impl PartialGuild {
     ///
     /// assert_eq!(guild.shard_id(17), 7);
     /// ```
-    #[cfg(all(feature = "utils", not(feature = "cache")))]
+    #[cfg(feature = "utils")]
     #[inline]
-    pub fn shard_id(&self, shard_count: u64) -> u64 { self.id.shard_id(shard_count) }
+    pub fn shard_id(&self, shard_count: impl ShardCount) -> u64 { self.id.shard_id(shard_count) }
 
     /// Returns the formatted URL of the guild's splash image, if one exists.
     pub fn splash_url(&self) -> Option<String> {
-        self.icon
+        self.splash
+            .as_ref()
+            .map(|splash| cdn!("/splashes/{}/{}.webp?size=1024", self.id, splash))
+    }
+
+    /// Returns the formatted URL of the guild's discovery splash image, if one
+    /// exists.
+    pub fn discovery_splash_url(&self) -> Option<String> {
+        self.discovery_splash
             .as_ref()
-            .map(|icon| format!(cdn!("/splashes/{}/{}.webp"), self.id, icon))
+            .map(|splash| cdn!("/discovery-splashes/{}/{}.webp?size=1024", self.id, splash))
     }
 
     /// Starts an integration sync for the given integration Id.
@@ -6,6 +6,9 @@ pub enum PremiumTier {
     Tier1,
     Tier2,
     Tier3,
+    /// A value not yet known to the library, containing the raw value sent
+    /// by Discord.
+    Unknown(u64),
     #[doc(hidden)]
     __Nonexhaustive,
 }
@@ -16,7 +19,7 @@ enum_number!(
         Tier1,
         Tier2,
         Tier3,
-        __Nonexhaustive,
+        _ => Unknown,
     }
 );
 
@@ -27,9 +30,25 @@ impl PremiumTier {
             PremiumTier::Tier1 => 1,
             PremiumTier::Tier2 => 2,
             PremiumTier::Tier3 => 3,
+            PremiumTier::Unknown(num) => num,
             PremiumTier::__Nonexhaustive => unreachable!(),
         }
     }
+
+    /// The highest bitrate, in bits per second, which voice channels of a
+    /// guild with this tier may be set to.
+    ///
+    /// Tiers unknown to the library are given the limit of [`Tier0`].
+    ///
+    /// [`Tier0`]: #variant.Tier0
+    pub fn max_voice_bitrate(self) -> u32 {
+        match self {
+            PremiumTier::Tier1 => 128_000,
+            PremiumTier::Tier2 => 256_000,
+            PremiumTier::Tier3 => 384_000,
+            _ => 96_000,
+        }
+    }
 }
 
 impl Default for PremiumTier {
@@ -0,0 +1,30 @@
+use super::*;
+
+/// The welcome screen shown to new members of a guild with the
+/// `WELCOME_SCREEN_ENABLED` feature.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct GuildWelcomeScreen {
+    /// The server description shown in the welcome screen.
+    pub description: Option<String>,
+    /// The channels shown in the welcome screen, up to 5.
+    #[serde(default)]
+    pub welcome_channels: Vec<GuildWelcomeChannel>,
+}
+
+/// A channel shown in a guild's [`GuildWelcomeScreen`].
+///
+/// [`GuildWelcomeScreen`]: struct.GuildWelcomeScreen.html
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct GuildWelcomeChannel {
+    /// The Id of the channel.
+    pub channel_id: ChannelId,
+    /// The description shown for the channel.
+    pub description: String,
+    /// The Id of the emoji shown for the channel, if it is a custom emoji.
+    pub emoji_id: Option<EmojiId>,
+    /// The name of the emoji shown for the channel, or the unicode
+    /// character if it is a standard emoji.
+    pub emoji_name: Option<String>,
+}
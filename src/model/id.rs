@@ -1,25 +1,69 @@
 //! A collection of newtypes defining type-strong IDs.
 
-use chrono::{FixedOffset, DateTime, NaiveDateTime};
+use chrono::{FixedOffset, DateTime, NaiveDateTime, TimeZone};
 use crate::internal::prelude::*;
 use serde::de::{Deserialize, Deserializer};
 use std::fmt::{Display, Formatter, Result as FmtResult};
 use super::utils::U64Visitor;
 
+/// The first second of 2015, the epoch of Discord's snowflakes in
+/// milliseconds since the Unix epoch.
+const DISCORD_EPOCH: u64 = 1_420_070_400_000;
+
 macro_rules! id_u64 {
     ($($name:ident;)*) => {
         $(
             impl $name {
+                /// Creates the lowest possible Id created at the given time.
+                ///
+                /// This is useful for retrieving items created before or
+                /// after a date, by giving the resulting Id as a `before` or
+                /// `after` parameter. Times before the first second of 2015
+                /// result in an Id of `0`.
+                pub fn from_timestamp<Tz: TimeZone>(timestamp: DateTime<Tz>) -> Self {
+                    let millis = timestamp.timestamp_millis().max(0) as u64;
+
+                    $name(millis.saturating_sub(DISCORD_EPOCH) << 22)
+                }
+
                 /// Retrieves the time that the Id was created at.
                 pub fn created_at(&self) -> DateTime<FixedOffset> {
-                    let offset = self.0 >> 22;
-                    let secs = offset / 1000;
-                    let millis = (offset % 1000) * 1_000_000; // 1 million nanoseconds in a millisecond
+                    let timestamp = self.timestamp();
+                    let secs = timestamp / 1000;
+                    let millis = (timestamp % 1000) * 1_000_000; // 1 million nanoseconds in a millisecond
 
-                    let tm = NaiveDateTime::from_timestamp(1_420_070_400 + secs as i64, millis as u32);
+                    let tm = NaiveDateTime::from_timestamp(secs, millis as u32);
                     DateTime::from_utc(tm, FixedOffset::east(0))
                 }
 
+                /// Retrieves the time that the Id was created at, in
+                /// milliseconds since the Unix epoch.
+                #[inline]
+                pub fn timestamp(&self) -> i64 {
+                    ((self.0 >> 22) + DISCORD_EPOCH) as i64
+                }
+
+                /// Retrieves the Id of the internal worker that generated the
+                /// Id.
+                #[inline]
+                pub fn worker_id(&self) -> u8 {
+                    ((self.0 & 0x3E_0000) >> 17) as u8
+                }
+
+                /// Retrieves the Id of the internal process that generated
+                /// the Id.
+                #[inline]
+                pub fn process_id(&self) -> u8 {
+                    ((self.0 & 0x1_F000) >> 12) as u8
+                }
+
+                /// Retrieves the increment of the Id, which is incremented
+                /// for every Id generated by the same process.
+                #[inline]
+                pub fn increment(&self) -> u16 {
+                    (self.0 & 0xFFF) as u16
+                }
+
                 /// Immutably borrow inner Id.
                 #[inline]
                 pub fn as_u64(&self) -> &u64 {
@@ -115,6 +159,14 @@ pub struct MessageId(pub u64);
 #[derive(Copy, Clone, Default, Debug, Eq, Hash, PartialEq, PartialOrd, Ord, Serialize)]
 pub struct RoleId(pub u64);
 
+/// An identifier for a [`StageInstance`](../channel/struct.StageInstance.html).
+#[derive(Copy, Clone, Default, Debug, Eq, Hash, PartialEq, PartialOrd, Ord, Serialize)]
+pub struct StageInstanceId(pub u64);
+
+/// An identifier for a Sticker
+#[derive(Copy, Clone, Default, Debug, Eq, Hash, PartialEq, PartialOrd, Ord, Serialize)]
+pub struct StickerId(pub u64);
+
 /// An identifier for a User
 #[derive(Copy, Clone, Default, Debug, Eq, Hash, PartialEq, PartialOrd, Ord, Serialize)]
 pub struct UserId(pub u64);
@@ -140,7 +192,37 @@ id_u64! {
     IntegrationId;
     MessageId;
     RoleId;
+    StageInstanceId;
+    StickerId;
     UserId;
     WebhookId;
     AuditLogEntryId;
 }
+
+#[cfg(test)]
+mod test {
+    use chrono::{TimeZone, Utc};
+    use super::{MessageId, UserId};
+
+    #[test]
+    fn test_snowflake_decomposition() {
+        // 175928847299117063, as given in Discord's documentation.
+        let id = UserId(175_928_847_299_117_063);
+
+        assert_eq!(id.timestamp(), 1_462_015_105_796);
+        assert_eq!(id.worker_id(), 1);
+        assert_eq!(id.process_id(), 0);
+        assert_eq!(id.increment(), 7);
+        assert_eq!(id.created_at().timestamp_millis(), 1_462_015_105_796);
+    }
+
+    #[test]
+    fn test_from_timestamp() {
+        let time = Utc.timestamp_millis_opt(1_462_015_105_796).unwrap();
+        let id = MessageId::from_timestamp(time);
+
+        assert_eq!(id.timestamp(), 1_462_015_105_796);
+        assert_eq!(id.increment(), 0);
+        assert_eq!(MessageId::from_timestamp(Utc.timestamp_opt(0, 0).unwrap()), MessageId(0));
+    }
+}
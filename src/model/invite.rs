@@ -13,8 +13,8 @@ use crate::internal::prelude::*;
 use super::{Permissions, utils as model_utils};
 #[cfg(feature = "model")]
 use crate::utils;
-#[cfg(feature = "cache")]
-use crate::cache::CacheRwLock;
+#[cfg(all(feature = "model", feature = "utils"))]
+use crate::utils::ShardCount;
 #[cfg(feature = "http")]
 use crate::http::Http;
 use std::ops::Deref;
@@ -23,6 +23,7 @@ use std::ops::Deref;
 ///
 /// Information can not be accessed for guilds the current user is banned from.
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct Invite {
     /// The approximate number of [`Member`]s in the related [`Guild`].
     ///
@@ -63,8 +64,17 @@ pub struct Invite {
     ///
     /// [`User`]: ../user/struct.User.html
     pub inviter: Option<InviteUser>,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
+    /// The user whose stream is displayed by the invite, if the
+    /// [`target_type`] is [`InviteTargetType::Stream`].
+    ///
+    /// [`target_type`]: #structfield.target_type
+    /// [`InviteTargetType::Stream`]: enum.InviteTargetType.html#variant.Stream
+    pub target_user: Option<User>,
+    /// The kind of target of a voice channel invite, if any.
+    pub target_type: Option<InviteTargetType>,
+    /// When the invite expires, if it was fetched with its expiration and
+    /// is not permanent.
+    pub expires_at: Option<DateTime<FixedOffset>>,
 }
 
 #[cfg(feature = "model")]
@@ -201,13 +211,12 @@ impl Invite {
 
 /// A minimal amount of information about the inviter (person who created the invite).
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct InviteUser {
     pub id: UserId,
     #[serde(rename = "username")] pub name: String,
     #[serde(deserialize_with = "deserialize_u16")] pub discriminator: u16,
     pub avatar: Option<String>,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 /// InviteUser implements a Deref to UserId so it gains the convenience methods
@@ -224,16 +233,16 @@ impl Deref for InviteUser {
 
 /// A minimal amount of information about the channel an invite points to.
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct InviteChannel {
     pub id: ChannelId,
     pub name: String,
     #[serde(rename = "type")] pub kind: ChannelType,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 /// A minimal amount of information about the guild an invite points to.
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct InviteGuild {
     pub id: GuildId,
     pub icon: Option<String>,
@@ -241,33 +250,18 @@ pub struct InviteGuild {
     pub splash_hash: Option<String>,
     pub text_channel_count: Option<u64>,
     pub voice_channel_count: Option<u64>,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 #[cfg(feature = "model")]
 impl InviteGuild {
-    /// Returns the Id of the shard associated with the guild.
+    /// Returns the Id of the shard associated with the guild, given either the
+    /// total number of shards in use or the cache to retrieve it from.
     ///
-    /// When the cache is enabled this will automatically retrieve the total
-    /// number of shards.
+    /// This is calculated as `(guild_id >> 22) % shard_count`.
     ///
-    /// **Note**: When the cache is enabled, this function unlocks the cache to
-    /// retrieve the total number of shards in use. If you already have the
-    /// total, consider using [`utils::shard_id`].
-    ///
-    /// [`utils::shard_id`]: ../../utils/fn.shard_id.html
-    #[cfg(all(feature = "cache", feature = "utils"))]
-    #[inline]
-    pub fn shard_id(&self, cache: impl AsRef<CacheRwLock>) -> u64 { self.id.shard_id(&cache) }
-
-    /// Returns the Id of the shard associated with the guild.
-    ///
-    /// When the cache is enabled this will automatically retrieve the total
-    /// number of shards.
-    ///
-    /// When the cache is not enabled, the total number of shards being used
-    /// will need to be passed.
+    /// **Note**: When given the cache, this function unlocks it to retrieve the
+    /// total number of shards in use. If you already have the total, consider
+    /// passing it instead.
     ///
     /// # Examples
     ///
@@ -281,9 +275,42 @@ impl InviteGuild {
     ///
     /// assert_eq!(guild.shard_id(17), 7);
     /// ```
-    #[cfg(all(feature = "utils", not(feature = "cache")))]
+    #[cfg(feature = "utils")]
     #[inline]
-    pub fn shard_id(&self, shard_count: u64) -> u64 { self.id.shard_id(shard_count) }
+    pub fn shard_id(&self, shard_count: impl ShardCount) -> u64 { self.id.shard_id(shard_count) }
+}
+
+/// The kind of target of a voice channel invite.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum InviteTargetType {
+    /// The invite displays a user's stream in the channel.
+    Stream,
+    /// The invite opens an embedded application in the channel.
+    EmbeddedApplication,
+    /// A value not yet known to the library, containing the raw value sent
+    /// by Discord.
+    Unknown(u64),
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+enum_number!(
+    InviteTargetType {
+        Stream,
+        EmbeddedApplication,
+        _ => Unknown,
+    }
+);
+
+impl InviteTargetType {
+    pub fn num(self) -> u64 {
+        match self {
+            InviteTargetType::Stream => 1,
+            InviteTargetType::EmbeddedApplication => 2,
+            InviteTargetType::Unknown(num) => num,
+            InviteTargetType::__Nonexhaustive => unreachable!(),
+        }
+    }
 }
 
 /// Detailed information about an invite.
@@ -294,6 +321,7 @@ impl InviteGuild {
 /// [`Invite`]: struct.Invite.html
 /// [Manage Guild]: ../permissions/struct.Permissions.html#associatedconstant.MANAGE_GUILD
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct RichInvite {
     /// A representation of the minimal amount of information needed about the
     /// channel being invited to.
@@ -312,7 +340,10 @@ pub struct RichInvite {
     /// [`Group`]: ../channel/struct.Group.html
     pub guild: Option<InviteGuild>,
     /// The user that created the invite.
-    pub inviter: User,
+    ///
+    /// This can be `None` for invites created by Discord such as invite-widgets
+    /// or vanity invite links.
+    pub inviter: Option<User>,
     /// The maximum age of the invite in seconds, from when it was created.
     pub max_age: u64,
     /// The maximum number of times that an invite may be used before it expires.
@@ -331,8 +362,16 @@ pub struct RichInvite {
     pub temporary: bool,
     /// The amount of times that an invite has been used.
     pub uses: u64,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
+    /// The user whose stream is displayed by the invite, if the
+    /// [`target_type`] is [`InviteTargetType::Stream`].
+    ///
+    /// [`target_type`]: #structfield.target_type
+    /// [`InviteTargetType::Stream`]: enum.InviteTargetType.html#variant.Stream
+    pub target_user: Option<User>,
+    /// The kind of target of a voice channel invite, if any.
+    pub target_type: Option<InviteTargetType>,
+    /// When the invite expires, `None` if it is permanent.
+    pub expires_at: Option<DateTime<FixedOffset>>,
 }
 
 #[cfg(feature = "model")]
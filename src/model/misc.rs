@@ -15,12 +15,22 @@ use std::fmt;
 use crate::utils;
 
 /// Allows something - such as a channel or role - to be mentioned in a message.
+///
+/// This is used by [`MessageBuilder::mention`], so that every kind of mention
+/// is formatted the same way.
+///
+/// [`MessageBuilder::mention`]: ../../utils/struct.MessageBuilder.html#method.mention
 pub trait Mentionable {
     /// Creates a mentionable string, that will be able to notify and/or create
     /// a link to the item.
     fn mention(&self) -> String;
 }
 
+impl<'a, T: Mentionable + ?Sized> Mentionable for &'a T {
+    #[inline]
+    fn mention(&self) -> String { (**self).mention() }
+}
+
 impl Mentionable for ChannelId {
     fn mention(&self) -> String { format!("<#{}>", self.0) }
 }
@@ -39,7 +49,7 @@ impl Mentionable for Channel {
 
 impl Mentionable for ChannelCategory {
     fn mention(&self) -> String {
-        format!("<#{}>", self.name)
+        format!("<#{}>", self.id.0)
     }
 }
 
@@ -50,6 +60,10 @@ impl Mentionable for CurrentUser {
 }
 
 impl Mentionable for Emoji {
+    fn mention(&self) -> String { self.to_string() }
+}
+
+impl Mentionable for EmojiIdentifier {
     fn mention(&self) -> String { format!("<:{}:{}>", self.name, self.id.0) }
 }
 
@@ -224,16 +238,16 @@ impl FromStr for EmojiIdentifier {
 ///
 /// This is pulled from the Discord status page.
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct AffectedComponent {
     pub name: String,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 /// An incident retrieved from the Discord status page.
 ///
 /// This is not necessarily a representation of an ongoing incident.
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct Incident {
     pub created_at: String,
     pub id: String,
@@ -246,8 +260,6 @@ pub struct Incident {
     pub short_link: String,
     pub status: String,
     pub updated_at: String,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 /// An update to an incident from the Discord status page.
@@ -255,6 +267,7 @@ pub struct Incident {
 /// This will typically state what new information has been discovered about an
 /// incident.
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct IncidentUpdate {
     pub affected_components: Vec<AffectedComponent>,
     pub body: String,
@@ -264,8 +277,6 @@ pub struct IncidentUpdate {
     pub incident_id: String,
     pub status: IncidentStatus,
     pub updated_at: String,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 /// The type of status update during a service incident.
@@ -279,19 +290,21 @@ pub enum IncidentStatus {
     Resolved,
     #[doc(hidden)]
     __Nonexhaustive,
+    /// A status not yet known to the library.
+    #[serde(other)]
+    Unknown,
 }
 
 /// A Discord status maintenance message. This can be either for active
 /// maintenances or for scheduled maintenances.
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct Maintenance {
     pub description: String,
     pub id: String,
     pub name: String,
     pub start: String,
     pub stop: String,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 #[cfg(test)]
@@ -330,8 +343,7 @@ mod test {
                 topic: None,
                 user_limit: None,
                 nsfw: false,
-                slow_mode_rate: Some(0),
-                _nonexhaustive: (),
+                rate_limit_per_user: Some(0),
             })));
             let emoji = Emoji {
                 animated: false,
@@ -340,7 +352,7 @@ mod test {
                 managed: true,
                 require_colons: true,
                 roles: vec![],
-                _nonexhaustive: (),
+                available: true,
             };
             let role = Role {
                 id: RoleId(2),
@@ -351,7 +363,6 @@ mod test {
                 name: "fake role".to_string(),
                 permissions: Permissions::empty(),
                 position: 1,
-                _nonexhaustive: (),
             };
             let user = User {
                 id: UserId(6),
@@ -359,7 +370,6 @@ mod test {
                 bot: false,
                 discriminator: 4132,
                 name: "fake".to_string(),
-                _nonexhaustive: (),
             };
             let member = Member {
                 deaf: false,
@@ -367,19 +377,22 @@ mod test {
                 joined_at: None,
                 mute: false,
                 nick: None,
+                premium_since: None,
                 roles: vec![],
                 user: Arc::new(RwLock::new(user.clone())),
-                _nonexhaustive: (),
             };
 
             assert_eq!(ChannelId(1).mention(), "<#1>");
             assert_eq!(channel.mention(), "<#4>");
             assert_eq!(emoji.mention(), "<:a:5>");
+            assert_eq!(Emoji { animated: true, ..emoji.clone() }.mention(), "<a:a:5>");
+            assert_eq!(EmojiIdentifier { id: EmojiId(5), name: "a".to_string() }.mention(), "<:a:5>");
             assert_eq!(member.mention(), "<@6>");
             assert_eq!(role.mention(), "<@&2>");
             assert_eq!(role.id.mention(), "<@&2>");
             assert_eq!(user.mention(), "<@6>");
             assert_eq!(user.id.mention(), "<@6>");
+            assert_eq!((&user).mention(), "<@6>");
         }
     }
 }
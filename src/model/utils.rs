@@ -236,6 +236,12 @@ pub fn deserialize_u64_or_zero<'de, D: Deserializer<'de>>(deserializer: D) -> St
     deserialize_u64(deserializer).or(Ok(0))
 }
 
+/// Deserializes an u64 which may be sent as a float, such as the voice
+/// gateway's heartbeat interval, truncating any fractional part.
+pub fn deserialize_f64_as_u64<'de, D: Deserializer<'de>>(deserializer: D) -> StdResult<u64, D::Error> {
+    f64::deserialize(deserializer).map(|num| num as u64)
+}
+
 #[allow(clippy::trivially_copy_pass_by_ref)]
 pub fn serialize_u64<S: Serializer>(data: &u64, ser: S) -> StdResult<S::Ok, S::Error> {
     ser.serialize_str(&data.to_string())
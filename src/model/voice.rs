@@ -1,9 +1,20 @@
 //! Representations of voice information.
 
+use bitflags::__impl_bitflags;
+use chrono::{DateTime, FixedOffset};
+use serde::{
+    de::{Deserialize, Deserializer, Error as DeError, Visitor},
+    ser::{Serialize, Serializer},
+};
+use std::{
+    fmt::{Formatter, Result as FmtResult},
+    result::Result as StdResult,
+};
 use super::id::{ChannelId, UserId};
 
 /// Information about an available voice region.
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct VoiceRegion {
     /// Whether it is a custom voice region, which is used for events.
     pub custom: bool,
@@ -21,22 +32,130 @@ pub struct VoiceRegion {
     pub sample_port: u64,
     /// Indicator of whether the voice region is only for VIP guilds.
     pub vip: bool,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 /// A user's state within a voice channel.
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct VoiceState {
     pub channel_id: Option<ChannelId>,
     pub deaf: bool,
     pub mute: bool,
     pub self_deaf: bool,
     pub self_mute: bool,
+    #[serde(default)]
+    pub self_stream: bool,
+    #[serde(default)]
+    pub self_video: bool,
     pub session_id: String,
     pub suppress: bool,
     pub token: Option<String>,
     pub user_id: UserId,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
+    pub request_to_speak_timestamp: Option<DateTime<FixedOffset>>,
+}
+
+/// The ways in which a user is speaking in a voice channel.
+///
+/// Sent by the voice gateway for other users, and sent by the library for the
+/// current user while it plays audio.
+#[derive(Copy, PartialEq, Eq, Clone, PartialOrd, Ord, Hash)]
+pub struct SpeakingState {
+    pub bits: u8,
+}
+
+__impl_bitflags! {
+    SpeakingState: u8 {
+        /// Normal transmission of voice audio.
+        MICROPHONE = 1;
+        /// Transmission of context audio for video, such as screen sharing.
+        ///
+        /// No speaking indicator is shown for this audio.
+        SOUNDSHARE = 1 << 1;
+        /// Priority speech, lowering the volume of other speakers.
+        PRIORITY = 1 << 2;
+    }
+}
+
+impl SpeakingState {
+    /// Whether the user is speaking in any way.
+    #[inline]
+    pub fn is_speaking(self) -> bool {
+        !self.is_empty()
+    }
+}
+
+impl Default for SpeakingState {
+    fn default() -> Self {
+        SpeakingState::empty()
+    }
+}
+
+impl From<bool> for SpeakingState {
+    /// Converts whether a user is speaking, as sent by version 3 of the voice
+    /// gateway, into the [`MICROPHONE`] flag.
+    ///
+    /// [`MICROPHONE`]: #associatedconstant.MICROPHONE
+    fn from(speaking: bool) -> Self {
+        if speaking {
+            SpeakingState::MICROPHONE
+        } else {
+            SpeakingState::empty()
+        }
+    }
+}
+
+struct SpeakingStateVisitor;
+
+impl<'de> Visitor<'de> for SpeakingStateVisitor {
+    type Value = SpeakingState;
+
+    fn expecting(&self, formatter: &mut Formatter<'_>) -> FmtResult {
+        formatter.write_str("speaking flags or a boolean")
+    }
+
+    fn visit_bool<E: DeError>(self, v: bool) -> StdResult<Self::Value, E> {
+        Ok(SpeakingState::from(v))
+    }
+
+    fn visit_i64<E: DeError>(self, v: i64) -> StdResult<Self::Value, E> {
+        Ok(SpeakingState::from_bits_truncate(v as u8))
+    }
+
+    fn visit_u64<E: DeError>(self, v: u64) -> StdResult<Self::Value, E> {
+        Ok(SpeakingState::from_bits_truncate(v as u8))
+    }
+}
+
+impl<'de> Deserialize<'de> for SpeakingState {
+    fn deserialize<D>(deserializer: D) -> StdResult<Self, D::Error>
+    where D: Deserializer<'de>
+    {
+        deserializer.deserialize_any(SpeakingStateVisitor)
+    }
+}
+
+impl Serialize for SpeakingState {
+    fn serialize<S>(&self, serializer: S) -> StdResult<S::Ok, S::Error>
+    where S: Serializer
+    {
+        serializer.serialize_u8(self.bits())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+    use super::SpeakingState;
+
+    #[test]
+    fn test_speaking_state_deserialize() {
+        let v3: SpeakingState = serde_json::from_value(json!(true)).unwrap();
+        assert_eq!(v3, SpeakingState::MICROPHONE);
+
+        let v4: SpeakingState = serde_json::from_value(json!(5)).unwrap();
+        assert_eq!(v4, SpeakingState::MICROPHONE | SpeakingState::PRIORITY);
+
+        let silent: SpeakingState = serde_json::from_value(json!(false)).unwrap();
+        assert!(!silent.is_speaking());
+    }
 }
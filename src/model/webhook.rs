@@ -26,6 +26,7 @@ use crate::http::Http;
 /// channels. They do not necessarily require a bot user or authentication to
 /// use.
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[non_exhaustive]
 pub struct Webhook {
     /// The unique Id.
     ///
@@ -53,12 +54,53 @@ pub struct Webhook {
     ///
     /// **Note**: This is not received when getting a webhook by its token.
     pub user: Option<User>,
-    #[serde(skip)]
-    pub(crate) _nonexhaustive: (),
 }
 
 #[cfg(feature = "model")]
 impl Webhook {
+    /// Creates a webhook from its Id and token, without requesting it over
+    /// the REST API.
+    ///
+    /// The returned webhook can be [executed][`execute`], [edited][`edit`] and
+    /// [deleted][`delete`] right away. Only its `id` and `token` are known,
+    /// its `channel_id` is set to `0` and all other fields are empty until
+    /// the webhook is [refreshed][`refresh`] or edited.
+    ///
+    /// # Examples
+    ///
+    /// Execute a webhook without fetching it first:
+    ///
+    /// ```rust,no_run
+    /// use serenity::http::Http;
+    /// use serenity::model::webhook::Webhook;
+    /// # use std::sync::Arc;
+    /// #
+    /// # let http = Arc::new(Http::default());
+    ///
+    /// let id = 245037420704169985;
+    /// let token = "ig5AO-wdVWpCBtUUMxmgsWryqgsW3DChbKYOINftJ4DCrUbnkedoYZD0VOH1QLr-S3sV";
+    ///
+    /// let webhook = Webhook::from_id_token(id, token);
+    ///
+    /// let _ = webhook.execute(&http, false, |w| w.content("test"));
+    /// ```
+    ///
+    /// [`delete`]: #method.delete
+    /// [`edit`]: #method.edit
+    /// [`execute`]: #method.execute
+    /// [`refresh`]: #method.refresh
+    pub fn from_id_token<W: Into<WebhookId>>(id: W, token: impl Into<String>) -> Self {
+        Webhook {
+            id: id.into(),
+            avatar: None,
+            channel_id: ChannelId(0),
+            guild_id: None,
+            name: None,
+            token: token.into(),
+            user: None,
+        }
+    }
+
     /// Deletes the webhook.
     ///
     /// As this calls the [`http::delete_webhook_with_token`] function,
@@ -219,6 +261,8 @@ impl Webhook {
         f(&mut execute_webhook);
         let map = utils::hashmap_to_json_map(execute_webhook.0);
 
+        Message::check_embed_length(&map)?;
+
      http.as_ref().execute_webhook(self.id.0, &self.token, wait, &map)
     }
 
@@ -26,7 +26,8 @@ pub use crate::gateway::GatewayError;
 pub use crate::http::HttpError;
 #[cfg(feature = "model")]
 pub use crate::model::ModelError;
-#[cfg(feature = "typemap")]
-pub use typemap::{Key as TypeMapKey, ShareMap};
+#[cfg(feature = "client")]
+#[allow(deprecated)]
+pub use crate::client::{ShareMap, TypeMap, TypeMapKey};
 #[cfg(feature = "voice")]
 pub use crate::voice::VoiceError;
@@ -0,0 +1,318 @@
+use crate::http::{CacheHttp, HttpError, StatusCode};
+use crate::internal::prelude::*;
+use crate::model::prelude::*;
+use super::{parse_channel, parse_role, parse_username};
+use std::{
+    error::Error as StdError,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+
+/// A type that can be resolved from a command argument, such as a mention,
+/// a raw Id or a name.
+///
+/// The cache is searched first when it is available, falling back to
+/// requesting the item over HTTP.
+///
+/// # Examples
+///
+/// Resolve the member given as the first argument of a command:
+///
+/// ```rust,ignore
+/// use serenity::utils::ArgumentConvert;
+///
+/// // assuming a `ctx`, `msg` and `args` have already been bound
+///
+/// let member = Member::convert(&ctx, msg.guild_id, args.current().unwrap())?;
+/// ```
+pub trait ArgumentConvert: Sized {
+    /// Resolves the item from the argument `s`, within the context of the
+    /// guild, if any, that the argument was given in.
+    fn convert(cache_http: impl CacheHttp, guild_id: Option<GuildId>, s: &str) -> StdResult<Self, ArgumentConvertError>;
+}
+
+/// An error returned when an [`ArgumentConvert`] implementation could not
+/// resolve an argument.
+///
+/// [`ArgumentConvert`]: trait.ArgumentConvert.html
+#[derive(Debug)]
+pub enum ArgumentConvertError {
+    /// The item can only be resolved within a guild, but no guild was given.
+    NoGuild,
+    /// No item matching the argument could be found, including when Discord
+    /// responded that an item with the given Id does not exist.
+    NotFound,
+    /// An error occurred while requesting the item over HTTP.
+    Http(Error),
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+impl Display for ArgumentConvertError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match *self {
+            ArgumentConvertError::NoGuild => f.write_str("The argument can only be resolved within a guild."),
+            ArgumentConvertError::NotFound => f.write_str("No item matching the argument was found."),
+            ArgumentConvertError::Http(ref inner) => Display::fmt(inner, f),
+            ArgumentConvertError::__Nonexhaustive => unreachable!(),
+        }
+    }
+}
+
+impl From<Error> for ArgumentConvertError {
+    /// Maps a 404 response, as given for Ids which do not exist, to
+    /// `NotFound`, and other errors to `Http`.
+    fn from(error: Error) -> Self {
+        if let Error::Http(ref inner) = error {
+            if let HttpError::UnsuccessfulRequest(ref response) = **inner {
+                if response.status_code == StatusCode::NOT_FOUND {
+                    return ArgumentConvertError::NotFound;
+                }
+            }
+        }
+
+        ArgumentConvertError::Http(error)
+    }
+}
+
+impl StdError for ArgumentConvertError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match *self {
+            ArgumentConvertError::Http(ref inner) => Some(inner),
+            _ => None,
+        }
+    }
+}
+
+/// Parses an Id from either a mention, using `parse_mention`, or a raw Id.
+fn parse_id(s: &str, parse_mention: fn(&str) -> Option<u64>) -> Option<u64> {
+    parse_mention(s).or_else(|| s.parse().ok())
+}
+
+/// Checks whether a user is referred to by either their name or their tag.
+fn user_matches(user: &User, s: &str) -> bool {
+    user.name == s || user.tag() == s
+}
+
+/// Checks whether a member is referred to by either their user's name or
+/// tag, or by their nickname.
+fn member_matches(member: &Member, s: &str) -> bool {
+    user_matches(&member.user.read(), s) || member.nick.as_ref().map_or(false, |nick| nick == s)
+}
+
+/// Resolves a [`UserId`] from a mention, a raw Id, a name or a tag.
+///
+/// Mentions and raw Ids are parsed without a lookup. Names and tags are
+/// resolved against the members of the guild, if any, and then against the
+/// users in the cache.
+///
+/// [`UserId`]: ../model/id/struct.UserId.html
+impl ArgumentConvert for UserId {
+    fn convert(cache_http: impl CacheHttp, guild_id: Option<GuildId>, s: &str) -> StdResult<Self, ArgumentConvertError> {
+        if let Some(id) = parse_id(s, |s| parse_username(s)) {
+            return Ok(UserId(id));
+        }
+
+        if let Some(guild_id) = guild_id {
+            match find_member(&cache_http, guild_id, s) {
+                Ok(member) => return Ok(member.user.read().id),
+                Err(ArgumentConvertError::NotFound) => {},
+                Err(why) => return Err(why),
+            }
+        }
+
+        #[cfg(feature = "cache")]
+        {
+            if let Some(cache) = cache_http.cache() {
+                let cache = cache.read();
+                let found = cache.users.values().find(|user| user_matches(&user.read(), s));
+
+                if let Some(user) = found {
+                    return Ok(user.read().id);
+                }
+            }
+        }
+
+        Err(ArgumentConvertError::NotFound)
+    }
+}
+
+/// Resolves a [`Member`] of the guild from a mention, a raw Id, a name, a tag
+/// or a nickname.
+///
+/// When searching by name over HTTP, only the first 1000 members of the guild
+/// are searched.
+///
+/// [`Member`]: ../model/guild/struct.Member.html
+impl ArgumentConvert for Member {
+    fn convert(cache_http: impl CacheHttp, guild_id: Option<GuildId>, s: &str) -> StdResult<Self, ArgumentConvertError> {
+        let guild_id = guild_id.ok_or(ArgumentConvertError::NoGuild)?;
+
+        find_member(&cache_http, guild_id, s)
+    }
+}
+
+fn find_member<C: CacheHttp>(cache_http: &C, guild_id: GuildId, s: &str) -> StdResult<Member, ArgumentConvertError> {
+    let id = parse_id(s, |s| parse_username(s)).map(UserId);
+
+    #[cfg(feature = "cache")]
+    {
+        if let Some(cache) = cache_http.cache() {
+            if let Some(guild) = cache.read().guild(guild_id) {
+                let guild = guild.read();
+                let found = match id {
+                    Some(id) => guild.members.get(&id),
+                    None => guild.members.values().find(|member| member_matches(member, s)),
+                };
+
+                if let Some(member) = found {
+                    return Ok(member.clone());
+                }
+            }
+        }
+    }
+
+    if let Some(id) = id {
+        return cache_http.http().get_member(guild_id.0, id.0).map_err(ArgumentConvertError::from);
+    }
+
+    let members = cache_http.http()
+        .get_guild_members(guild_id.0, Some(1000), None)
+        .map_err(ArgumentConvertError::from)?;
+
+    members
+        .into_iter()
+        .find(|member| member_matches(member, s))
+        .ok_or(ArgumentConvertError::NotFound)
+}
+
+/// Resolves a [`Role`] of the guild from a mention, a raw Id or a name.
+///
+/// [`Role`]: ../model/guild/struct.Role.html
+impl ArgumentConvert for Role {
+    fn convert(cache_http: impl CacheHttp, guild_id: Option<GuildId>, s: &str) -> StdResult<Self, ArgumentConvertError> {
+        let guild_id = guild_id.ok_or(ArgumentConvertError::NoGuild)?;
+        let id = parse_id(s, |s| parse_role(s)).map(RoleId);
+        let matches = |role: &Role| match id {
+            Some(id) => role.id == id,
+            None => role.name == s,
+        };
+
+        #[cfg(feature = "cache")]
+        {
+            if let Some(cache) = cache_http.cache() {
+                if let Some(guild) = cache.read().guild(guild_id) {
+                    if let Some(role) = guild.read().roles.values().find(|role| matches(role)) {
+                        return Ok(role.clone());
+                    }
+                }
+            }
+        }
+
+        let roles = cache_http.http()
+            .get_guild_roles(guild_id.0)
+            .map_err(ArgumentConvertError::from)?;
+
+        roles.into_iter().find(|role| matches(role)).ok_or(ArgumentConvertError::NotFound)
+    }
+}
+
+/// Resolves a [`GuildChannel`] from a mention, a raw Id or a name, optionally
+/// prefixed with `#`.
+///
+/// When a guild is given, only channels of that guild are resolved. Names
+/// can only be resolved within a guild.
+///
+/// [`GuildChannel`]: ../model/channel/struct.GuildChannel.html
+impl ArgumentConvert for GuildChannel {
+    fn convert(cache_http: impl CacheHttp, guild_id: Option<GuildId>, s: &str) -> StdResult<Self, ArgumentConvertError> {
+        let in_guild = |channel: &GuildChannel| guild_id.map_or(true, |id| channel.guild_id == id);
+
+        if let Some(id) = parse_id(s, |s| parse_channel(s)).map(ChannelId) {
+            #[cfg(feature = "cache")]
+            {
+                if let Some(cache) = cache_http.cache() {
+                    if let Some(channel) = cache.read().guild_channel(id) {
+                        let channel = channel.read();
+
+                        return if in_guild(&channel) {
+                            Ok(channel.clone())
+                        } else {
+                            Err(ArgumentConvertError::NotFound)
+                        };
+                    }
+                }
+            }
+
+            let channel = cache_http.http()
+                .get_channel(id.0)
+                .map_err(ArgumentConvertError::from)?;
+
+            return channel
+                .guild()
+                .map(|channel| channel.read().clone())
+                .filter(|channel| in_guild(channel))
+                .ok_or(ArgumentConvertError::NotFound);
+        }
+
+        let guild_id = guild_id.ok_or(ArgumentConvertError::NoGuild)?;
+        let name = if s.starts_with('#') { &s[1..] } else { s };
+
+        #[cfg(feature = "cache")]
+        {
+            if let Some(cache) = cache_http.cache() {
+                if let Some(guild) = cache.read().guild(guild_id) {
+                    let guild = guild.read();
+                    let found = guild.channels.values().find(|channel| channel.read().name == name);
+
+                    if let Some(channel) = found {
+                        return Ok(channel.read().clone());
+                    }
+                }
+            }
+        }
+
+        let channels = cache_http.http()
+            .get_channels(guild_id.0)
+            .map_err(ArgumentConvertError::from)?;
+
+        channels.into_iter().find(|channel| channel.name == name).ok_or(ArgumentConvertError::NotFound)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::http::{error::ErrorResponse, HttpError, StatusCode};
+    use crate::internal::prelude::*;
+    use reqwest::Url;
+    use super::{parse_id, parse_username, ArgumentConvertError};
+
+    fn unsuccessful_request(status_code: StatusCode) -> Error {
+        let response = ErrorResponse {
+            status_code,
+            url: Url::parse("https://discordapp.com/api/v6/users/1").unwrap(),
+            error: serde_json::from_str(r#"{"code": 10013, "message": "Unknown User"}"#).unwrap(),
+        };
+
+        Error::Http(Box::new(HttpError::UnsuccessfulRequest(response)))
+    }
+
+    #[test]
+    fn test_parse_id() {
+        assert_eq!(parse_id("<@!114941315417899012>", |s| parse_username(s)), Some(114941315417899012));
+        assert_eq!(parse_id("114941315417899012", |s| parse_username(s)), Some(114941315417899012));
+        assert_eq!(parse_id("zey#5479", |s| parse_username(s)), None);
+    }
+
+    #[test]
+    fn test_from_error() {
+        match ArgumentConvertError::from(unsuccessful_request(StatusCode::NOT_FOUND)) {
+            ArgumentConvertError::NotFound => {},
+            other => panic!("expected a 404 to be not found, got {:?}", other),
+        }
+
+        match ArgumentConvertError::from(unsuccessful_request(StatusCode::FORBIDDEN)) {
+            ArgumentConvertError::Http(_) => {},
+            other => panic!("expected a 403 to be kept, got {:?}", other),
+        }
+    }
+}
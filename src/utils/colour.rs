@@ -1,6 +1,12 @@
 // Disable this lint to avoid it wanting to change `0xABCDEF` to `0xAB_CDEF`.
 #![allow(clippy::unreadable_literal)]
 
+use std::{
+    error::Error as StdError,
+    fmt::{Display, Formatter, Result as FmtResult},
+    str::FromStr,
+};
+
 macro_rules! colour {
     ($(#[$attr:meta] $constname:ident, $name:ident, $val:expr;)*) => {
         impl Colour {
@@ -203,6 +209,280 @@ impl Colour {
     pub fn hex(self) -> String {
         format!("{:06X}", self.0)
     }
+
+    /// Parses a Colour from a hexadecimal string, such as `"#7289DA"`.
+    ///
+    /// The leading `#` is optional, digits are case-insensitive, and the
+    /// three digit shorthand - such as `"#FFF"` - is accepted as well.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity::utils::Colour;
+    ///
+    /// assert_eq!(Colour::from_hex_str("#7289da"), Ok(Colour::BLURPLE));
+    /// assert_eq!(Colour::from_hex_str("fff"), Ok(Colour::new(0xFFFFFF)));
+    /// assert!(Colour::from_hex_str("#7289d").is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ColourParseError::InvalidLength`] if there are not 3 or 6
+    /// digits, or a [`ColourParseError::InvalidDigit`] if any of them is not
+    /// hexadecimal.
+    ///
+    /// [`ColourParseError::InvalidDigit`]: enum.ColourParseError.html#variant.InvalidDigit
+    /// [`ColourParseError::InvalidLength`]: enum.ColourParseError.html#variant.InvalidLength
+    pub fn from_hex_str(hex: &str) -> Result<Colour, ColourParseError> {
+        let digits = if hex.starts_with('#') { &hex[1..] } else { hex };
+        let shorthand = match digits.chars().count() {
+            3 => true,
+            6 => false,
+            _ => return Err(ColourParseError::InvalidLength),
+        };
+
+        let mut value = 0;
+
+        for digit in digits.chars() {
+            let digit = digit.to_digit(16).ok_or(ColourParseError::InvalidDigit)?;
+
+            value = value << 4 | digit;
+
+            // Repeat each digit of the shorthand, so that `F` becomes `FF`.
+            if shorthand {
+                value = value << 4 | digit;
+            }
+        }
+
+        Ok(Colour(value))
+    }
+
+    /// Generates a new Colour from a hue in degrees, and a saturation and
+    /// lightness from `0.0` to `1.0`.
+    ///
+    /// Hues outside of `0.0` to `360.0` wrap around, and other values outside
+    /// of their range are clamped to it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity::utils::Colour;
+    ///
+    /// assert_eq!(Colour::from_hsl(0.0, 1.0, 0.5), Colour::new(0xFF0000));
+    /// assert_eq!(Colour::from_hsl(240.0, 1.0, 0.25), Colour::new(0x000080));
+    /// ```
+    pub fn from_hsl(hue: f32, saturation: f32, lightness: f32) -> Colour {
+        let saturation = clamp_unit(saturation);
+        let lightness = clamp_unit(lightness);
+
+        let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+
+        from_chroma(hue, chroma, lightness - chroma / 2.0)
+    }
+
+    /// Returns the hue in degrees, and the saturation and lightness from
+    /// `0.0` to `1.0`, of this Colour.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity::utils::Colour;
+    ///
+    /// assert_eq!(Colour::new(0x00FF00).to_hsl(), (120.0, 1.0, 0.5));
+    /// ```
+    pub fn to_hsl(self) -> (f32, f32, f32) {
+        let (max, min) = self.extrema();
+        let chroma = max - min;
+        let lightness = (max + min) / 2.0;
+
+        let saturation = if chroma == 0.0 {
+            0.0
+        } else {
+            chroma / (1.0 - (2.0 * lightness - 1.0).abs())
+        };
+
+        (self.hue(max, chroma), saturation, lightness)
+    }
+
+    /// Generates a new Colour from a hue in degrees, and a saturation and
+    /// value from `0.0` to `1.0`.
+    ///
+    /// Hues outside of `0.0` to `360.0` wrap around, and other values outside
+    /// of their range are clamped to it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity::utils::Colour;
+    ///
+    /// assert_eq!(Colour::from_hsv(0.0, 1.0, 1.0), Colour::new(0xFF0000));
+    /// assert_eq!(Colour::from_hsv(0.0, 0.0, 1.0), Colour::new(0xFFFFFF));
+    /// ```
+    pub fn from_hsv(hue: f32, saturation: f32, value: f32) -> Colour {
+        let value = clamp_unit(value);
+        let chroma = value * clamp_unit(saturation);
+
+        from_chroma(hue, chroma, value - chroma)
+    }
+
+    /// Returns the hue in degrees, and the saturation and value from `0.0` to
+    /// `1.0`, of this Colour.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity::utils::Colour;
+    ///
+    /// assert_eq!(Colour::new(0xFF0000).to_hsv(), (0.0, 1.0, 1.0));
+    /// ```
+    pub fn to_hsv(self) -> (f32, f32, f32) {
+        let (max, min) = self.extrema();
+        let chroma = max - min;
+
+        let saturation = if max == 0.0 { 0.0 } else { chroma / max };
+
+        (self.hue(max, chroma), saturation, max)
+    }
+
+    /// Returns a lighter version of this Colour, increasing its lightness by
+    /// the given amount from `0.0` to `1.0`.
+    ///
+    /// # Examples
+    ///
+    /// Lightening black by half gives grey:
+    ///
+    /// ```rust
+    /// use serenity::utils::Colour;
+    ///
+    /// assert_eq!(Colour::new(0x000000).lighten(0.5), Colour::new(0x808080));
+    /// ```
+    pub fn lighten(self, amount: f32) -> Colour {
+        let (hue, saturation, lightness) = self.to_hsl();
+
+        Colour::from_hsl(hue, saturation, lightness + clamp_unit(amount))
+    }
+
+    /// Returns a darker version of this Colour, decreasing its lightness by
+    /// the given amount from `0.0` to `1.0`.
+    ///
+    /// # Examples
+    ///
+    /// Darkening white by half gives grey:
+    ///
+    /// ```rust
+    /// use serenity::utils::Colour;
+    ///
+    /// assert_eq!(Colour::new(0xFFFFFF).darken(0.5), Colour::new(0x808080));
+    /// ```
+    pub fn darken(self, amount: f32) -> Colour {
+        let (hue, saturation, lightness) = self.to_hsl();
+
+        Colour::from_hsl(hue, saturation, lightness - clamp_unit(amount))
+    }
+
+    /// Returns the largest and smallest of the RGB components, from `0.0` to
+    /// `1.0`.
+    fn extrema(self) -> (f32, f32) {
+        let (r, g, b) = self.unit_tuple();
+
+        (r.max(g).max(b), r.min(g).min(b))
+    }
+
+    /// Returns the hue in degrees given the largest component and the
+    /// chroma.
+    #[allow(clippy::float_cmp)]
+    fn hue(self, max: f32, chroma: f32) -> f32 {
+        if chroma == 0.0 {
+            return 0.0;
+        }
+
+        let (r, g, b) = self.unit_tuple();
+
+        let sector = if max == r {
+            ((g - b) / chroma) % 6.0
+        } else if max == g {
+            (b - r) / chroma + 2.0
+        } else {
+            (r - g) / chroma + 4.0
+        };
+
+        let hue = sector * 60.0;
+
+        if hue < 0.0 { hue + 360.0 } else { hue }
+    }
+
+    fn unit_tuple(self) -> (f32, f32, f32) {
+        (
+            f32::from(self.r()) / 255.0,
+            f32::from(self.g()) / 255.0,
+            f32::from(self.b()) / 255.0,
+        )
+    }
+}
+
+/// Clamps a value to `0.0` to `1.0`, treating NaN as `0.0`.
+fn clamp_unit(value: f32) -> f32 {
+    value.max(0.0).min(1.0)
+}
+
+/// Generates a Colour from a hue, a chroma, and the amount to add to each
+/// component to match the lightness or value.
+fn from_chroma(hue: f32, chroma: f32, offset: f32) -> Colour {
+    let hue = if hue.is_finite() { hue % 360.0 } else { 0.0 };
+    let hue = if hue < 0.0 { hue + 360.0 } else { hue };
+    let sector = hue / 60.0;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+
+    let (r, g, b) = match sector as u8 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+
+    let component = |value: f32| (clamp_unit(value + offset) * 255.0).round() as u8;
+
+    Colour::from_rgb(component(r), component(g), component(b))
+}
+
+/// An error returned when parsing a [`Colour`] from a hexadecimal string.
+///
+/// [`Colour`]: struct.Colour.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ColourParseError {
+    /// The string does not contain 3 or 6 digits.
+    InvalidLength,
+    /// The string contains a character which is not a hexadecimal digit.
+    InvalidDigit,
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+impl Display for ColourParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult { f.write_str(self.description()) }
+}
+
+impl StdError for ColourParseError {
+    fn description(&self) -> &str {
+        match *self {
+            ColourParseError::InvalidLength => "invalid number of hex digits",
+            ColourParseError::InvalidDigit => "invalid hex digit",
+            ColourParseError::__Nonexhaustive => unreachable!(),
+        }
+    }
+}
+
+impl FromStr for Colour {
+    type Err = ColourParseError;
+
+    /// Parses a Colour from a hexadecimal string.
+    ///
+    /// Refer to [`from_hex_str`] for the accepted formats.
+    ///
+    /// [`from_hex_str`]: #method.from_hex_str
+    fn from_str(s: &str) -> Result<Self, Self::Err> { Colour::from_hex_str(s) }
 }
 
 impl From<i32> for Colour {
@@ -325,7 +605,7 @@ impl Default for Colour {
 
 #[cfg(test)]
 mod test {
-    use super::Colour;
+    use super::{Colour, ColourParseError};
     use std::u32;
 
     #[test]
@@ -373,4 +653,56 @@ mod test {
         assert_eq!(Colour::from(7u32).0, 7);
         assert_eq!(Colour::from(7u64).0, 7);
     }
+
+    #[test]
+    fn from_hex_str() {
+        assert_eq!(Colour::from_hex_str("#7289DA"), Ok(Colour::new(0x7289DA)));
+        assert_eq!(Colour::from_hex_str("7289da"), Ok(Colour::new(0x7289DA)));
+        assert_eq!(Colour::from_hex_str("#abc"), Ok(Colour::new(0xAABBCC)));
+        assert_eq!("#000000".parse(), Ok(Colour::new(0)));
+        assert_eq!(Colour::from_hex_str(""), Err(ColourParseError::InvalidLength));
+        assert_eq!(Colour::from_hex_str("#7289DA0"), Err(ColourParseError::InvalidLength));
+        assert_eq!(Colour::from_hex_str("#7289DG"), Err(ColourParseError::InvalidDigit));
+        assert_eq!(Colour::from_hex_str("#7289D\u{e9}"), Err(ColourParseError::InvalidDigit));
+        assert_eq!(Colour::from_hex_str("##ab"), Err(ColourParseError::InvalidDigit));
+        assert_eq!(Colour::from_hex_str("##7289DA"), Err(ColourParseError::InvalidLength));
+    }
+
+    #[test]
+    fn hsl() {
+        assert_eq!(Colour::from_hsl(0.0, 0.0, 0.0), Colour::new(0x000000));
+        assert_eq!(Colour::from_hsl(120.0, 1.0, 0.5), Colour::new(0x00FF00));
+        assert_eq!(Colour::from_hsl(-120.0, 1.0, 0.5), Colour::new(0x0000FF));
+        assert_eq!(Colour::from_hsl(480.0, 2.0, 0.5), Colour::new(0x00FF00));
+        assert_eq!(Colour::from_hsl(std::f32::NAN, 1.0, 0.5), Colour::new(0xFF0000));
+        assert_eq!(Colour::new(0xFFFFFF).to_hsl(), (0.0, 0.0, 1.0));
+        assert_eq!(Colour::new(0x0000FF).to_hsl(), (240.0, 1.0, 0.5));
+
+        for &value in &[0x7289DA, 0x336123, 0xDEA584, 0x123456] {
+            let (h, s, l) = Colour::new(value).to_hsl();
+
+            assert_eq!(Colour::from_hsl(h, s, l), Colour::new(value));
+        }
+    }
+
+    #[test]
+    fn hsv() {
+        assert_eq!(Colour::from_hsv(300.0, 1.0, 1.0), Colour::new(0xFF00FF));
+        assert_eq!(Colour::new(0x000000).to_hsv(), (0.0, 0.0, 0.0));
+
+        for &value in &[0x7289DA, 0x336123, 0xDEA584, 0x123456] {
+            let (h, s, v) = Colour::new(value).to_hsv();
+
+            assert_eq!(Colour::from_hsv(h, s, v), Colour::new(value));
+        }
+    }
+
+    #[test]
+    fn lighten_darken() {
+        assert_eq!(Colour::new(0xFF0000).lighten(0.25), Colour::new(0xFF8080));
+        assert_eq!(Colour::new(0xFF0000).darken(0.25), Colour::new(0x800000));
+        assert_eq!(Colour::new(0xFF0000).lighten(2.0), Colour::new(0xFFFFFF));
+        assert_eq!(Colour::new(0xFF0000).darken(2.0), Colour::new(0x000000));
+        assert_eq!(Colour::new(0x7289DA).lighten(0.0), Colour::new(0x7289DA));
+    }
 }
@@ -243,7 +243,6 @@ fn dummy_message() -> Message {
             bot: false,
             discriminator: 0x0000,
             name: String::new(),
-            _nonexhaustive: (),
         },
         channel_id: ChannelId::default(),
         content: String::new(),
@@ -269,7 +268,8 @@ fn dummy_message() -> Message {
         activity: None,
         application: None,
         message_reference: None,
+        referenced_message: None,
         flags: None,
-        _nonexhaustive: (),
+        sticker_items: Vec::new(),
     }
 }
@@ -0,0 +1,153 @@
+use chrono::{DateTime, TimeZone};
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+/// A timestamp formatted for display in a Discord message, rendered by the
+/// client in the reader's own timezone and locale.
+///
+/// # Examples
+///
+/// Display a timestamp as a relative time, such as "in 2 hours":
+///
+/// ```rust
+/// use serenity::utils::{FormattedTimestamp, TimestampStyle};
+///
+/// let timestamp = FormattedTimestamp::new(1_600_000_000, Some(TimestampStyle::RelativeTime));
+///
+/// assert_eq!(timestamp.to_string(), "<t:1600000000:R>");
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct FormattedTimestamp {
+    timestamp: i64,
+    style: Option<TimestampStyle>,
+}
+
+impl FormattedTimestamp {
+    /// Creates a new formatted timestamp from a UNIX timestamp in seconds
+    /// and an optional style.
+    ///
+    /// If no style is given, the client displays the timestamp as a
+    /// [`TimestampStyle::ShortDateTime`].
+    ///
+    /// [`TimestampStyle::ShortDateTime`]: enum.TimestampStyle.html#variant.ShortDateTime
+    pub fn new(timestamp: i64, style: Option<TimestampStyle>) -> Self {
+        Self {
+            timestamp,
+            style,
+        }
+    }
+
+    /// Returns the UNIX timestamp in seconds.
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    /// Returns the style of the timestamp.
+    pub fn style(&self) -> Option<TimestampStyle> {
+        self.style
+    }
+}
+
+impl<Tz: TimeZone> From<DateTime<Tz>> for FormattedTimestamp {
+    fn from(datetime: DateTime<Tz>) -> Self {
+        Self::new(datetime.timestamp(), None)
+    }
+}
+
+impl<'a, Tz: TimeZone> From<&'a DateTime<Tz>> for FormattedTimestamp {
+    fn from(datetime: &'a DateTime<Tz>) -> Self {
+        Self::new(datetime.timestamp(), None)
+    }
+}
+
+impl Display for FormattedTimestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.style {
+            Some(style) => write!(f, "<t:{}:{}>", self.timestamp, style),
+            None => write!(f, "<t:{}>", self.timestamp),
+        }
+    }
+}
+
+/// The style in which a [`FormattedTimestamp`] is displayed.
+///
+/// [`FormattedTimestamp`]: struct.FormattedTimestamp.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum TimestampStyle {
+    /// A short time, e.g. `16:20`.
+    ShortTime,
+    /// A long time, e.g. `16:20:30`.
+    LongTime,
+    /// A short date, e.g. `20/04/2021`.
+    ShortDate,
+    /// A long date, e.g. `20 April 2021`.
+    LongDate,
+    /// A short date and time, e.g. `20 April 2021 16:20`.
+    ShortDateTime,
+    /// A long date and time, e.g. `Tuesday, 20 April 2021 16:20`.
+    LongDateTime,
+    /// A time relative to now, e.g. `2 months ago`.
+    RelativeTime,
+}
+
+impl TimestampStyle {
+    /// Returns the character Discord uses for the style.
+    pub fn as_char(self) -> char {
+        match self {
+            TimestampStyle::ShortTime => 't',
+            TimestampStyle::LongTime => 'T',
+            TimestampStyle::ShortDate => 'd',
+            TimestampStyle::LongDate => 'D',
+            TimestampStyle::ShortDateTime => 'f',
+            TimestampStyle::LongDateTime => 'F',
+            TimestampStyle::RelativeTime => 'R',
+        }
+    }
+}
+
+impl Display for TimestampStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_char())
+    }
+}
+
+impl FromStr for TimestampStyle {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "t" => Ok(TimestampStyle::ShortTime),
+            "T" => Ok(TimestampStyle::LongTime),
+            "d" => Ok(TimestampStyle::ShortDate),
+            "D" => Ok(TimestampStyle::LongDate),
+            "f" => Ok(TimestampStyle::ShortDateTime),
+            "F" => Ok(TimestampStyle::LongDateTime),
+            "R" => Ok(TimestampStyle::RelativeTime),
+            _ => Err(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{TimeZone, Utc};
+    use super::{FormattedTimestamp, TimestampStyle};
+
+    #[test]
+    fn test_display() {
+        let timestamp = FormattedTimestamp::from(Utc.timestamp_opt(1_618_935_630, 0).unwrap());
+        assert_eq!(timestamp.to_string(), "<t:1618935630>");
+
+        let timestamp = FormattedTimestamp::new(1_618_935_630, Some(TimestampStyle::LongDate));
+        assert_eq!(timestamp.to_string(), "<t:1618935630:D>");
+    }
+
+    #[test]
+    fn test_style_from_str() {
+        assert_eq!("R".parse(), Ok(TimestampStyle::RelativeTime));
+        assert_eq!("t".parse(), Ok(TimestampStyle::ShortTime));
+        assert_eq!("x".parse::<TimestampStyle>(), Err(()));
+    }
+}
@@ -3,6 +3,7 @@ use crate::model::{
     id::{ChannelId, RoleId, UserId},
     misc::Mentionable
 };
+use super::{FormattedTimestamp, TimestampStyle};
 use std::{
     default::Default,
     fmt::{self, Display, Write},
@@ -190,20 +191,47 @@ impl MessageBuilder {
     ///
     /// [Display implementation]: ../model/guild/struct.Emoji.html#method.fmt
     pub fn emoji(&mut self, emoji: &Emoji) -> &mut Self {
-        let _ = write!(self.0, "{}", emoji);
-
-        self
+        self.mention(emoji)
     }
 
-    /// Mentions something that implements the [`Mentionable`] trait.
+    /// Mentions something that implements the [`Mentionable`] trait, such as
+    /// a [`User`], [`Role`], [`GuildChannel`] or [`Emoji`], or any of their
+    /// Ids.
     ///
+    /// [`Emoji`]: ../model/guild/struct.Emoji.html
+    /// [`GuildChannel`]: ../model/channel/struct.GuildChannel.html
     /// [`Mentionable`]: ../model/misc/trait.Mentionable.html
-    pub fn mention<M: Mentionable>(&mut self, item: &M) -> &mut Self {
+    /// [`Role`]: ../model/guild/struct.Role.html
+    /// [`User`]: ../model/user/struct.User.html
+    pub fn mention<M: Mentionable + ?Sized>(&mut self, item: &M) -> &mut Self {
         let _ = write!(self.0, "{}", item.mention());
 
         self
     }
 
+    /// Pushes a [`FormattedTimestamp`] to the content, displayed by the
+    /// client in the reader's timezone and locale.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity::utils::{MessageBuilder, TimestampStyle};
+    ///
+    /// let content = MessageBuilder::new()
+    ///     .push("The event starts ")
+    ///     .push_timestamp(1_618_935_630, Some(TimestampStyle::RelativeTime))
+    ///     .build();
+    ///
+    /// assert_eq!(content, "The event starts <t:1618935630:R>");
+    /// ```
+    ///
+    /// [`FormattedTimestamp`]: struct.FormattedTimestamp.html
+    pub fn push_timestamp<T: Into<i64>>(&mut self, timestamp: T, style: Option<TimestampStyle>) -> &mut Self {
+        let _ = write!(self.0, "{}", FormattedTimestamp::new(timestamp.into(), style));
+
+        self
+    }
+
     /// Pushes a string to the internal message content.
     ///
     /// Note that this does not mutate either the given data or the internal
@@ -383,8 +411,8 @@ impl MessageBuilder {
         self
     }
 
-    /// Pushes a quoted inline text to the content
-    pub fn push_quote<D: I>(mut self, content: D) -> Self {
+    /// Pushes a quoted inline text to the content.
+    pub fn push_quote<D: I>(&mut self, content: D) -> &mut Self {
         self.0.push_str("> ");
         self.0.push_str(&content.into().to_string());
 
@@ -544,8 +572,8 @@ impl MessageBuilder {
     ///
     /// assert_eq!(content, "> hello\nworld");
     /// ```
-    pub fn push_quote_line<D: I>(mut self, content: D) -> Self {
-        self = self.push_quote(content);
+    pub fn push_quote_line<D: I>(&mut self, content: D) -> &mut Self {
+        self.push_quote(content);
         self.0.push('\n');
 
         self
@@ -568,6 +596,21 @@ impl MessageBuilder {
     }
 
     /// Pushes a code-block to your message normalizing content.
+    ///
+    /// Triple backticks within the content are escaped with zero-width
+    /// spaces, so that they cannot end the code-block early.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serenity::utils::MessageBuilder;
+    ///
+    /// let content = MessageBuilder::new()
+    ///     .push_codeblock_safe("```@everyone```", None)
+    ///     .build();
+    ///
+    /// assert_eq!(content, "```\n`\u{200B}`\u{200B}`@\u{200B}everyone`\u{200B}`\u{200B}`\n```");
+    /// ```
     pub fn push_codeblock_safe<D: I>(&mut self, content: D, language: Option<&str>) -> &mut Self {
         self.0.push_str("```");
 
@@ -578,7 +621,7 @@ impl MessageBuilder {
         self.0.push('\n');
         {
             let mut c = content.into();
-            c.inner = normalize(&c.inner).replace("```", " ");
+            c.inner = normalize(&c.inner).replace("```", "`\u{200B}`\u{200B}`");
             self.0.push_str(&c.to_string());
         }
         self.0.push_str("\n```");
@@ -665,7 +708,7 @@ impl MessageBuilder {
     }
 
     /// Pushes a quoted inline text to the content normalizing content.
-    pub fn push_quote_safe<D: I>(mut self, content: D) -> Self {
+    pub fn push_quote_safe<D: I>(&mut self, content: D) -> &mut Self {
         self.0.push_str("> ");
         {
             let mut c = content.into();
@@ -848,15 +891,15 @@ impl MessageBuilder {
     ///
     /// assert_eq!(content, "> @\u{200B}everyone\nIsn't a mention.");
     /// ```
-    pub fn push_quote_line_safe<D: I>(mut self, content: D) -> Self {
-        self = self.push_quote_safe(content);
+    pub fn push_quote_line_safe<D: I>(&mut self, content: D) -> &mut Self {
+        self.push_quote_safe(content);
         self.0.push('\n');
 
         self
     }
 
-    /// Starts a multi-line quote, every push after this one will be quoted
-    pub fn quote_rest(mut self) -> Self {
+    /// Starts a multi-line quote, every push after this one will be quoted.
+    pub fn quote_rest(&mut self) -> &mut Self {
         self.0.push_str("\n>>> ");
 
         self
@@ -1336,7 +1379,7 @@ mod test {
                 managed: false,
                 require_colons: true,
                 roles: vec![],
-                _nonexhaustive: (),
+                available: true,
             })
             .build();
         let content_mentions = MessageBuilder::new()
@@ -1421,10 +1464,22 @@ mod test {
         );
         assert_eq!(
             MessageBuilder::new().push_codeblock_safe("```.```", None).0,
-            "```\n . \n```",
+            "```\n`\u{200B}`\u{200B}`.`\u{200B}`\u{200B}`\n```",
         );
     }
 
+    #[test]
+    fn push_quote() {
+        let content = MessageBuilder::new()
+            .push_quote_line("foo")
+            .push_quote_safe("@here")
+            .quote_rest()
+            .push("bar")
+            .build();
+
+        assert_eq!(content, "> foo\n> @\u{200B}here\n>>> bar");
+    }
+
     #[test]
     fn push_safe() {
         gen! {
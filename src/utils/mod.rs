@@ -1,12 +1,19 @@
 //! A set of utilities to help with common use cases that are not required to
 //! fully use the library.
 
+#[cfg(feature = "model")]
+mod argument_convert;
 mod colour;
+mod formatted_timestamp;
 mod message_builder;
 mod custom_message;
+pub mod token;
 
+#[cfg(feature = "model")]
+pub use self::argument_convert::{ArgumentConvert, ArgumentConvertError};
 pub use self::{
-    colour::Colour,
+    colour::{Colour, ColourParseError},
+    formatted_timestamp::{FormattedTimestamp, TimestampStyle},
     message_builder::{
         Content,
         ContentModifier,
@@ -435,6 +442,124 @@ pub fn parse_quotes(s: impl AsRef<str>) -> Vec<String> {
     args
 }
 
+/// Splits `content` into pages of at most `limit` unicode code points each,
+/// such as the [`MESSAGE_CODE_LIMIT`] of a message.
+///
+/// Pages are split at line breaks where possible, then at whitespace, and
+/// only mid-word as a last resort. Code blocks spanning several pages are
+/// closed at the end of one page and reopened, in the same language, at the
+/// start of the next, unless the `limit` is too small to fit their opening
+/// line alongside any content.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity::utils::split_message;
+///
+/// let content = "```rust\nlet a = 1;\nlet b = 2;\n```";
+/// let pages = split_message(content, 24);
+///
+/// assert_eq!(pages, [
+///     "```rust\nlet a = 1;\n```",
+///     "```rust\nlet b = 2;\n```",
+/// ]);
+/// ```
+///
+/// [`MESSAGE_CODE_LIMIT`]: ../constants/constant.MESSAGE_CODE_LIMIT.html
+pub fn split_message(content: impl AsRef<str>, limit: usize) -> Vec<String> {
+    const FENCE: &str = "```";
+
+    // Room kept free on every page for closing an open code block.
+    let reserve = FENCE.len() + 1;
+
+    let mut pages = Vec::new();
+    let mut page = String::new();
+    let mut page_len = 0;
+    // Length of the reopened code block at the start of the page.
+    let mut header_len = 0;
+    // Whether we are in a code block.
+    let mut in_block = false;
+    // The line opening the code block we are in, if it is carried over to
+    // the next page. Blocks whose opening line leaves no room for content on
+    // a page are split like plain text instead.
+    let mut fence: Option<String> = None;
+
+    let content = content.as_ref();
+    let line_count = content.split('\n').count();
+
+    for (i, line) in content.split('\n').enumerate() {
+        let is_fence = line.trim_start().starts_with(FENCE);
+        let opening = if is_fence && !in_block {
+            Some(line.trim())
+                .filter(|opening| opening.chars().count() + 1 + reserve < limit)
+        } else {
+            None
+        };
+        // Whether a carried code block is still open after this line.
+        let carried = if is_fence { opening.is_some() } else { fence.is_some() };
+        let reserved = if carried { reserve } else { 0 };
+
+        let mut text = if i + 1 < line_count {
+            format!("{}\n", line)
+        } else {
+            line.to_string()
+        };
+
+        loop {
+            let text_len = text.chars().count();
+
+            if page_len + text_len + reserved <= limit {
+                page.push_str(&text);
+                page_len += text_len;
+
+                break;
+            }
+
+            if page_len == header_len {
+                // Not even an empty page fits the text, so split it. There is
+                // always room for at least one character, so that every split
+                // makes progress.
+                let room = limit.saturating_sub(page_len + reserved).max(1);
+                let end = text.char_indices().nth(room).map_or(text.len(), |(i, _)| i);
+                let end = text[..end]
+                    .rfind(char::is_whitespace)
+                    .filter(|&i| i > 0)
+                    .map_or(end, |i| i + 1);
+
+                page.push_str(&text[..end]);
+                text = text[end..].to_string();
+            }
+
+            if let Some(ref opening) = fence {
+                if !page.ends_with('\n') {
+                    page.push('\n');
+                }
+
+                page.push_str(FENCE);
+                pages.push(page);
+                page = format!("{}\n", opening);
+            } else {
+                pages.push(page);
+                page = String::new();
+            }
+
+            page_len = page.chars().count();
+            header_len = page_len;
+        }
+
+        if is_fence {
+            in_block = !in_block;
+            fence = opening.map(str::to_string);
+        }
+    }
+
+    if page_len > header_len {
+        pages.push(page);
+    }
+
+    pages
+}
+
 /// Calculates the Id of the shard responsible for a guild, given its Id and
 /// total number of shards used.
 ///
@@ -451,6 +576,30 @@ pub fn parse_quotes(s: impl AsRef<str>) -> Vec<String> {
 #[inline]
 pub fn shard_id(guild_id: u64, shard_count: u64) -> u64 { (guild_id >> 22) % shard_count }
 
+/// A source of the total number of shards in use, as accepted by the
+/// `shard_id` methods of guilds.
+///
+/// This is implemented for a `u64` of the total itself, and, with the `cache`
+/// feature, for the cache, from which the total is retrieved.
+///
+/// **Note**: Retrieving the total from the cache unlocks it. If you already
+/// have the total, consider passing it instead.
+pub trait ShardCount {
+    /// Returns the total number of shards in use.
+    fn shard_count(&self) -> u64;
+}
+
+impl ShardCount for u64 {
+    #[inline]
+    fn shard_count(&self) -> u64 { *self }
+}
+
+#[cfg(feature = "cache")]
+impl<T: AsRef<CacheRwLock>> ShardCount for T {
+    #[inline]
+    fn shard_count(&self) -> u64 { self.as_ref().read().shard_count }
+}
+
 /// A function for doing automatic `read`ing (and the releasing of the guard as well)
 /// This is particularly useful if you just want to use the cache for this one time,
 /// or don't want to be messing with the `RwLock` directly.
@@ -505,6 +654,7 @@ pub struct ContentSafeOptions {
     clean_here: bool,
     clean_everyone: bool,
     show_discriminator: bool,
+    show_nickname: bool,
     guild_reference: Option<GuildId>,
 }
 
@@ -569,6 +719,20 @@ impl ContentSafeOptions {
         self
     }
 
+    /// If set to true, when a guild is set via [`display_as_member_from`],
+    /// [`content_safe`] will show a member's nickname, if they have one,
+    /// instead of their username. Set to false to always show usernames.
+    ///
+    /// Defaults to true.
+    ///
+    /// [`content_safe`]: fn.content_safe.html
+    /// [`display_as_member_from`]: #method.display_as_member_from
+    pub fn show_nickname(mut self, b: bool) -> Self {
+        self.show_nickname = b;
+
+        self
+    }
+
     /// If set, [`content_safe`] will replace `@here` with a non-pinging
     /// alternative.
     ///
@@ -601,6 +765,7 @@ impl Default for ContentSafeOptions {
             clean_here: true,
             clean_everyone: true,
             show_discriminator: true,
+            show_nickname: true,
             guild_reference: None,
         }
     }
@@ -685,7 +850,7 @@ fn clean_channels(cache: &RwLock<Cache>, s: &mut String) {
 
 #[cfg(feature = "cache")]
 #[inline]
-fn clean_users(cache: &RwLock<Cache>, s: &mut String, show_discriminator: bool, guild: Option<GuildId>) {
+fn clean_users(cache: &RwLock<Cache>, s: &mut String, options: &ContentSafeOptions) {
     let mut progress = 0;
 
     while let Some(mut mention_start) = s[progress..].find("<@") {
@@ -704,16 +869,21 @@ fn clean_users(cache: &RwLock<Cache>, s: &mut String, show_discriminator: bool,
             };
 
             if let Ok(id) = UserId::from_str(&s[mention_start..mention_end]) {
-                let replacement = if let Some(guild) = guild {
+                let replacement = if let Some(guild) = options.guild_reference {
 
                     if let Some(guild) = cache.read().guild(&guild) {
 
                         if let Some(member) = guild.read().members.get(&id) {
+                            let name = if options.show_nickname {
+                                member.display_name().into_owned()
+                            } else {
+                                member.user.read().name.clone()
+                            };
 
-                            if show_discriminator {
-                                format!("@{}", member.distinct())
+                            if options.show_discriminator {
+                                format!("@{}#{:04}", name, member.user.read().discriminator)
                             } else {
-                                format!("@{}", member.display_name())
+                                format!("@{}", name)
                             }
                         } else {
                             "@invalid-user".to_string()
@@ -727,7 +897,7 @@ fn clean_users(cache: &RwLock<Cache>, s: &mut String, show_discriminator: bool,
                     if let Some(user) = user {
                         let user = user.read();
 
-                        if show_discriminator {
+                        if options.show_discriminator {
                             format!("@{}#{:04}", user.name, user.discriminator)
                         } else {
                             format!("@{}", user.name)
@@ -801,7 +971,7 @@ pub fn content_safe(cache: impl AsRef<CacheRwLock>, s: impl AsRef<str>, options:
     }
 
     if options.clean_user {
-        clean_users(&cache, &mut s, options.show_discriminator, options.guild_reference);
+        clean_users(&cache, &mut s, options);
     }
 
     if options.clean_here {
@@ -862,6 +1032,47 @@ mod test {
         assert_eq!(parsed, ["a", "b c", "d", "e f", "g"]);
     }
 
+    #[test]
+    fn test_split_message() {
+        assert_eq!(split_message("short", 2000), ["short"]);
+        assert!(split_message("", 2000).is_empty());
+
+        assert_eq!(split_message("one\ntwo\nthree", 9), ["one\ntwo\n", "three"]);
+        assert_eq!(split_message("aaaa bbbb cccc", 10), ["aaaa bbbb ", "cccc"]);
+        assert_eq!(split_message("abcdefgh", 3), ["abc", "def", "gh"]);
+
+        let pages = split_message("text\n```\n1\n2\n3\n```\nmore", 12);
+        assert_eq!(pages, ["text\n", "```\n1\n2\n```", "```\n3\n```\n", "more"]);
+
+        for page in split_message(&"word ".repeat(1000), 2000) {
+            assert!(page.chars().count() <= 2000);
+        }
+
+        // Code blocks whose opening line leaves no room for content are not
+        // carried over to the next page.
+        let pages = split_message("```rust\n1\n\n2\n3\n```", 10);
+        assert_eq!(pages.concat(), "```rust\n1\n\n2\n3\n```");
+
+        for page in pages {
+            assert!(page.chars().count() <= 10);
+        }
+    }
+
+    #[cfg(feature = "cache")]
+    #[test]
+    fn test_shard_count() {
+        use crate::model::id::GuildId;
+        use std::sync::Arc;
+
+        let cache: CacheRwLock = Arc::new(RwLock::new(Cache::default())).into();
+        cache.write().shard_count = 17;
+
+        assert_eq!(17u64.shard_count(), 17);
+        assert_eq!(cache.shard_count(), 17);
+        assert_eq!(GuildId(81384788765712384).shard_id(&cache), 7);
+        assert_eq!(GuildId(81384788765712384).shard_id(17), 7);
+    }
+
     #[cfg(feature = "cache")]
     #[test]
     fn test_content_safe() {
@@ -882,7 +1093,6 @@ mod test {
             bot: false,
             discriminator: 0000,
             name: "Crab".to_string(),
-            _nonexhaustive: (),
         };
 
         let mut guild = Guild {
@@ -909,6 +1119,7 @@ mod test {
             region: "Ferris Island".to_string(),
             roles: HashMap::new(),
             splash: None,
+            discovery_splash: None,
             system_channel_id: None,
             verification_level: VerificationLevel::None,
             voice_states: HashMap::new(),
@@ -918,7 +1129,13 @@ mod test {
             banner: None,
             vanity_url_code: Some("bruhmoment1".to_string()),
             preferred_locale: "en-US".to_string(),
-            _nonexhaustive: (),
+            stage_instances: vec![],
+            max_members: None,
+            max_presences: None,
+            max_video_channel_users: None,
+            approximate_member_count: None,
+            approximate_presence_count: None,
+            welcome_screen: None,
         };
 
         let member = Member {
@@ -927,9 +1144,9 @@ mod test {
             joined_at: None,
             mute: false,
             nick: Some("Ferris".to_string()),
+            premium_since: None,
             roles: Vec::new(),
             user: Arc::new(RwLock::new(user.clone())),
-            _nonexhaustive: (),
         };
 
         let role = Role {
@@ -941,7 +1158,6 @@ mod test {
             name: "ferris-club-member".to_string(),
             permissions: Permissions::all(),
             position: 0,
-            _nonexhaustive: (),
         };
 
         let channel = GuildChannel {
@@ -958,8 +1174,7 @@ mod test {
             topic: None,
             user_limit: None,
             nsfw: false,
-            slow_mode_rate: Some(0),
-            _nonexhaustive: (),
+            rate_limit_per_user: Some(0),
         };
 
         let cache: CacheRwLock = Arc::new(RwLock::new(Cache::default())).into();
@@ -1006,7 +1221,19 @@ mod test {
             content_safe(&cache, "<@100000000000000000>", &options));
 
         let options = options.display_as_member_from(guild.id);
-        assert_eq!(format!("@{}", member.nick.unwrap()),
+        assert_eq!(format!("@{}", member.nick.clone().unwrap()),
+            content_safe(&cache, "<@!100000000000000000>", &options));
+
+        let options = options.show_discriminator(true);
+        assert_eq!(format!("@{}#{:04}", member.nick.clone().unwrap(), user.discriminator),
+            content_safe(&cache, "<@!100000000000000000>", &options));
+
+        let options = options.show_nickname(false);
+        assert_eq!(format!("@{}#{:04}", user.name, user.discriminator),
+            content_safe(&cache, "<@!100000000000000000>", &options));
+
+        let options = options.show_discriminator(false);
+        assert_eq!(format!("@{}", user.name),
             content_safe(&cache, "<@!100000000000000000>", &options));
 
         let options = options.clean_user(false);
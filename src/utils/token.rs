@@ -0,0 +1,179 @@
+//! Utilities for working with bot tokens.
+
+use crate::model::id::UserId;
+use std::{
+    error::Error as StdError,
+    fmt::{Debug, Display, Formatter, Result as FmtResult},
+};
+
+/// Validates that a token is likely in a valid format, returning the Id of the
+/// bot user it belongs to.
+///
+/// Discord tokens consist of three parts separated by periods, the first of
+/// which is the base64-encoded Id of the user. An optional `"Bot "` prefix is
+/// ignored.
+///
+/// This does not check with Discord that the token is in use.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity::model::id::UserId;
+/// use serenity::utils::token::{self, TokenError};
+///
+/// let id = token::validate("Mjg4NzYwMjQxMzYzODc3ODg4.C_ikow.j3VupLBuE1QWZng3TMGH0z_UAwg");
+/// assert_eq!(id, Ok(UserId(288760241363877888)));
+///
+/// assert_eq!(token::validate("cat"), Err(TokenError::Malformed));
+/// assert_eq!(token::validate("aaa.abcdefgh.bbb"), Err(TokenError::InvalidUserId));
+/// ```
+///
+/// # Errors
+///
+/// Returns a [`TokenError`] describing the first check which failed.
+///
+/// [`TokenError`]: enum.TokenError.html
+pub fn validate(token: impl AsRef<str>) -> Result<UserId, TokenError> {
+    let token = token.as_ref();
+    let token = if token.starts_with("Bot ") { &token[4..] } else { token };
+
+    let encoded_id = check_structure(token)?;
+    let decoded = base64::decode_config(encoded_id.trim_end_matches('='), base64::URL_SAFE_NO_PAD)
+        .map_err(|_| TokenError::InvalidUserId)?;
+
+    String::from_utf8(decoded)
+        .ok()
+        .and_then(|id| id.parse().ok())
+        .map(UserId)
+        .ok_or(TokenError::InvalidUserId)
+}
+
+/// Checks that a token consists of three parts separated by periods, with no
+/// surrounding whitespace, returning its first part.
+pub(crate) fn check_structure(token: &str) -> Result<&str, TokenError> {
+    if token.is_empty() {
+        return Err(TokenError::Empty);
+    }
+
+    if token.trim() != token {
+        return Err(TokenError::Whitespace);
+    }
+
+    let parts: Vec<&str> = token.split('.').collect();
+
+    // The second part - the timestamp - is at least 6 characters long.
+    if parts.len() != 3 || parts[1].len() < 6 {
+        return Err(TokenError::Malformed);
+    }
+
+    Ok(parts[0])
+}
+
+/// An error returned when validating a token via [`validate`].
+///
+/// [`validate`]: fn.validate.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum TokenError {
+    /// The token is empty.
+    Empty,
+    /// The token has whitespace before or after it.
+    Whitespace,
+    /// The token does not consist of three parts separated by periods, or its
+    /// second part is too short.
+    Malformed,
+    /// The first part of the token is not a base64-encoded user Id.
+    InvalidUserId,
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+impl Display for TokenError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult { f.write_str(self.description()) }
+}
+
+impl StdError for TokenError {
+    fn description(&self) -> &str {
+        match *self {
+            TokenError::Empty => "token is empty",
+            TokenError::Whitespace => "token has surrounding whitespace",
+            TokenError::Malformed => "token is malformed",
+            TokenError::InvalidUserId => "token does not contain a valid user Id",
+            TokenError::__Nonexhaustive => unreachable!(),
+        }
+    }
+}
+
+/// A token which is redacted when formatted, so that it is not leaked into
+/// logs or error messages.
+///
+/// # Examples
+///
+/// ```rust
+/// use serenity::utils::token::SecretToken;
+///
+/// let token = SecretToken::new("Mjg4NzYwMjQxMzYzODc3ODg4.C_ikow.j3VupLBuE1QWZng3TMGH0z_UAwg");
+///
+/// assert_eq!(token.to_string(), "[redacted]");
+/// assert_eq!(format!("{:?}", token), "SecretToken([redacted])");
+/// assert!(token.expose().starts_with("Mjg4"));
+/// ```
+#[derive(Clone, Default, Eq, Hash, PartialEq)]
+pub struct SecretToken(String);
+
+impl SecretToken {
+    /// Wraps the given token.
+    pub fn new(token: impl Into<String>) -> Self {
+        SecretToken(token.into())
+    }
+
+    /// Returns the token itself, for use where it must be sent to Discord.
+    #[inline]
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Debug for SecretToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult { f.write_str("SecretToken([redacted])") }
+}
+
+impl Display for SecretToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult { f.write_str("[redacted]") }
+}
+
+impl From<String> for SecretToken {
+    fn from(token: String) -> Self { SecretToken(token) }
+}
+
+impl<'a> From<&'a str> for SecretToken {
+    fn from(token: &str) -> Self { SecretToken(token.to_string()) }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::id::UserId;
+    use super::{validate, SecretToken, TokenError};
+
+    #[test]
+    fn test_validate() {
+        let token = "Mjg4NzYwMjQxMzYzODc3ODg4.C_ikow.j3VupLBuE1QWZng3TMGH0z_UAwg";
+
+        assert_eq!(validate(token), Ok(UserId(288760241363877888)));
+        assert_eq!(validate(format!("Bot {}", token)), Ok(UserId(288760241363877888)));
+        assert_eq!(validate(""), Err(TokenError::Empty));
+        assert_eq!(validate(format!("{} ", token)), Err(TokenError::Whitespace));
+        assert_eq!(validate("a.abcde.b"), Err(TokenError::Malformed));
+        assert_eq!(validate("a.b.c.d"), Err(TokenError::Malformed));
+        // "hello" encoded, which is not an Id.
+        assert_eq!(validate("aGVsbG8.abcdef.b"), Err(TokenError::InvalidUserId));
+        assert_eq!(validate("!!!.abcdef.b"), Err(TokenError::InvalidUserId));
+    }
+
+    #[test]
+    fn test_secret_token() {
+        let token = SecretToken::from("secret");
+
+        assert_eq!(token.expose(), "secret");
+        assert!(!format!("{} {:?}", token, token).contains("secret"));
+    }
+}
@@ -4,10 +4,17 @@ use std::{
     sync::Arc,
     time::Duration,
 };
+use crate::model::{id::UserId, voice::SpeakingState};
+use super::Metadata;
 
 pub const HEADER_LEN: usize = 12;
 pub const SAMPLE_RATE: SampleRate = SampleRate::Hz48000;
 pub const DEFAULT_BITRATE: Bitrate = Bitrate::BitsPerSecond(128_000);
+/// An Opus frame of silence.
+pub const SILENCE_FRAME: [u8; 3] = [0xf8, 0xff, 0xfe];
+/// The number of silence frames to send once audio stops, before no longer
+/// speaking, to avoid unintended Opus interpolation with later audio.
+pub const SILENCE_FRAME_COUNT: u8 = 5;
 
 /// A readable audio source.
 pub trait AudioSource: Send {
@@ -20,11 +27,28 @@ pub trait AudioSource: Send {
     fn read_opus_frame(&mut self) -> Option<Vec<u8>>;
 
     fn decode_and_add_opus_frame(&mut self, float_buffer: &mut [f32; 1920], volume: f32) -> Option<usize>;
+
+    /// Information describing the track, such as its title, if known.
+    fn metadata(&self) -> Option<&Metadata> { None }
+
+    /// Moves playback to the given position, returning whether the source
+    /// supports seeking.
+    ///
+    /// Called when the [`position`] of the [`Audio`] playing the source is
+    /// changed. Sources are not seekable by default; wrap one in a
+    /// [`Restartable`] to seek by re-opening it.
+    ///
+    /// [`Audio`]: struct.Audio.html
+    /// [`Restartable`]: struct.Restartable.html
+    /// [`position`]: struct.Audio.html#method.position
+    fn seek(&mut self, _position: Duration) -> bool { false }
 }
 
 /// A receiver for incoming audio.
 pub trait AudioReceiver: Send {
-    fn speaking_update(&mut self, _ssrc: u32, _user_id: u64, _speaking: bool) { }
+    /// Called when a user starts or stops speaking, or changes how they are
+    /// speaking, such as by becoming a priority speaker.
+    fn speaking_update(&mut self, _ssrc: u32, _user_id: u64, _speaking: SpeakingState) { }
 
     #[allow(clippy::too_many_arguments)]
     fn voice_packet(&mut self,
@@ -48,6 +72,47 @@ pub enum AudioType {
     __Nonexhaustive,
 }
 
+/// Why a source stopped playing, given to the functions registered via
+/// [`Handler::on_track_end`].
+///
+/// [`Handler::on_track_end`]: struct.Handler.html#method.on_track_end
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum TrackEndReason {
+    /// The source reached the end of its stream.
+    Finished,
+    /// Reading from the source failed.
+    Errored,
+    /// The source was skipped in the [`TrackQueue`].
+    ///
+    /// [`TrackQueue`]: struct.TrackQueue.html
+    Skipped,
+    /// The source was stopped, or replaced via [`Handler::play_only`].
+    ///
+    /// [`Handler::play_only`]: struct.Handler.html#method.play_only
+    Stopped,
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+/// A function called by the voice thread whenever a source stops playing.
+pub type TrackEndHandler = dyn FnMut(&LockedAudio, TrackEndReason) + Send;
+
+/// A function called by the voice thread whenever a user connects to the
+/// channel, given the user's Id and audio SSRC.
+pub type ClientConnectHandler = dyn FnMut(UserId, u32) + Send;
+
+/// A function called by the voice thread whenever a user disconnects from the
+/// channel, given the user's Id.
+pub type ClientDisconnectHandler = dyn FnMut(UserId) + Send;
+
+/// A user connecting to or disconnecting from the channel, collected by the
+/// connection for the voice thread to pass on to the registered handlers.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum ClientEvent {
+    Connect(UserId, u32),
+    Disconnect(UserId),
+}
+
 /// Control object for audio playback.
 ///
 /// Accessed by both commands and the playback code -- as such, access is
@@ -84,9 +149,11 @@ pub struct Audio {
     /// [`pause`]: #method.pause
     pub playing: bool,
 
-    /// The desired volume for playback.
+    /// The desired volume for playback, which is the gain applied to this
+    /// source when it is mixed with any others playing at the same time.
     ///
-    /// Sensible values fall between `0.0` and `1.0`.
+    /// Sensible values fall between `0.0` and `1.0`, although greater values
+    /// amplify the source. Negative values are treated as `0.0`.
     ///
     /// Can be controlled with [`volume`] if chaining is desired.
     ///
@@ -151,7 +218,10 @@ impl Audio {
 
     /// Change the position in the stream for subsequent playback.
     ///
-    /// Currently a No-op.
+    /// This only has an effect on sources which support seeking, such as a
+    /// [`Restartable`].
+    ///
+    /// [`Restartable`]: struct.Restartable.html
     pub fn position(&mut self, position: Duration) -> &mut Self {
         self.position = position;
         self.position_modified = true;
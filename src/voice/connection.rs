@@ -1,18 +1,16 @@
 use byteorder::{
     BigEndian,
     ByteOrder,
-    LittleEndian,
     ReadBytesExt,
     WriteBytesExt
 };
-use crate::constants::VOICE_GATEWAY_VERSION;
-use crate::gateway::WsClient;
+use crate::gateway::{GatewayError, WsClient};
 use crate::internal::prelude::*;
 use crate::internal::{
     ws_impl::{ReceiverExt, SenderExt},
     Timer
 };
-use crate::model::event::VoiceEvent;
+use crate::model::{event::VoiceEvent, voice::SpeakingState};
 
 use audiopus::{
     packet as opus_packet,
@@ -28,7 +26,7 @@ use rand::random;
 use serde::Deserialize;
 use sodiumoxide::crypto::secretbox::{self, Key, Nonce};
 use std::{
-    collections::HashMap,
+    collections::{HashMap, VecDeque},
     io::Write,
     net::{SocketAddr, ToSocketAddrs, UdpSocket},
     sync::{
@@ -47,28 +45,46 @@ use std::{
     time::Duration
 };
 
-use super::audio::{AudioReceiver, AudioType, HEADER_LEN, SAMPLE_RATE, DEFAULT_BITRATE, LockedAudio};
+use super::audio::{
+    AudioReceiver,
+    AudioType,
+    ClientEvent,
+    HEADER_LEN,
+    SAMPLE_RATE,
+    DEFAULT_BITRATE,
+    LockedAudio,
+    SILENCE_FRAME,
+    SILENCE_FRAME_COUNT,
+    TrackEndReason,
+};
 use super::connection_info::ConnectionInfo;
-use super::{payload, VoiceError, CRYPTO_MODE};
+use super::mixer::{self, MixBuffer, MONO_FRAME_LEN, STEREO_FRAME_LEN};
+use super::protocol::ProtocolVersion;
+use super::{payload, EncoderSettings, VoiceError, CRYPTO_MODE};
 use url::Url;
-use log::{debug, info, warn};
+use log::{debug, info, trace, warn};
 
 #[cfg(not(feature = "native_tls_backend"))]
 use crate::internal::ws_impl::create_rustls_client;
 
+/// The length of an IP discovery packet.
+const IP_DISCOVERY_LEN: usize = 74;
+/// How often, in milliseconds, to send a UDP keepalive while no audio is sent.
+const UDP_KEEPALIVE_INTERVAL: u64 = 5000;
+
 enum ReceiverStatus {
     Udp(Vec<u8>),
     Websocket(VoiceEvent),
+    WebsocketClosed(Option<u16>),
 }
 
-#[allow(dead_code)]
 struct ThreadItems {
     rx: MpscReceiver<ReceiverStatus>,
     tx: MpscSender<ReceiverStatus>,
     udp_close_sender: MpscSender<i32>,
-    udp_thread: JoinHandle<()>,
+    udp_thread: Option<JoinHandle<()>>,
     ws_close_sender: MpscSender<i32>,
-    ws_thread: JoinHandle<()>,
+    ws_thread: Option<JoinHandle<()>>,
 }
 
 pub struct Connection {
@@ -78,14 +94,16 @@ pub struct Connection {
     decoder_map: HashMap<(u32, Channels), OpusDecoder>,
     destination: SocketAddr,
     encoder: OpusEncoder,
-    encoder_stereo: bool,
     keepalive_timer: Timer,
     key: Key,
     last_heartbeat_nonce: Option<u64>,
+    packet_decoder: OpusDecoder,
+    protocol: ProtocolVersion,
     sequence: u16,
     silence_frames: u8,
     soft_clip: SoftClip,
-    speaking: bool,
+    speaking: SpeakingState,
+    speaking_flags: SpeakingState,
     ssrc: u32,
     thread_items: ThreadItems,
     timestamp: u32,
@@ -94,7 +112,8 @@ pub struct Connection {
 
 impl Connection {
     pub fn new(mut info: ConnectionInfo) -> Result<Connection> {
-        let url = generate_url(&mut info.endpoint)?;
+        let protocol = ProtocolVersion::CURRENT;
+        let url = generate_url(&mut info.endpoint, protocol)?;
 
         #[cfg(not(feature = "native_tls_backend"))]
         let mut client = create_rustls_client(url)?;
@@ -144,40 +163,19 @@ impl Connection {
             .next()
             .ok_or(Error::Voice(VoiceError::HostnameResolve))?;
 
-        // Important to note here: the length of the packet can be of either 4
-        // or 70 bytes. If it is 4 bytes, then we need to send a 70-byte packet
-        // to determine the IP.
-        //
-        // Past the initial 4 bytes, the packet _must_ be completely empty data.
-        //
-        // The returned packet will be a null-terminated string of the IP, and
-        // the port encoded in LE in the last two bytes of the packet.
-        let udp = UdpSocket::bind("0.0.0.0:0")?;
+        // Bind to the same address family as the voice server, which may be
+        // reached over IPv6.
+        let udp = if destination.is_ipv6() {
+            UdpSocket::bind("[::]:0")?
+        } else {
+            UdpSocket::bind("0.0.0.0:0")?
+        };
 
         {
-            let mut bytes = [0; 70];
-
-            (&mut bytes[..]).write_u32::<BigEndian>(ready.ssrc)?;
-            udp.send_to(&bytes, destination)?;
-
-            let mut bytes = [0; 256];
-            let (len, _addr) = udp.recv_from(&mut bytes)?;
-
-            // Find the position in the bytes that contains the first byte of 0,
-            // indicating the "end of the address".
-            let index = bytes
-                .iter()
-                .skip(4)
-                .position(|&x| x == 0)
-                .ok_or(Error::Voice(VoiceError::FindingByte))?;
-
-            let pos = 4 + index;
-            let addr = String::from_utf8_lossy(&bytes[4..pos]);
-            let port_pos = len - 2;
-            let port = (&bytes[port_pos..]).read_u16::<LittleEndian>()?;
+            let (addr, port) = discover_ip(&udp, destination, ready.ssrc)?;
 
             client
-                .send_json(&payload::build_select_protocol(addr, port))?;
+                .send_json(&payload::build_select_protocol(addr.into(), port))?;
         }
 
         let key = encryption_key(&mut client)?;
@@ -188,44 +186,50 @@ impl Connection {
 
         info!("[Voice] Connected to: {}", info.endpoint);
 
-        // Encode for Discord in Stereo, as required.
+        // Encode for Discord in Stereo, as required. Every source is mixed
+        // into a stereo frame, so the encoder never needs to change.
         let mut encoder = OpusEncoder::new(SAMPLE_RATE, Channels::Stereo, CodingMode::Audio)?;
         encoder.set_bitrate(DEFAULT_BITRATE)?;
         let soft_clip = SoftClip::new(Channels::Stereo);
 
-        // Per discord dev team's current recommendations:
-        // (https://discordapp.com/developers/docs/topics/voice-connections#heartbeating)
-        let temp_heartbeat = (hello.heartbeat_interval as f64 * 0.75) as u64;
+        let temp_heartbeat = protocol.heartbeat_interval(&hello);
         info!(
-            "[Voice] WS heartbeat duration given as {}ms, adjusted to {}ms.",
+            "[Voice] WS heartbeat duration given as {}ms, using {}ms.",
             hello.heartbeat_interval,
             temp_heartbeat,
         );
 
         Ok(Connection {
-            audio_timer: Timer::new(1000 * 60 * 4),
+            audio_timer: Timer::new(UDP_KEEPALIVE_INTERVAL),
             client: mutexed_client,
             connection_info: info,
             decoder_map: HashMap::new(),
             destination,
             encoder,
-            encoder_stereo: false,
             key,
             keepalive_timer: Timer::new(temp_heartbeat),
             last_heartbeat_nonce: None,
+            packet_decoder: OpusDecoder::new(SAMPLE_RATE, Channels::Stereo)?,
+            protocol,
             udp,
             sequence: 0,
             silence_frames: 0,
             soft_clip,
-            speaking: false,
+            speaking: SpeakingState::empty(),
+            speaking_flags: SpeakingState::MICROPHONE,
             ssrc: ready.ssrc,
             thread_items,
             timestamp: 0,
         })
     }
 
-    pub fn reconnect(&mut self) -> Result<()> {
-        let url = generate_url(&mut self.connection_info.endpoint)?;
+    /// Resumes the voice session over a new websocket, keeping the UDP
+    /// session and thus avoiding the gap in audio of a new connection.
+    ///
+    /// Fails if the session can no longer be resumed, in which case a new
+    /// connection must be made.
+    pub fn resume(&mut self) -> Result<()> {
+        let url = generate_url(&mut self.connection_info.endpoint, self.protocol)?;
 
         // Thread may have died, we want to send to prompt a clean exit
         // (if at all possible) and then proceed as normal.
@@ -271,16 +275,22 @@ impl Connection {
 
         let hello = hello.expect("[Voice] Hello packet expected in connection initialisation, but not found.");
 
-        self.keepalive_timer = Timer::new((hello.heartbeat_interval as f64 * 0.75) as u64);
+        self.keepalive_timer = Timer::new(self.protocol.heartbeat_interval(&hello));
 
         unset_blocking(&mut client)?;
         let mutexed_client = Arc::new(Mutex::new(client));
-        let (ws_close_sender, ws_thread) = start_ws_thread(mutexed_client, &self.thread_items.tx)?;
+        let (ws_close_sender, ws_thread) = start_ws_thread(Arc::clone(&mutexed_client), &self.thread_items.tx)?;
 
+        self.client = mutexed_client;
+        self.last_heartbeat_nonce = None;
         self.thread_items.ws_close_sender = ws_close_sender;
-        self.thread_items.ws_thread = ws_thread;
 
-        info!("[Voice] Reconnected to: {}", &self.connection_info.endpoint);
+        // The old thread was told to exit before resuming.
+        if let Some(old) = self.thread_items.ws_thread.replace(ws_thread) {
+            let _ = old.join();
+        }
+
+        info!("[Voice] Resumed session with: {}", &self.connection_info.endpoint);
         Ok(())
     }
 
@@ -347,14 +357,16 @@ impl Connection {
         Ok(())
     }
 
+    /// Sends a UDP keepalive if no audio was sent recently, so that any NAT
+    /// between the client and the voice server keeps the mapping open.
     #[inline]
     fn check_audio_timer(&mut self) -> Result<()> {
         if self.audio_timer.check() {
-            info!("[Voice] UDP keepalive");
+            trace!("[Voice] UDP keepalive");
             let mut bytes = [0; 4];
             (&mut bytes[..]).write_u32::<BigEndian>(self.ssrc)?;
             self.udp.send_to(&bytes, self.destination)?;
-            info!("[Voice] UDP keepalive sent");
+            trace!("[Voice] UDP keepalive sent");
         }
 
         Ok(())
@@ -373,13 +385,18 @@ impl Connection {
         Ok(())
     }
 
+    /// Reads a frame from every playing source and adds it to the mix, each
+    /// scaled by the volume of its source, removing those which have finished.
+    ///
+    /// Returns the greatest number of samples read from any source, which is
+    /// `0` if nothing is playing.
     #[inline]
-    fn remove_unfinished_files(
+    fn mix_sources(
         &mut self,
         sources: &mut Vec<LockedAudio>,
-        opus_frame: &[u8],
-        buffer: &mut [i16; 1920],
-        mut mix_buffer: &mut [f32; 1920],
+        ended: &mut Vec<(LockedAudio, TrackEndReason)>,
+        buffer: &mut [i16; STEREO_FRAME_LEN],
+        mix_buffer: &mut MixBuffer,
     ) -> Result<usize> {
         let mut len = 0;
         let mut i = 0;
@@ -390,6 +407,16 @@ impl Connection {
             let aud_lock = (&sources[i]).clone();
             let mut aud = aud_lock.lock();
 
+            if aud.position_modified {
+                let position = aud.position;
+
+                if !aud.source.seek(position) {
+                    debug!("[Voice] Source does not support seeking");
+                }
+
+                aud.position_modified = false;
+            }
+
             let vol = aud.volume;
             let skip = !aud.playing;
 
@@ -402,47 +429,41 @@ impl Connection {
                     continue;
                 }
 
-                // Assume this for now, at least.
-                // We'll be fusing streams, so we can either keep
-                // as stereo or downmix to mono.
-                let is_stereo = true;
-                let source_stereo = stream.is_stereo();
+                // `None` indicates that reading from the source failed.
+                let read = match stream.get_type() {
+                    AudioType::Opus => stream.decode_and_add_opus_frame(mix_buffer, vol),
+                    AudioType::Pcm => {
+                        let source_stereo = stream.is_stereo();
+                        let buffer_len = if source_stereo { STEREO_FRAME_LEN } else { MONO_FRAME_LEN };
+
+                        // Pad a partially read frame - such as the last of a
+                        // stream - with silence rather than the previous
+                        // source's samples.
+                        for value in buffer.iter_mut() {
+                            *value = 0;
+                        }
 
-                if is_stereo != self.encoder_stereo {
-                    let channels = if is_stereo {
-                        Channels::Stereo
-                    } else {
-                        Channels::Mono
-                    };
-                    self.encoder = OpusEncoder::new(SAMPLE_RATE, channels, CodingMode::Audio)?;
-                    self.encoder_stereo = is_stereo;
-                }
+                        let read = stream.read_pcm_frame(&mut buffer[..buffer_len]);
 
-                let temp_len = match stream.get_type() {
-                    AudioType::Opus => if stream.decode_and_add_opus_frame(&mut mix_buffer, vol).is_some() {
-                            opus_frame.len()
-                        } else {
-                            0
-                        },
-                    AudioType::Pcm => {
-                        let buffer_len = if source_stereo { 960 * 2 } else { 960 };
+                        mixer::add_pcm(mix_buffer, &buffer[..buffer_len], source_stereo, vol);
 
-                        match stream.read_pcm_frame(&mut buffer[..buffer_len]) {
-                            Some(len) => len,
-                            None => 0,
-                        }
+                        read
                     },
                     AudioType::__Nonexhaustive => unreachable!(),
                 };
-
-                // May need to force interleave/copy.
-                combine_audio(*buffer, &mut mix_buffer, source_stereo, vol);
+                let temp_len = read.unwrap_or(0);
 
                 len = len.max(temp_len);
                 i += if temp_len > 0 {
                     1
                 } else {
-                    sources.remove(i);
+                    let reason = if read.is_some() {
+                        TrackEndReason::Finished
+                    } else {
+                        TrackEndReason::Errored
+                    };
+
+                    ended.push((sources.remove(i), reason));
                     finished = true;
 
                     0
@@ -462,21 +483,24 @@ impl Connection {
     #[allow(unused_variables)]
     pub fn cycle(&mut self,
                 mut sources: &mut Vec<LockedAudio>,
+                packets: &mut VecDeque<Vec<u8>>,
+                ended: &mut Vec<(LockedAudio, TrackEndReason)>,
+                clients: &mut Vec<ClientEvent>,
                 mut receiver: &mut Option<Box<dyn AudioReceiver>>,
                 audio_timer: &mut Timer,
-                bitrate: Bitrate)
+                encoder_settings: &EncoderSettings)
                  -> Result<()> {
         // We need to actually reserve enough space for the desired bitrate.
-        let size = match bitrate {
+        let size = match encoder_settings.bitrate {
             // If user specified, we can calculate. 20ms means 50fps.
             Bitrate::BitsPerSecond(b) => b / 50,
             // Otherwise, just have a lot preallocated.
             _ => 5120,
         } + 16;
 
-        let mut buffer = [0i16; 960 * 2];
-        let mut mix_buffer = [0f32; 960 * 2];
-        let mut packet = vec![0u8; size as usize].into_boxed_slice();
+        let mut buffer = [0i16; STEREO_FRAME_LEN];
+        let mut mix_buffer = [0f32; STEREO_FRAME_LEN];
+        let mut packet = vec![0u8; size as usize];
         let mut nonce = secretbox::Nonce([0; 24]);
 
         while let Ok(status) = self.thread_items.rx.try_recv() {
@@ -493,11 +517,15 @@ impl Connection {
                     if let Some(receiver) = receiver.as_mut() {
                         receiver.client_connect(ev.audio_ssrc, ev.user_id.0);
                     }
+
+                    clients.push(ClientEvent::Connect(ev.user_id, ev.audio_ssrc));
                 },
                 ReceiverStatus::Websocket(VoiceEvent::ClientDisconnect(ev)) => {
                     if let Some(receiver) = receiver.as_mut() {
                         receiver.client_disconnect(ev.user_id.0);
                     }
+
+                    clients.push(ClientEvent::Disconnect(ev.user_id));
                 },
                 ReceiverStatus::Websocket(VoiceEvent::HeartbeatAck(ev)) => {
                     if let Some(nonce) = self.last_heartbeat_nonce {
@@ -514,6 +542,9 @@ impl Connection {
                 ReceiverStatus::Websocket(other) => {
                     info!("[Voice] Received other websocket data: {:?}", other);
                 },
+                ReceiverStatus::WebsocketClosed(code) => {
+                    return Err(Error::Voice(VoiceError::WebsocketClosed(code)));
+                },
             }
         }
 
@@ -523,43 +554,59 @@ impl Connection {
         // Send UDP keepalive if it's time
         self.check_audio_timer()?;
 
-        // Reconfigure encoder bitrate.
+        // Reconfigure the encoder.
         // From my testing, it seemed like this needed to be set every cycle.
-        if let Err(e) = self.encoder.set_bitrate(bitrate) {
-            warn!("[Voice] Bitrate set unsuccessfully: {:?}", e);
-        }
+        self.apply_encoder_settings(encoder_settings);
 
         let mut opus_frame = Vec::new();
 
-        // Walk over all the audio files, removing those which have finished.
-        // For this purpose, we need a while loop in Rust.
-        let len = self.remove_unfinished_files(&mut sources, &opus_frame, &mut buffer,&mut mix_buffer)?;
+        // Mix every playing source together, removing those which have
+        // finished.
+        let mut len = self.mix_sources(&mut sources, ended, &mut buffer, &mut mix_buffer)?;
+
+        // Send a packet given via `Handler::send_opus_packet` untouched if
+        // nothing else is playing, and mix it in otherwise.
+        if let Some(sent) = packets.pop_front() {
+            if len == 0 {
+                opus_frame = sent;
+            } else {
+                len = len.max(self.decode_and_add_packet(&sent, &mut mix_buffer));
+            }
+        }
 
         self.soft_clip.apply(&mut mix_buffer[..])?;
+        mixer::saturate(&mut mix_buffer);
 
-        if len == 0 {
+        // Nothing is playing when every source is paused, has finished, or
+        // ran out of data to play.
+        if len == 0 && opus_frame.is_empty() {
             if self.silence_frames > 0 {
                 self.silence_frames -= 1;
 
                 // Explicit "Silence" frame.
-                opus_frame.extend_from_slice(&[0xf8, 0xff, 0xfe]);
+                opus_frame.extend_from_slice(&SILENCE_FRAME);
             } else {
                 // Per official guidelines, send 5x silence BEFORE we stop speaking.
-                self.set_speaking(false)?;
+                self.set_speaking(SpeakingState::empty())?;
 
                 audio_timer.r#await();
 
                 return Ok(());
             }
         } else {
-            self.silence_frames = 5;
-
-            for value in &mut buffer[len..] {
-                *value = 0;
-            }
+            self.silence_frames = SILENCE_FRAME_COUNT;
         }
 
-        self.set_speaking(true)?;
+        let flags = self.speaking_flags;
+        self.set_speaking(flags)?;
+
+        // Make room for a sent packet larger than those encoded at the
+        // current bitrate, along with its authentication tag.
+        let needed = HEADER_LEN + opus_frame.len() + 16;
+
+        if packet.len() < needed {
+            packet.resize(needed, 0);
+        }
 
         let index = self.prep_packet(&mut packet, mix_buffer, &opus_frame, nonce)?;
         audio_timer.r#await();
@@ -570,9 +617,46 @@ impl Connection {
         Ok(())
     }
 
+    fn decode_and_add_packet(&mut self, packet: &[u8], mix_buffer: &mut MixBuffer) -> usize {
+        let mut decoded = [0f32; STEREO_FRAME_LEN];
+
+        match self.packet_decoder.decode_float(packet, &mut decoded[..], false) {
+            Ok(count) => {
+                // The decoder is stereo, so returns the number of samples per
+                // channel.
+                mixer::add_float(mix_buffer, &decoded[..(count * 2).min(STEREO_FRAME_LEN)], 1.0);
+
+                count
+            },
+            Err(why) => {
+                warn!("[Voice] Error decoding sent Opus packet: {:?}", why);
+
+                0
+            },
+        }
+    }
+
+    fn apply_encoder_settings(&mut self, settings: &EncoderSettings) {
+        if let Err(e) = self.encoder.set_bitrate(settings.bitrate) {
+            warn!("[Voice] Bitrate set unsuccessfully: {:?}", e);
+        }
+
+        if let Err(e) = self.encoder.set_inband_fec(settings.inband_fec) {
+            warn!("[Voice] Inband FEC set unsuccessfully: {:?}", e);
+        }
+
+        if let Err(e) = self.encoder.set_packet_loss_perc(settings.packet_loss_percentage) {
+            warn!("[Voice] Packet loss percentage set unsuccessfully: {:?}", e);
+        }
+
+        if let Err(e) = self.encoder.set_signal(settings.signal) {
+            warn!("[Voice] Signal set unsuccessfully: {:?}", e);
+        }
+    }
+
     fn prep_packet(&mut self,
                    packet: &mut [u8],
-                   buffer: [f32; 1920],
+                   buffer: MixBuffer,
                    opus_frame: &[u8],
                    mut nonce: Nonce)
                    -> Result<usize> {
@@ -588,11 +672,10 @@ impl Connection {
             .clone_from_slice(&packet[..HEADER_LEN]);
 
         let sl_index = packet.len() - 16;
-        let buffer_len = if self.encoder_stereo { 960 * 2 } else { 960 };
 
         let len = if opus_frame.is_empty() {
             self.encoder
-                .encode_float(&buffer[..buffer_len], &mut packet[HEADER_LEN..sl_index])?
+                .encode_float(&buffer[..], &mut packet[HEADER_LEN..sl_index])?
         } else {
             let len = opus_frame.len();
             packet[HEADER_LEN..HEADER_LEN + len]
@@ -613,15 +696,21 @@ impl Connection {
         Ok(HEADER_LEN + crypted.len())
     }
 
-    fn set_speaking(&mut self, speaking: bool) -> Result<()> {
+    /// Sets the flags to send while playing audio, such as to become a
+    /// priority speaker.
+    pub fn set_speaking_flags(&mut self, flags: SpeakingState) {
+        self.speaking_flags = flags;
+    }
+
+    fn set_speaking(&mut self, speaking: SpeakingState) -> Result<()> {
         if self.speaking == speaking {
             return Ok(());
         }
 
         self.speaking = speaking;
 
-        info!("[Voice] Speaking update: {}", speaking);
-        let o = self.client.lock().send_json(&payload::build_speaking(speaking));
+        info!("[Voice] Speaking update: {:?}", speaking);
+        let o = self.client.lock().send_json(&payload::build_speaking(self.protocol, speaking, self.ssrc));
         info!("[Voice] Speaking update confirmed.");
         o
     }
@@ -632,33 +721,78 @@ impl Drop for Connection {
         let _ = self.thread_items.udp_close_sender.send(0);
         let _ = self.thread_items.ws_close_sender.send(0);
 
+        // Wait for both threads, so that no socket outlives the connection.
+        for thread in self.thread_items.udp_thread.take().into_iter().chain(self.thread_items.ws_thread.take()) {
+            let _ = thread.join();
+        }
+
         info!("[Voice] Disconnected");
     }
 }
 
-#[inline]
-fn combine_audio(
-    raw_buffer: [i16; 1920],
-    float_buffer: &mut [f32; 1920],
-    true_stereo: bool,
-    volume: f32,
-) {
-    for (i, float_buffer_element) in float_buffer.iter_mut().enumerate().take(1920) {
-        let sample_index = if true_stereo { i } else { i / 2 };
-        let sample = f32::from(raw_buffer[sample_index]) / 32768.0;
-
-        *float_buffer_element += sample * volume;
+/// Discovers the external address and port of the UDP socket, as seen by the
+/// voice server.
+///
+/// The request and response share the same 74-byte layout:
+///
+/// - the type, `1` for a request and `2` for a response (2 bytes);
+/// - the length of the remainder, always `70` (2 bytes);
+/// - the SSRC (4 bytes);
+/// - the address as a null-terminated string, empty in the request (64
+///   bytes);
+/// - the port, empty in the request (2 bytes).
+///
+/// All numbers are big-endian.
+fn discover_ip(udp: &UdpSocket, destination: SocketAddr, ssrc: u32) -> Result<(String, u16)> {
+    let mut bytes = [0; IP_DISCOVERY_LEN];
+
+    {
+        let mut cursor = &mut bytes[..];
+        cursor.write_u16::<BigEndian>(1)?;
+        cursor.write_u16::<BigEndian>(70)?;
+        cursor.write_u32::<BigEndian>(ssrc)?;
+    }
+
+    // Don't wait forever on a voice server which never responds.
+    udp.set_read_timeout(Some(Duration::from_secs(5)))?;
+    udp.send_to(&bytes, destination)?;
+
+    let mut bytes = [0; 256];
+    let (len, _addr) = udp.recv_from(&mut bytes)?;
+    udp.set_read_timeout(None)?;
+
+    parse_ip_discovery(&bytes[..len])
+}
+
+/// Reads the external address and port from a response to an IP discovery
+/// request.
+fn parse_ip_discovery(bytes: &[u8]) -> Result<(String, u16)> {
+    if bytes.len() < IP_DISCOVERY_LEN || BigEndian::read_u16(&bytes[..2]) != 2 {
+        return Err(Error::Voice(VoiceError::FindingByte));
     }
+
+    // Find the position in the bytes that contains the first byte of 0,
+    // indicating the "end of the address".
+    let address = &bytes[8..72];
+    let index = address
+        .iter()
+        .position(|&x| x == 0)
+        .ok_or(Error::Voice(VoiceError::FindingByte))?;
+
+    let addr = String::from_utf8_lossy(&address[..index]).into_owned();
+    let port = BigEndian::read_u16(&bytes[72..74]);
+
+    Ok((addr, port))
 }
 
-fn generate_url(endpoint: &mut String) -> Result<Url> {
+fn generate_url(endpoint: &mut String, protocol: ProtocolVersion) -> Result<Url> {
     if endpoint.ends_with(":80") {
         let len = endpoint.len();
 
         endpoint.truncate(len - 3);
     }
 
-    Url::parse(&format!("wss://{}/?v={}", endpoint, VOICE_GATEWAY_VERSION))
+    Url::parse(&format!("wss://{}/?v={}", endpoint, protocol.num()))
         .or(Err(Error::Voice(VoiceError::EndpointUrl)))
 }
 
@@ -718,6 +852,12 @@ fn start_threads(client: Arc<Mutex<WsClient>>, udp: &UdpSocket) -> Result<Thread
             let mut buffer = [0; 512];
 
             'outer: loop {
+                // Checked on every packet, as a busy channel may never leave
+                // time for reading to time out.
+                if udp_close_reader.try_recv().is_ok() {
+                    break 'outer;
+                }
+
                 if let Ok((len, _)) = udp_clone.recv_from(&mut buffer) {
                     let piece = buffer[..len].to_vec();
                     let send = tx_udp.send(ReceiverStatus::Udp(piece));
@@ -725,8 +865,6 @@ fn start_threads(client: Arc<Mutex<WsClient>>, udp: &UdpSocket) -> Result<Thread
                     if send.is_err() {
                         break 'outer;
                     }
-                } else if udp_close_reader.try_recv().is_ok() {
-                    break 'outer;
                 }
             }
 
@@ -739,9 +877,9 @@ fn start_threads(client: Arc<Mutex<WsClient>>, udp: &UdpSocket) -> Result<Thread
         rx,
         tx,
         udp_close_sender,
-        udp_thread,
+        udp_thread: Some(udp_thread),
         ws_close_sender,
-        ws_thread,
+        ws_thread: Some(ws_thread),
     })
 }
 
@@ -757,7 +895,25 @@ fn start_ws_thread(client: Arc<Mutex<WsClient>>, tx: &MpscSender<ReceiverStatus>
         .name(format!("{} WS", thread_name))
         .spawn(move || {
             'outer: loop {
-                while let Ok(Some(value)) = client.lock().try_recv_json() {
+                loop {
+                    let value = match client.lock().try_recv_json() {
+                        Ok(Some(value)) => value,
+                        Ok(None) => break,
+                        Err(why) => {
+                            let code = match why {
+                                Error::Gateway(GatewayError::Closed(Some(ref frame))) => Some(frame.code.into()),
+                                _ => None,
+                            };
+
+                            info!("[Voice] WS closed: {:?}", why);
+
+                            // Let the voice thread resume the session.
+                            let _ = tx_ws.send(ReceiverStatus::WebsocketClosed(code));
+
+                            break 'outer;
+                        },
+                    };
+
                     let msg = match VoiceEvent::deserialize(value) {
                         Ok(msg) => msg,
                         Err(_) => break,
@@ -794,3 +950,36 @@ fn unset_blocking(client: &mut WsClient) -> Result<()> {
     stream.set_nonblocking(true)
         .map_err(Into::into)
 }
+
+#[cfg(test)]
+mod test {
+    use super::{parse_ip_discovery, IP_DISCOVERY_LEN};
+
+    fn response(kind: u16, address: &str, port: u16) -> Vec<u8> {
+        let mut bytes = vec![0; IP_DISCOVERY_LEN];
+        bytes[..2].copy_from_slice(&kind.to_be_bytes());
+        bytes[2..4].copy_from_slice(&70u16.to_be_bytes());
+        bytes[8..8 + address.len()].copy_from_slice(address.as_bytes());
+        bytes[72..74].copy_from_slice(&port.to_be_bytes());
+
+        bytes
+    }
+
+    #[test]
+    fn test_parse_ip_discovery() {
+        let (address, port) = parse_ip_discovery(&response(2, "203.0.113.7", 50004)).unwrap();
+        assert_eq!(address, "203.0.113.7");
+        assert_eq!(port, 50004);
+
+        // A request echoed back rather than answered.
+        assert!(parse_ip_discovery(&response(1, "203.0.113.7", 50004)).is_err());
+        assert!(parse_ip_discovery(&response(2, "203.0.113.7", 50004)[..20]).is_err());
+
+        // An address without its terminating null byte.
+        let mut bytes = response(2, "", 50004);
+        for byte in &mut bytes[8..72] {
+            *byte = b'1';
+        }
+        assert!(parse_ip_discovery(&bytes).is_err());
+    }
+}
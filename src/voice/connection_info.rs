@@ -1,10 +1,11 @@
 use crate::model::id::{GuildId, UserId};
+use crate::utils::token::SecretToken;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ConnectionInfo {
     pub endpoint: String,
     pub guild_id: GuildId,
     pub session_id: String,
-    pub token: String,
+    pub token: SecretToken,
     pub user_id: UserId,
 }
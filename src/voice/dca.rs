@@ -1,14 +1,160 @@
-#[derive(Debug, Deserialize)]
+use serde_json::Value;
+use std::time::Duration;
+
+/// The JSON metadata header of a DCA1 file.
+///
+/// Refer to the [DCA1 specification] for a description of each field.
+///
+/// [DCA1 specification]: https://github.com/bwmarrin/dca/tree/master/cmd/dca
+#[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct DcaMetadata {
-    opus: OpusInfo,
+    /// Information about the DCA file itself.
+    pub dca: Option<DcaInfo>,
+    /// Information about the Opus audio stored in the file.
+    pub opus: OpusInfo,
+    /// Information about the track, such as its title.
+    pub info: Option<TrackInfo>,
+    /// Information about the file the track was encoded from.
+    pub origin: Option<Origin>,
+    /// Custom values stored by the encoding tool.
+    pub extra: Option<Value>,
+    /// How long the audio plays for.
+    ///
+    /// This is not part of the header, and is instead computed from the
+    /// frames in the file when it is opened via [`voice::dca`].
+    ///
+    /// [`voice::dca`]: fn.dca.html
+    #[serde(skip)]
+    pub duration: Option<Duration>,
+}
+
+/// Information about a DCA file itself.
+#[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
+pub struct DcaInfo {
+    /// The version of the DCA format.
+    pub version: u64,
+    /// The tool used to encode the file.
+    pub tool: Option<Tool>,
+}
+
+/// The tool which encoded a DCA file.
+#[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
+pub struct Tool {
+    pub name: String,
+    pub version: String,
+    pub url: Option<String>,
+    pub author: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
-struct OpusInfo {
-    /// Number of channels
-    channels: u8,
+/// Information about the Opus audio stored in a DCA file.
+#[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
+pub struct OpusInfo {
+    /// The Opus application mode, such as `"voip"` or `"music"`.
+    pub mode: Option<String>,
+    /// The sample rate in Hz.
+    pub sample_rate: Option<u32>,
+    /// The number of samples per channel in each frame.
+    pub frame_size: Option<u64>,
+    /// The bitrate in bits per second.
+    pub abr: Option<u64>,
+    /// Whether the audio uses a variable bitrate.
+    pub vbr: Option<bool>,
+    /// Number of channels.
+    pub channels: u8,
+}
+
+/// Information about the track stored in a DCA file.
+#[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
+pub struct TrackInfo {
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    pub genre: Option<String>,
+    /// Base64-encoded cover art.
+    pub cover: Option<String>,
+    pub comments: Option<String>,
+}
+
+/// Information about the file a DCA file was encoded from.
+#[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
+pub struct Origin {
+    /// The kind of source, such as `"file"`.
+    pub source: Option<String>,
+    /// The bitrate of the source in bits per second.
+    pub abr: Option<u64>,
+    /// The number of channels of the source.
+    pub channels: Option<u8>,
+    /// The encoding of the source, such as `"MP3/MPEG-2 L3"`.
+    pub encoding: Option<String>,
+    /// The URL the source was retrieved from.
+    pub url: Option<String>,
 }
 
 impl DcaMetadata {
     pub fn is_stereo(&self) -> bool { self.opus.channels == 2 }
+
+    /// The title of the track, if given.
+    pub fn title(&self) -> Option<&str> {
+        self.info.as_ref()?.title.as_ref().map(String::as_str)
+    }
+
+    /// The artist of the track, if given.
+    pub fn artist(&self) -> Option<&str> {
+        self.info.as_ref()?.artist.as_ref().map(String::as_str)
+    }
+
+    /// The URL the track was retrieved from, if given.
+    pub fn source_url(&self) -> Option<&str> {
+        self.origin.as_ref()?.url.as_ref().map(String::as_str)
+    }
+
+    /// The length of each frame, assuming Discord's 20ms frames at 48kHz if
+    /// the header does not say otherwise.
+    pub(crate) fn frame_duration(&self) -> Duration {
+        let frame_size = self.opus.frame_size.unwrap_or(960);
+        let sample_rate = u64::from(self.opus.sample_rate.unwrap_or(48_000).max(1));
+
+        Duration::from_micros(frame_size * 1_000_000 / sample_rate)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json;
+    use std::time::Duration;
+    use super::DcaMetadata;
+
+    #[test]
+    fn test_metadata() {
+        let metadata: DcaMetadata = serde_json::from_str(r#"{
+            "dca": {"version": 1, "tool": {"name": "dca-rs", "version": "1.0.0"}},
+            "opus": {"mode": "music", "sample_rate": 48000, "frame_size": 1920, "channels": 2},
+            "info": {"title": "Song", "artist": "Artist"},
+            "origin": {"source": "file", "url": "https://example.com/song"},
+            "extra": {}
+        }"#).unwrap();
+
+        assert!(metadata.is_stereo());
+        assert_eq!(metadata.title(), Some("Song"));
+        assert_eq!(metadata.artist(), Some("Artist"));
+        assert_eq!(metadata.source_url(), Some("https://example.com/song"));
+        assert_eq!(metadata.frame_duration(), Duration::from_millis(40));
+        assert_eq!(metadata.duration, None);
+    }
+
+    #[test]
+    fn test_minimal_metadata() {
+        let metadata: DcaMetadata = serde_json::from_str(r#"{"opus": {"channels": 1}}"#).unwrap();
+
+        assert!(!metadata.is_stereo());
+        assert_eq!(metadata.title(), None);
+        assert_eq!(metadata.source_url(), None);
+        assert_eq!(metadata.frame_duration(), Duration::from_millis(20));
+    }
 }
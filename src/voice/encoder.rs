@@ -0,0 +1,97 @@
+use audiopus::{Bitrate, Signal};
+use crate::model::guild::PremiumTier;
+use super::audio::DEFAULT_BITRATE;
+
+/// Settings of the Opus encoder used for audio sent over a voice connection.
+///
+/// Set via [`Handler::set_encoder_settings`].
+///
+/// # Examples
+///
+/// Use the highest bitrate allowed in a boosted guild, and ask the encoder to
+/// prepare for lossy connections:
+///
+/// ```rust,ignore
+/// use serenity::voice::EncoderSettings;
+///
+/// let mut settings = EncoderSettings::default();
+/// settings
+///     .max_bitrate_for(guild.premium_tier)
+///     .inband_fec(true)
+///     .packet_loss_percentage(10);
+///
+/// handler.set_encoder_settings(settings);
+/// ```
+///
+/// [`Handler::set_encoder_settings`]: struct.Handler.html#method.set_encoder_settings
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub struct EncoderSettings {
+    /// The bitrate to encode at, 128 kbps by default.
+    pub bitrate: Bitrate,
+    /// Whether to include forward error correction data in packets, letting
+    /// listeners recover from lost packets. Disabled by default.
+    pub inband_fec: bool,
+    /// The expected percentage of packets lost, from 0 to 100, which the
+    /// encoder adapts to. 0 by default.
+    pub packet_loss_percentage: u8,
+    /// The kind of audio being encoded, automatically detected by default.
+    pub signal: Signal,
+}
+
+impl EncoderSettings {
+    /// Sets the bitrate to encode at.
+    ///
+    /// Sensible values range between `BitsPerSecond(512)` and the limit of the
+    /// guild, as given by [`PremiumTier::max_voice_bitrate`]. Alternatively,
+    /// `Auto` and `Max` remain available.
+    ///
+    /// [`PremiumTier::max_voice_bitrate`]: ../model/guild/enum.PremiumTier.html#method.max_voice_bitrate
+    pub fn bitrate(&mut self, bitrate: Bitrate) -> &mut Self {
+        self.bitrate = bitrate;
+
+        self
+    }
+
+    /// Sets the bitrate to the highest allowed in a guild of the given premium
+    /// tier.
+    pub fn max_bitrate_for(&mut self, tier: PremiumTier) -> &mut Self {
+        self.bitrate(Bitrate::BitsPerSecond(tier.max_voice_bitrate() as i32))
+    }
+
+    /// Sets whether to include forward error correction data in packets.
+    ///
+    /// This only has an effect if the [`packet_loss_percentage`] is above 0.
+    ///
+    /// [`packet_loss_percentage`]: #structfield.packet_loss_percentage
+    pub fn inband_fec(&mut self, inband_fec: bool) -> &mut Self {
+        self.inband_fec = inband_fec;
+
+        self
+    }
+
+    /// Sets the expected percentage of lost packets, capped at 100.
+    pub fn packet_loss_percentage(&mut self, percentage: u8) -> &mut Self {
+        self.packet_loss_percentage = percentage.min(100);
+
+        self
+    }
+
+    /// Sets the kind of audio being encoded.
+    pub fn signal(&mut self, signal: Signal) -> &mut Self {
+        self.signal = signal;
+
+        self
+    }
+}
+
+impl Default for EncoderSettings {
+    fn default() -> Self {
+        EncoderSettings {
+            bitrate: DEFAULT_BITRATE,
+            inband_fec: false,
+            packet_loss_percentage: 0,
+            signal: Signal::Auto,
+        }
+    }
+}
@@ -18,6 +18,13 @@ pub enum VoiceError {
     Streams,
     #[doc(hidden)] VoiceModeInvalid,
     #[doc(hidden)] VoiceModeUnavailable,
+    /// The voice websocket was closed, with the close code sent by Discord if
+    /// any.
+    ///
+    /// Refer to [`voice_close_codes`] for the meaning of each code.
+    ///
+    /// [`voice_close_codes`]: ../constants/voice_close_codes/index.html
+    WebsocketClosed(Option<u16>),
     /// An error occurred while running `youtube-dl`.
     YouTubeDLRun(Output),
     /// An error occurred while processing the JSON output from `youtube-dl`.
@@ -1,20 +1,38 @@
 use crate::constants::VoiceOpCode;
 use crate::gateway::InterMessage;
+use crate::utils::token::SecretToken;
 use crate::model::{
     id::{
         ChannelId,
         GuildId,
         UserId
     },
-    voice::VoiceState
+    voice::{SpeakingState, VoiceState},
 };
 use parking_lot::Mutex;
-use std::sync::{
-    mpsc::{self, Sender as MpscSender},
-    Arc
+use log::warn;
+use std::{
+    sync::{
+        mpsc::{self, Sender as MpscSender},
+        Arc,
+    },
+    thread::JoinHandle,
 };
 use super::connection_info::ConnectionInfo;
-use super::{Audio, AudioReceiver, AudioSource, Bitrate, Status as VoiceStatus, threading, LockedAudio};
+use super::{
+    Audio,
+    AudioReceiver,
+    AudioSource,
+    Bitrate,
+    EncoderSettings,
+    LockedAudio,
+    LockedQueue,
+    LoopMode,
+    Status as VoiceStatus,
+    Track,
+    TrackEndReason,
+    threading,
+};
 use serde_json::json;
 
 /// The handler is responsible for "handling" a single voice connection, acting
@@ -74,20 +92,26 @@ pub struct Handler {
     ///
     /// [`mute`]: #method.mute
     pub self_mute: bool,
+    /// The queue of tracks played one after another.
+    queue: LockedQueue,
     /// The internal sender to the voice connection monitor thread.
     sender: MpscSender<VoiceStatus>,
+    /// The voice connection monitor thread, shared with clones of this handler
+    /// until it is restarted.
+    thread: Arc<Mutex<Option<JoinHandle<()>>>>,
     /// The session Id of the current voice connection, if any.
     ///
     /// **Note**: This _should_ be set through an [`update_state`] call.
     ///
     /// [`update_state`]: #method.update_state
     pub session_id: Option<String>,
-    /// The token of the current voice connection, if any.
+    /// The token of the current voice connection, if any, which is redacted
+    /// when formatted.
     ///
     /// **Note**: This _should_ be set through an [`update_server`] call.
     ///
     /// [`update_server`]: #method.update_server
-    pub token: Option<String>,
+    pub token: Option<SecretToken>,
     /// The Id of the current user.
     ///
     /// This is configured via [`new`] or [`standalone`].
@@ -192,6 +216,65 @@ impl Handler {
         }
     }
 
+    /// Leaves the current connected voice channel, if connected to one, and
+    /// stops the thread maintaining the connection, waiting for it and the
+    /// connection to be torn down.
+    ///
+    /// Unlike [`leave`], this frees all resources of the connection while the
+    /// handler is kept around. Functions registered via [`on_track_end`] and
+    /// similar, the [`listen`]ing receiver, and encoder settings are dropped
+    /// along with the thread, which is restarted when the handler is next
+    /// used. The [`queue`] is kept.
+    ///
+    /// **Note**: This must not be called from within those functions, as they
+    /// are run by the thread being waited for.
+    ///
+    /// **Note**: When the handler is reached through a [`Client`]'s
+    /// `voice_manager`, its lock is held while waiting. This blocks shards
+    /// from handling voice events until the thread exits, and deadlocks if any
+    /// of those functions locks the `voice_manager` itself. In that case,
+    /// prefer [`Manager::remove`], which does not wait for the thread.
+    ///
+    /// [`Client`]: ../client/struct.Client.html
+    /// [`Manager::remove`]: struct.Manager.html#method.remove
+    /// [`leave`]: #method.leave
+    /// [`listen`]: #method.listen
+    /// [`on_track_end`]: #method.on_track_end
+    /// [`queue`]: #method.queue
+    pub fn disconnect(&mut self) {
+        self.leave();
+
+        // Sent directly, as `send` would restart a thread which has exited.
+        let _ = self.sender.send(VoiceStatus::Shutdown);
+
+        let thread = self.thread.lock().take();
+
+        if let Some(thread) = thread {
+            if thread.join().is_err() {
+                warn!("[Voice] Voice thread of guild {} panicked", self.guild_id);
+            }
+        }
+    }
+
+    /// Adds a source to the back of the [`queue`], to be played once the
+    /// tracks ahead of it have ended.
+    ///
+    /// Use [`enqueue_track`] for a track which can be looped.
+    ///
+    /// [`enqueue_track`]: #method.enqueue_track
+    /// [`queue`]: #method.queue
+    pub fn enqueue(&mut self, source: Box<dyn AudioSource>) {
+        self.enqueue_track(Track::new(source));
+    }
+
+    /// Adds a track to the back of the [`queue`], to be played once the
+    /// tracks ahead of it have ended.
+    ///
+    /// [`queue`]: #method.queue
+    pub fn enqueue_track(&mut self, track: Track) {
+        self.queue.lock().enqueue(track);
+    }
+
     /// Connect - or switch - to the given voice channel by its Id.
     pub fn join(&mut self, channel_id: ChannelId) {
         self.channel_id = Some(channel_id);
@@ -247,6 +330,86 @@ impl Handler {
         }
     }
 
+    /// Registers a function called whenever a user connects to the channel,
+    /// given the user's Id and the SSRC of their audio.
+    ///
+    /// The function is called from the voice connection's thread, and so
+    /// should return quickly.
+    pub fn on_client_connect<F>(&mut self, f: F)
+        where F: FnMut(UserId, u32) + Send + 'static {
+        self.send(VoiceStatus::AddClientConnectHandler(Box::new(f)))
+    }
+
+    /// Registers a function called whenever a user disconnects from the
+    /// channel, given the user's Id.
+    ///
+    /// The function is called from the voice connection's thread, and so
+    /// should return quickly.
+    ///
+    /// # Examples
+    ///
+    /// Pausing playback once the last listener has left:
+    ///
+    /// ```rust,ignore
+    /// use std::{collections::HashSet, sync::{Arc, Mutex}};
+    ///
+    /// let listeners = Arc::new(Mutex::new(HashSet::new()));
+    /// let queue = handler.queue().clone();
+    ///
+    /// let joined = Arc::clone(&listeners);
+    /// handler.on_client_connect(move |user_id, _ssrc| {
+    ///     joined.lock().unwrap().insert(user_id);
+    /// });
+    ///
+    /// handler.on_client_disconnect(move |user_id| {
+    ///     let mut listeners = listeners.lock().unwrap();
+    ///     listeners.remove(&user_id);
+    ///
+    ///     if listeners.is_empty() {
+    ///         queue.lock().pause();
+    ///     }
+    /// });
+    /// ```
+    pub fn on_client_disconnect<F>(&mut self, f: F)
+        where F: FnMut(UserId) + Send + 'static {
+        self.send(VoiceStatus::AddClientDisconnectHandler(Box::new(f)))
+    }
+
+    /// Registers a function called whenever a source stops playing, along with
+    /// why it stopped.
+    ///
+    /// This covers sources played via [`play`] and its variants as well as
+    /// tracks from the [`queue`]. The function is called from the voice
+    /// connection's thread, and so should return quickly.
+    ///
+    /// # Examples
+    ///
+    /// Logging the tracks which could not be played:
+    ///
+    /// ```rust,ignore
+    /// use serenity::voice::TrackEndReason;
+    ///
+    /// handler.on_track_end(|_audio, reason| {
+    ///     if reason == TrackEndReason::Errored {
+    ///         println!("A track could not be played");
+    ///     }
+    /// });
+    /// ```
+    ///
+    /// [`play`]: #method.play
+    /// [`queue`]: #method.queue
+    pub fn on_track_end<F>(&mut self, f: F)
+        where F: FnMut(&LockedAudio, TrackEndReason) + Send + 'static {
+        self.send(VoiceStatus::AddTrackEndHandler(Box::new(f)))
+    }
+
+    /// Pauses the track currently playing from the [`queue`], if any.
+    ///
+    /// [`queue`]: #method.queue
+    pub fn pause_current(&mut self) {
+        self.queue.lock().pause();
+    }
+
     /// Plays audio from a source.
     ///
     /// This can be a source created via [`voice::ffmpeg`] or [`voice::ytdl`].
@@ -270,8 +433,12 @@ impl Handler {
     /// Unlike [`play`] or [`play_returning`], this stops all other sources attached
     /// to the channel.
     ///
+    /// **Note**: This ends the track currently playing from the [`queue`], and
+    /// the queue then carries on with its next track.
+    ///
     /// [`play`]: #method.play
     /// [`play_returning`]: #method.play_returning
+    /// [`queue`]: #method.queue
     pub fn play_only(&mut self, source: Box<dyn AudioSource>) -> LockedAudio {
         let player = Arc::new(Mutex::new(Audio::new(source)));
         self.send(VoiceStatus::SetSender(Some(player.clone())));
@@ -279,19 +446,133 @@ impl Handler {
         player
     }
 
+    /// The queue of tracks played one after another, for inspecting or
+    /// reordering it.
+    ///
+    /// The next track in the queue is started automatically when the current
+    /// one ends.
+    #[inline]
+    pub fn queue(&self) -> &LockedQueue {
+        &self.queue
+    }
+
+    /// Resumes the track currently playing from the [`queue`], if any.
+    ///
+    /// [`queue`]: #method.queue
+    pub fn resume_current(&mut self) {
+        self.queue.lock().resume();
+    }
+
+    /// Sends a pre-encoded Opus packet along the channel being managed,
+    /// without decoding and re-encoding it.
+    ///
+    /// The packet must hold 20ms of 48kHz audio, as Discord expects. Packets
+    /// are queued and one is sent every 20ms, so this may be called as packets
+    /// arrive from e.g. a TTS engine. While other audio is playing, the packet
+    /// is instead mixed with it.
+    ///
+    /// To play a stream of packets as any other source, such as alongside a
+    /// [`queue`], use [`voice::opus_packets`].
+    ///
+    /// [`queue`]: #method.queue
+    /// [`voice::opus_packets`]: fn.opus_packets.html
+    pub fn send_opus_packet(&mut self, packet: &[u8]) {
+        if packet.is_empty() {
+            return;
+        }
+
+        self.send(VoiceStatus::SendOpusPacket(packet.to_vec()))
+    }
+
     /// Sets the bitrate for encoding Opus packets sent along
     /// the channel being managed.
     ///
     /// The default rate is 128 kbps.
-    /// Sensible values range between `Bits(512)` and `Bits(512_000)`
-    /// bits per second.
+    /// Sensible values range between `Bits(512)` and the limit of the guild,
+    /// as given by [`PremiumTier::max_voice_bitrate`].
     /// Alternatively, `Auto` and `Max` remain available.
+    ///
+    /// Use [`set_encoder_settings`] to change other settings of the encoder.
+    ///
+    /// [`PremiumTier::max_voice_bitrate`]: ../model/guild/enum.PremiumTier.html#method.max_voice_bitrate
+    /// [`set_encoder_settings`]: #method.set_encoder_settings
     pub fn set_bitrate(&mut self, bitrate: Bitrate) {
         self.send(VoiceStatus::SetBitrate(bitrate))
     }
 
+    /// Sets the bitrate, forward error correction, expected packet loss and
+    /// signal type used when encoding Opus packets sent along the channel
+    /// being managed.
+    ///
+    /// Refer to [`EncoderSettings`] for an example.
+    ///
+    /// [`EncoderSettings`]: struct.EncoderSettings.html
+    pub fn set_encoder_settings(&mut self, settings: EncoderSettings) {
+        self.send(VoiceStatus::SetEncoderSettings(settings))
+    }
+
+    /// Sets how the [`queue`] behaves once the current track has ended.
+    ///
+    /// [`queue`]: #method.queue
+    pub fn set_loop_mode(&mut self, mode: LoopMode) {
+        self.queue.lock().set_loop_mode(mode);
+    }
+
+    /// Sets the flags sent while audio is playing, which default to
+    /// [`SpeakingState::MICROPHONE`].
+    ///
+    /// For example, include [`SpeakingState::PRIORITY`] to become a priority
+    /// speaker, lowering the volume of other users while playing. This needs
+    /// the `PRIORITY_SPEAKER` permission.
+    ///
+    /// [`SpeakingState::MICROPHONE`]: ../model/voice/struct.SpeakingState.html#associatedconstant.MICROPHONE
+    /// [`SpeakingState::PRIORITY`]: ../model/voice/struct.SpeakingState.html#associatedconstant.PRIORITY
+    pub fn set_speaking_flags(&mut self, flags: SpeakingState) {
+        self.send(VoiceStatus::SetSpeakingFlags(flags))
+    }
+
+    /// Randomly reorders the tracks waiting in the [`queue`].
+    ///
+    /// [`queue`]: #method.queue
+    pub fn shuffle_queue(&mut self) {
+        self.queue.lock().shuffle();
+    }
+
+    /// Stops the track currently playing from the [`queue`] and starts the
+    /// next one.
+    ///
+    /// [`queue`]: #method.queue
+    pub fn skip(&mut self) {
+        self.queue.lock().skip();
+    }
+
     /// Stops playing audio from a source, if one is set.
-    pub fn stop(&mut self) { self.send(VoiceStatus::SetSender(None)) }
+    ///
+    /// This also empties the [`queue`].
+    ///
+    /// [`queue`]: #method.queue
+    pub fn stop(&mut self) {
+        self.queue.lock().clear();
+        self.send(VoiceStatus::SetSender(None))
+    }
+
+    /// Stops all audio and starts playing the given source in its place on the
+    /// next frame, without reconnecting.
+    ///
+    /// Unlike [`play_only`], if a track from the [`queue`] is playing then the
+    /// new source takes its place, so the queue carries on with its next track
+    /// only once the new source has ended. Use [`skip`] to move on to the next
+    /// track instead.
+    ///
+    /// [`play_only`]: #method.play_only
+    /// [`queue`]: #method.queue
+    /// [`skip`]: #method.skip
+    pub fn switch_source(&mut self, source: Box<dyn AudioSource>) -> LockedAudio {
+        let player = Arc::new(Mutex::new(Audio::new(source)));
+        self.send(VoiceStatus::SwitchSource(player.clone()));
+
+        player
+    }
 
     /// Switches the current connected voice channel to the given `channel_id`.
     ///
@@ -338,10 +619,15 @@ impl Handler {
     /// Refer to the documentation for [`connect`] for when this will
     /// automatically connect to a voice channel.
     ///
+    /// If already connected, the connection is moved to the new voice server
+    /// while keeping what is playing. An absent `endpoint` means that the
+    /// voice server is going away, in which case the connection is closed
+    /// until an update with the new endpoint is received.
+    ///
     /// [`connect`]: #method.connect
     /// [`standalone`]: #method.standalone
     pub fn update_server(&mut self, endpoint: &Option<String>, token: &str) {
-        self.token = Some(token.to_string());
+        self.token = Some(SecretToken::new(token));
 
         if let Some(endpoint) = endpoint.clone() {
             self.endpoint = Some(endpoint);
@@ -350,7 +636,8 @@ impl Handler {
                 self.connect();
             }
         } else {
-            self.leave();
+            self.endpoint = None;
+            self.send(VoiceStatus::Suspend);
         }
     }
 
@@ -388,8 +675,9 @@ impl Handler {
         user_id: UserId,
     ) -> Self {
         let (tx, rx) = mpsc::channel();
+        let queue = LockedQueue::default();
 
-        threading::start(guild_id, rx);
+        let thread = threading::start(guild_id, rx, Arc::clone(&queue));
 
         Handler {
             channel_id: None,
@@ -397,7 +685,9 @@ impl Handler {
             guild_id,
             self_deaf: false,
             self_mute: false,
+            queue,
             sender: tx,
+            thread: Arc::new(Mutex::new(Some(thread))),
             session_id: None,
             token: None,
             user_id,
@@ -414,7 +704,8 @@ impl Handler {
             self.sender = tx;
             self.sender.send(status).unwrap();
 
-            threading::start(self.guild_id, rx);
+            let thread = threading::start(self.guild_id, rx, Arc::clone(&self.queue));
+            self.thread = Arc::new(Mutex::new(Some(thread)));
 
             self.update();
         }
@@ -1,5 +1,8 @@
 use crate::gateway::InterMessage;
-use crate::model::id::{ChannelId, GuildId, UserId};
+use crate::model::{
+    id::{ChannelId, GuildId, UserId},
+    voice::VoiceState,
+};
 use std::{
     collections::HashMap,
     sync::mpsc::Sender as MpscSender
@@ -18,23 +21,75 @@ use super::Handler;
 /// If a `guild_id` is provided, then the target is the guild, as a user
 /// can not be connected to two channels within one guild simultaneously.
 ///
+/// A manager need not be tied to a [`Shard`]: one created via [`standalone`]
+/// is instead driven by voice state and server updates received elsewhere,
+/// such as from another process's gateway connection, through
+/// [`update_state`] and [`update_server`].
+///
 /// [`Group`]: ../../model/channel/struct.Group.html
 /// [`Handler`]: struct.Handler.html
 /// [guild's channel]: ../../model/channel/enum.ChannelType.html#variant.Voice
 /// [`Shard`]: ../gateway/struct.Shard.html
+/// [`standalone`]: #method.standalone
+/// [`update_server`]: #method.update_server
+/// [`update_state`]: #method.update_state
 #[derive(Clone, Debug)]
 pub struct Manager {
     handlers: HashMap<GuildId, Handler>,
     user_id: UserId,
-    ws: MpscSender<InterMessage>,
+    ws: Option<MpscSender<InterMessage>>,
 }
 
 impl Manager {
-    pub(crate) fn new(ws: MpscSender<InterMessage>, user_id: UserId) -> Manager {
+    /// Creates a manager whose handlers send their voice state updates - such
+    /// as joining a channel - over the given sender, to be sent on to the
+    /// gateway.
+    ///
+    /// A [`Client`] creates one of these for every shard; create one yourself
+    /// only when running shards without a client.
+    ///
+    /// [`Client`]: ../client/struct.Client.html
+    pub fn new(ws: MpscSender<InterMessage>, user_id: UserId) -> Manager {
         Manager {
             handlers: HashMap::new(),
             user_id,
-            ws,
+            ws: Some(ws),
+        }
+    }
+
+    /// Creates a manager which is not connected to a gateway, for use when the
+    /// voice connections are made by a different process or library than the
+    /// one connected to the gateway.
+    ///
+    /// The voice states and voice servers of the current user must be
+    /// supplied via [`update_state`] and [`update_server`]; handlers are
+    /// created as needed. Joining, leaving, muting and deafening must be done
+    /// through the gateway connection, as the handlers only update their
+    /// internal state.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use serenity::voice::Manager;
+    ///
+    /// let mut manager = Manager::standalone(user_id);
+    ///
+    /// // Forwarded from the process connected to the gateway:
+    /// manager.update_state(guild_id, &voice_state);
+    /// manager.update_server(guild_id, &endpoint, &token);
+    ///
+    /// if let Some(handler) = manager.get_mut(guild_id) {
+    ///     handler.play(voice::ffmpeg("song.mp3")?);
+    /// }
+    /// ```
+    ///
+    /// [`update_server`]: #method.update_server
+    /// [`update_state`]: #method.update_state
+    pub fn standalone(user_id: UserId) -> Manager {
+        Manager {
+            handlers: HashMap::new(),
+            user_id,
+            ws: None,
         }
     }
 
@@ -106,7 +161,7 @@ impl Manager {
             }
         }
 
-        let mut handler = Handler::new(guild_id, self.ws.clone(), self.user_id);
+        let mut handler = self.new_handler(guild_id);
         handler.join(channel_id);
 
         self.handlers.insert(guild_id, handler);
@@ -115,6 +170,68 @@ impl Manager {
         self.handlers.get_mut(&guild_id).unwrap()
     }
 
+    fn new_handler(&self, guild_id: GuildId) -> Handler {
+        match self.ws {
+            Some(ref ws) => Handler::new(guild_id, ws.clone(), self.user_id),
+            None => Handler::standalone(guild_id, self.user_id),
+        }
+    }
+
+    /// Retrieves the handler for the given guild to receive an update, creating
+    /// it if the manager is [`standalone`].
+    ///
+    /// [`standalone`]: #method.standalone
+    fn handler_for_update(&mut self, guild_id: GuildId) -> Option<&mut Handler> {
+        if self.ws.is_none() && !self.handlers.contains_key(&guild_id) {
+            let handler = self.new_handler(guild_id);
+
+            self.handlers.insert(guild_id, handler);
+        }
+
+        self.handlers.get_mut(&guild_id)
+    }
+
+    /// Passes the voice server details of a guild, received via a
+    /// [`VoiceServerUpdateEvent`], to its handler.
+    ///
+    /// This is done automatically for managers created by a [`Client`].
+    ///
+    /// [`Client`]: ../client/struct.Client.html
+    /// [`VoiceServerUpdateEvent`]: ../model/event/struct.VoiceServerUpdateEvent.html
+    #[inline]
+    pub fn update_server<G: Into<GuildId>>(&mut self, guild_id: G, endpoint: &Option<String>, token: &str) {
+        self._update_server(guild_id.into(), endpoint, token)
+    }
+
+    fn _update_server(&mut self, guild_id: GuildId, endpoint: &Option<String>, token: &str) {
+        if let Some(handler) = self.handler_for_update(guild_id) {
+            handler.update_server(endpoint, token);
+        }
+    }
+
+    /// Passes a voice state of the current user, received via a
+    /// [`VoiceStateUpdateEvent`], to the handler of its guild.
+    ///
+    /// Voice states of other users are ignored. This is done automatically for
+    /// managers created by a [`Client`].
+    ///
+    /// [`Client`]: ../client/struct.Client.html
+    /// [`VoiceStateUpdateEvent`]: ../model/event/struct.VoiceStateUpdateEvent.html
+    #[inline]
+    pub fn update_state<G: Into<GuildId>>(&mut self, guild_id: G, voice_state: &VoiceState) {
+        self._update_state(guild_id.into(), voice_state)
+    }
+
+    fn _update_state(&mut self, guild_id: GuildId, voice_state: &VoiceState) {
+        if voice_state.user_id != self.user_id {
+            return;
+        }
+
+        if let Some(handler) = self.handler_for_update(guild_id) {
+            handler.update_state(voice_state);
+        }
+    }
+
     /// Retrieves the [handler][`Handler`] for the given target and leaves the
     /// associated voice channel, if connected.
     ///
@@ -140,9 +257,16 @@ impl Manager {
     /// Retrieves the [`Handler`] for the given target and leaves the associated
     /// voice channel, if connected.
     ///
-    /// The handler is then dropped, removing settings for the target.
+    /// The handler is then dropped, removing settings for the target, and its
+    /// thread exits shortly after. Use [`Handler::disconnect`] beforehand to
+    /// wait for the thread to exit.
+    ///
+    /// This is done automatically for guilds the current user is removed from
+    /// when using a [`Client`].
     ///
+    /// [`Client`]: ../client/struct.Client.html
     /// [`Handler`]: struct.Handler.html
+    /// [`Handler::disconnect`]: struct.Handler.html#method.disconnect
     #[inline]
     pub fn remove<G: Into<GuildId>>(&mut self, guild_id: G) {
         self._remove(guild_id.into())
@@ -0,0 +1,37 @@
+use std::time::Duration;
+use super::DcaMetadata;
+
+/// Information describing the track played by an [`AudioSource`], for
+/// displaying it in a queue or "now playing" message.
+///
+/// Retrieved via [`AudioSource::metadata`]; every field is optional as sources
+/// differ in what they can tell about their audio.
+///
+/// [`AudioSource`]: trait.AudioSource.html
+/// [`AudioSource::metadata`]: trait.AudioSource.html#method.metadata
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct Metadata {
+    /// The title of the track.
+    pub title: Option<String>,
+    /// The artist or uploader of the track.
+    pub artist: Option<String>,
+    /// How long the track plays for.
+    pub duration: Option<Duration>,
+    /// The URL the track was retrieved from.
+    pub source_url: Option<String>,
+    /// The URL of a thumbnail image for the track.
+    pub thumbnail: Option<String>,
+}
+
+impl<'a> From<&'a DcaMetadata> for Metadata {
+    fn from(dca: &'a DcaMetadata) -> Self {
+        Metadata {
+            title: dca.title().map(str::to_string),
+            artist: dca.artist().map(str::to_string),
+            duration: dca.duration,
+            source_url: dca.source_url().map(str::to_string),
+            thumbnail: None,
+        }
+    }
+}
@@ -0,0 +1,119 @@
+//! Mixing of the frames of every playing source into the single stereo frame
+//! sent each cycle.
+//!
+//! Frames are summed into a floating point buffer, each scaled by the gain -
+//! the [`volume`] - of its source, so that loud sources playing together never
+//! wrap around. The sum is then soft clipped and saturated to the range
+//! accepted by the encoder, such that e.g. a sound effect played over music
+//! neither distorts the music nor waits for it to finish.
+//!
+//! [`volume`]: ../struct.Audio.html#structfield.volume
+
+/// The number of samples in a 20ms stereo frame at 48kHz.
+pub(crate) const STEREO_FRAME_LEN: usize = 960 * 2;
+/// The number of samples in a 20ms mono frame at 48kHz.
+pub(crate) const MONO_FRAME_LEN: usize = 960;
+
+/// A frame of mixed, interleaved stereo samples.
+pub(crate) type MixBuffer = [f32; STEREO_FRAME_LEN];
+
+/// Adds a frame of 16-bit PCM to the mix, duplicating mono samples into both
+/// channels.
+pub(crate) fn add_pcm(mix: &mut MixBuffer, frame: &[i16], stereo: bool, gain: f32) {
+    let gain = sanitise_gain(gain);
+
+    if gain == 0.0 {
+        return;
+    }
+
+    for (i, sample) in mix.iter_mut().enumerate() {
+        let index = if stereo { i } else { i / 2 };
+
+        if let Some(&raw) = frame.get(index) {
+            *sample += f32::from(raw) / 32768.0 * gain;
+        }
+    }
+}
+
+/// Adds a frame of interleaved stereo floating point samples, such as those
+/// produced by an Opus decoder, to the mix.
+pub(crate) fn add_float(mix: &mut MixBuffer, frame: &[f32], gain: f32) {
+    let gain = sanitise_gain(gain);
+
+    if gain == 0.0 {
+        return;
+    }
+
+    for (sample, &value) in mix.iter_mut().zip(frame) {
+        *sample += value * gain;
+    }
+}
+
+/// Clamps every sample of the mix to `[-1.0, 1.0]`, so that any peaks left
+/// after soft clipping saturate rather than overflow once encoded.
+pub(crate) fn saturate(mix: &mut MixBuffer) {
+    for sample in mix.iter_mut() {
+        *sample = sample.max(-1.0).min(1.0);
+    }
+}
+
+/// Treats negative and non-finite gains as silence.
+#[inline]
+fn sanitise_gain(gain: f32) -> f32 {
+    if gain.is_finite() && gain > 0.0 {
+        gain
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{add_float, add_pcm, saturate, MixBuffer, STEREO_FRAME_LEN};
+
+    #[test]
+    fn test_add_pcm() {
+        let mut mix: MixBuffer = [0.0; STEREO_FRAME_LEN];
+        add_pcm(&mut mix, &[16384, -16384], true, 1.0);
+        assert_eq!(&mix[..3], &[0.5, -0.5, 0.0]);
+
+        // Mono samples are played on both channels.
+        let mut mix: MixBuffer = [0.0; STEREO_FRAME_LEN];
+        add_pcm(&mut mix, &[16384, -16384], false, 0.5);
+        assert_eq!(&mix[..5], &[0.25, 0.25, -0.25, -0.25, 0.0]);
+    }
+
+    #[test]
+    fn test_add_float() {
+        let mut mix: MixBuffer = [0.0; STEREO_FRAME_LEN];
+        add_float(&mut mix, &[0.5, -0.5], 1.0);
+        add_float(&mut mix, &[0.25, 0.25], 2.0);
+        assert_eq!(&mix[..3], &[1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_invalid_gain() {
+        let mut mix: MixBuffer = [0.0; STEREO_FRAME_LEN];
+
+        for &gain in &[0.0, -1.0, std::f32::NAN, std::f32::INFINITY] {
+            add_pcm(&mut mix, &[16384; STEREO_FRAME_LEN], true, gain);
+            add_float(&mut mix, &[0.5; STEREO_FRAME_LEN], gain);
+        }
+
+        assert!(mix.iter().all(|&sample| sample == 0.0));
+    }
+
+    #[test]
+    fn test_loud_sources_saturate() {
+        let mut mix: MixBuffer = [0.0; STEREO_FRAME_LEN];
+
+        // Two sources at full scale neither wrap around nor exceed the range
+        // accepted by the encoder.
+        add_pcm(&mut mix, &[32767, -32768], true, 1.0);
+        add_pcm(&mut mix, &[32767, -32768], true, 1.0);
+        assert!(mix[0] > 1.9 && mix[1] < -1.9);
+
+        saturate(&mut mix);
+        assert_eq!(&mix[..3], &[1.0, -1.0, 0.0]);
+    }
+}
@@ -4,31 +4,56 @@ mod audio;
 mod connection;
 mod connection_info;
 mod dca;
+mod encoder;
 mod error;
 mod manager;
+mod metadata;
+mod mixer;
 mod handler;
 mod payload;
+mod protocol;
+mod queue;
+mod restartable;
 mod streamer;
 mod threading;
+mod ytdl;
 
 pub use self::{
-    audio::{Audio, AudioReceiver, AudioSource, AudioType, LockedAudio},
-    dca::DcaMetadata,
+    audio::{
+        Audio,
+        AudioReceiver,
+        AudioSource,
+        AudioType,
+        ClientConnectHandler,
+        ClientDisconnectHandler,
+        LockedAudio,
+        TrackEndHandler,
+        TrackEndReason,
+    },
+    dca::{DcaInfo, DcaMetadata, Origin, OpusInfo, Tool, TrackInfo},
+    encoder::EncoderSettings,
     error::{DcaError, VoiceError},
     handler::Handler,
     manager::Manager,
+    metadata::Metadata,
+    queue::{LockedQueue, LoopMode, Track, TrackQueue},
+    restartable::Restartable,
     streamer::{
         dca,
         ffmpeg,
         ffmpeg_optioned,
+        ffmpeg_with_options,
+        FfmpegOptions,
         opus,
+        opus_packets,
         pcm,
         ytdl,
         ytdl_search
     }
 };
-pub use audiopus::Bitrate;
+pub use audiopus::{Bitrate, Signal};
 
+use crate::model::voice::SpeakingState;
 use self::connection_info::ConnectionInfo;
 
 const CRYPTO_MODE: &str = "xsalsa20_poly1305";
@@ -36,8 +61,17 @@ const CRYPTO_MODE: &str = "xsalsa20_poly1305";
 pub(crate) enum Status {
     Connect(ConnectionInfo),
     Disconnect,
+    Shutdown,
+    Suspend,
     SetReceiver(Option<Box<dyn AudioReceiver>>),
     SetSender(Option<LockedAudio>),
     AddSender(LockedAudio),
+    SwitchSource(LockedAudio),
+    AddTrackEndHandler(Box<TrackEndHandler>),
+    AddClientConnectHandler(Box<ClientConnectHandler>),
+    AddClientDisconnectHandler(Box<ClientDisconnectHandler>),
+    SendOpusPacket(Vec<u8>),
     SetBitrate(Bitrate),
+    SetEncoderSettings(EncoderSettings),
+    SetSpeakingFlags(SpeakingState),
 }
@@ -1,6 +1,8 @@
 use crate::constants::VoiceOpCode;
+use crate::model::{event::VoiceResume, voice::SpeakingState};
 use serde_json::{json, Value};
 use super::connection_info::ConnectionInfo;
+use super::protocol::ProtocolVersion;
 
 #[inline]
 pub fn build_identify(info: &ConnectionInfo) -> Value {
@@ -9,7 +11,7 @@ pub fn build_identify(info: &ConnectionInfo) -> Value {
         "d": {
             "server_id": info.guild_id.0,
             "session_id": &info.session_id,
-            "token": &info.token,
+            "token": info.token.expose(),
             "user_id": info.user_id.0,
         }
     })
@@ -27,10 +29,10 @@ pub fn build_heartbeat(nonce: u64) -> Value {
 pub fn build_resume(info: &ConnectionInfo) -> Value {
     json!({
         "op": VoiceOpCode::Resume.num(),
-        "d": {
-            "server_id": info.guild_id.0,
-            "session_id": &info.session_id,
-            "token": &info.token,
+        "d": VoiceResume {
+            server_id: info.guild_id.0.to_string(),
+            session_id: info.session_id.clone(),
+            token: info.token.expose().to_string(),
         },
     })
 }
@@ -51,12 +53,13 @@ pub fn build_select_protocol(address: ::std::borrow::Cow<'_, str>, port: u16) ->
 }
 
 #[inline]
-pub fn build_speaking(speaking: bool) -> Value {
+pub fn build_speaking(version: ProtocolVersion, speaking: SpeakingState, ssrc: u32) -> Value {
     json!({
         "op": VoiceOpCode::Speaking.num(),
         "d": {
             "delay": 0,
-            "speaking": speaking,
+            "speaking": version.speaking_value(speaking),
+            "ssrc": ssrc,
         }
     })
 }
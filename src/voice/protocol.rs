@@ -0,0 +1,43 @@
+//! Details of the voice gateway which differ between its versions.
+//!
+//! Payload builders and the connection ask the [`ProtocolVersion`] in use
+//! whenever a version-dependent choice must be made, so that supporting a new
+//! version of the voice gateway only touches this module.
+//!
+//! [`ProtocolVersion`]: enum.ProtocolVersion.html
+
+use crate::model::{event::VoiceHello, voice::SpeakingState};
+use serde_json::{json, Value};
+
+/// A version of the voice gateway protocol.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub(crate) enum ProtocolVersion {
+    V4,
+}
+
+impl ProtocolVersion {
+    /// The version negotiated by new connections.
+    pub const CURRENT: ProtocolVersion = ProtocolVersion::V4;
+
+    /// The number sent as the `v` query parameter of the voice gateway URL.
+    pub fn num(self) -> u8 {
+        match self {
+            ProtocolVersion::V4 => 4,
+        }
+    }
+
+    /// The interval, in milliseconds, at which to heartbeat given the
+    /// interval received in the Hello payload.
+    pub fn heartbeat_interval(self, hello: &VoiceHello) -> u64 {
+        match self {
+            ProtocolVersion::V4 => hello.heartbeat_interval,
+        }
+    }
+
+    /// The value of the `speaking` field of a Speaking payload.
+    pub fn speaking_value(self, speaking: SpeakingState) -> Value {
+        match self {
+            ProtocolVersion::V4 => json!(speaking.bits()),
+        }
+    }
+}
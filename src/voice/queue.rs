@@ -0,0 +1,561 @@
+use crate::internal::prelude::*;
+use log::warn;
+use parking_lot::Mutex;
+use rand::seq::SliceRandom;
+use std::{
+    collections::VecDeque,
+    fmt::{Debug, Formatter, Result as FmtResult},
+    sync::{
+        mpsc::{self, Receiver, TryRecvError},
+        Arc,
+    },
+    thread::Builder as ThreadBuilder,
+};
+use super::{Audio, AudioSource, LockedAudio, TrackEndReason};
+
+/// How a [`TrackQueue`] behaves once the current track has ended.
+///
+/// [`TrackQueue`]: struct.TrackQueue.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum LoopMode {
+    /// Every track is played once, in order.
+    Off,
+    /// The current track is played again until the mode is changed or the
+    /// track is skipped.
+    Track,
+    /// Ended tracks are moved to the back of the queue.
+    Queue,
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+impl Default for LoopMode {
+    fn default() -> Self {
+        LoopMode::Off
+    }
+}
+
+type SourceFn = dyn FnMut() -> Result<Box<dyn AudioSource>> + Send;
+
+/// A track which can be placed in a [`TrackQueue`].
+///
+/// A track created via [`new`] wraps an already opened source and can only be
+/// played once; it is dropped after ending even while looping. A track created
+/// via [`from_fn`] opens a new source every time it is played, and so can be
+/// looped.
+///
+/// [`TrackQueue`]: struct.TrackQueue.html
+/// [`new`]: #method.new
+/// [`from_fn`]: #method.from_fn
+pub struct Track {
+    source: Option<Box<dyn AudioSource>>,
+    open: Option<Box<SourceFn>>,
+}
+
+impl Track {
+    /// Creates a track playing the given source once.
+    pub fn new(source: Box<dyn AudioSource>) -> Self {
+        Self {
+            source: Some(source),
+            open: None,
+        }
+    }
+
+    /// Creates a track opening its source via the given function whenever it
+    /// starts playing, such as `|| voice::ffmpeg("song.mp3")`.
+    ///
+    /// The source is only opened once the track is reached, so queueing many
+    /// of these does not keep many processes or files open. It is opened on a
+    /// separate thread, so that slow sources do not hold up playback.
+    pub fn from_fn<F>(open: F) -> Self
+        where F: FnMut() -> Result<Box<dyn AudioSource>> + Send + 'static {
+        Self {
+            source: None,
+            open: Some(Box::new(open)),
+        }
+    }
+
+    /// Whether the track can be played again after it has ended.
+    #[inline]
+    pub fn is_repeatable(&self) -> bool {
+        self.open.is_some()
+    }
+
+    fn open(&mut self) -> Result<Box<dyn AudioSource>> {
+        match self.open.as_mut() {
+            Some(open) => open(),
+            None => Err(Error::Other("track cannot be opened")),
+        }
+    }
+}
+
+impl Debug for Track {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("Track")
+            .field("repeatable", &self.is_repeatable())
+            .finish()
+    }
+}
+
+struct Playing {
+    track: Track,
+    audio: LockedAudio,
+}
+
+/// The result of opening a track's source on a separate thread.
+type Opened = (Track, Result<Box<dyn AudioSource>>);
+
+/// A queue of tracks played one after another by a [`Handler`].
+///
+/// The next track is started automatically by the voice connection's thread
+/// when the current one finishes. Retrieve the queue via [`Handler::queue`];
+/// the [`Handler`] also exposes the common operations directly.
+///
+/// While the next track's source is being opened, there is no [`current`]
+/// track and the track is not counted by [`len`].
+///
+/// [`Handler`]: struct.Handler.html
+/// [`Handler::queue`]: struct.Handler.html#method.queue
+/// [`current`]: #method.current
+/// [`len`]: #method.len
+#[derive(Default)]
+pub struct TrackQueue {
+    current: Option<Playing>,
+    opening: Option<Receiver<Opened>>,
+    tracks: VecDeque<Track>,
+    loop_mode: LoopMode,
+    skip: bool,
+    stopped: Vec<LockedAudio>,
+}
+
+/// Threadsafe form of a [`TrackQueue`], shared by a [`Handler`] and its voice
+/// connection's thread.
+///
+/// [`Handler`]: struct.Handler.html
+/// [`TrackQueue`]: struct.TrackQueue.html
+pub type LockedQueue = Arc<Mutex<TrackQueue>>;
+
+impl TrackQueue {
+    /// Adds a track to the back of the queue.
+    pub fn enqueue(&mut self, track: Track) {
+        self.tracks.push_back(track);
+    }
+
+    /// The control object of the track currently playing, if any.
+    pub fn current(&self) -> Option<LockedAudio> {
+        self.current.as_ref().map(|playing| Arc::clone(&playing.audio))
+    }
+
+    /// The number of tracks waiting to be played, excluding the current one.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.tracks.len()
+    }
+
+    /// Whether no tracks are waiting to be played.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.tracks.is_empty()
+    }
+
+    /// The current loop mode.
+    #[inline]
+    pub fn loop_mode(&self) -> LoopMode {
+        self.loop_mode
+    }
+
+    /// Sets how the queue behaves once the current track has ended.
+    pub fn set_loop_mode(&mut self, mode: LoopMode) {
+        self.loop_mode = mode;
+    }
+
+    /// Randomly reorders the waiting tracks. The current track is unaffected.
+    pub fn shuffle(&mut self) {
+        let mut tracks = self.tracks.drain(..).collect::<Vec<_>>();
+        tracks.shuffle(&mut rand::thread_rng());
+
+        self.tracks.extend(tracks);
+    }
+
+    /// Stops the current track on the next frame and starts the next one,
+    /// regardless of the loop mode.
+    ///
+    /// If the next track is still being opened, it is skipped instead.
+    pub fn skip(&mut self) {
+        if self.current.is_some() {
+            self.skip = true;
+        } else {
+            self.opening = None;
+        }
+    }
+
+    /// Pauses the current track, if any.
+    pub fn pause(&mut self) {
+        if let Some(audio) = self.current() {
+            audio.lock().pause();
+        }
+    }
+
+    /// Resumes the current track, if any.
+    pub fn resume(&mut self) {
+        if let Some(audio) = self.current() {
+            audio.lock().play();
+        }
+    }
+
+    /// Removes all waiting tracks and stops the current one.
+    pub fn clear(&mut self) {
+        self.tracks.clear();
+        self.opening = None;
+        self.skip = false;
+
+        if let Some(playing) = self.current.take() {
+            self.stopped.push(playing.audio);
+        }
+    }
+
+    /// Puts the given audio in place of the current track, if any, such that
+    /// the queue only advances once it has ended.
+    ///
+    /// The replaced track is never repeated, as the given audio cannot be
+    /// played again.
+    pub(crate) fn replace_current(&mut self, audio: LockedAudio) {
+        self.skip = false;
+
+        if let Some(playing) = self.current.as_mut() {
+            playing.audio = audio;
+            playing.track = Track {
+                source: None,
+                open: None,
+            };
+        }
+    }
+
+    /// Ends the current track if it finished or was skipped, and starts the
+    /// next track if none is playing.
+    ///
+    /// Called by the voice thread before every frame. Sources which need to
+    /// be opened are opened on a separate thread, with the track starting on
+    /// the first call after they are ready.
+    ///
+    /// Tracks stopped or skipped through the queue are added to `ended`.
+    pub(crate) fn advance(
+        &mut self,
+        senders: &mut Vec<LockedAudio>,
+        ended: &mut Vec<(LockedAudio, TrackEndReason)>,
+    ) {
+        for audio in self.stopped.drain(..) {
+            if remove_sender(senders, &audio) {
+                ended.push((audio, TrackEndReason::Stopped));
+            }
+        }
+
+        if let Some(playing) = self.current.take() {
+            let finished = playing.audio.lock().finished;
+            let playing_here = senders.iter().any(|s| Arc::ptr_eq(s, &playing.audio));
+
+            if !self.skip && !finished && playing_here {
+                self.current = Some(playing);
+
+                return;
+            }
+
+            let skipped = self.skip;
+            self.skip = false;
+
+            if remove_sender(senders, &playing.audio) {
+                ended.push((Arc::clone(&playing.audio), TrackEndReason::Skipped));
+            }
+
+            if playing.track.is_repeatable() {
+                match self.loop_mode {
+                    LoopMode::Track if !skipped => self.tracks.push_front(playing.track),
+                    LoopMode::Queue => self.tracks.push_back(playing.track),
+                    _ => {},
+                }
+            }
+        }
+
+        loop {
+            if let Some(receiver) = self.opening.as_ref() {
+                match receiver.try_recv() {
+                    Ok((track, Ok(source))) => {
+                        self.opening = None;
+                        self.start(track, source, senders);
+
+                        return;
+                    },
+                    Ok((_, Err(why))) => {
+                        warn!("[Voice] Error opening queued track: {:?}", why);
+                    },
+                    Err(TryRecvError::Empty) => return,
+                    Err(TryRecvError::Disconnected) => {
+                        warn!("[Voice] Thread opening queued track stopped");
+                    },
+                }
+
+                self.opening = None;
+            }
+
+            let mut track = match self.tracks.pop_front() {
+                Some(track) => track,
+                None => return,
+            };
+
+            if let Some(source) = track.source.take() {
+                self.start(track, source, senders);
+
+                return;
+            }
+
+            if track.is_repeatable() {
+                self.opening = open_on_thread(track);
+            }
+        }
+    }
+
+    fn start(&mut self, track: Track, source: Box<dyn AudioSource>, senders: &mut Vec<LockedAudio>) {
+        let audio = Arc::new(Mutex::new(Audio::new(source)));
+        senders.push(Arc::clone(&audio));
+
+        self.current = Some(Playing {
+            track,
+            audio,
+        });
+    }
+}
+
+/// Opens the track's source on a new thread, returning the receiver of the
+/// track along with the source once done.
+fn open_on_thread(mut track: Track) -> Option<Receiver<Opened>> {
+    let (tx, rx) = mpsc::channel();
+
+    let spawned = ThreadBuilder::new()
+        .name("Serenity Voice Track".to_string())
+        .spawn(move || {
+            let source = track.open();
+
+            // The queue may have been cleared in the meantime, in which case
+            // the track is dropped.
+            let _ = tx.send((track, source));
+        });
+
+    match spawned {
+        Ok(_) => Some(rx),
+        Err(why) => {
+            warn!("[Voice] Error starting thread to open queued track: {:?}", why);
+
+            None
+        },
+    }
+}
+
+/// Removes the given audio from the senders, returning whether it was present.
+fn remove_sender(senders: &mut Vec<LockedAudio>, audio: &LockedAudio) -> bool {
+    let len = senders.len();
+    senders.retain(|s| !Arc::ptr_eq(s, audio));
+
+    senders.len() != len
+}
+
+impl Debug for TrackQueue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("TrackQueue")
+            .field("playing", &self.current.is_some())
+            .field("len", &self.tracks.len())
+            .field("loop_mode", &self.loop_mode)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::internal::prelude::*;
+    use crate::voice::{AudioSource, AudioType, LockedAudio, TrackEndReason};
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        thread,
+        time::Duration,
+    };
+    use super::{remove_sender, LoopMode, Track, TrackQueue};
+
+    struct Silence;
+
+    impl AudioSource for Silence {
+        fn is_stereo(&mut self) -> bool { false }
+
+        fn get_type(&self) -> AudioType { AudioType::Pcm }
+
+        fn read_pcm_frame(&mut self, _buffer: &mut [i16]) -> Option<usize> { None }
+
+        fn read_opus_frame(&mut self) -> Option<Vec<u8>> { None }
+
+        fn decode_and_add_opus_frame(&mut self, _float_buffer: &mut [f32; 1920], _volume: f32) -> Option<usize> { None }
+    }
+
+    fn track() -> Track {
+        Track::new(Box::new(Silence))
+    }
+
+    fn counted_track(opened: &Arc<AtomicUsize>) -> Track {
+        let opened = Arc::clone(opened);
+
+        Track::from_fn(move || {
+            opened.fetch_add(1, Ordering::SeqCst);
+
+            Ok(Box::new(Silence) as Box<dyn AudioSource>)
+        })
+    }
+
+    // Advances the queue until no source is being opened anymore.
+    fn advance(
+        queue: &mut TrackQueue,
+        senders: &mut Vec<LockedAudio>,
+        ended: &mut Vec<(LockedAudio, TrackEndReason)>,
+    ) {
+        for _ in 0..500 {
+            queue.advance(senders, ended);
+
+            if queue.opening.is_none() {
+                return;
+            }
+
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        panic!("track was not opened in time");
+    }
+
+    // Ends the current track the way the voice connection does.
+    fn finish(queue: &TrackQueue, senders: &mut Vec<LockedAudio>) {
+        let audio = queue.current().unwrap();
+        audio.lock().finished = true;
+
+        remove_sender(senders, &audio);
+    }
+
+    #[test]
+    fn test_advance() {
+        let mut queue = TrackQueue::default();
+        let mut senders = Vec::new();
+        let mut ended = Vec::new();
+
+        queue.enqueue(track());
+        queue.enqueue(track());
+        advance(&mut queue, &mut senders, &mut ended);
+
+        let first = queue.current().unwrap();
+        assert_eq!(senders.len(), 1);
+        assert_eq!(queue.len(), 1);
+
+        advance(&mut queue, &mut senders, &mut ended);
+        assert!(Arc::ptr_eq(&first, &queue.current().unwrap()));
+
+        finish(&queue, &mut senders);
+        advance(&mut queue, &mut senders, &mut ended);
+        assert!(!Arc::ptr_eq(&first, &queue.current().unwrap()));
+        assert_eq!(senders.len(), 1);
+        assert!(queue.is_empty());
+        assert!(ended.is_empty());
+
+        finish(&queue, &mut senders);
+        advance(&mut queue, &mut senders, &mut ended);
+        assert!(queue.current().is_none());
+        assert!(senders.is_empty());
+    }
+
+    #[test]
+    fn test_skip_and_clear() {
+        let mut queue = TrackQueue::default();
+        let mut senders = Vec::new();
+        let mut ended = Vec::new();
+
+        queue.enqueue(track());
+        queue.enqueue(track());
+        queue.enqueue(track());
+        advance(&mut queue, &mut senders, &mut ended);
+
+        queue.skip();
+        advance(&mut queue, &mut senders, &mut ended);
+        assert_eq!(ended.len(), 1);
+        assert_eq!(ended[0].1, TrackEndReason::Skipped);
+        assert_eq!(senders.len(), 1);
+        assert_eq!(queue.len(), 1);
+
+        ended.clear();
+        queue.clear();
+        advance(&mut queue, &mut senders, &mut ended);
+        assert_eq!(ended.len(), 1);
+        assert_eq!(ended[0].1, TrackEndReason::Stopped);
+        assert!(senders.is_empty());
+        assert!(queue.current().is_none());
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_loop_track() {
+        let opened = Arc::new(AtomicUsize::new(0));
+        let mut queue = TrackQueue::default();
+        let mut senders = Vec::new();
+        let mut ended = Vec::new();
+
+        queue.set_loop_mode(LoopMode::Track);
+        queue.enqueue(counted_track(&opened));
+        queue.enqueue(track());
+        advance(&mut queue, &mut senders, &mut ended);
+        assert_eq!(opened.load(Ordering::SeqCst), 1);
+
+        finish(&queue, &mut senders);
+        advance(&mut queue, &mut senders, &mut ended);
+        assert_eq!(opened.load(Ordering::SeqCst), 2);
+        assert_eq!(queue.len(), 1);
+
+        // Skipping ends the loop.
+        queue.skip();
+        advance(&mut queue, &mut senders, &mut ended);
+        assert_eq!(opened.load(Ordering::SeqCst), 2);
+        assert!(queue.is_empty());
+        assert_eq!(senders.len(), 1);
+    }
+
+    #[test]
+    fn test_loop_queue() {
+        let opened = Arc::new(AtomicUsize::new(0));
+        let mut queue = TrackQueue::default();
+        let mut senders = Vec::new();
+        let mut ended = Vec::new();
+
+        queue.set_loop_mode(LoopMode::Queue);
+        queue.enqueue(counted_track(&opened));
+        queue.enqueue(track());
+        advance(&mut queue, &mut senders, &mut ended);
+
+        finish(&queue, &mut senders);
+        advance(&mut queue, &mut senders, &mut ended);
+        assert_eq!(queue.len(), 1);
+
+        // The track played once is not requeued.
+        finish(&queue, &mut senders);
+        advance(&mut queue, &mut senders, &mut ended);
+        assert_eq!(opened.load(Ordering::SeqCst), 2);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_open_error() {
+        let mut queue = TrackQueue::default();
+        let mut senders = Vec::new();
+        let mut ended = Vec::new();
+
+        queue.enqueue(Track::from_fn(|| Err(Error::Other("unavailable"))));
+        queue.enqueue(track());
+        advance(&mut queue, &mut senders, &mut ended);
+
+        assert!(queue.current().is_some());
+        assert!(queue.is_empty());
+        assert_eq!(senders.len(), 1);
+    }
+}
@@ -0,0 +1,461 @@
+use crate::internal::prelude::*;
+use log::{debug, warn};
+use parking_lot::Mutex;
+use std::{
+    ffi::OsString,
+    fmt::{Debug, Formatter, Result as FmtResult},
+    sync::{
+        mpsc::{self, Receiver, TryRecvError},
+        Arc,
+    },
+    thread::Builder as ThreadBuilder,
+    time::Duration,
+};
+use super::{
+    audio::SILENCE_FRAME,
+    streamer::{self, FfmpegOptions},
+    AudioSource,
+    AudioType,
+    Metadata,
+};
+
+/// How many times reading is retried, by re-opening the source, before the
+/// source is considered to have errored.
+const MAX_RETRIES: u8 = 3;
+
+type Recipe = dyn FnMut(Duration) -> Result<Box<dyn AudioSource>> + Send;
+
+/// The state of the underlying source of a [`Restartable`].
+///
+/// [`Restartable`]: struct.Restartable.html
+enum State<'a> {
+    Open(&'a mut Box<dyn AudioSource>),
+    Opening,
+    Failed,
+}
+
+/// An audio source which re-creates its underlying source from a recipe.
+///
+/// The underlying source - such as an `ffmpeg` process - is only opened once
+/// playback starts, so queueing many restartable sources does not hold open
+/// many processes or files. It is then re-opened from the current position:
+///
+/// - when [`Audio::position`] is used to seek;
+/// - when opening or reading from it fails, up to three times in a row.
+///
+/// The source is opened on a separate thread, so that slow recipes do not hold
+/// up playback; silence is played in the meantime.
+///
+/// # Examples
+///
+/// Queue a playlist without starting any `ffmpeg` processes up front:
+///
+/// ```rust,ignore
+/// use serenity::voice::Restartable;
+///
+/// for path in &["a.mp3", "b.mp3", "c.mp3"] {
+///     handler.enqueue(Box::new(Restartable::ffmpeg(path)));
+/// }
+/// ```
+///
+/// [`Audio::position`]: struct.Audio.html#method.position
+pub struct Restartable {
+    recipe: Arc<Mutex<Box<Recipe>>>,
+    source: Option<Box<dyn AudioSource>>,
+    opening: Option<Receiver<Result<Box<dyn AudioSource>>>>,
+    kind: AudioType,
+    stereo: bool,
+    position: Duration,
+    retries: u8,
+    max_retries: u8,
+}
+
+impl Restartable {
+    /// Creates a source opened by the given recipe, which is given the
+    /// position to start playing from.
+    ///
+    /// Every source created by the recipe must be of the given type.
+    pub fn new<F>(kind: AudioType, recipe: F) -> Self
+        where F: FnMut(Duration) -> Result<Box<dyn AudioSource>> + Send + 'static {
+        Self {
+            recipe: Arc::new(Mutex::new(Box::new(recipe))),
+            source: None,
+            opening: None,
+            kind,
+            stereo: false,
+            position: Duration::from_secs(0),
+            retries: 0,
+            max_retries: MAX_RETRIES,
+        }
+    }
+
+    /// Creates a source which plays a file through `ffmpeg`, seeking by
+    /// restarting `ffmpeg` at the new position.
+    ///
+    /// Refer to [`voice::ffmpeg`] for more information.
+    ///
+    /// [`voice::ffmpeg`]: fn.ffmpeg.html
+    pub fn ffmpeg<P: Into<OsString>>(path: P) -> Self {
+        Self::ffmpeg_with_options(path, FfmpegOptions::default())
+    }
+
+    /// Creates a source which plays a file through `ffmpeg` with the given
+    /// options, seeking by restarting `ffmpeg` at the new position.
+    ///
+    /// The position seeked to takes the place of any given via
+    /// [`FfmpegOptions::seek`].
+    ///
+    /// [`FfmpegOptions::seek`]: struct.FfmpegOptions.html#method.seek
+    pub fn ffmpeg_with_options<P: Into<OsString>>(path: P, options: FfmpegOptions) -> Self {
+        let path = path.into();
+        let start = options.start();
+        let mut options = options;
+
+        Self::new(AudioType::Pcm, move |position| {
+            options.seek(start + position);
+
+            streamer::ffmpeg_with_options(&path, &options)
+        })
+    }
+
+    /// Creates a source which streams a URL through `youtube-dl`, seeking by
+    /// restarting the stream at the new position.
+    ///
+    /// Failed reads are not retried, as the stream already restarts itself
+    /// should it stall. Refer to [`voice::ytdl`] for more information.
+    ///
+    /// [`voice::ytdl`]: fn.ytdl.html
+    pub fn ytdl<S: Into<String>>(uri: S) -> Self {
+        let uri = uri.into();
+
+        Self {
+            max_retries: 0,
+            ..Self::new(AudioType::Pcm, move |position| streamer::ytdl_at(&uri, position))
+        }
+    }
+
+    /// Whether the underlying source is currently open.
+    #[inline]
+    pub fn is_open(&self) -> bool {
+        self.source.is_some()
+    }
+
+    /// Returns the underlying source if it is open, starting to open it at
+    /// the current position otherwise.
+    fn source(&mut self) -> State<'_> {
+        if self.source.is_none() {
+            let opening = match self.opening.take() {
+                Some(opening) => opening,
+                None => match self.open() {
+                    Some(opening) => opening,
+                    None => return State::Failed,
+                },
+            };
+
+            match opening.try_recv() {
+                Ok(Ok(mut source)) => {
+                    self.stereo = source.is_stereo();
+                    self.source = Some(source);
+                },
+                Ok(Err(why)) => {
+                    warn!("[Voice] Error opening restartable source: {:?}", why);
+
+                    return if self.retry() { State::Opening } else { State::Failed };
+                },
+                Err(TryRecvError::Empty) => {
+                    self.opening = Some(opening);
+
+                    return State::Opening;
+                },
+                Err(TryRecvError::Disconnected) => {
+                    warn!("[Voice] Thread opening restartable source stopped");
+
+                    return State::Failed;
+                },
+            }
+        }
+
+        match self.source.as_mut() {
+            Some(source) => State::Open(source),
+            None => State::Failed,
+        }
+    }
+
+    /// Runs the recipe at the current position on a new thread, returning the
+    /// receiver of the source.
+    fn open(&self) -> Option<Receiver<Result<Box<dyn AudioSource>>>> {
+        debug!("[Voice] Opening restartable source at {:?}", self.position);
+
+        let (tx, rx) = mpsc::channel();
+        let recipe = Arc::clone(&self.recipe);
+        let position = self.position;
+
+        let spawned = ThreadBuilder::new()
+            .name("serenity restartable".to_string())
+            .spawn(move || {
+                let source = (recipe.lock())(position);
+
+                // The source may have been seeked in the meantime, in which
+                // case this one is dropped.
+                let _ = tx.send(source);
+            });
+
+        match spawned {
+            Ok(_) => Some(rx),
+            Err(why) => {
+                warn!("[Voice] Error starting thread to open restartable source: {:?}", why);
+
+                None
+            },
+        }
+    }
+
+    /// Re-opens the source after a failed attempt, returning whether another
+    /// attempt is allowed.
+    fn retry(&mut self) -> bool {
+        self.source = None;
+
+        if self.retries >= self.max_retries {
+            return false;
+        }
+
+        self.retries += 1;
+
+        true
+    }
+
+    /// Records a successful read of the given length of audio.
+    fn advance(&mut self, duration: Duration) {
+        self.position += duration;
+        self.retries = 0;
+    }
+}
+
+impl AudioSource for Restartable {
+    fn is_stereo(&mut self) -> bool {
+        match self.source() {
+            State::Open(source) => source.is_stereo(),
+            _ => self.stereo,
+        }
+    }
+
+    fn get_type(&self) -> AudioType { self.kind }
+
+    fn read_pcm_frame(&mut self, buffer: &mut [i16]) -> Option<usize> {
+        loop {
+            let (read, stereo) = match self.source() {
+                State::Open(source) => (source.read_pcm_frame(buffer), source.is_stereo()),
+                State::Opening => {
+                    for sample in buffer.iter_mut() {
+                        *sample = 0;
+                    }
+
+                    return Some(buffer.len());
+                },
+                State::Failed => return None,
+            };
+
+            match read {
+                Some(len) => {
+                    let channels = if stereo { 2 } else { 1 };
+                    self.advance(Duration::from_micros(len as u64 * 1_000_000 / (48_000 * channels)));
+
+                    return Some(len);
+                },
+                None if self.retry() => continue,
+                None => return None,
+            }
+        }
+    }
+
+    fn read_opus_frame(&mut self) -> Option<Vec<u8>> {
+        loop {
+            let read = match self.source() {
+                State::Open(source) => source.read_opus_frame(),
+                State::Opening => return Some(SILENCE_FRAME.to_vec()),
+                State::Failed => return None,
+            };
+
+            match read {
+                Some(frame) => {
+                    if !frame.is_empty() {
+                        self.advance(Duration::from_millis(20));
+                    }
+
+                    return Some(frame);
+                },
+                None if self.retry() => continue,
+                None => return None,
+            }
+        }
+    }
+
+    fn decode_and_add_opus_frame(&mut self, float_buffer: &mut [f32; 1920], volume: f32) -> Option<usize> {
+        loop {
+            let read = match self.source() {
+                State::Open(source) => source.decode_and_add_opus_frame(float_buffer, volume),
+                // Nothing is added to the mix, standing in for a frame of
+                // silence.
+                State::Opening => return Some(float_buffer.len() / 2),
+                State::Failed => return None,
+            };
+
+            match read {
+                Some(count) => {
+                    if count > 0 {
+                        self.advance(Duration::from_millis(20));
+                    }
+
+                    return Some(count);
+                },
+                None if self.retry() => continue,
+                None => return None,
+            }
+        }
+    }
+
+    fn metadata(&self) -> Option<&Metadata> {
+        self.source.as_ref()?.metadata()
+    }
+
+    fn seek(&mut self, position: Duration) -> bool {
+        // Opened again at the new position on the next read.
+        self.source = None;
+        self.opening = None;
+        self.position = position;
+        self.retries = 0;
+
+        true
+    }
+}
+
+impl Debug for Restartable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("Restartable")
+            .field("open", &self.is_open())
+            .field("opening", &self.opening.is_some())
+            .field("position", &self.position)
+            .field("retries", &self.retries)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::internal::prelude::*;
+    use crate::voice::{AudioSource, AudioType};
+    use parking_lot::Mutex;
+    use std::{
+        sync::{mpsc, Arc},
+        thread,
+        time::Duration,
+    };
+    use super::Restartable;
+
+    struct Constant(Option<i16>);
+
+    impl AudioSource for Constant {
+        fn is_stereo(&mut self) -> bool { true }
+
+        fn get_type(&self) -> AudioType { AudioType::Pcm }
+
+        fn read_pcm_frame(&mut self, buffer: &mut [i16]) -> Option<usize> {
+            let value = self.0?;
+
+            for sample in buffer.iter_mut() {
+                *sample = value;
+            }
+
+            Some(buffer.len())
+        }
+
+        fn read_opus_frame(&mut self) -> Option<Vec<u8>> { None }
+
+        fn decode_and_add_opus_frame(&mut self, _float_buffer: &mut [f32; 1920], _volume: f32) -> Option<usize> { None }
+    }
+
+    // Reads until the source was opened, or reading failed.
+    fn read_opened(source: &mut Restartable, buffer: &mut [i16]) -> Option<usize> {
+        for _ in 0..500 {
+            let read = source.read_pcm_frame(buffer);
+
+            if read.is_none() || source.is_open() {
+                return read;
+            }
+
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        panic!("source was not opened in time");
+    }
+
+    #[test]
+    fn test_opens_off_thread() {
+        let (release, released) = mpsc::channel::<()>();
+        let released = Mutex::new(released);
+        let mut source = Restartable::new(AudioType::Pcm, move |_| {
+            let _ = released.lock().recv();
+
+            Ok(Box::new(Constant(Some(1))) as Box<dyn AudioSource>)
+        });
+        let mut buffer = [7; 4];
+
+        // Silence is read while the recipe is running.
+        assert_eq!(source.read_pcm_frame(&mut buffer), Some(4));
+        assert_eq!(buffer, [0; 4]);
+        assert!(!source.is_open());
+
+        release.send(()).unwrap();
+        assert_eq!(read_opened(&mut source, &mut buffer), Some(4));
+        assert_eq!(buffer, [1; 4]);
+    }
+
+    #[test]
+    fn test_retries() {
+        let opened = Arc::new(Mutex::new(0));
+        let counter = Arc::clone(&opened);
+        let mut source = Restartable::new(AudioType::Pcm, move |_| {
+            *counter.lock() += 1;
+
+            Err(Error::Other("unavailable"))
+        });
+        let mut buffer = [0; 4];
+
+        assert_eq!(read_opened(&mut source, &mut buffer), None);
+        assert_eq!(*opened.lock(), 4);
+
+        let opened = Arc::new(Mutex::new(0));
+        let counter = Arc::clone(&opened);
+        let mut source = Restartable::new(AudioType::Pcm, move |_| {
+            *counter.lock() += 1;
+
+            Ok(Box::new(Constant(None)) as Box<dyn AudioSource>)
+        });
+        source.max_retries = 1;
+
+        assert_eq!(read_opened(&mut source, &mut buffer), None);
+        assert_eq!(*opened.lock(), 2);
+    }
+
+    #[test]
+    fn test_seek() {
+        let positions = Arc::new(Mutex::new(Vec::new()));
+        let opened = Arc::clone(&positions);
+        let mut source = Restartable::new(AudioType::Pcm, move |position| {
+            opened.lock().push(position);
+
+            Ok(Box::new(Constant(Some(1))) as Box<dyn AudioSource>)
+        });
+        let mut buffer = [0; 960];
+
+        read_opened(&mut source, &mut buffer);
+        source.read_pcm_frame(&mut buffer);
+        assert_eq!(source.position, Duration::from_millis(20));
+
+        assert!(source.seek(Duration::from_secs(5)));
+        assert!(!source.is_open());
+        read_opened(&mut source, &mut buffer);
+
+        assert_eq!(*positions.lock(), vec![Duration::from_secs(0), Duration::from_secs(5)]);
+    }
+}
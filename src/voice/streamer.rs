@@ -10,16 +10,26 @@ use serde_json;
 use std::{
     ffi::OsStr,
     fs::File,
-    io::{BufReader, ErrorKind as IoErrorKind, Read, Result as IoResult},
+    io::{BufReader, ErrorKind as IoErrorKind, Read, Result as IoResult, Seek, SeekFrom},
     process::{Child, Command, Stdio},
     result::Result as StdResult,
     sync::Arc,
+    time::Duration,
+};
+use super::{
+    AudioSource,
+    AudioType,
+    DcaError,
+    DcaMetadata,
+    Metadata,
+    VoiceError,
+    audio,
+    mixer::{self, STEREO_FRAME_LEN},
+    ytdl::YtdlReader,
 };
-use super::{AudioSource, AudioType, DcaError, DcaMetadata, VoiceError, audio};
 use log::{debug, warn};
-use crate::prelude::SerenityError;
 
-struct ChildContainer(Child);
+pub(super) struct ChildContainer(pub(super) Child);
 
 impl Read for ChildContainer {
     fn read(&mut self, buffer: &mut [u8]) -> IoResult<usize> {
@@ -53,6 +63,7 @@ struct InputSource<R: Read + Send + 'static> {
     reader: R,
     kind: AudioType,
     decoder: Option<Arc<Mutex<SendDecoder>>>,
+    metadata: Option<Metadata>,
 }
 
 impl<R: Read + Send> AudioSource for InputSource<R> {
@@ -108,50 +119,229 @@ impl<R: Read + Send> AudioSource for InputSource<R> {
     fn decode_and_add_opus_frame(&mut self, float_buffer: &mut [f32; 1920], volume: f32) -> Option<usize> {
         let decoder_lock = self.decoder.as_mut()?.clone();
         let frame = self.read_opus_frame()?;
-        let mut local_buf = [0f32; 960 * 2];
 
-        let count = {
-            let mut decoder = decoder_lock.lock();
+        let mut decoder = decoder_lock.lock();
+
+        decode_and_add(&mut decoder, &frame, float_buffer, volume)
+    }
+
+    fn metadata(&self) -> Option<&Metadata> { self.metadata.as_ref() }
+}
+
+/// An audio source over Opus packets produced by an iterator.
+struct PacketSource<I: Iterator<Item = Vec<u8>> + Send> {
+    packets: I,
+    decoder: SendDecoder,
+}
+
+impl<I: Iterator<Item = Vec<u8>> + Send> AudioSource for PacketSource<I> {
+    fn is_stereo(&mut self) -> bool { true }
+
+    fn get_type(&self) -> AudioType { AudioType::Opus }
+
+    fn read_pcm_frame(&mut self, _buffer: &mut [i16]) -> Option<usize> { None }
+
+    fn read_opus_frame(&mut self) -> Option<Vec<u8>> {
+        Some(self.packets.next().unwrap_or_default())
+    }
+
+    fn decode_and_add_opus_frame(&mut self, float_buffer: &mut [f32; 1920], volume: f32) -> Option<usize> {
+        let frame = self.read_opus_frame()?;
+
+        decode_and_add(&mut self.decoder, &frame, float_buffer, volume)
+    }
+}
+
+/// Decodes an Opus frame and adds it to the mix, returning the number of
+/// samples decoded per channel.
+fn decode_and_add(
+    decoder: &mut SendDecoder,
+    frame: &[u8],
+    float_buffer: &mut [f32; 1920],
+    volume: f32,
+) -> Option<usize> {
+    // An empty frame marks the end of the stream.
+    if frame.is_empty() {
+        return Some(0);
+    }
+
+    let mut local_buf = [0f32; STEREO_FRAME_LEN];
+    let count = decoder.decode_float(frame, &mut local_buf, false).ok()?;
+
+    // The decoder is stereo, so returns the number of samples per channel.
+    mixer::add_float(float_buffer, &local_buf[..(count * 2).min(STEREO_FRAME_LEN)], volume);
+
+    Some(count)
+}
+
+/// Options for opening audio through `ffmpeg`, passed to
+/// [`ffmpeg_with_options`].
+///
+/// The arguments which make `ffmpeg` output the PCM expected by the library
+/// are always passed; these options add to them.
+///
+/// # Examples
+///
+/// Start a song one minute in, with its volume normalised:
+///
+/// ```rust,no_run
+/// use serenity::voice::{self, FfmpegOptions};
+/// use std::time::Duration;
+///
+/// let mut options = FfmpegOptions::default();
+/// options
+///     .seek(Duration::from_secs(60))
+///     .filter("loudnorm");
+///
+/// let source = voice::ffmpeg_with_options("./some_file.mp3", &options);
+/// ```
+///
+/// [`ffmpeg_with_options`]: fn.ffmpeg_with_options.html
+#[derive(Clone, Debug, Default)]
+pub struct FfmpegOptions {
+    seek: Option<Duration>,
+    input_sample_rate: Option<u32>,
+    stereo: Option<bool>,
+    input_args: Vec<String>,
+    filters: Vec<String>,
+    args: Vec<String>,
+}
+
+impl FfmpegOptions {
+    /// Starts playback at the given position in the input, by seeking before
+    /// decoding it.
+    pub fn seek(&mut self, position: Duration) -> &mut Self {
+        self.seek = Some(position);
+
+        self
+    }
+
+    /// Sets the sample rate of the input, for raw formats which do not
+    /// describe it themselves.
+    ///
+    /// The output is always resampled to the 48kHz Discord expects.
+    pub fn input_sample_rate(&mut self, rate: u32) -> &mut Self {
+        self.input_sample_rate = Some(rate);
+
+        self
+    }
+
+    /// Sets whether to play the input in stereo, rather than asking `ffprobe`
+    /// whether it is.
+    pub fn stereo(&mut self, stereo: bool) -> &mut Self {
+        self.stereo = Some(stereo);
+
+        self
+    }
+
+    /// Adds an argument to pass to `ffmpeg` before the input, such as
+    /// `-reconnect`.
+    pub fn input_arg<S: Into<String>>(&mut self, arg: S) -> &mut Self {
+        self.input_args.push(arg.into());
+
+        self
+    }
+
+    /// Adds an audio filter, such as `"volume=0.5"` or `"atempo=1.25"`.
+    ///
+    /// Filters are applied in the order they were added.
+    pub fn filter<S: Into<String>>(&mut self, filter: S) -> &mut Self {
+        self.filters.push(filter.into());
+
+        self
+    }
+
+    /// Adds an argument to pass to `ffmpeg` after the input, before those
+    /// describing the output.
+    pub fn arg<S: Into<String>>(&mut self, arg: S) -> &mut Self {
+        self.args.push(arg.into());
+
+        self
+    }
+
+    /// The position in the input at which playback starts.
+    pub(crate) fn start(&self) -> Duration {
+        self.seek.unwrap_or_else(|| Duration::from_secs(0))
+    }
+
+    fn build_command(&self, path: &OsStr, is_stereo: bool) -> Command {
+        let mut command = Command::new("ffmpeg");
+
+        if let Some(position) = self.seek {
+            command.arg("-ss").arg(format!("{}.{:03}", position.as_secs(), position.subsec_millis()));
+        }
+
+        if let Some(rate) = self.input_sample_rate {
+            command.arg("-ar").arg(rate.to_string());
+        }
 
-            decoder.decode_float(frame.as_slice(), &mut local_buf, false).ok()?
-        };
+        command
+            .args(&self.input_args)
+            .arg("-i")
+            .arg(path)
+            .args(&self.args);
 
-        for (i, float_buffer_element) in float_buffer.iter_mut().enumerate().take(1920) {
-            *float_buffer_element += local_buf[i] * volume;
+        if !self.filters.is_empty() {
+            command.arg("-af").arg(self.filters.join(","));
         }
 
-        Some(count)
+        command.args(&[
+            "-f",
+            "s16le",
+            "-ac",
+            if is_stereo { "2" } else { "1" },
+            "-ar",
+            "48000",
+            "-acodec",
+            "pcm_s16le",
+            "-",
+        ]);
+
+        command
     }
 }
 
 /// Opens an audio file through `ffmpeg` and creates an audio source.
+///
+/// Use [`ffmpeg_with_options`] to seek or filter the audio.
+///
+/// [`ffmpeg_with_options`]: fn.ffmpeg_with_options.html
 pub fn ffmpeg<P: AsRef<OsStr>>(path: P) -> Result<Box<dyn AudioSource>> {
-    _ffmpeg(path.as_ref())
+    _ffmpeg_with_options(path.as_ref(), &FfmpegOptions::default())
+}
+
+/// Opens an audio file through `ffmpeg` with the given options and creates an
+/// audio source.
+///
+/// Refer to [`FfmpegOptions`] for an example.
+///
+/// [`FfmpegOptions`]: struct.FfmpegOptions.html
+pub fn ffmpeg_with_options<P: AsRef<OsStr>>(
+    path: P,
+    options: &FfmpegOptions,
+) -> Result<Box<dyn AudioSource>> {
+    _ffmpeg_with_options(path.as_ref(), options)
 }
 
-fn _ffmpeg(path: &OsStr) -> Result<Box<dyn AudioSource>> {
+fn _ffmpeg_with_options(path: &OsStr, options: &FfmpegOptions) -> Result<Box<dyn AudioSource>> {
     // Will fail if the path is not to a file on the fs. Likely a YouTube URI.
-    let is_stereo = is_stereo(path).unwrap_or(false);
-    let stereo_val = if is_stereo { "2" } else { "1" };
-
-    _ffmpeg_optioned(path, &[
-        "-f",
-        "s16le",
-        "-ac",
-        stereo_val,
-        "-ar",
-        "48000",
-        "-acodec",
-        "pcm_s16le",
-        "-",
-    ], Some(is_stereo))
+    let is_stereo = options.stereo
+        .unwrap_or_else(|| is_stereo(path).unwrap_or(false));
+
+    let command = options.build_command(path, is_stereo)
+        .stderr(Stdio::null())
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    Ok(pcm(is_stereo, ChildContainer(command)))
 }
 
 /// Opens an audio file through `ffmpeg` and creates an audio source, with
 /// user-specified arguments to pass to ffmpeg.
 ///
 /// Note that this does _not_ build on the arguments passed by the [`ffmpeg`]
-/// function.
+/// function. Prefer [`ffmpeg_with_options`], which does.
 ///
 /// # Examples
 ///
@@ -173,6 +363,10 @@ fn _ffmpeg(path: &OsStr) -> Result<Box<dyn AudioSource>> {
 ///     "pcm_s16le",
 ///     "-",
 /// ]);
+/// ```
+///
+/// [`ffmpeg`]: fn.ffmpeg.html
+/// [`ffmpeg_with_options`]: fn.ffmpeg_with_options.html
 pub fn ffmpeg_optioned<P: AsRef<OsStr>>(
     path: P,
     args: &[&str],
@@ -236,10 +430,39 @@ fn _dca(path: &OsStr) -> StdResult<Box<dyn AudioSource>, DcaError> {
             .map_err(DcaError::IoError)?;
     }
 
-    let metadata = serde_json::from_slice::<DcaMetadata>(raw_json.as_slice())
+    let mut metadata = serde_json::from_slice::<DcaMetadata>(raw_json.as_slice())
         .map_err(DcaError::InvalidMetadata)?;
 
-    Ok(opus(metadata.is_stereo(), reader))
+    let frames = count_dca_frames(&mut reader).map_err(DcaError::IoError)?;
+    metadata.duration = Some(metadata.frame_duration() * frames);
+
+    Ok(Box::new(InputSource {
+        metadata: Some(Metadata::from(&metadata)),
+        ..opus_source(metadata.is_stereo(), reader)
+    }))
+}
+
+/// Counts the Opus frames following the metadata header, leaving the reader
+/// where it was.
+fn count_dca_frames<R: Read + Seek>(reader: &mut R) -> IoResult<u32> {
+    let start = reader.seek(SeekFrom::Current(0))?;
+    let mut frames = 0;
+
+    loop {
+        match reader.read_i16::<LittleEndian>() {
+            Ok(size) if size > 0 => {
+                reader.seek(SeekFrom::Current(i64::from(size)))?;
+                frames += 1;
+            },
+            Ok(_) => break,
+            Err(ref e) if e.kind() == IoErrorKind::UnexpectedEof => break,
+            Err(e) => return Err(e),
+        }
+    }
+
+    reader.seek(SeekFrom::Start(start))?;
+
+    Ok(frames)
 }
 
 /// Creates an Opus audio source. This makes certain assumptions: namely, that the input stream
@@ -249,7 +472,11 @@ fn _dca(path: &OsStr) -> StdResult<Box<dyn AudioSource>, DcaError> {
 ///
 /// [`ffmpeg`]: fn.ffmpeg.html
 pub fn opus<R: Read + Send + 'static>(is_stereo: bool, reader: R) -> Box<dyn AudioSource> {
-    Box::new(InputSource {
+    Box::new(opus_source(is_stereo, reader))
+}
+
+fn opus_source<R: Read + Send + 'static>(is_stereo: bool, reader: R) -> InputSource<R> {
+    InputSource {
         stereo: is_stereo,
         reader,
         kind: AudioType::Opus,
@@ -259,110 +486,79 @@ pub fn opus<R: Read + Send + 'static>(is_stereo: bool, reader: R) -> Box<dyn Aud
                 SendDecoder(OpusDecoder::new(audio::SAMPLE_RATE, Channels::Stereo).unwrap())
             ))
         ),
+        metadata: None,
+    }
+}
+
+/// Creates an audio source playing Opus packets produced by an iterator, such
+/// as those from a TTS engine or another voice server, ending once the
+/// iterator does.
+///
+/// Each packet must hold 20ms of 48kHz audio, as Discord expects. One packet
+/// is taken every 20ms by the voice connection's thread, so an iterator which
+/// blocks - such as that of an [`mpsc::Receiver`] - must produce packets at
+/// least that quickly. To send packets as they arrive instead, use
+/// [`Handler::send_opus_packet`].
+///
+/// [`Handler::send_opus_packet`]: struct.Handler.html#method.send_opus_packet
+/// [`mpsc::Receiver`]: https://doc.rust-lang.org/std/sync/mpsc/struct.Receiver.html
+pub fn opus_packets<I>(packets: I) -> Box<dyn AudioSource>
+    where I: IntoIterator<Item = Vec<u8>>, I::IntoIter: Send + 'static {
+    Box::new(PacketSource {
+        packets: packets.into_iter(),
+        // We always want to decode *to* stereo, for mixing reasons.
+        decoder: SendDecoder(OpusDecoder::new(audio::SAMPLE_RATE, Channels::Stereo).unwrap()),
     })
 }
 
 /// Creates a PCM audio source.
 pub fn pcm<R: Read + Send + 'static>(is_stereo: bool, reader: R) -> Box<dyn AudioSource> {
-    Box::new(InputSource {
+    Box::new(pcm_source(is_stereo, reader))
+}
+
+fn pcm_source<R: Read + Send + 'static>(is_stereo: bool, reader: R) -> InputSource<R> {
+    InputSource {
         stereo: is_stereo,
         reader,
         kind: AudioType::Pcm,
         decoder: None,
-    })
+        metadata: None,
+    }
 }
 
 /// Creates a streamed audio source with `youtube-dl` and `ffmpeg`.
+///
+/// `yt-dlp` is used instead if `youtube-dl` is not installed.
+///
+/// The title, uploader, duration and thumbnail given by `youtube-dl` are
+/// available via [`AudioSource::metadata`]. Should the stream stall or end
+/// early, it is restarted from the position it reached on a separate thread,
+/// with silence playing until it has caught up again.
+///
+/// [`AudioSource::metadata`]: trait.AudioSource.html#method.metadata
 pub fn ytdl(uri: &str) -> Result<Box<dyn AudioSource>> {
-    let ytdl_args = [
-        "-f",
-        "webm[abr>0]/bestaudio/best",
-        "-R",
-        "infinite",
-        "--no-playlist",
-        "--ignore-config",
-        uri,
-        "-o",
-        "-"
-    ];
-
-    let ffmpeg_args = [
-        "-f",
-        "s16le",
-        "-ac",
-        "2",
-        "-ar",
-        "48000",
-        "-acodec",
-        "pcm_s16le",
-        "-",
-    ];
-
-    let youtube_dl = Command::new("youtube-dl")
-        .args(&ytdl_args)
-        .stdin(Stdio::null())
-        .stderr(Stdio::null())
-        .stdout(Stdio::piped())
-        .spawn()?;
+    ytdl_at(uri, Duration::from_secs(0))
+}
 
-    let ffmpeg = Command::new("ffmpeg")
-        .arg("-re")
-        .arg("-i")
-        .arg("-")
-        .args(&ffmpeg_args)
-        .stdin(youtube_dl.stdout.ok_or(SerenityError::Other("Failed to open youtube-dl stdout"))?)
-        .stderr(Stdio::null())
-        .stdout(Stdio::piped())
-        .spawn()?;
+/// Creates a streamed audio source with `youtube-dl` and `ffmpeg`, starting
+/// at the given position.
+pub(crate) fn ytdl_at(uri: &str, position: Duration) -> Result<Box<dyn AudioSource>> {
+    let (reader, metadata) = YtdlReader::new(uri, position)?;
 
-    Ok(pcm(true, ChildContainer(ffmpeg)))
+    Ok(Box::new(InputSource {
+        metadata: Some(metadata),
+        ..pcm_source(true, reader)
+    }))
 }
 
 /// Creates a streamed audio source from YouTube search results with `youtube-dl`,`ffmpeg`, and `ytsearch`.
 /// Takes the first video listed from the YouTube search.
+///
+/// Refer to [`ytdl`] for the metadata and restarting behaviour.
+///
+/// [`ytdl`]: fn.ytdl.html
 pub fn ytdl_search(name: &str) -> Result<Box<dyn AudioSource>> {
-    let ytdl_args = [
-        "-f",
-        "webm[abr>0]/bestaudio/best",
-        "-R",
-        "infinite",
-        "--no-playlist",
-        "--ignore-config",
-        &format!("ytsearch1:{}",name),
-        "-o",
-        "-"
-    ];
-
-    let ffmpeg_args = [
-        "-f",
-        "s16le",
-        "-ac",
-        "2",
-        "-ar",
-        "48000",
-        "-acodec",
-        "pcm_s16le",
-        "-",
-    ];
-
-    let youtube_dl = Command::new("youtube-dl")
-        .args(&ytdl_args)
-        .stdin(Stdio::null())
-        .stderr(Stdio::null())
-        .stdout(Stdio::piped())
-        .spawn()?;
-
-    let ffmpeg = Command::new("ffmpeg")
-        .arg("-re")
-        .arg("-i")
-        .arg("-")
-        .args(&ffmpeg_args)
-        .stdin(youtube_dl.stdout.ok_or(SerenityError::Other("Failed to open youtube-dl stdout"))?)
-        .stderr(Stdio::null())
-        .stdout(Stdio::piped())
-        .spawn()?;
-
-    Ok(pcm(true, ChildContainer(ffmpeg)))
+    ytdl(&format!("ytsearch1:{}", name))
 }
 
 fn is_stereo(path: &OsStr) -> Result<bool> {
@@ -1,53 +1,114 @@
+use crate::constants::voice_close_codes;
+use crate::internal::prelude::*;
 use crate::internal::Timer;
-use crate::model::id::GuildId;
+use crate::model::{id::GuildId, voice::SpeakingState};
 use std::{
-    sync::mpsc::{Receiver as MpscReceiver, TryRecvError},
-    thread::Builder as ThreadBuilder
+    collections::VecDeque,
+    sync::{
+        mpsc::{Receiver as MpscReceiver, TryRecvError},
+        Arc,
+    },
+    thread::{Builder as ThreadBuilder, JoinHandle},
 };
 use super::{
+    audio::ClientEvent,
     connection::Connection,
+    connection_info::ConnectionInfo,
+    ClientConnectHandler,
+    ClientDisconnectHandler,
+    EncoderSettings,
+    LockedAudio,
+    LockedQueue,
     Status,
-    audio,
+    TrackEndHandler,
+    TrackEndReason,
+    VoiceError,
 };
-use log::{error, warn};
+use log::{error, info, warn};
 
-pub(crate) fn start(guild_id: GuildId, rx: MpscReceiver<Status>) {
+/// How often, in milliseconds, to retry connecting after a failed attempt.
+const RETRY_INTERVAL: u64 = 5000;
+
+pub(crate) fn start(guild_id: GuildId, rx: MpscReceiver<Status>, queue: LockedQueue) -> JoinHandle<()> {
     let name = format!("Serenity Voice (G{})", guild_id);
 
     ThreadBuilder::new()
         .name(name)
-        .spawn(move || runner(&rx))
-        .unwrap_or_else(|_| panic!("[Voice] Error starting guild: {:?}", guild_id));
+        .spawn(move || {
+            runner(&rx, &queue);
+
+            info!("[Voice] Thread for guild {} exited", guild_id);
+        })
+        .unwrap_or_else(|_| panic!("[Voice] Error starting guild: {:?}", guild_id))
 }
 
-fn runner(rx: &MpscReceiver<Status>) {
+fn runner(rx: &MpscReceiver<Status>, queue: &LockedQueue) {
     let mut senders = Vec::new();
+    let mut ended = Vec::new();
+    let mut track_end_handlers: Vec<Box<TrackEndHandler>> = Vec::new();
+    let mut clients = Vec::new();
+    let mut connect_handlers: Vec<Box<ClientConnectHandler>> = Vec::new();
+    let mut disconnect_handlers: Vec<Box<ClientDisconnectHandler>> = Vec::new();
     let mut receiver = None;
     let mut connection = None;
+    let mut info: Option<ConnectionInfo> = None;
+    let mut retry_timer = Timer::new(RETRY_INTERVAL);
     let mut timer = Timer::new(20);
-    let mut bitrate = audio::DEFAULT_BITRATE;
+    let mut encoder_settings = EncoderSettings::default();
+    let mut packets = VecDeque::new();
+    let mut speaking_flags = SpeakingState::MICROPHONE;
 
     'runner: loop {
+        // Without a connection to maintain, sleep until told to do something
+        // rather than polling.
+        let mut block = connection.is_none() && info.is_none();
+
         loop {
-            match rx.try_recv() {
-                Ok(Status::Connect(info)) => {
-                    connection = match Connection::new(info) {
-                        Ok(connection) => Some(connection),
-                        Err(why) => {
-                            warn!("[Voice] Error connecting: {:?}", why);
+            let status = if block {
+                block = false;
+
+                rx.recv().map_err(|_| TryRecvError::Disconnected)
+            } else {
+                rx.try_recv()
+            };
+
+            match status {
+                Ok(Status::Connect(new_info)) => {
+                    // Voice state updates re-send the same details, which do
+                    // not warrant a new connection.
+                    if connection.is_some() && info.as_ref() == Some(&new_info) {
+                        continue;
+                    }
+
+                    if connection.is_some() {
+                        info!("[Voice] Moving to voice server: {}", new_info.endpoint);
+                    }
 
-                            None
-                        },
-                    };
+                    // Close the old connection before opening the new one.
+                    connection = None;
+                    connection = connect(&new_info, speaking_flags);
+                    info = Some(new_info);
+                    retry_timer.reset();
                 },
                 Ok(Status::Disconnect) => {
                     connection = None;
+                    info = None;
+                    packets.clear();
+                },
+                Ok(Status::Shutdown) => {
+                    break 'runner;
+                },
+                Ok(Status::Suspend) => {
+                    // The voice server is going away; wait for the next one
+                    // rather than retrying the old one.
+                    connection = None;
+                    info = None;
                 },
                 Ok(Status::SetReceiver(r)) => {
                     receiver = r;
                 },
                 Ok(Status::SetSender(s)) => {
-                    senders.clear();
+                    ended.extend(senders.drain(..).map(|aud| (aud, TrackEndReason::Stopped)));
 
                     if let Some(aud) = s {
                         senders.push(aud);
@@ -56,8 +117,35 @@ fn runner(rx: &MpscReceiver<Status>) {
                 Ok(Status::AddSender(s)) => {
                     senders.push(s);
                 },
+                Ok(Status::SwitchSource(s)) => {
+                    ended.extend(senders.drain(..).map(|aud| (aud, TrackEndReason::Stopped)));
+                    queue.lock().replace_current(Arc::clone(&s));
+                    senders.push(s);
+                },
+                Ok(Status::AddTrackEndHandler(h)) => {
+                    track_end_handlers.push(h);
+                },
+                Ok(Status::AddClientConnectHandler(h)) => {
+                    connect_handlers.push(h);
+                },
+                Ok(Status::AddClientDisconnectHandler(h)) => {
+                    disconnect_handlers.push(h);
+                },
+                Ok(Status::SendOpusPacket(packet)) => {
+                    packets.push_back(packet);
+                },
                 Ok(Status::SetBitrate(b)) => {
-                    bitrate = b;
+                    encoder_settings.bitrate = b;
+                },
+                Ok(Status::SetEncoderSettings(settings)) => {
+                    encoder_settings = settings;
+                },
+                Ok(Status::SetSpeakingFlags(flags)) => {
+                    speaking_flags = flags;
+
+                    if let Some(connection) = connection.as_mut() {
+                        connection.set_speaking_flags(flags);
+                    }
                 },
                 Err(TryRecvError::Empty) => {
                     // If we received nothing, then we can perform an update.
@@ -69,6 +157,9 @@ fn runner(rx: &MpscReceiver<Status>) {
             }
         }
 
+        queue.lock().advance(&mut senders, &mut ended);
+        fire_track_ends(&mut ended, &mut track_end_handlers);
+
         // Overall here, check if there's an error.
         //
         // If there is a connection, try to send an update. This should not
@@ -79,34 +170,119 @@ fn runner(rx: &MpscReceiver<Status>) {
         // another event.
         let error = match connection.as_mut() {
             Some(connection) => {
-                let cycle = connection.cycle(&mut senders, &mut receiver, &mut timer, bitrate);
+                let cycle = connection.cycle(
+                    &mut senders,
+                    &mut packets,
+                    &mut ended,
+                    &mut clients,
+                    &mut receiver,
+                    &mut timer,
+                    &encoder_settings,
+                );
 
                 match cycle {
-                    Ok(()) => false,
+                    Ok(()) => None,
                     Err(why) => {
                         error!(
                             "(╯°□°）╯︵ ┻━┻ Error updating connection: {:?}",
                             why
                         );
 
-                        true
+                        Some(why)
                     },
                 }
             },
             None => {
+                // Keep trying to connect if the last attempt failed.
+                if let Some(ref info) = info {
+                    if retry_timer.check() {
+                        connection = connect(info, speaking_flags);
+                    }
+                }
+
                 timer.r#await();
 
-                false
+                None
             },
         };
 
-        // If there was an error, then just reset the connection and try to get
-        // another.
-        if error {
-            let mut conn = connection.expect("[Voice] Shouldn't have had a voice connection error without a connection.");
-            connection = conn.reconnect()
-                .ok()
-                .map(|_| conn);
+        // If there was an error, then try to resume the session, falling back
+        // to a new connection should it no longer be valid.
+        if let Some(why) = error {
+            let mut conn = connection.take().expect("[Voice] Shouldn't have had a voice connection error without a connection.");
+
+            let resumed = match why {
+                Error::Voice(VoiceError::WebsocketClosed(code)) if !voice_close_codes::is_resumable(code) => {
+                    info!("[Voice] Session closed with {:?}, not resuming", code);
+
+                    false
+                },
+                _ => match conn.resume() {
+                    Ok(()) => true,
+                    Err(why) => {
+                        warn!("[Voice] Error resuming: {:?}", why);
+
+                        false
+                    },
+                },
+            };
+
+            connection = if resumed {
+                Some(conn)
+            } else {
+                drop(conn);
+
+                info.as_ref().and_then(|info| connect(info, speaking_flags))
+            };
+            retry_timer.reset();
+        }
+
+        fire_track_ends(&mut ended, &mut track_end_handlers);
+        fire_client_events(&mut clients, &mut connect_handlers, &mut disconnect_handlers);
+    }
+}
+
+fn connect(info: &ConnectionInfo, speaking_flags: SpeakingState) -> Option<Connection> {
+    match Connection::new(info.clone()) {
+        Ok(mut connection) => {
+            connection.set_speaking_flags(speaking_flags);
+
+            Some(connection)
+        },
+        Err(why) => {
+            warn!("[Voice] Error connecting: {:?}", why);
+
+            None
+        },
+    }
+}
+
+fn fire_client_events(
+    clients: &mut Vec<ClientEvent>,
+    connect_handlers: &mut [Box<ClientConnectHandler>],
+    disconnect_handlers: &mut [Box<ClientDisconnectHandler>],
+) {
+    for event in clients.drain(..) {
+        match event {
+            ClientEvent::Connect(user_id, ssrc) => for handler in connect_handlers.iter_mut() {
+                handler(user_id, ssrc);
+            },
+            ClientEvent::Disconnect(user_id) => for handler in disconnect_handlers.iter_mut() {
+                handler(user_id);
+            },
+        }
+    }
+}
+
+fn fire_track_ends(
+    ended: &mut Vec<(LockedAudio, TrackEndReason)>,
+    handlers: &mut [Box<TrackEndHandler>],
+) {
+    for (audio, reason) in ended.drain(..) {
+        audio.lock().finished = true;
+
+        for handler in handlers.iter_mut() {
+            handler(&audio, reason);
         }
     }
 }
@@ -0,0 +1,531 @@
+//! Streaming of audio through `youtube-dl` and `ffmpeg`, restarting the
+//! stream from its last position should it stall.
+
+use crate::internal::prelude::*;
+use log::{debug, warn};
+use serde_json;
+use std::{
+    collections::VecDeque,
+    io::{self, BufRead, BufReader, ErrorKind as IoErrorKind, Read, Result as IoResult},
+    process::{Child, ChildStdout, Command, Stdio},
+    sync::mpsc::{self, Receiver as MpscReceiver, RecvTimeoutError, SyncSender, TryRecvError},
+    thread::Builder as ThreadBuilder,
+    time::Duration,
+};
+use super::{streamer::ChildContainer, Metadata, VoiceError};
+
+/// How long no audio may be received before the stream is considered stalled.
+const STALL_TIMEOUT: Duration = Duration::from_secs(10);
+/// How many times a stream is restarted before giving up on it.
+const MAX_RESTARTS: u8 = 5;
+/// The number of bytes in a second of 48kHz stereo `s16le` PCM.
+const PCM_BYTES_PER_SECOND: u64 = 48_000 * 2 * 2;
+/// The size of the chunks read from `ffmpeg`.
+const CHUNK_SIZE: usize = 16 * 1024;
+/// The number of bytes in a stereo `s16le` sample.
+const SAMPLE_SIZE: usize = 2 * 2;
+/// How much audio is buffered before playing, at the start and after the
+/// stream has fallen behind, such as when it was restarted.
+const BUFFER_SIZE: usize = PCM_BYTES_PER_SECOND as usize / 10;
+
+/// A running `youtube-dl` and `ffmpeg` pair, along with the chunks of PCM read
+/// from `ffmpeg` by a separate thread.
+struct Stream {
+    chunks: MpscReceiver<IoResult<Vec<u8>>>,
+    // Held so that both processes are killed when the stream is dropped.
+    _youtube_dl: ChildContainer,
+    _ffmpeg: ChildContainer,
+}
+
+impl Stream {
+    fn start(query: &str, position: Duration) -> Result<(Self, Metadata)> {
+        let args = [
+            "--print-json",
+            "-f",
+            "webm[abr>0]/bestaudio/best",
+            "-R",
+            "infinite",
+            "--no-playlist",
+            "--ignore-config",
+            query,
+            "-o",
+            "-",
+        ];
+
+        let mut youtube_dl = spawn_youtube_dl(&args)?;
+
+        // When streaming to stdout, the JSON is printed to stderr instead,
+        // possibly after warnings.
+        let stderr = youtube_dl.stderr.take()
+            .ok_or(Error::Other("Failed to open youtube-dl stderr"))?;
+        let mut stderr = BufReader::new(stderr);
+        let value = read_json(&mut stderr)?;
+        let metadata = metadata_from_json(&value);
+
+        // Keep draining stderr, so that youtube-dl never blocks writing to it.
+        ThreadBuilder::new()
+            .name("serenity ytdl stderr".to_string())
+            .spawn(move || {
+                let _ = io::copy(&mut stderr, &mut io::sink());
+            })?;
+
+        let stdout = youtube_dl.stdout.take()
+            .ok_or(Error::Other("Failed to open youtube-dl stdout"))?;
+
+        let mut ffmpeg = Command::new("ffmpeg")
+            .arg("-re")
+            .arg("-ss")
+            .arg(format!("{}.{:03}", position.as_secs(), position.subsec_millis()))
+            .arg("-i")
+            .arg("-")
+            .args(&[
+                "-f",
+                "s16le",
+                "-ac",
+                "2",
+                "-ar",
+                "48000",
+                "-acodec",
+                "pcm_s16le",
+                "-",
+            ])
+            .stdin(stdout)
+            .stderr(Stdio::null())
+            .stdout(Stdio::piped())
+            .spawn()?;
+
+        let pcm = ffmpeg.stdout.take()
+            .ok_or(Error::Other("Failed to open ffmpeg stdout"))?;
+
+        let stream = Stream {
+            chunks: read_chunks(pcm)?,
+            _youtube_dl: ChildContainer(youtube_dl),
+            _ffmpeg: ChildContainer(ffmpeg),
+        };
+
+        Ok((stream, metadata))
+    }
+}
+
+/// Forwards the PCM of a `youtube-dl` stream to a [`YtdlReader`], restarting
+/// the stream from the current position when reading stalls, fails, or ends
+/// before the duration given by `youtube-dl`.
+///
+/// Runs on its own thread, so that waiting for a stalled stream and restarting
+/// it never blocks the voice thread.
+///
+/// [`YtdlReader`]: struct.YtdlReader.html
+struct Restarter {
+    query: String,
+    stream: Option<Stream>,
+    duration: Option<Duration>,
+    // A partial stereo sample, which is sent along with the next chunk.
+    partial: Vec<u8>,
+    bytes_read: u64,
+    restarts: u8,
+}
+
+impl Restarter {
+    fn run(mut self, tx: SyncSender<Vec<u8>>) {
+        loop {
+            let received = match self.stream {
+                Some(ref stream) => stream.chunks.recv_timeout(STALL_TIMEOUT),
+                None => Err(RecvTimeoutError::Disconnected),
+            };
+
+            let retry = match received {
+                Ok(Ok(chunk)) => {
+                    if let Some(chunk) = self.align(chunk) {
+                        self.bytes_read += chunk.len() as u64;
+
+                        // The reader is gone, so stop streaming.
+                        if tx.send(chunk).is_err() {
+                            return;
+                        }
+                    }
+
+                    continue;
+                },
+                Ok(Err(why)) => {
+                    warn!("[Voice] Error reading ytdl stream: {:?}", why);
+
+                    true
+                },
+                Err(RecvTimeoutError::Timeout) => {
+                    warn!("[Voice] ytdl stream stalled");
+
+                    true
+                },
+                Err(RecvTimeoutError::Disconnected) => self.stream.is_none() || self.ended_early(),
+            };
+
+            if !(retry && self.restart()) {
+                return;
+            }
+        }
+    }
+
+    /// Prepends the partial sample left over from the last chunk, returning
+    /// the whole samples of the chunk, if any.
+    ///
+    /// This keeps every chunk aligned to stereo samples, so that the reader
+    /// can play silence in between chunks without garbling the audio.
+    fn align(&mut self, chunk: Vec<u8>) -> Option<Vec<u8>> {
+        let mut chunk = if self.partial.is_empty() {
+            chunk
+        } else {
+            let mut joined = self.partial.split_off(0);
+            joined.extend(chunk);
+
+            joined
+        };
+
+        let whole = chunk.len() - chunk.len() % SAMPLE_SIZE;
+        self.partial = chunk.split_off(whole);
+
+        if chunk.is_empty() { None } else { Some(chunk) }
+    }
+
+    fn position(&self) -> Duration {
+        let millis = self.bytes_read * 1000 / PCM_BYTES_PER_SECOND;
+
+        Duration::from_millis(millis)
+    }
+
+    /// Whether the stream ended well before the duration given by
+    /// `youtube-dl`.
+    fn ended_early(&self) -> bool {
+        self.duration.map_or(false, |duration| {
+            self.position() + Duration::from_secs(1) < duration
+        })
+    }
+
+    /// Restarts the stream from the current position, returning whether
+    /// another attempt is allowed.
+    fn restart(&mut self) -> bool {
+        // Kill the stalled processes before starting new ones.
+        self.stream = None;
+        self.partial.clear();
+
+        if self.restarts >= MAX_RESTARTS {
+            return false;
+        }
+
+        self.restarts += 1;
+        let position = self.position();
+        debug!("[Voice] Restarting ytdl stream of {} at {:?}", self.query, position);
+
+        match Stream::start(&self.query, position) {
+            Ok((stream, _)) => self.stream = Some(stream),
+            Err(why) => warn!("[Voice] Error restarting ytdl stream: {:?}", why),
+        }
+
+        true
+    }
+}
+
+/// A reader over the PCM of a `youtube-dl` stream, which is restarted from the
+/// current position should it stall, fail, or end early.
+///
+/// Reading never blocks: while the stream is buffering or being restarted,
+/// silence is read instead.
+pub(crate) struct YtdlReader {
+    chunks: MpscReceiver<Vec<u8>>,
+    pending: VecDeque<Vec<u8>>,
+    pending_len: usize,
+    // The position in the first pending chunk.
+    chunk_pos: usize,
+    // The number of bytes read, including silence.
+    bytes_read: u64,
+    buffering: bool,
+    ended: bool,
+}
+
+impl YtdlReader {
+    /// Starts streaming the given URL or `youtube-dl` search query from the
+    /// given position.
+    pub(crate) fn new(query: &str, position: Duration) -> Result<(Self, Metadata)> {
+        let (stream, metadata) = Stream::start(query, position)?;
+
+        // Count the skipped audio as read, so that restarts resume from the
+        // right position.
+        let skipped = position.as_secs() * PCM_BYTES_PER_SECOND
+            + u64::from(position.subsec_millis()) * PCM_BYTES_PER_SECOND / 1000;
+
+        let restarter = Restarter {
+            query: query.to_string(),
+            stream: Some(stream),
+            duration: metadata.duration,
+            partial: Vec::new(),
+            bytes_read: skipped - skipped % SAMPLE_SIZE as u64,
+            restarts: 0,
+        };
+
+        let (tx, rx) = mpsc::sync_channel(4);
+
+        ThreadBuilder::new()
+            .name("serenity ytdl restarter".to_string())
+            .spawn(move || restarter.run(tx))?;
+
+        Ok((YtdlReader::from_chunks(rx), metadata))
+    }
+
+    fn from_chunks(chunks: MpscReceiver<Vec<u8>>) -> Self {
+        YtdlReader {
+            chunks,
+            pending: VecDeque::new(),
+            pending_len: 0,
+            chunk_pos: 0,
+            bytes_read: 0,
+            buffering: true,
+            ended: false,
+        }
+    }
+
+    /// Moves the chunks received so far into the pending chunks.
+    fn receive(&mut self) {
+        loop {
+            match self.chunks.try_recv() {
+                Ok(chunk) => {
+                    self.pending_len += chunk.len();
+                    self.pending.push_back(chunk);
+                },
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    self.ended = true;
+
+                    break;
+                },
+            }
+        }
+    }
+}
+
+impl Read for YtdlReader {
+    fn read(&mut self, buffer: &mut [u8]) -> IoResult<usize> {
+        self.receive();
+
+        // Only resume in between samples, so that the silence read while
+        // buffering does not garble the audio.
+        let aligned = self.bytes_read % SAMPLE_SIZE as u64 == 0;
+
+        if self.buffering && aligned && (self.pending_len >= BUFFER_SIZE || self.ended) {
+            self.buffering = false;
+        }
+
+        if !self.buffering {
+            if let Some(chunk) = self.pending.front() {
+                let len = buffer.len().min(chunk.len() - self.chunk_pos);
+                buffer[..len].copy_from_slice(&chunk[self.chunk_pos..self.chunk_pos + len]);
+
+                self.chunk_pos += len;
+                self.bytes_read += len as u64;
+                self.pending_len -= len;
+
+                if self.chunk_pos == chunk.len() {
+                    self.pending.pop_front();
+                    self.chunk_pos = 0;
+                }
+
+                return Ok(len);
+            }
+
+            if self.ended {
+                return Ok(0);
+            }
+
+            self.buffering = true;
+        }
+
+        for byte in buffer.iter_mut() {
+            *byte = 0;
+        }
+
+        self.bytes_read += buffer.len() as u64;
+
+        Ok(buffer.len())
+    }
+}
+
+/// Spawns `youtube-dl`, falling back to `yt-dlp` if it is not installed.
+fn spawn_youtube_dl(args: &[&str]) -> IoResult<Child> {
+    let spawn = |program: &str| Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stderr(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn();
+
+    match spawn("youtube-dl") {
+        Err(ref e) if e.kind() == IoErrorKind::NotFound => spawn("yt-dlp"),
+        other => other,
+    }
+}
+
+/// Reads chunks from `ffmpeg` on a separate thread, so that reads can time
+/// out.
+fn read_chunks(mut pcm: ChildStdout) -> Result<MpscReceiver<IoResult<Vec<u8>>>> {
+    let (tx, rx) = mpsc::sync_channel(4);
+
+    ThreadBuilder::new()
+        .name("serenity ytdl".to_string())
+        .spawn(move || loop {
+            let mut chunk = vec![0; CHUNK_SIZE];
+
+            let msg = match pcm.read(&mut chunk) {
+                Ok(0) => break,
+                Ok(len) => {
+                    chunk.truncate(len);
+
+                    Ok(chunk)
+                },
+                Err(ref e) if e.kind() == IoErrorKind::Interrupted => continue,
+                Err(e) => Err(e),
+            };
+
+            let failed = msg.is_err();
+
+            if tx.send(msg).is_err() || failed {
+                break;
+            }
+        })?;
+
+    Ok(rx)
+}
+
+/// Reads lines from `youtube-dl`'s stderr until one holds the JSON describing
+/// the track, skipping warnings printed before it.
+fn read_json<R: BufRead>(stderr: &mut R) -> Result<Value> {
+    let mut output = String::new();
+    let mut line = String::new();
+
+    loop {
+        line.clear();
+
+        if stderr.read_line(&mut line)? == 0 {
+            return Err(Error::Voice(VoiceError::YouTubeDLProcessing(Value::String(output))));
+        }
+
+        if line.trim_start().starts_with('{') {
+            if let Ok(value) = serde_json::from_str(&line) {
+                return Ok(value);
+            }
+        }
+
+        output.push_str(&line);
+    }
+}
+
+fn metadata_from_json(value: &Value) -> Metadata {
+    let string = |key: &str| value.get(key).and_then(Value::as_str).map(str::to_string);
+
+    Metadata {
+        title: string("title"),
+        artist: string("uploader"),
+        duration: value.get("duration")
+            .and_then(Value::as_f64)
+            .map(|secs| Duration::from_millis((secs * 1000.0) as u64)),
+        source_url: string("webpage_url"),
+        thumbnail: string("thumbnail"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json;
+    use std::{io::Read, sync::mpsc, time::Duration};
+    use super::{metadata_from_json, read_json, Restarter, YtdlReader, BUFFER_SIZE};
+
+    fn restarter() -> Restarter {
+        Restarter {
+            query: String::new(),
+            stream: None,
+            duration: Some(Duration::from_secs(60)),
+            partial: Vec::new(),
+            bytes_read: 0,
+            restarts: 0,
+        }
+    }
+
+    #[test]
+    fn test_read_json() {
+        let stderr = b"WARNING: unable to extract uploader\n{\"title\": \"a\"}\n[download] 1%\n";
+        let value = read_json(&mut &stderr[..]).unwrap();
+        assert_eq!(value["title"], "a");
+
+        assert!(read_json(&mut &b"ERROR: video unavailable\n"[..]).is_err());
+    }
+
+    #[test]
+    fn test_metadata_from_json() {
+        let value = serde_json::json!({
+            "title": "Song",
+            "uploader": "Artist",
+            "duration": 212.5,
+            "webpage_url": "https://example.com/watch",
+            "thumbnail": "https://example.com/thumbnail.jpg",
+        });
+        let metadata = metadata_from_json(&value);
+
+        assert_eq!(metadata.title.as_ref().map(String::as_str), Some("Song"));
+        assert_eq!(metadata.artist.as_ref().map(String::as_str), Some("Artist"));
+        assert_eq!(metadata.duration, Some(Duration::from_millis(212_500)));
+        assert_eq!(metadata.source_url.as_ref().map(String::as_str), Some("https://example.com/watch"));
+        assert_eq!(metadata.thumbnail.as_ref().map(String::as_str), Some("https://example.com/thumbnail.jpg"));
+
+        let metadata = metadata_from_json(&serde_json::json!({"duration": null}));
+        assert!(metadata.title.is_none());
+        assert!(metadata.duration.is_none());
+    }
+
+    #[test]
+    fn test_align() {
+        let mut restarter = restarter();
+
+        assert_eq!(restarter.align(vec![1, 2, 3]), None);
+        assert_eq!(restarter.align(vec![4, 5, 6, 7, 8, 9]), Some(vec![1, 2, 3, 4, 5, 6, 7, 8]));
+        assert_eq!(restarter.align(vec![10, 11, 12]), Some(vec![9, 10, 11, 12]));
+        assert!(restarter.partial.is_empty());
+        assert_eq!(restarter.position(), Duration::from_secs(0));
+    }
+
+    #[test]
+    fn test_reader_buffers() {
+        let (tx, rx) = mpsc::sync_channel(4);
+        let mut reader = YtdlReader::from_chunks(rx);
+        let mut buffer = [1; 2];
+
+        // Silence is read until enough audio is buffered.
+        assert_eq!(reader.read(&mut buffer).unwrap(), 2);
+        assert_eq!(buffer, [0, 0]);
+
+        tx.send(vec![7; BUFFER_SIZE]).unwrap();
+
+        // The silent sample is completed before the audio starts.
+        assert_eq!(reader.read(&mut buffer).unwrap(), 2);
+        assert_eq!(buffer, [0, 0]);
+        assert_eq!(reader.read(&mut buffer).unwrap(), 2);
+        assert_eq!(buffer, [7, 7]);
+
+        let mut rest = vec![0; BUFFER_SIZE];
+        assert_eq!(reader.read(&mut rest).unwrap(), BUFFER_SIZE - 2);
+
+        // Having fallen behind, silence is read until enough audio is
+        // buffered again.
+        assert_eq!(reader.read(&mut buffer).unwrap(), 2);
+        assert_eq!(buffer, [0, 0]);
+
+        tx.send(vec![8; 4]).unwrap();
+        assert_eq!(reader.read(&mut buffer).unwrap(), 2);
+        assert_eq!(reader.read(&mut buffer).unwrap(), 2);
+        assert_eq!(buffer, [0, 0]);
+
+        // Once the stream has ended, the remaining audio is read regardless.
+        drop(tx);
+        assert_eq!(reader.read(&mut buffer).unwrap(), 2);
+        assert_eq!(buffer, [0, 0]);
+        assert_eq!(reader.read(&mut rest).unwrap(), 4);
+        assert_eq!(&rest[..4], &[8; 4]);
+        assert_eq!(reader.read(&mut rest).unwrap(), 0);
+    }
+}
@@ -31,6 +31,13 @@ fn activity() {
 #[test]
 fn channel_create() {
     p!(ChannelCreateEvent, "channel_create_1");
+
+    // channel of a type unknown to the library
+    let event = p!(ChannelCreateEvent, "channel_create_2");
+    match event.channel {
+        Channel::Guild(channel) => assert_eq!(channel.read().kind, ChannelType::Unknown(99)),
+        _ => panic!("expected a guild channel"),
+    }
 }
 
 #[test]
@@ -48,6 +55,19 @@ fn channel_update() {
     p!(ChannelUpdateEvent, "channel_update_1");
 }
 
+#[test]
+fn current_application_info() {
+    // an application owned by a team
+    let info = p!(CurrentApplicationInfo, "current_application_info_1");
+    let team = info.team.expect("team");
+    assert_eq!(team.owner_user_id, UserId(114941315417899012));
+    assert_eq!(team.members[0].membership_state, MembershipState::Accepted);
+    assert_eq!(team.members[1].membership_state, MembershipState::Invited);
+    let flags = info.flags.expect("flags");
+    assert!(flags.has_presence_intent());
+    assert!(!flags.has_guild_members_intent());
+}
+
 #[test]
 fn emoji_animated() {
     p!(Emoji, "emoji_animated");
@@ -79,7 +99,12 @@ fn guild_some_application_id() {
 #[test]
 fn guild_create() {
     p!(GuildCreateEvent, "guild_create_1");
-    p!(GuildCreateEvent, "guild_create_2");
+
+    // a guild with a stage channel and a live stage
+    let event = p!(GuildCreateEvent, "guild_create_2");
+    let stage = &event.guild.stage_instances[0];
+    assert_eq!(stage.privacy_level, StagePrivacyLevel::GuildOnly);
+    assert_eq!(event.guild.channels[&stage.channel_id].read().kind, ChannelType::Stage);
 }
 
 #[test]
@@ -90,6 +115,25 @@ fn guild_delete() {
 #[test]
 fn guild_emojis_update() {
     p!(GuildEmojisUpdateEvent, "guild_emojis_update_1");
+
+    // an emoji restricted to a role and unavailable
+    let event = p!(GuildEmojisUpdateEvent, "guild_emojis_update_2");
+    let emoji = &event.emojis[&EmojiId(302920729475940354)];
+    assert!(!emoji.available);
+    assert_eq!(emoji.roles, vec![RoleId(249422519335911424)]);
+}
+
+#[test]
+fn guild_integrations() {
+    type Integrations = Vec<Integration>;
+
+    // a twitch and a bot integration
+    let integrations = p!(Integrations, "guild_integrations_1");
+
+    assert_eq!(integrations[0].subscriber_count, Some(12));
+    assert!(integrations[0].application.is_none());
+    assert!(integrations[1].role_id.is_none());
+    assert!(integrations[1].application.as_ref().unwrap().bot.is_some());
 }
 
 #[test]
@@ -105,6 +149,11 @@ fn guild_member_remove() {
 #[test]
 fn guild_member_update() {
     p!(GuildMemberUpdateEvent, "guild_member_update_1");
+
+    // member boosting the guild
+    let event = p!(GuildMemberUpdateEvent, "guild_member_update_2");
+    assert!(event.joined_at.is_some());
+    assert!(event.premium_since.is_some());
 }
 
 #[test]
@@ -125,6 +174,22 @@ fn guild_role_update() {
 #[test]
 fn guild_update() {
     p!(GuildUpdateEvent, "guild_update_1");
+
+    // a guild with a welcome screen
+    let event = p!(GuildUpdateEvent, "guild_update_2");
+    let welcome_screen = event.guild.welcome_screen.expect("welcome screen");
+    assert_eq!(welcome_screen.welcome_channels.len(), 2);
+    assert_eq!(welcome_screen.welcome_channels[0].emoji_id, None);
+    assert_eq!(welcome_screen.welcome_channels[1].emoji_id, Some(EmojiId(282924034348548097)));
+}
+
+#[test]
+fn invite() {
+    // a stream invite
+    let invite = p!(Invite, "invite_1");
+    assert_eq!(invite.target_type, Some(InviteTargetType::Stream));
+    assert_eq!(invite.target_user.map(|u| u.id), Some(UserId(165176875973476353)));
+    assert!(invite.expires_at.is_some());
 }
 
 #[test]
@@ -137,6 +202,13 @@ fn message_create() {
 
     // message from guild with partial member data
     p!(MessageCreateEvent, "message_create_3");
+
+    // reply containing the referenced message and a sticker
+    let event = p!(MessageCreateEvent, "message_create_4");
+    assert_eq!(event.message.sticker_items[0].format_type, StickerFormatType::Lottie);
+    let referenced = event.message.referenced_message.expect("referenced message");
+    assert_eq!(referenced.content, "original");
+    assert_eq!(event.message.message_reference.unwrap().message_id, Some(referenced.id));
 }
 
 #[test]
@@ -148,6 +220,13 @@ fn message_update() {
 fn message_reaction_add() {
     p!(ReactionAddEvent, "message_reaction_add_1");
     p!(ReactionAddEvent, "message_reaction_add_2");
+
+    // reaction in a guild with partial member data
+    let event = p!(ReactionAddEvent, "message_reaction_add_3");
+    assert_eq!(event.reaction.guild_id, Some(GuildId(244567637332328449)));
+    let member = event.reaction.member.expect("member");
+    assert_eq!(member.nick.as_ref().map(String::as_str), Some("zeyla"));
+    assert_eq!(member.user.map(|u| u.id), Some(UserId(114941315417899012)));
 }
 
 #[test]
@@ -161,6 +240,15 @@ fn message_reaction_remove_all() {
     p!(ReactionRemoveAllEvent, "message_reaction_remove_all_1");
 }
 
+#[test]
+fn presence() {
+    // presence with multiple activities
+    let presence = p!(Presence, "presence_1");
+    assert_eq!(presence.activities.len(), 2);
+    assert_eq!(presence.activities[1].name, "Spotify");
+    assert_eq!(presence.activity.map(|a| a.name), Some("Heroes of the Storm".to_string()));
+}
+
 #[test]
 fn ready() {
     p!(ReadyEvent, "ready_1");
@@ -175,6 +263,18 @@ fn typing_start() {
 fn voice_state_update() {
     p!(VoiceStateUpdateEvent, "voice_state_update_1");
     p!(VoiceStateUpdateEvent, "voice_state_update_2");
+
+    // member streaming and requesting to speak on a stage
+    let event = p!(VoiceStateUpdateEvent, "voice_state_update_3");
+    assert!(event.voice_state.self_stream);
+    assert!(event.voice_state.request_to_speak_timestamp.is_some());
+}
+
+// The voice gateway sends the heartbeat interval as a float since v4.
+#[test]
+fn voice_hello() {
+    let hello = p!(VoiceHello, "voice_hello_1");
+    assert_eq!(hello.heartbeat_interval, 41250);
 }
 
 #[test]
@@ -223,3 +323,49 @@ fn decode_footer_deser() {
 
     p!(Message, "message_footer_2");
 }
+
+#[test]
+fn unknown_enum_variants() {
+    let kind = serde_json::from_value::<MessageType>(Value::from(19)).unwrap();
+    assert_eq!(kind, MessageType::InlineReply);
+
+    let kind = serde_json::from_value::<MessageType>(Value::from(255)).unwrap();
+    assert_eq!(kind, MessageType::Unknown(255));
+    assert_eq!(serde_json::to_value(kind).unwrap(), Value::from(255));
+
+    let level = serde_json::from_value::<VerificationLevel>(Value::from(5)).unwrap();
+    assert_eq!(level, VerificationLevel::Unknown(5));
+
+    let level = serde_json::from_value::<VerificationLevel>(Value::from(1000)).unwrap();
+    assert_eq!(level, VerificationLevel::Unknown(1000));
+    assert_eq!(serde_json::to_value(level).unwrap(), Value::from(1000));
+
+    let region = serde_json::from_value::<Region>(Value::from("atlantis")).unwrap();
+    assert_eq!(region, Region::Unknown);
+
+    let status = serde_json::from_value::<OnlineStatus>(Value::from("busy")).unwrap();
+    assert_eq!(status, OnlineStatus::Unknown);
+    assert!(serde_json::to_value(status).is_err());
+
+    let kind = serde_json::from_value::<ActivityType>(Value::from(5)).unwrap();
+    assert_eq!(kind.num(), ActivityType::Competing.num());
+
+    let entry = |action: u64| serde_json::from_value::<AuditLogEntry>(serde_json::json!({
+        "target_id": "1",
+        "action_type": action,
+        "user_id": "2",
+        "id": "3",
+    })).unwrap();
+
+    match entry(61).action {
+        Action::Emoji(ActionEmoji::Update) => {},
+        other => panic!("expected an emoji update, got {:?}", other),
+    }
+
+    match entry(1000).action {
+        Action::Unknown(1000) => {},
+        other => panic!("expected an unknown action, got {:?}", other),
+    }
+
+    assert_eq!(serde_json::to_value(entry(1000)).unwrap()["action_type"], 1000);
+}